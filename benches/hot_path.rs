@@ -0,0 +1,5 @@
+use criterion::{criterion_group, criterion_main};
+use fuckyou_spam_rust::cli::bench::hot_path;
+
+criterion_group!(benches, hot_path);
+criterion_main!(benches);
@@ -0,0 +1,215 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{ClassificationDecision, ClassificationMap};
+
+use super::{ClassificationProvider, PromptEntry};
+
+/// Word and character-bigram counts of texts labeled spam and ham.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BayesModel {
+    pub trained_at: DateTime<Utc>,
+    spam_docs: u32,
+    ham_docs: u32,
+    spam_tokens: HashMap<String, u32>,
+    ham_tokens: HashMap<String, u32>,
+    spam_total: u64,
+    ham_total: u64,
+}
+
+impl BayesModel {
+    pub fn train<'a>(
+        spam: impl IntoIterator<Item = &'a str>,
+        ham: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let mut model = Self {
+            trained_at: Utc::now(),
+            spam_docs: 0,
+            ham_docs: 0,
+            spam_tokens: HashMap::new(),
+            ham_tokens: HashMap::new(),
+            spam_total: 0,
+            ham_total: 0,
+        };
+        for text in spam {
+            model.spam_docs += 1;
+            for token in tokenize(text) {
+                *model.spam_tokens.entry(token).or_default() += 1;
+                model.spam_total += 1;
+            }
+        }
+        for text in ham {
+            model.ham_docs += 1;
+            for token in tokenize(text) {
+                *model.ham_tokens.entry(token).or_default() += 1;
+                model.ham_total += 1;
+            }
+        }
+        model
+    }
+
+    /// Posterior probability that `text` is spam, with Laplace smoothing.
+    pub fn spam_probability(&self, text: &str) -> f64 {
+        let vocabulary = self
+            .spam_tokens
+            .keys()
+            .chain(self.ham_tokens.keys())
+            .collect::<HashSet<_>>()
+            .len() as f64
+            + 1.0;
+        let docs = (self.spam_docs + self.ham_docs).max(1) as f64;
+        let mut spam_score = ((self.spam_docs as f64 + 1.0) / (docs + 2.0)).ln();
+        let mut ham_score = ((self.ham_docs as f64 + 1.0) / (docs + 2.0)).ln();
+        for token in tokenize(text) {
+            let in_spam = self.spam_tokens.get(&token).copied().unwrap_or(0) as f64;
+            let in_ham = self.ham_tokens.get(&token).copied().unwrap_or(0) as f64;
+            if in_spam == 0.0 && in_ham == 0.0 {
+                continue;
+            }
+            spam_score += ((in_spam + 1.0) / (self.spam_total as f64 + vocabulary)).ln();
+            ham_score += ((in_ham + 1.0) / (self.ham_total as f64 + vocabulary)).ln();
+        }
+        1.0 / (1.0 + (ham_score - spam_score).exp())
+    }
+}
+
+/// Lowercased words of two or more characters, plus character bigrams of words
+/// containing Hangul so particles and spacing tricks still match.
+fn tokenize(text: &str) -> HashSet<String> {
+    let mut tokens = HashSet::new();
+    for word in text
+        .to_lowercase()
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2)
+    {
+        let chars: Vec<char> = word.chars().take(30).collect();
+        if chars.iter().any(|ch| ('가'..='힣').contains(ch)) {
+            for pair in chars.windows(2) {
+                tokens.insert(pair.iter().collect());
+            }
+        }
+        tokens.insert(chars.into_iter().collect());
+    }
+    tokens
+}
+
+/// Offline classifier backed by a [`BayesModel`] trained from the spam history and
+/// persisted in the data directory; used when every AI provider fails.
+pub struct BayesClassifier {
+    path: PathBuf,
+    threshold: f64,
+    model: RwLock<Option<BayesModel>>,
+}
+
+impl BayesClassifier {
+    /// Loads the persisted model if there is one; an untrained classifier answers with
+    /// an error until [`BayesClassifier::replace`] is called.
+    pub fn load(path: PathBuf, threshold: f64) -> Self {
+        let model = match std::fs::read_to_string(&path) {
+            Ok(source) => match serde_json::from_str::<BayesModel>(&source) {
+                Ok(model) => Some(model),
+                Err(err) => {
+                    tracing::warn!(
+                        target: "processor",
+                        error = %err,
+                        path = %path.display(),
+                        "ignoring unreadable offline model"
+                    );
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+        Self {
+            path,
+            threshold,
+            model: RwLock::new(model),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_trained(&self) -> bool {
+        self.model.read().is_some()
+    }
+
+    /// Persists `model` and starts classifying with it.
+    pub fn replace(&self, model: BayesModel) -> Result<()> {
+        let json = serde_json::to_string(&model)?;
+        std::fs::write(&self.path, json)
+            .with_context(|| format!("failed to write offline model to {}", self.path.display()))?;
+        *self.model.write() = Some(model);
+        Ok(())
+    }
+
+    fn decide(&self, entries: &[PromptEntry]) -> Result<ClassificationMap> {
+        let model = self.model.read();
+        let model = model
+            .as_ref()
+            .ok_or_else(|| anyhow!("offline model has not been trained yet"))?;
+        Ok(entries
+            .iter()
+            .map(|entry| {
+                let probability = model.spam_probability(&entry.text);
+                let spam = probability >= self.threshold;
+                (
+                    entry.id.clone(),
+                    ClassificationDecision {
+                        spam,
+                        reason: spam.then(|| {
+                            format!(
+                                "오프라인 분류기 판정 (스팸 확률 {:.0}%)",
+                                probability * 100.0
+                            )
+                        }),
+                        confidence: if spam { probability } else { 1.0 - probability } as f32,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+impl ClassificationProvider for BayesClassifier {
+    fn classify<'a>(
+        &'a self,
+        _system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        let result = self.decide(entries);
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_trained_spam_from_ham() {
+        let model = BayesModel::train(
+            [
+                "실시간 종목타점 무료 공개 리딩방 입장",
+                "확정 수익 보장 리딩방 지금 입장하세요",
+                "코인 에어드랍 무료 지급 지갑 연결",
+            ],
+            [
+                "오늘 회의는 세 시에 시작합니다",
+                "배포 끝났어요 확인 부탁드립니다",
+                "점심 뭐 먹을까요",
+            ],
+        );
+        assert!(model.spam_probability("무료 리딩방 입장 링크") > 0.8);
+        assert!(model.spam_probability("회의 확인 부탁드립니다") < 0.2);
+    }
+}
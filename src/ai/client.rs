@@ -1,43 +1,121 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
+use futures::future::BoxFuture;
 use reqwest::Client;
 
-use crate::{config::CerebrasConfig, domain::types::ClassificationMap};
+use crate::{
+    config::AiProviderConfig, db::usage::UsageRepository, domain::types::ClassificationMap,
+};
 
-use super::inference::{build_request, parse_response, CEREBRAS_API_URL};
+use super::{
+    inference::{
+        build_request, chunk_entries, parse_classification, parse_response, repair_messages,
+        ChatCompletionRequest, TokenUsage,
+    },
+    retry::RetryPolicy,
+    ClassificationProvider, PromptEntry,
+};
 
+/// Classifier backed by an OpenAI-compatible chat-completions API (Cerebras, OpenAI,
+/// Groq, Together or a custom endpoint).
 #[derive(Clone)]
-pub struct CerebrasClient {
+pub struct ChatCompletionClient {
     http: Client,
-    config: CerebrasConfig,
+    config: AiProviderConfig,
+    retry: RetryPolicy,
+    usage: Option<Arc<UsageRepository>>,
 }
 
-impl CerebrasClient {
-    pub fn new(http: Client, config: CerebrasConfig) -> Self {
-        Self { http, config }
+impl ChatCompletionClient {
+    pub fn new(
+        http: Client,
+        config: AiProviderConfig,
+        retry: RetryPolicy,
+        usage: Option<Arc<UsageRepository>>,
+    ) -> Self {
+        Self {
+            http,
+            config,
+            retry,
+            usage,
+        }
     }
 
-    pub async fn classify(&self, prompt: &str) -> Result<ClassificationMap> {
-        let api_key = self
-            .config
-            .api_key
-            .as_ref()
-            .context("CEREBRAS_API_KEY must be configured for spam classification")?;
+    pub fn provider(&self) -> &'static str {
+        self.config.kind.name()
+    }
+
+    /// Classifies the batch, split into several requests when it would not fit the
+    /// model's context window.
+    pub async fn classify(
+        &self,
+        system: &str,
+        entries: &[PromptEntry],
+    ) -> Result<ClassificationMap> {
+        let api_key = self.config.api_key.as_ref().with_context(|| {
+            format!(
+                "{}_API_KEY must be configured for spam classification",
+                self.config.kind.env_prefix()
+            )
+        })?;
+
+        let chunks = chunk_entries(system, entries, self.config.context_tokens);
+        if chunks.len() > 1 {
+            tracing::info!(
+                provider = self.provider(),
+                chunks = chunks.len(),
+                context_tokens = self.config.context_tokens,
+                "batch split to fit the model context"
+            );
+        }
+        let mut classification = ClassificationMap::new();
+        for chunk in chunks {
+            classification.extend(self.request(api_key, system, chunk).await?);
+        }
+        Ok(classification)
+    }
 
-        let request = build_request(self.config.model.clone(), prompt);
+    async fn request(
+        &self,
+        api_key: &str,
+        system: &str,
+        entries: &[PromptEntry],
+    ) -> Result<ClassificationMap> {
+        let mut request = build_request(self.config.model.clone(), system, entries);
+        let content = self.send(api_key, &request).await?;
+        match parse_classification(&content, entries) {
+            Ok(classification) => Ok(classification),
+            Err(err) => {
+                tracing::warn!(
+                    provider = self.provider(),
+                    error = %format!("{err:#}"),
+                    "invalid model output; asking for a repaired answer"
+                );
+                request.messages.extend(repair_messages(&content, &err));
+                let content = self.send(api_key, &request).await?;
+                parse_classification(&content, entries)
+                    .context("model output still invalid after a repair attempt")
+            }
+        }
+    }
 
+    /// Sends one request and returns the answer text, recording its token usage.
+    async fn send(&self, api_key: &str, request: &ChatCompletionRequest) -> Result<String> {
         // Log request details for debugging
         tracing::debug!(
+            provider = self.provider(),
             model = %self.config.model,
-            prompt_len = %prompt.len(),
-            "Sending request to Cerebras API"
+            messages = request.messages.len(),
+            "Sending request to AI provider"
         );
 
+        let url = format!("{}/chat/completions", self.config.base_url);
         let http_response = self
-            .http
-            .post(CEREBRAS_API_URL)
-            .bearer_auth(api_key)
-            .json(&request)
-            .send()
+            .retry
+            .send(self.provider(), || {
+                self.http.post(&url).bearer_auth(api_key).json(request)
+            })
             .await?;
 
         // Check status and log error details
@@ -45,16 +123,56 @@ impl CerebrasClient {
             let status = http_response.status();
             let error_text = http_response.text().await.unwrap_or_default();
             tracing::error!(
+                provider = self.provider(),
                 status = %status,
                 error_body = %error_text,
-                "Cerebras API request failed"
+                "AI provider request failed"
             );
-            return Err(err).context(format!("Cerebras API error {}: {}", status, error_text));
+            return Err(err).context(format!(
+                "{} API error {}: {}",
+                self.provider(),
+                status,
+                error_text
+            ));
         }
 
         let response = http_response;
 
-        let classification = parse_response(response).await?;
-        Ok(classification)
+        let (content, usage) = parse_response(response).await?;
+        if let Some(usage) = usage {
+            self.record_usage(usage).await;
+        }
+        Ok(content)
+    }
+
+    async fn record_usage(&self, usage: TokenUsage) {
+        let Some(repo) = &self.usage else {
+            return;
+        };
+        let cost = self
+            .config
+            .estimate_cost(usage.prompt_tokens, usage.completion_tokens);
+        if let Err(err) = repo
+            .record(
+                self.provider(),
+                &self.config.model,
+                usage.prompt_tokens,
+                usage.completion_tokens,
+                cost,
+            )
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, "failed to record AI usage");
+        }
+    }
+}
+
+impl ClassificationProvider for ChatCompletionClient {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(ChatCompletionClient::classify(self, system, entries))
     }
 }
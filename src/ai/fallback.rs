@@ -0,0 +1,73 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use tokio::time::timeout;
+
+use crate::domain::types::ClassificationMap;
+
+use super::{ClassificationProvider, PromptEntry};
+
+/// Tries each provider in order until one answers within the deadline.
+pub struct FallbackClassifier {
+    providers: Vec<(&'static str, Arc<dyn ClassificationProvider>)>,
+    deadline: Duration,
+}
+
+impl FallbackClassifier {
+    pub fn new(
+        providers: Vec<(&'static str, Arc<dyn ClassificationProvider>)>,
+        deadline: Duration,
+    ) -> Self {
+        Self {
+            providers,
+            deadline,
+        }
+    }
+
+    async fn classify_chain(
+        &self,
+        system: &str,
+        entries: &[PromptEntry],
+    ) -> Result<ClassificationMap> {
+        let mut last_error = None;
+        for (index, (name, provider)) in self.providers.iter().enumerate() {
+            let outcome = match timeout(self.deadline, provider.classify(system, entries)).await {
+                Ok(result) => result,
+                Err(_) => Err(anyhow!("timed out after {:?}", self.deadline)),
+            };
+            match outcome {
+                Ok(classification) => {
+                    tracing::info!(
+                        target: "processor",
+                        provider = name,
+                        fallback = index > 0,
+                        entries = classification.len(),
+                        "batch classified"
+                    );
+                    return Ok(classification);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        target: "processor",
+                        provider = name,
+                        error = %format!("{err:#}"),
+                        "classifier failed; trying next provider"
+                    );
+                    last_error = Some(err.context(format!("{name} failed")));
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("no classifier configured")))
+    }
+}
+
+impl ClassificationProvider for FallbackClassifier {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(self.classify_chain(system, entries))
+    }
+}
@@ -1,4 +1,112 @@
+pub mod bayes;
 pub mod client;
+pub mod fallback;
+pub mod heuristic;
 pub mod inference;
+pub mod ollama;
+pub mod prompt;
+pub mod recording;
+pub mod retry;
+pub mod shadow;
+pub mod two_stage;
 
-pub use client::CerebrasClient;
+pub use client::ChatCompletionClient;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use reqwest::Client;
+
+use crate::{
+    config::{AiProviderConfig, AiProviderKind, AppConfig},
+    db::usage::UsageRepository,
+    domain::types::ClassificationMap,
+};
+
+use fallback::FallbackClassifier;
+use heuristic::HeuristicClassifier;
+use ollama::OllamaClient;
+use retry::RetryPolicy;
+
+/// One message of a batch. Providers key their answers by `id` and read the fields
+/// they need directly, never by parsing `rendered` back, so a message imitating an entry
+/// header cannot forge or take over another entry's verdict.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptEntry {
+    pub id: String,
+    /// The message text alone.
+    pub text: String,
+    pub risk_score: i32,
+    /// Header, text and context lines as shown to language models.
+    pub rendered: String,
+}
+
+/// The entries' rendered text as one batch prompt.
+pub fn render_batch(entries: &[PromptEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| entry.rendered.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Classifies a batch into per-message spam decisions, following the rendered system
+/// prompt.
+pub trait ClassificationProvider: Send + Sync {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>>;
+}
+
+/// Builds the primary provider, wrapped in a [`FallbackClassifier`] when
+/// `AI_FALLBACK_PROVIDERS` lists backups or an offline classifier is given as the
+/// last resort.
+pub fn build_classifier(
+    http: &Client,
+    config: &AppConfig,
+    offline: Option<Arc<dyn ClassificationProvider>>,
+    usage: Option<Arc<UsageRepository>>,
+) -> Arc<dyn ClassificationProvider> {
+    let primary = build_provider(http, &config.ai, config, usage.clone());
+    if config.ai_fallbacks.is_empty() && offline.is_none() {
+        return primary;
+    }
+    let mut chain = vec![(config.ai.kind.name(), primary)];
+    chain.extend(config.ai_fallbacks.iter().map(|provider| {
+        (
+            provider.kind.name(),
+            build_provider(http, provider, config, usage.clone()),
+        )
+    }));
+    chain.extend(offline.map(|classifier| ("bayes", classifier)));
+    Arc::new(FallbackClassifier::new(chain, config.ai_timeout))
+}
+
+/// Builds a single provider without fallbacks; chat-completions providers record their
+/// token usage when `usage` is given.
+pub fn build_provider(
+    http: &Client,
+    provider: &AiProviderConfig,
+    config: &AppConfig,
+    usage: Option<Arc<UsageRepository>>,
+) -> Arc<dyn ClassificationProvider> {
+    let retry = RetryPolicy {
+        max_retries: config.resilience.ai_max_retries,
+        base_delay: config.resilience.ai_retry_base_delay,
+    };
+    match provider.kind {
+        AiProviderKind::Ollama => {
+            Arc::new(OllamaClient::new(http.clone(), provider.clone(), retry))
+        }
+        AiProviderKind::Heuristic => Arc::new(HeuristicClassifier::new(config.live.clone())),
+        _ => Arc::new(ChatCompletionClient::new(
+            http.clone(),
+            provider.clone(),
+            retry,
+            usage,
+        )),
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::AiProviderConfig, domain::types::ClassificationMap};
+
+use super::{
+    inference::{
+        build_messages, chunk_entries, classification_schema, completion_tokens,
+        parse_classification, repair_messages, ChatMessage,
+    },
+    render_batch,
+    retry::RetryPolicy,
+    ClassificationProvider, PromptEntry,
+};
+
+/// Classifier backed by a local Ollama server's native `/api/chat` endpoint, so group
+/// messages never leave the host.
+#[derive(Clone)]
+pub struct OllamaClient {
+    http: Client,
+    config: AiProviderConfig,
+    retry: RetryPolicy,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    /// JSON schema the answer must follow.
+    format: serde_json::Value,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    num_ctx: usize,
+    num_predict: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    message: Option<OllamaMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaMessage {
+    content: String,
+}
+
+impl OllamaClient {
+    pub fn new(http: Client, config: AiProviderConfig, retry: RetryPolicy) -> Self {
+        Self {
+            http,
+            config,
+            retry,
+        }
+    }
+
+    /// Classifies the batch, split into several requests when it would not fit the
+    /// model's context window.
+    pub async fn classify(
+        &self,
+        system: &str,
+        entries: &[PromptEntry],
+    ) -> Result<ClassificationMap> {
+        let chunks = chunk_entries(system, entries, self.config.context_tokens);
+        if chunks.len() > 1 {
+            tracing::info!(
+                provider = "ollama",
+                chunks = chunks.len(),
+                context_tokens = self.config.context_tokens,
+                "batch split to fit the model context"
+            );
+        }
+        let mut classification = ClassificationMap::new();
+        for chunk in chunks {
+            classification.extend(self.request(system, chunk).await?);
+        }
+        Ok(classification)
+    }
+
+    async fn request(&self, system: &str, entries: &[PromptEntry]) -> Result<ClassificationMap> {
+        let mut request = OllamaChatRequest {
+            model: self.config.model.clone(),
+            messages: build_messages(system, &render_batch(entries)),
+            stream: false,
+            format: classification_schema(),
+            options: OllamaOptions {
+                temperature: 0.2,
+                num_ctx: self.config.context_tokens,
+                num_predict: completion_tokens(entries.len()),
+            },
+        };
+        let content = self.send(&request).await?;
+        match parse_classification(&content, entries) {
+            Ok(classification) => Ok(classification),
+            Err(err) => {
+                tracing::warn!(
+                    provider = "ollama",
+                    error = %format!("{err:#}"),
+                    "invalid model output; asking for a repaired answer"
+                );
+                request.messages.extend(repair_messages(&content, &err));
+                let content = self.send(&request).await?;
+                parse_classification(&content, entries)
+                    .context("model output still invalid after a repair attempt")
+            }
+        }
+    }
+
+    /// Sends one request and returns the answer text.
+    async fn send(&self, request: &OllamaChatRequest) -> Result<String> {
+        tracing::debug!(
+            provider = "ollama",
+            model = %self.config.model,
+            messages = request.messages.len(),
+            "Sending request to AI provider"
+        );
+
+        let url = format!("{}/api/chat", self.config.base_url);
+        let http_response = self
+            .retry
+            .send("ollama", || {
+                let builder = self.http.post(&url).json(request);
+                match &self.config.api_key {
+                    Some(api_key) => builder.bearer_auth(api_key),
+                    None => builder,
+                }
+            })
+            .await
+            .with_context(|| format!("failed to reach Ollama at {}", self.config.base_url))?;
+
+        if let Err(err) = http_response.error_for_status_ref() {
+            let status = http_response.status();
+            let error_text = http_response.text().await.unwrap_or_default();
+            tracing::error!(
+                provider = "ollama",
+                status = %status,
+                error_body = %error_text,
+                "AI provider request failed"
+            );
+            return Err(err).context(format!("ollama API error {}: {}", status, error_text));
+        }
+
+        let response: OllamaChatResponse = http_response.json().await?;
+        response
+            .message
+            .map(|message| message.content)
+            .context("Ollama response missing message content")
+    }
+}
+
+impl ClassificationProvider for OllamaClient {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(OllamaClient::classify(self, system, entries))
+    }
+}
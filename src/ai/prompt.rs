@@ -0,0 +1,216 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+
+use crate::{config::PromptConfig, heuristics::language};
+
+/// Built-in classifier instructions, used unless `SYSTEM_PROMPT_FILE` points elsewhere.
+/// Placeholders: `{{chat_title}}`, `{{message_language}}`, `{{language}}` and
+/// `{{custom_criteria}}`.
+pub const DEFAULT_TEMPLATE: &str = r#"You are a bot that reads Telegram messages (including quoted channel/group content and extracted link previews) and classifies them as spam or not spam. Focus only on spam detection—do not censor or flag content just because it contains adult language/images unless it is clearly promotional spam.
+Classify as spam (true) ONLY if at least one of the following is present:
+1. Cryptocurrency, NFT, or Web3 promotions.
+2. Illegal advertising, gambling, drugs, adult content, or unsafe links.
+3. Multi-level marketing or pyramid schemes.
+4. Link or invite spam intended to drive users to other groups, channels, or websites (always inspect the provided channel/group name and the linked URL together, including deep links like https://t.me/c/...).
+5. Obvious phishing or scam attempts.
+6. Investment, stock/coin tipping, "real-time entry" or profit-guarantee promotions, even when formatted as an invitation to a Telegram channel or group. Treat quoted channel text plus its link as part of the message.
+7. Korean stock pump phrases such as "실시간 종목타점", "종목 추천", "타점 공유", "확정 수익" combined with Telegram links or invitations. These are always spam.
+{{custom_criteria}}
+
+If a message merely contains adult or explicit content but is not promoting anything and does not meet any spam criteria above, return `spam: false`.
+
+Ignore non-spam messages, normal conversation, admin messages, or bot commands.
+
+The messages were posted in: {{chat_title}}. Most of them are written in {{message_language}}.
+
+Entries may carry metadata lines such as "감지된 신호: ..." (local heuristic findings like wallet addresses or phone numbers) and "추정 계정 나이: 약 N일" (account age estimated from the user ID). Treat them as supporting evidence only; they never make a message spam on their own.
+
+Return a JSON object with one result per message using this schema:
+{
+  "results": [
+    {
+      "id": "<message_id>",
+      "spam": <bool>,
+      "reason": <string|null>,
+      "confidence": <number between 0 and 1>
+    }
+  ]
+}
+- Always include all four fields. When spam is true, reason MUST be a short {{language}} sentence (<80 chars) that cites the specific spam signal (e.g., "실시간 종목타점 채널 홍보 링크"). When spam is false, set reason to null.
+- When spam is true, reason is MANDATORY and must be a non-empty {{language}} sentence (<80 chars) explaining the exact spam signal. If you cannot determine a signal, set reason to "모델이 사유를 제공하지 않았습니다." Do NOT leave reason blank or null when spam is true.
+- confidence is how sure you are that the spam value is correct: close to 1.0 for unmistakable cases, around 0.5 when the message is ambiguous.
+- Never invent message IDs or return extra keys; id is the number before the colon of each message.
+
+Example classification for the message
+123: [실시간 종목타점 공유하는 채널 ... 확인하기(URL: https://t.me/c/2485256729/1/205)]
+Output: {"results": [{"id": "123", "spam": true, "reason": "실시간 종목타점 텔레그램 채널 홍보", "confidence": 0.97}]}."#;
+
+/// Extra criteria for languages whose spam differs from the Korean patterns above;
+/// `PROMPT_CRITERIA_<LANG>` replaces them.
+fn builtin_criteria(code: &str) -> Option<&'static str> {
+    match code {
+        "en" => Some(
+            "8. English \"DM me\" offers: crypto or account recovery services, \"earn $500 daily\" remote jobs and dating or adult bait pointing to a profile or link.",
+        ),
+        "ru" => Some(
+            "8. Russian \"заработок\", \"удалённая работа\", \"пиши в ЛС\" offers, drug shop adverts (\"закладки\", \"клад\") and paid-subscription bait, even without a link.",
+        ),
+        _ => None,
+    }
+}
+
+struct Templates {
+    base: String,
+    /// Whole-template overrides from `SYSTEM_PROMPT_FILE_<LANG>`, keyed by language code.
+    by_language: HashMap<String, String>,
+}
+
+/// The classifier's system prompt template, rendered per batch.
+pub struct SystemPrompt {
+    config: PromptConfig,
+    templates: RwLock<Templates>,
+}
+
+impl SystemPrompt {
+    /// Reads the configured template files, or uses [`DEFAULT_TEMPLATE`] when none is set.
+    pub fn load(config: PromptConfig) -> Result<Self> {
+        let templates = read_templates(&config)?;
+        Ok(Self {
+            config,
+            templates: RwLock::new(templates),
+        })
+    }
+
+    /// Re-reads the template files, keeping the current templates if any of them fails.
+    /// Returns the files read; empty when the built-in prompt is in use.
+    pub fn reload(&self) -> Result<Vec<PathBuf>> {
+        let templates = read_templates(&self.config)?;
+        *self.templates.write() = templates;
+        let sources: Vec<PathBuf> = self
+            .config
+            .file
+            .iter()
+            .chain(self.config.language_files.values())
+            .map(PathBuf::from)
+            .collect();
+        tracing::info!(
+            target: "processor",
+            files = sources.len(),
+            "system prompt reloaded"
+        );
+        Ok(sources)
+    }
+
+    /// Fills in the placeholders for a batch drawn from the given chats, picking the
+    /// template and extra criteria for the batch's dominant language.
+    pub fn render(&self, chat_titles: &[&str], message_language: Option<&str>) -> String {
+        let chat_title = if chat_titles.is_empty() {
+            "Unknown".to_string()
+        } else {
+            chat_titles.join(", ")
+        };
+        let language_criteria = message_language.and_then(|code| {
+            self.config
+                .language_criteria
+                .get(code)
+                .map(String::as_str)
+                .or_else(|| builtin_criteria(code))
+        });
+        let criteria = [Some(self.config.custom_criteria.trim()), language_criteria]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|criteria| !criteria.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let templates = self.templates.read();
+        let template = message_language
+            .and_then(|code| templates.by_language.get(code))
+            .unwrap_or(&templates.base);
+        render_template(
+            template,
+            &[
+                ("chat_title", &chat_title),
+                (
+                    "message_language",
+                    message_language.map_or("mixed or unknown languages", language::english_name),
+                ),
+                ("language", &self.config.language),
+                ("custom_criteria", &criteria),
+            ],
+        )
+    }
+}
+
+fn read_templates(config: &PromptConfig) -> Result<Templates> {
+    let base = match &config.file {
+        Some(path) => read_file(path)?,
+        None => DEFAULT_TEMPLATE.to_string(),
+    };
+    let by_language = config
+        .language_files
+        .iter()
+        .map(|(code, path)| Ok((code.clone(), read_file(path)?)))
+        .collect::<Result<_>>()?;
+    Ok(Templates { base, by_language })
+}
+
+fn read_file(path: &str) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read system prompt from {path}"))
+}
+
+/// Replaces each `{{name}}`; unknown placeholders are left as written.
+fn render_template(template: &str, vars: &[(&str, &str)]) -> String {
+    vars.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(template: &str) -> SystemPrompt {
+        SystemPrompt {
+            config: PromptConfig {
+                file: None,
+                language: "English".to_string(),
+                custom_criteria: "8. Job offers.\n".to_string(),
+                few_shot_examples: 0,
+                language_files: HashMap::new(),
+                language_criteria: HashMap::from([("ru".to_string(), "9. Закладки.".to_string())]),
+            },
+            templates: RwLock::new(Templates {
+                base: template.to_string(),
+                by_language: HashMap::from([(
+                    "ja".to_string(),
+                    "日本語 {{chat_title}}".to_string(),
+                )]),
+            }),
+        }
+    }
+
+    #[test]
+    fn render_fills_known_placeholders_only() {
+        let prompt =
+            prompt("Chats: {{chat_title}} / {{language}} / {{custom_criteria}} / {{other}}");
+        assert_eq!(
+            prompt.render(&["A", "B"], None),
+            "Chats: A, B / English / 8. Job offers. / {{other}}"
+        );
+    }
+
+    #[test]
+    fn render_picks_language_variants() {
+        let prompt = prompt("{{message_language}}: {{custom_criteria}}");
+        assert_eq!(
+            prompt.render(&[], Some("ru")),
+            "Russian: 8. Job offers.\n9. Закладки."
+        );
+        assert!(prompt.render(&[], Some("en")).contains("DM me"));
+        assert_eq!(prompt.render(&["A"], Some("ja")), "日本語 A");
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::domain::ClassificationMap;
+
+use super::{render_batch, ClassificationProvider, PromptEntry};
+
+/// Sender display name and username in a prompt entry header (`id: [name | username | ...]`).
+static HEADER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^(\d+): \[[^|\n]*\| [^|\n]* \|").expect("valid prompt header regex")
+});
+static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").expect("valid email regex")
+});
+static MENTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@[A-Za-z0-9_]{4,}").expect("valid mention regex"));
+static PHONE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\+?\d[\d .-]{7,}\d").expect("valid phone regex"));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    recorded_at: DateTime<Utc>,
+    prompt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response: Option<ClassificationMap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Masks sender names, usernames, mentions, e-mail addresses and phone numbers so
+/// recordings can leave production; message IDs and the rest of the text are kept.
+pub fn redact(prompt: &str) -> String {
+    let redacted = HEADER_REGEX.replace_all(prompt, "$1: [<user> | <user> |");
+    let redacted = EMAIL_REGEX.replace_all(&redacted, "<email>");
+    let redacted = MENTION_REGEX.replace_all(&redacted, "@<user>");
+    PHONE_REGEX.replace_all(&redacted, "<phone>").into_owned()
+}
+
+/// Passes calls through to the real classifier and appends each redacted
+/// prompt/response pair to a JSONL file.
+pub struct RecordingClassifier {
+    inner: Arc<dyn ClassificationProvider>,
+    path: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl RecordingClassifier {
+    pub fn new(inner: Arc<dyn ClassificationProvider>, path: PathBuf) -> Self {
+        Self {
+            inner,
+            path,
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn append(&self, interaction: &Interaction) -> Result<()> {
+        let mut line = serde_json::to_string(interaction)?;
+        line.push('\n');
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+impl ClassificationProvider for RecordingClassifier {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(async move {
+            let result = self.inner.classify(system, entries).await;
+            let interaction = Interaction {
+                recorded_at: Utc::now(),
+                prompt: redact(&render_batch(entries)),
+                response: result.as_ref().ok().cloned(),
+                error: result.as_ref().err().map(|err| format!("{err:#}")),
+            };
+            if let Err(err) = self.append(&interaction).await {
+                tracing::warn!(
+                    target: "processor",
+                    error = %err,
+                    path = %self.path.display(),
+                    "failed to record classifier interaction"
+                );
+            }
+            result
+        })
+    }
+}
+
+/// Serves recorded responses instead of calling the API. Prompts are matched after
+/// redaction; repeated prompts get their recordings in order, the last one repeating.
+pub struct ReplayClassifier {
+    recordings: Mutex<HashMap<String, VecDeque<Interaction>>>,
+}
+
+impl ReplayClassifier {
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read AI recordings from {}", path.display()))?;
+        let mut recordings: HashMap<String, VecDeque<Interaction>> = HashMap::new();
+        for (index, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let interaction: Interaction = serde_json::from_str(line)
+                .with_context(|| format!("invalid recording on line {}", index + 1))?;
+            recordings
+                .entry(interaction.prompt.clone())
+                .or_default()
+                .push_back(interaction);
+        }
+        tracing::info!(
+            target: "processor",
+            prompts = recordings.len(),
+            path = %path.display(),
+            "loaded AI recordings for replay"
+        );
+        Ok(Self {
+            recordings: Mutex::new(recordings),
+        })
+    }
+
+    fn next(&self, prompt: &str) -> Result<ClassificationMap> {
+        let mut recordings = self.recordings.lock();
+        let queue = recordings
+            .get_mut(&redact(prompt))
+            .ok_or_else(|| anyhow!("no recorded response for this prompt"))?;
+        let interaction = if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+        .expect("recording queues are never empty");
+        match (interaction.response, interaction.error) {
+            (Some(response), _) => Ok(response),
+            (None, error) => Err(anyhow!(
+                "recorded classifier error: {}",
+                error.unwrap_or_default()
+            )),
+        }
+    }
+}
+
+impl ClassificationProvider for ReplayClassifier {
+    fn classify<'a>(
+        &'a self,
+        _system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        let result = self.next(&render_batch(entries));
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_masks_identities_but_keeps_message_ids() {
+        let prompt = "42: [홍길동 | gildong | 비멤버] [위험 점수: 11] 문의 @seller_bot 010-1234-5678 a.b@mail.com";
+        assert_eq!(
+            redact(prompt),
+            "42: [<user> | <user> | 비멤버] [위험 점수: 11] 문의 @<user> <phone> <email>"
+        );
+    }
+}
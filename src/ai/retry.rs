@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+use tokio::time::sleep;
+
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Re-sends requests that failed transiently (connection errors, timeouts, 429 and 5xx)
+/// with exponential, jittered backoff. `Retry-After` is honoured when the API sends it.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns the first non-transient outcome, or the last one once retries run out.
+    pub async fn send(
+        &self,
+        provider: &str,
+        request: impl Fn() -> RequestBuilder,
+    ) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let delay = match request().send().await {
+                Ok(response) if attempt < self.max_retries && is_transient(response.status()) => {
+                    let delay = retry_after(&response).unwrap_or_else(|| self.backoff(attempt));
+                    tracing::warn!(
+                        target: "processor",
+                        provider,
+                        status = %response.status(),
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "transient AI provider error; retrying"
+                    );
+                    delay
+                }
+                Err(err)
+                    if attempt < self.max_retries && (err.is_timeout() || err.is_connect()) =>
+                {
+                    let delay = self.backoff(attempt);
+                    tracing::warn!(
+                        target: "processor",
+                        provider,
+                        error = %err,
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "AI provider unreachable; retrying"
+                    );
+                    delay
+                }
+                outcome => return outcome,
+            };
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_DELAY);
+        exponential.mul_f64(0.5 + fastrand::f64())
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    let secs: u64 = response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs).min(MAX_DELAY))
+}
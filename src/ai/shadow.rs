@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use super::{prompt::SystemPrompt, ClassificationProvider};
+
+/// Candidate classifier that sees every batch the live one does; its verdicts are only
+/// recorded for comparison, never acted on.
+pub struct ShadowClassifier {
+    pub provider: Arc<dyn ClassificationProvider>,
+    /// Candidate system prompt; the live one when `None`.
+    pub prompt: Option<Arc<SystemPrompt>>,
+}
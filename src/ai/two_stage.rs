@@ -0,0 +1,131 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+
+use crate::domain::{ClassificationDecision, ClassificationMap};
+
+use super::{ClassificationProvider, PromptEntry};
+
+/// Verdict counts since startup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TwoStageReport {
+    pub flagged: u64,
+    pub confirmed: u64,
+}
+
+impl TwoStageReport {
+    pub fn overturned(&self) -> u64 {
+        self.flagged - self.confirmed
+    }
+
+    /// Share of triage spam verdicts the confirmation model rejected.
+    pub fn disagreement_rate(&self) -> f64 {
+        if self.flagged == 0 {
+            return 0.0;
+        }
+        self.overturned() as f64 / self.flagged as f64
+    }
+}
+
+/// Classifies every batch with a cheap triage model and re-checks only its spam
+/// verdicts with a stronger model; a message is spam only when both agree.
+pub struct TwoStageClassifier {
+    triage: Arc<dyn ClassificationProvider>,
+    confirmation: Arc<dyn ClassificationProvider>,
+    flagged: AtomicU64,
+    confirmed: AtomicU64,
+}
+
+impl TwoStageClassifier {
+    pub fn new(
+        triage: Arc<dyn ClassificationProvider>,
+        confirmation: Arc<dyn ClassificationProvider>,
+    ) -> Self {
+        Self {
+            triage,
+            confirmation,
+            flagged: AtomicU64::new(0),
+            confirmed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn report(&self) -> TwoStageReport {
+        TwoStageReport {
+            flagged: self.flagged.load(Ordering::Relaxed),
+            confirmed: self.confirmed.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn classify_twice(
+        &self,
+        system: &str,
+        entries: &[PromptEntry],
+    ) -> Result<ClassificationMap> {
+        let mut classification = self.triage.classify(system, entries).await?;
+        let flagged: HashSet<&str> = classification
+            .iter()
+            .filter(|(_, decision)| decision.spam)
+            .map(|(message_id, _)| message_id.as_str())
+            .collect();
+        let subset: Vec<PromptEntry> = entries
+            .iter()
+            .filter(|entry| flagged.contains(entry.id.as_str()))
+            .cloned()
+            .collect();
+        if subset.is_empty() {
+            return Ok(classification);
+        }
+
+        let mut verdicts = self
+            .confirmation
+            .classify(system, &subset)
+            .await
+            .context("confirmation model failed")?;
+        let mut confirmed = 0u64;
+        for entry in &subset {
+            let decision = match verdicts.remove(&entry.id) {
+                Some(decision) if decision.spam => {
+                    confirmed += 1;
+                    decision
+                }
+                Some(decision) => decision,
+                None => ClassificationDecision {
+                    spam: false,
+                    reason: None,
+                    confidence: 1.0,
+                },
+            };
+            classification.insert(entry.id.clone(), decision);
+        }
+
+        let flagged = subset.len() as u64;
+        self.flagged.fetch_add(flagged, Ordering::Relaxed);
+        self.confirmed.fetch_add(confirmed, Ordering::Relaxed);
+        tracing::info!(
+            target: "processor",
+            flagged,
+            confirmed,
+            overturned = flagged - confirmed,
+            disagreement_rate = format!("{:.3}", self.report().disagreement_rate()),
+            "triage spam verdicts re-checked"
+        );
+        Ok(classification)
+    }
+}
+
+impl ClassificationProvider for TwoStageClassifier {
+    fn classify<'a>(
+        &'a self,
+        system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(self.classify_twice(system, entries))
+    }
+}
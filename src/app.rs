@@ -6,25 +6,55 @@ use chrono_tz::Tz;
 use reqwest::Client;
 use teloxide::prelude::*;
 use tokio::{
+    sync::Semaphore,
     task::JoinHandle,
     time::{sleep, timeout},
 };
 use tokio_cron_scheduler::JobScheduler;
 
 use crate::{
-    ai::CerebrasClient,
-    config::AppConfig,
-    db::{self, whitelist::WhitelistRepository},
+    ai::{
+        self,
+        bayes::BayesClassifier,
+        prompt::SystemPrompt,
+        recording::{RecordingClassifier, ReplayClassifier},
+        shadow::ShadowClassifier,
+        two_stage::TwoStageClassifier,
+        ClassificationProvider,
+    },
+    cas::CasClient,
+    config::{AiRecordMode, AppConfig, ExtraBotConfig, PromptConfig},
+    db::{self, whitelist::WhitelistRepository, Repositories},
     domain::{MessageJob, QueueSnapshot},
+    heuristics::{flood::FloodDetector, raid::RaidDetector},
+    i18n::{t, tf, Msg},
     infrastructure::{
-        directories::ResolvedPaths, notifier::notify_admin_group, shutdown::Shutdown,
+        directories::ResolvedPaths, notifier::notify_admin_group, process_stats, shutdown::Shutdown,
     },
+    media::{ocr::VideoOcr, transcription::Transcriber, vision::ImageDescriber},
+    rules::RuleEngine,
     tasks::{
+        backup::DatabaseBackup,
+        ban_expiry::BanExpiry,
+        bayes_training::BayesTrainer,
+        db_maintenance::DatabaseMaintenance,
+        digest::SpamDigest,
+        export::SpamLogExporter,
         processor::MessageProcessor,
         queue::MessageQueue,
-        scheduler::{configure_restart_jobs, RestartCallback},
+        reputation_decay::ReputationDecay,
+        retention::RetentionEnforcer,
+        scheduler::{wait_for_idle, JobRegistry, RestartCallback, RestartScheduleManager},
+        status_board::StatusBoard,
+        usage_summary::UsageSummary,
+        whitelist_audit::WhitelistAuditor,
+        whitelist_expiry::WhitelistExpiry,
+    },
+    telegram::{
+        captcha::CaptchaGate, chat_admins::ChatAdminCache, profile::ProfileCache, raid,
+        types::AppState, TelegramService,
     },
-    telegram::TelegramService,
+    url_reputation::UrlReputationClient,
     web_content::WebContentFetcher,
 };
 
@@ -33,6 +63,8 @@ pub struct SpamGuardApp {
     scheduler: JobScheduler,
     processor_handle: JoinHandle<()>,
     telegram: TelegramService,
+    /// Dispatchers of the `EXTRA_BOTS`, run alongside the primary one.
+    extra_telegram: Vec<TelegramService>,
     whitelist: Arc<WhitelistRepository>,
     shutdown: Shutdown,
     config: Arc<AppConfig>,
@@ -47,17 +79,119 @@ impl SpamGuardApp {
     ) -> Result<Self> {
         let config = Arc::new(config);
         let pool = db::init_pool(&paths.db_path).await?;
-        let whitelist = Arc::new(WhitelistRepository::new(pool));
+        let repos = Repositories::new(pool);
+        let whitelist = repos.whitelist.clone();
 
         let http_client = Client::builder()
             .user_agent(format!("fuckyou-spam-rust/{}", env!("CARGO_PKG_VERSION")))
             .build()?;
 
-        let cerebras = Arc::new(CerebrasClient::new(
-            http_client.clone(),
-            config.cerebras.clone(),
-        ));
-        let web_fetcher = Arc::new(WebContentFetcher::new(http_client, config.web.clone())?);
+        let offline = config.bayes.as_ref().map(|bayes| {
+            Arc::new(BayesClassifier::load(
+                paths.data_dir.join(&bayes.model_file),
+                bayes.spam_threshold,
+            ))
+        });
+        let provider = ai::build_classifier(
+            &http_client,
+            &config,
+            offline
+                .clone()
+                .map(|classifier| classifier as Arc<dyn ClassificationProvider>),
+            Some(repos.usage.clone()),
+        );
+        let two_stage = config.ai_confirmation.as_ref().map(|confirmation| {
+            tracing::info!(
+                target: "processor",
+                triage = %config.ai.model,
+                confirmation = %confirmation.model,
+                "two-stage classification enabled"
+            );
+            Arc::new(TwoStageClassifier::new(
+                provider.clone(),
+                ai::build_provider(
+                    &http_client,
+                    confirmation,
+                    &config,
+                    Some(repos.usage.clone()),
+                ),
+            ))
+        });
+        let provider: Arc<dyn ClassificationProvider> = match &two_stage {
+            Some(two_stage) => two_stage.clone(),
+            None => provider,
+        };
+        let classifier: Arc<dyn ClassificationProvider> = match &config.ai_recording {
+            Some(recording) => {
+                let path = paths.data_dir.join(&recording.file);
+                match recording.mode {
+                    AiRecordMode::Record => {
+                        tracing::warn!(
+                            target: "processor",
+                            path = %path.display(),
+                            "recording classifier prompts and responses"
+                        );
+                        Arc::new(RecordingClassifier::new(provider, path))
+                    }
+                    AiRecordMode::Replay => Arc::new(ReplayClassifier::load(&path)?),
+                }
+            }
+            None => provider,
+        };
+        let shadow = match &config.shadow {
+            Some(shadow) => {
+                tracing::info!(
+                    target: "processor",
+                    model = %shadow.provider.model,
+                    prompt_file = shadow.prompt_file.as_deref().unwrap_or("-"),
+                    "shadow classification enabled"
+                );
+                let prompt = match &shadow.prompt_file {
+                    Some(file) => Some(Arc::new(SystemPrompt::load(PromptConfig {
+                        file: Some(file.clone()),
+                        language_files: Default::default(),
+                        ..config.prompt.clone()
+                    })?)),
+                    None => None,
+                };
+                Some(ShadowClassifier {
+                    provider: ai::build_provider(
+                        &http_client,
+                        &shadow.provider,
+                        &config,
+                        Some(repos.usage.clone()),
+                    ),
+                    prompt,
+                })
+            }
+            None => None,
+        };
+        let transcriber = config
+            .transcription
+            .clone()
+            .map(|stt| Arc::new(Transcriber::new(http_client.clone(), stt)));
+        let vision = config
+            .vision
+            .clone()
+            .map(|vision| Arc::new(ImageDescriber::new(http_client.clone(), vision)));
+        let cas = config.cas.clone().map(|cas| {
+            Arc::new(CasClient::new(
+                http_client.clone(),
+                cas,
+                repos.cas_cache.clone(),
+            ))
+        });
+        let url_reputation = config.url_reputation.clone().map(|url_reputation| {
+            Arc::new(UrlReputationClient::new(
+                http_client.clone(),
+                url_reputation,
+                repos.url_reputation_cache.clone(),
+            ))
+        });
+        let web_fetcher = Arc::new(WebContentFetcher::new(
+            config.live.clone(),
+            config.page_render.clone(),
+        )?);
 
         let bot = Bot::new(&config.telegram_bot_token);
         let queue = Arc::new(MessageQueue::<MessageJob>::new());
@@ -66,34 +200,142 @@ impl SpamGuardApp {
             Arc::new(move || queue.snapshot())
         };
 
-        let restart_callback =
-            build_restart_callback(bot.clone(), config.clone(), whitelist.clone());
-        let telegram = TelegramService::new(
+        let processor = MessageProcessor::new(
+            queue.clone(),
             bot.clone(),
+            classifier,
+            Arc::new(SystemPrompt::load(config.prompt.clone())?),
+            web_fetcher,
             config.clone(),
-            whitelist.clone(),
-            queue.clone(),
-            queue_snapshot_provider,
-            restart_callback.clone(),
+            repos.clone(),
         );
+        let mut processor = match shadow {
+            Some(shadow) => processor.with_shadow(shadow),
+            None => processor,
+        };
+        let extra_bots: Vec<(ExtraBotConfig, Bot)> = config
+            .extra_bots
+            .iter()
+            .map(|extra| (extra.clone(), Bot::new(&extra.token)))
+            .collect();
+        for (extra, extra_bot) in &extra_bots {
+            processor = processor.with_chat_bot(&extra.chat_ids, extra_bot.clone());
+        }
+        let processor = Arc::new(processor);
 
-        let processor = Arc::new(MessageProcessor::new(
+        let restart_callback =
+            build_restart_callback(bot.clone(), config.clone(), whitelist.clone());
+        let scheduled_restart = if config.scheduler.restart_wait_for_idle {
+            let queue = queue.clone();
+            let processor = processor.clone();
+            wait_for_idle(
+                restart_callback.clone(),
+                Arc::new(move || queue.snapshot().is_empty() && !processor.is_busy()),
+                config.scheduler.restart_max_delay,
+            )
+        } else {
+            restart_callback.clone()
+        };
+        let scheduler = JobScheduler::new().await?;
+        let restart_schedule = RestartScheduleManager::load(
+            scheduler.clone(),
+            repos.restart_crons.clone(),
+            &config.scheduler.cron_specs,
+            scheduled_restart,
+        )
+        .await?;
+        let rules = RuleEngine::load(
+            repos.rules.clone(),
+            config.timezone.parse().unwrap_or(chrono_tz::Asia::Seoul),
+        )
+        .await?;
+        if let Some(rules_file) = &config.rules_file.path {
+            rules.clone().watch_file(
+                paths.data_dir.join(rules_file),
+                config.rules_file.poll_interval,
+                shutdown.subscribe(),
+            );
+        }
+        let state = AppState {
+            config: config.clone(),
+            db: repos.clone(),
             queue,
-            bot.clone(),
-            cerebras,
-            web_fetcher,
-            config.clone(),
-        ));
+            queue_snapshot: queue_snapshot_provider,
+            restart_schedule,
+            processor: processor.clone(),
+            rules,
+            profiles: Arc::new(ProfileCache::new(
+                config.live.get().heuristics.profile_cache_ttl,
+            )),
+            chat_admins: Arc::new(ChatAdminCache::new(config.chat_admins.cache_ttl)),
+            whitelist_requests: Arc::default(),
+            transcriber,
+            video_ocr: config
+                .video_ocr
+                .clone()
+                .map(|ocr| Arc::new(VideoOcr::new(ocr))),
+            vision,
+            media_slots: Arc::new(Semaphore::new(config.media_analysis_concurrency)),
+            two_stage,
+            raids: config
+                .raid
+                .clone()
+                .map(|raid| Arc::new(RaidDetector::new(raid))),
+            floods: config
+                .flood
+                .clone()
+                .map(|flood| Arc::new(FloodDetector::new(flood))),
+            captcha: config
+                .captcha
+                .clone()
+                .map(|captcha| Arc::new(CaptchaGate::new(captcha))),
+            cas,
+            url_reputation,
+            admin_bot: bot.clone(),
+            extra_bot: None,
+        };
+        let extra_telegram = extra_bots
+            .into_iter()
+            .map(|(extra, extra_bot)| {
+                tracing::info!(target: "telegram", name = %extra.name, chats = extra.chat_ids.len(), "추가 봇 구성");
+                TelegramService::new(
+                    extra_bot,
+                    AppState {
+                        extra_bot: Some(Arc::new(extra)),
+                        ..state.clone()
+                    },
+                    restart_callback.clone(),
+                )
+            })
+            .collect();
+        let telegram = TelegramService::new(bot.clone(), state, restart_callback);
+
         let processor_handle = processor.clone().spawn(shutdown.subscribe());
+        {
+            let processor = processor.clone();
+            raid::resume_lockdowns(
+                move |chat_id| processor.bot_for(chat_id).clone(),
+                repos.lockdowns.clone(),
+            )
+            .await;
+        }
+        processor.resume_deletion_notices().await;
 
-        let scheduler =
-            configure_restart_jobs(&config.scheduler.cron_specs, restart_callback).await?;
+        let jobs = JobRegistry::new(
+            scheduler.clone(),
+            repos.job_runs.clone(),
+            config.scheduler.catch_up_missed_jobs,
+        );
+        register_maintenance_jobs(&jobs, &bot, &config, &repos, &paths, &processor, offline)
+            .await?;
+        scheduler.start().await?;
 
         Ok(Self {
             _paths: paths,
             scheduler,
             processor_handle,
             telegram,
+            extra_telegram,
             whitelist,
             shutdown,
             config,
@@ -107,6 +349,7 @@ impl SpamGuardApp {
             mut scheduler,
             mut processor_handle,
             telegram,
+            extra_telegram,
             whitelist,
             shutdown,
             config,
@@ -114,11 +357,27 @@ impl SpamGuardApp {
         } = self;
 
         tracing::info!("텔레그램 스팸 감지 봇 (Rust) 시작");
+        // CPU usage is measured between samples and uptime from the first one, so take
+        // it now.
+        process_stats::sample();
 
-        notify_admin_group(&bot, config.as_ref(), "스팸 감지 봇이 시작되었습니다.").await;
+        notify_admin_group(&bot, config.as_ref(), t(config.language, Msg::BotStarted)).await;
+        #[cfg(unix)]
+        install_reload_handler(bot.clone(), config.clone());
 
         let mut shutdown_listener = shutdown.subscribe();
         let shutdown_timeout = Duration::from_secs(5);
+        let extra_handles: Vec<JoinHandle<()>> = extra_telegram
+            .into_iter()
+            .map(|service| {
+                let listener = shutdown.subscribe();
+                tokio::spawn(async move {
+                    if let Err(err) = service.run(listener).await {
+                        tracing::error!(?err, "추가 봇 dispatcher 종료 중 오류");
+                    }
+                })
+            })
+            .collect();
         let mut telegram_future = Box::pin(telegram.run(shutdown.subscribe()));
         let mut telegram_completed = false;
 
@@ -157,6 +416,18 @@ impl SpamGuardApp {
             }
         }
 
+        if !extra_handles.is_empty()
+            && timeout(shutdown_timeout, futures::future::join_all(extra_handles))
+                .await
+                .is_err()
+        {
+            tracing::warn!(
+                target: "telegram",
+                "추가 봇 dispatcher가 {:?} 내에 종료되지 않았습니다.",
+                shutdown_timeout
+            );
+        }
+
         match timeout(shutdown_timeout, scheduler.shutdown()).await {
             Ok(Ok(())) => {}
             Ok(Err(err)) => {
@@ -200,11 +471,158 @@ impl SpamGuardApp {
         }
 
         tracing::info!("봇 종료 완료");
-        notify_admin_group(&bot, config.as_ref(), "스팸 감지 봇이 종료되었습니다.").await;
+        notify_admin_group(&bot, config.as_ref(), t(config.language, Msg::BotStopped)).await;
         Ok(())
     }
 }
 
+async fn register_maintenance_jobs(
+    jobs: &JobRegistry,
+    bot: &Bot,
+    config: &Arc<AppConfig>,
+    repos: &Repositories,
+    paths: &ResolvedPaths,
+    processor: &Arc<MessageProcessor>,
+    offline: Option<Arc<BayesClassifier>>,
+) -> Result<()> {
+    if let Some(spec) = &config.scheduler.whitelist_audit_cron {
+        let auditor = Arc::new(WhitelistAuditor::new(
+            bot.clone(),
+            repos.whitelist.clone(),
+            config.clone(),
+        ));
+        jobs.register("whitelist_audit", spec, auditor.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.scheduler.whitelist_expiry_cron {
+        let expiry = Arc::new(WhitelistExpiry::new(
+            bot.clone(),
+            repos.whitelist.clone(),
+            config.clone(),
+        ));
+        jobs.register("whitelist_expiry", spec, expiry.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.scheduler.ban_expiry_cron {
+        let expiry = Arc::new(BanExpiry::new(
+            bot.clone(),
+            repos.bans.clone(),
+            processor.clone(),
+            config.clone(),
+        ));
+        jobs.register("ban_expiry", spec, expiry.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.scheduler.status_board_cron {
+        let board = Arc::new(StatusBoard::new(
+            bot.clone(),
+            repos.status_messages.clone(),
+            processor.clone(),
+            config.clone(),
+        ));
+        jobs.register("status_board", spec, board.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.scheduler.db_maintenance_cron {
+        let maintenance = Arc::new(DatabaseMaintenance::new(repos.maintenance.clone(), config));
+        jobs.register("database_maintenance", spec, maintenance.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.backup.cron {
+        let backup = Arc::new(DatabaseBackup::new(repos.backups.clone(), config));
+        jobs.register("database_backup", spec, backup.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.export.cron {
+        let exporter = Arc::new(SpamLogExporter::new(
+            repos.spam_log.clone(),
+            repos.export_cursors.clone(),
+            paths.data_dir.join(&config.export.dir),
+        ));
+        jobs.register("spam_log_export", spec, exporter.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.reputation.decay_cron {
+        let decay = Arc::new(ReputationDecay::new(
+            repos.reputation.clone(),
+            config.clone(),
+        ));
+        jobs.register("reputation_decay", spec, decay.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.retention.cron {
+        let enforcer = Arc::new(RetentionEnforcer::new(
+            bot.clone(),
+            repos.retention.clone(),
+            config.clone(),
+        ));
+        jobs.register("retention_purge", spec, enforcer.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.usage.summary_cron {
+        let summary = Arc::new(UsageSummary::new(
+            bot.clone(),
+            repos.usage.clone(),
+            config.clone(),
+        ));
+        jobs.register("usage_summary", spec, summary.into_task())
+            .await?;
+    }
+    if let Some(spec) = &config.digest.cron {
+        let digest = Arc::new(SpamDigest::new(
+            bot.clone(),
+            repos.clone(),
+            processor.clone(),
+            config.clone(),
+        ));
+        jobs.register("spam_digest", spec, digest.into_task())
+            .await?;
+    }
+    if let (Some(bayes), Some(classifier)) = (&config.bayes, offline) {
+        let untrained = !classifier.is_trained();
+        let trainer = Arc::new(BayesTrainer::new(repos.clone(), classifier, bayes.clone()));
+        if untrained {
+            let trainer = trainer.clone();
+            tokio::spawn(async move {
+                if let Err(err) = trainer.run().await {
+                    tracing::warn!(target: "scheduler", error = %err, "initial offline model training failed");
+                }
+            });
+        }
+        if let Some(spec) = &bayes.train_cron {
+            jobs.register("bayes_training", spec, trainer.into_task())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Reloads the live settings on SIGHUP and tells the admin group what is now in effect.
+#[cfg(unix)]
+fn install_reload_handler(bot: Bot, config: Arc<AppConfig>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+            tracing::warn!(target: "config", "failed to install SIGHUP handler");
+            return;
+        };
+        while hangup.recv().await.is_some() {
+            tracing::info!(target: "config", "SIGHUP received; reloading configuration");
+            let settings = config.live.reload();
+            let message = tf(
+                config.language,
+                Msg::ConfigReloaded,
+                &[
+                    ("chats", &settings.allowed_chat_ids.len()),
+                    ("threshold", &settings.ai_confidence_threshold),
+                ],
+            );
+            notify_admin_group(&bot, config.as_ref(), &message).await;
+        }
+    });
+}
+
 fn build_restart_callback(
     bot: Bot,
     config: Arc<AppConfig>,
@@ -220,7 +638,7 @@ fn build_restart_callback(
         tokio::spawn(async move {
             let tz: Tz = config.timezone.parse().unwrap_or(chrono_tz::Asia::Seoul);
             let ts = Utc::now().with_timezone(&tz).format("%Y-%m-%d %H:%M:%S");
-            let message = format!("자동 재부팅을 시작합니다.\n현재 시각: {ts}");
+            let message = tf(config.language, Msg::RestartStarting, &[("time", &ts)]);
             notify_admin_group(&bot, config.as_ref(), &message).await;
             whitelist.close().await;
             sleep(Duration::from_secs(5)).await;
@@ -230,7 +648,7 @@ fn build_restart_callback(
                 notify_admin_group(
                     &bot,
                     config.as_ref(),
-                    "자동 재부팅 실패: 실행 파일 경로를 찾을 수 없습니다.",
+                    t(config.language, Msg::RestartNoExecutable),
                 )
                 .await;
                 return;
@@ -258,7 +676,7 @@ fn build_restart_callback(
                 notify_admin_group(
                     &bot,
                     config.as_ref(),
-                    "자동 재부팅 실패: 새 프로세스를 시작할 수 없습니다.",
+                    t(config.language, Msg::RestartSpawnFailed),
                 )
                 .await;
                 return;
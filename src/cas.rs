@@ -0,0 +1,100 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::{config::CasConfig, db::cas_cache::CasCacheRepository};
+
+/// How long lookups are skipped after one fails, so an outage costs one timeout per
+/// window instead of one per message.
+const FAILURE_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Client for the CAS (Combot Anti-Spam) ban list, backed by a SQLite cache.
+pub struct CasClient {
+    http: Client,
+    config: CasConfig,
+    cache: Arc<CasCacheRepository>,
+    /// Set after a failed lookup; until then every uncached user counts as not listed.
+    failed_until: Mutex<Option<Instant>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckResponse {
+    /// `true` only when the user has a CAS ban record.
+    ok: bool,
+}
+
+impl CasClient {
+    pub fn new(http: Client, config: CasConfig, cache: Arc<CasCacheRepository>) -> Self {
+        Self {
+            http,
+            config,
+            cache,
+            failed_until: Mutex::new(None),
+        }
+    }
+
+    pub fn ban_on_match(&self) -> bool {
+        self.config.ban
+    }
+
+    /// Whether CAS lists `user_id`; lookup failures count as not listed, and after one
+    /// the remaining uncached users are treated the same for [`FAILURE_BACKOFF`].
+    pub async fn is_banned(&self, user_id: i64) -> bool {
+        let now = Utc::now();
+        let fresh_after =
+            now - chrono::Duration::from_std(self.config.cache_ttl).unwrap_or_default();
+        match self.cache.get(user_id, fresh_after).await {
+            Ok(Some(banned)) => return banned,
+            Ok(None) => {}
+            Err(err) => tracing::warn!(target: "db", error = %err, "failed to read CAS cache"),
+        }
+
+        if self
+            .failed_until
+            .lock()
+            .is_some_and(|until| Instant::now() < until)
+        {
+            return false;
+        }
+        let banned = match self.check(user_id).await {
+            Ok(banned) => banned,
+            Err(err) => {
+                tracing::warn!(target: "telegram", error = %err, user_id, "CAS lookup failed");
+                *self.failed_until.lock() = Some(Instant::now() + FAILURE_BACKOFF);
+                return false;
+            }
+        };
+        if let Err(err) = self.cache.put(user_id, banned, now).await {
+            tracing::warn!(target: "db", error = %err, "failed to cache CAS result");
+        }
+        if banned {
+            tracing::info!(target: "telegram", user_id, "user is on the CAS ban list");
+        }
+        banned
+    }
+
+    async fn check(&self, user_id: i64) -> Result<bool> {
+        let response: CheckResponse = self
+            .http
+            .get(format!(
+                "{}/check",
+                self.config.base_url.trim_end_matches('/')
+            ))
+            .query(&[("user_id", user_id)])
+            .timeout(self.config.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse CAS response")?;
+        Ok(response.ok)
+    }
+}
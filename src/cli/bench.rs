@@ -0,0 +1,138 @@
+use std::hint::black_box;
+
+use chrono::Utc;
+use criterion::Criterion;
+use teloxide::types::{ChatId, MessageId};
+
+use crate::{
+    config::load_heuristics_config,
+    domain::MessageJob,
+    heuristics::{
+        self, known_spam, raid,
+        risk::{self, RiskInput},
+        HeuristicInput,
+    },
+    tasks::processor::format_prompt_entry,
+    telegram::utils::{contains_url, extract_urls, normalize_url},
+};
+
+use super::samples;
+
+const BATCH_SIZE: usize = 500;
+
+/// Runs the hot-path benchmarks and prints Criterion's report; an argument after
+/// `bench` limits the run to benchmarks whose name matches it.
+pub fn run() {
+    let mut criterion = Criterion::default();
+    if let Some(filter) = std::env::args().nth(2) {
+        criterion = criterion.with_filter(filter);
+    }
+    hot_path(&mut criterion);
+    criterion.final_summary();
+}
+
+/// The hot-path benchmark group, shared by the `bench` subcommand and `cargo bench`.
+pub fn hot_path(criterion: &mut Criterion) {
+    let config = load_heuristics_config();
+    let samples: Vec<&str> = samples::all().collect();
+    let raw_urls: Vec<&str> = samples
+        .iter()
+        .flat_map(|text| text.split_whitespace())
+        .filter(|token| token.contains("://"))
+        .collect();
+    let jobs: Vec<MessageJob> = (0..BATCH_SIZE).map(sample_job).collect();
+
+    criterion.bench_function("extract_urls", |b| {
+        b.iter(|| {
+            for text in &samples {
+                black_box(extract_urls(black_box(text), usize::MAX));
+            }
+        })
+    });
+    criterion.bench_function("normalize_url", |b| {
+        b.iter(|| {
+            for url in &raw_urls {
+                black_box(normalize_url(black_box(url)));
+            }
+        })
+    });
+    criterion.bench_function("priority_scoring", |b| {
+        let known = known_spam::KnownSpam::default();
+        b.iter(|| {
+            for text in &samples {
+                let has_links = contains_url(text);
+                let signals = heuristics::analyze(
+                    &HeuristicInput {
+                        text: black_box(text),
+                        entities: &[],
+                        invite_links: &[],
+                        username: None,
+                        account_age_days: Some(20),
+                        has_links,
+                        has_profile_photo: Some(false),
+                        known_spam: &known,
+                    },
+                    &config,
+                );
+                black_box(risk::assess(
+                    &RiskInput {
+                        is_member: false,
+                        has_url: has_links,
+                        invite_links: 0,
+                        reputation: 0,
+                        signals: &signals,
+                    },
+                    &config.risk,
+                ));
+            }
+        })
+    });
+    criterion.bench_function("text_fingerprint", |b| {
+        b.iter(|| {
+            for text in &samples {
+                black_box(known_spam::text_fingerprint(black_box(text)));
+            }
+        })
+    });
+    criterion.bench_function("raid_fingerprint", |b| {
+        b.iter(|| {
+            for text in &samples {
+                black_box(raid::fingerprint(black_box(text)));
+            }
+        })
+    });
+    criterion.bench_function("prompt_assembly_500", |b| {
+        b.iter(|| {
+            let entries: Vec<String> = jobs.iter().map(format_prompt_entry).collect();
+            black_box(entries.join("\n\n"))
+        })
+    });
+}
+
+fn sample_job(index: usize) -> MessageJob {
+    let samples: Vec<&str> = samples::all().collect();
+    let text = samples[index % samples.len()];
+    MessageJob {
+        chat_id: ChatId(-1001234567890),
+        chat_title: Some("Benchmark".to_string()),
+        message_id: MessageId(index as i32),
+        topic_id: None,
+        from_id: Some(7_000_000_000 + index as i64),
+        sender_chat_id: None,
+        sender_is_chat_admin: false,
+        from_display: format!("user{index}"),
+        username: index.is_multiple_of(2).then(|| format!("user{index}")),
+        text: text.to_string(),
+        urls: extract_urls(text, 2),
+        is_group_member: !index.is_multiple_of(3),
+        on_probation: false,
+        member_since: None,
+        account_age_days: Some(30),
+        language: None,
+        priority_score: 12,
+        reputation: 0,
+        signals: Vec::new(),
+        timestamp: Utc::now(),
+        attempts: 0,
+    }
+}
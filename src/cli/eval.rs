@@ -0,0 +1,176 @@
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use reqwest::Client;
+use teloxide::types::{ChatId, MessageId};
+
+use crate::{
+    ai::{self, prompt::SystemPrompt},
+    config::{load_config, load_heuristics_config, HeuristicsConfig},
+    db::{self, feedback::FeedbackRepository},
+    domain::MessageJob,
+    heuristics::{self, known_spam::KnownSpam, language, HeuristicInput},
+    infrastructure::directories,
+    tasks::processor::prompt_entry,
+    telegram::utils::{contains_url, extract_urls},
+};
+
+struct Options {
+    limit: i64,
+    batch: usize,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut options = Self {
+            limit: 200,
+            batch: 10,
+        };
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .with_context(|| format!("{flag} requires a value"))
+            };
+            match flag.as_str() {
+                "--limit" => options.limit = value()?.parse().context("--limit")?,
+                "--batch" => options.batch = value()?.parse().context("--batch")?,
+                other => bail!("unknown option '{other}'; usage: eval [--limit N] [--batch N]"),
+            }
+        }
+        if options.batch == 0 {
+            bail!("--batch must be positive");
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    correct: usize,
+    false_positives: usize,
+    false_negatives: usize,
+    unanswered: usize,
+}
+
+/// Replays the admin-labeled messages in the `feedback` table through the configured
+/// classifier and reports how many it now gets right, to check prompt changes against
+/// past false positives before deploying them.
+pub async fn run(args: &[String]) -> Result<()> {
+    let options = Options::parse(args)?;
+    let config = load_config()?;
+    let paths = directories::ensure_directories(&config.directories)?;
+    let pool = db::init_pool(&paths.db_path).await?;
+    let feedback = FeedbackRepository::new(pool)
+        .recent(None, options.limit)
+        .await?;
+    if feedback.is_empty() {
+        println!("no feedback recorded yet; label deletions with the spam log or /review first");
+        return Ok(());
+    }
+    let classifier = ai::build_classifier(&Client::new(), &config, None, None);
+    let system = SystemPrompt::load(config.prompt.clone())?.render(&["Eval"], None);
+    let heuristics = load_heuristics_config();
+
+    println!(
+        "evaluating {} labeled messages in batches of {}",
+        feedback.len(),
+        options.batch
+    );
+    let mut tally = Tally::default();
+    for chunk in feedback.chunks(options.batch) {
+        let entries: Vec<_> = chunk
+            .iter()
+            .map(|row| prompt_entry(&eval_job(row.id, &row.text, &heuristics)))
+            .collect();
+        let verdicts = match classifier.classify(&system, &entries).await {
+            Ok(verdicts) => verdicts,
+            Err(err) => {
+                eprintln!("classification failed: {err:#}");
+                tally.unanswered += chunk.len();
+                continue;
+            }
+        };
+        for row in chunk {
+            match verdicts.get(&row.id.to_string()) {
+                Some(decision) if decision.spam == row.spam => tally.correct += 1,
+                Some(decision) => {
+                    if decision.spam {
+                        tally.false_positives += 1;
+                    } else {
+                        tally.false_negatives += 1;
+                    }
+                    println!(
+                        "#{} labeled {} but classified {}: {}",
+                        row.id,
+                        label(row.spam),
+                        label(decision.spam),
+                        decision.reason.as_deref().unwrap_or("-")
+                    );
+                }
+                None => tally.unanswered += 1,
+            }
+        }
+    }
+
+    let answered = feedback.len() - tally.unanswered;
+    println!();
+    println!(
+        "correct {}/{} ({:.1}%), false positives {}, false negatives {}, unanswered {}",
+        tally.correct,
+        answered,
+        tally.correct as f64 * 100.0 / answered.max(1) as f64,
+        tally.false_positives,
+        tally.false_negatives,
+        tally.unanswered
+    );
+    Ok(())
+}
+
+fn label(spam: bool) -> &'static str {
+    if spam {
+        "spam"
+    } else {
+        "ham"
+    }
+}
+
+/// A job carrying only what the text itself tells; sender details were not recorded.
+fn eval_job(id: i64, text: &str, config: &HeuristicsConfig) -> MessageJob {
+    let has_links = contains_url(text);
+    let signals = heuristics::analyze(
+        &HeuristicInput {
+            text,
+            entities: &[],
+            invite_links: &[],
+            username: None,
+            account_age_days: None,
+            has_links,
+            has_profile_photo: None,
+            known_spam: &KnownSpam::default(),
+        },
+        config,
+    );
+    MessageJob {
+        chat_id: ChatId(-1000000000001),
+        chat_title: Some("Eval".to_string()),
+        message_id: MessageId(id as i32),
+        topic_id: None,
+        from_id: None,
+        sender_chat_id: None,
+        sender_is_chat_admin: false,
+        from_display: format!("eval{id}"),
+        username: None,
+        urls: extract_urls(text, 2),
+        text: text.to_string(),
+        is_group_member: true,
+        on_probation: false,
+        member_since: None,
+        account_age_days: None,
+        language: language::detect(text),
+        priority_score: 0,
+        reputation: 0,
+        signals,
+        timestamp: Utc::now(),
+        attempts: 0,
+    }
+}
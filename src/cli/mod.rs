@@ -0,0 +1,50 @@
+//! Developer subcommands, selected by the first command-line argument. Without one the
+//! binary runs the bot.
+
+#[cfg(feature = "bench")]
+pub mod bench;
+mod eval;
+mod samples;
+mod simulate;
+
+use anyhow::Result;
+
+pub enum Subcommand {
+    /// Criterion benchmarks of the message hot path.
+    Bench,
+    /// Admin-labeled false positives replayed through the configured classifier.
+    Eval { args: Vec<String> },
+    /// Synthetic traffic through the queue and classifier, for capacity planning.
+    Simulate { args: Vec<String> },
+}
+
+impl Subcommand {
+    pub fn from_args() -> Option<Self> {
+        match std::env::args().nth(1).as_deref() {
+            Some("bench") => Some(Self::Bench),
+            Some("eval") => Some(Self::Eval {
+                args: std::env::args().skip(2).collect(),
+            }),
+            Some("simulate") => Some(Self::Simulate {
+                args: std::env::args().skip(2).collect(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Simulate { args } => simulate::run(&args).await,
+            Self::Eval { args } => eval::run(&args).await,
+            #[cfg(feature = "bench")]
+            Self::Bench => {
+                bench::run();
+                Ok(())
+            }
+            #[cfg(not(feature = "bench"))]
+            Self::Bench => {
+                anyhow::bail!("benchmarks are not compiled in; rebuild with `--features bench`")
+            }
+        }
+    }
+}
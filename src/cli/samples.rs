@@ -0,0 +1,20 @@
+//! Representative message texts shared by the benchmark and simulation subcommands.
+
+pub const SPAM: [&str; 4] = [
+    "🔥🔥 무료 리딩방 입장 🔥🔥 매일 수익 300% 보장!! https://t.me/+AbCdEf123 지금 바로 (선착순 50명)",
+    "USDT 지급 이벤트 진행중 지갑주소 TXk3f8Qm2nJc7VvZr9pLwE4yHsD1uGaB6o 로 입금하면 두배 반환 http://bit.ly/x9y8z7.",
+    "카톡 문의 주세요 010-1234-5678 @promo_admin @promo_bot @promo_news @promo_vip @promo_help",
+    "고수익 부업 모집합니다 하루 30분 투자로 월 500 가능 https://side-job.example.xyz/join?ref=abc",
+];
+
+pub const HAM: [&str; 4] = [
+    "오늘 회의는 3시에 시작합니다. 자료는 https://docs.example.com/meeting?id=42 에 올려두었어요.",
+    "ㅋㅋㅋㅋ 그거 진짜 웃기네요",
+    "Check (https://example.org/path_(with)_parens), then [https://example.net/a?b=c]!",
+    "혹시 이번 주 스터디 장소 바뀌었나요? 지난번이랑 같은 곳이면 거기서 뵐게요",
+];
+
+#[cfg(feature = "bench")]
+pub fn all() -> impl Iterator<Item = &'static str> {
+    SPAM.into_iter().chain(HAM)
+}
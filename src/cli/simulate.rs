@@ -0,0 +1,334 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use reqwest::Client;
+use teloxide::types::{ChatId, MessageId};
+use tokio::time::{interval, sleep, MissedTickBehavior};
+
+use crate::{
+    ai::{self, prompt::SystemPrompt, ClassificationProvider, PromptEntry},
+    config::{load_config, load_heuristics_config, HeuristicsConfig},
+    domain::{ClassificationDecision, ClassificationMap, MessageJob},
+    heuristics::{
+        self,
+        known_spam::KnownSpam,
+        language,
+        risk::{self, RiskInput},
+        HeuristicInput,
+    },
+    tasks::{
+        processor::prompt_entry,
+        queue::{MessageQueue, Priority},
+    },
+    telegram::utils::{contains_url, extract_urls},
+};
+
+use super::samples;
+
+/// Marker the simulated classifier looks for; appended to every synthetic spam text.
+const SPAM_MARKER: &str = "#sim-spam";
+
+struct Options {
+    rate: f64,
+    duration: Duration,
+    spam_ratio: f64,
+    real: bool,
+    latency: Duration,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut options = Self {
+            rate: 20.0,
+            duration: Duration::from_secs(30),
+            spam_ratio: 0.3,
+            real: false,
+            latency: Duration::from_millis(800),
+        };
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .with_context(|| format!("{flag} requires a value"))
+            };
+            match flag.as_str() {
+                "--rate" => options.rate = value()?.parse().context("--rate")?,
+                "--duration" => {
+                    options.duration = Duration::from_secs(value()?.parse().context("--duration")?)
+                }
+                "--spam-ratio" => options.spam_ratio = value()?.parse().context("--spam-ratio")?,
+                "--latency-ms" => {
+                    options.latency =
+                        Duration::from_millis(value()?.parse().context("--latency-ms")?)
+                }
+                "--classifier" => match value()?.as_str() {
+                    "mock" => options.real = false,
+                    "real" => options.real = true,
+                    other => bail!("unknown classifier '{other}' (expected mock or real)"),
+                },
+                other => bail!(
+                    "unknown option '{other}'; usage: simulate [--rate N] [--duration SECS] \
+                     [--spam-ratio 0-1] [--classifier mock|real] [--latency-ms N]"
+                ),
+            }
+        }
+        if options.rate <= 0.0 {
+            bail!("--rate must be positive");
+        }
+        Ok(options)
+    }
+}
+
+/// Answers after a fixed delay, flagging entries that carry [`SPAM_MARKER`].
+struct SimulatedClassifier {
+    latency: Duration,
+}
+
+impl ClassificationProvider for SimulatedClassifier {
+    fn classify<'a>(
+        &'a self,
+        _system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        Box::pin(async move {
+            sleep(self.latency).await;
+            Ok(entries
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.id.clone(),
+                        ClassificationDecision {
+                            spam: entry.text.contains(SPAM_MARKER),
+                            reason: None,
+                            confidence: 1.0,
+                        },
+                    )
+                })
+                .collect())
+        })
+    }
+}
+
+#[derive(Default)]
+struct Stats {
+    latencies_ms: Vec<u64>,
+    batches: usize,
+    flagged: usize,
+    failed_batches: usize,
+}
+
+/// Feeds synthetic traffic through the real queue and scoring code and a mock or real
+/// classifier, then reports throughput, queue depth over time and end-to-end latency.
+pub async fn run(args: &[String]) -> Result<()> {
+    let options = Options::parse(args)?;
+    let (classifier, system): (Arc<dyn ClassificationProvider>, String) = if options.real {
+        let config = load_config()?;
+        (
+            ai::build_classifier(&Client::new(), &config, None, None),
+            SystemPrompt::load(config.prompt)?.render(&["Simulation"], None),
+        )
+    } else {
+        let classifier = Arc::new(SimulatedClassifier {
+            latency: options.latency,
+        });
+        (classifier, String::new())
+    };
+    let heuristics = load_heuristics_config();
+    let queue = Arc::new(MessageQueue::<MessageJob>::new());
+    let stats = Arc::new(Mutex::new(Stats::default()));
+    let producing = Arc::new(AtomicBool::new(true));
+    let sent = Arc::new(AtomicUsize::new(0));
+
+    println!(
+        "simulating {:.1} msg/s for {}s ({:.0}% spam, {} classifier)",
+        options.rate,
+        options.duration.as_secs(),
+        options.spam_ratio * 100.0,
+        if options.real { "real" } else { "mock" }
+    );
+
+    let consumer = {
+        let queue = queue.clone();
+        let stats = stats.clone();
+        let producing = producing.clone();
+        tokio::spawn(async move {
+            loop {
+                let batch = queue.drain_ordered();
+                if batch.is_empty() {
+                    if !producing.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(500)).await;
+                    continue;
+                }
+                let entries: Vec<_> = batch.iter().map(prompt_entry).collect();
+                let result = classifier.classify(&system, &entries).await;
+                let finished = Utc::now();
+                let mut stats = stats.lock();
+                stats.batches += 1;
+                match result {
+                    Ok(classification) => {
+                        stats.flagged += classification.values().filter(|d| d.spam).count();
+                    }
+                    Err(err) => {
+                        stats.failed_batches += 1;
+                        eprintln!("classification failed: {err:#}");
+                    }
+                }
+                for job in &batch {
+                    let latency = (finished - job.timestamp).num_milliseconds().max(0);
+                    stats.latencies_ms.push(latency as u64);
+                }
+            }
+        })
+    };
+
+    let sampler = {
+        let queue = queue.clone();
+        let producing = producing.clone();
+        let sent = sent.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let mut ticker = interval(Duration::from_secs(1));
+            println!("{:>6} {:>8} {:>8} {:>8}", "t(s)", "sent", "high", "normal");
+            loop {
+                ticker.tick().await;
+                let snapshot = queue.snapshot();
+                println!(
+                    "{:>6} {:>8} {:>8} {:>8}",
+                    started.elapsed().as_secs(),
+                    sent.load(Ordering::SeqCst),
+                    snapshot.high_priority,
+                    snapshot.normal_priority
+                );
+                if !producing.load(Ordering::SeqCst) && snapshot.is_empty() {
+                    break;
+                }
+            }
+        })
+    };
+
+    let started = Instant::now();
+    let mut ticker = interval(Duration::from_secs_f64(1.0 / options.rate));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+    let mut index = 0usize;
+    while started.elapsed() < options.duration {
+        ticker.tick().await;
+        let spam = (index as f64 * options.spam_ratio).floor()
+            != ((index + 1) as f64 * options.spam_ratio).floor();
+        let (priority, job) = synthetic_job(index, spam, &heuristics);
+        queue.push(priority, job);
+        sent.fetch_add(1, Ordering::SeqCst);
+        index += 1;
+    }
+    let produced_for = started.elapsed();
+    producing.store(false, Ordering::SeqCst);
+    consumer.await?;
+    sampler.await?;
+    let total = started.elapsed();
+
+    let mut stats = stats.lock();
+    stats.latencies_ms.sort_unstable();
+    let processed = stats.latencies_ms.len();
+    println!();
+    println!(
+        "sent {index} messages in {:.1}s ({:.1} msg/s offered)",
+        produced_for.as_secs_f64(),
+        index as f64 / produced_for.as_secs_f64()
+    );
+    println!(
+        "processed {processed} in {:.1}s ({:.1} msg/s), {} batches (avg {:.1} msgs), {} failed",
+        total.as_secs_f64(),
+        processed as f64 / total.as_secs_f64(),
+        stats.batches,
+        processed as f64 / stats.batches.max(1) as f64,
+        stats.failed_batches
+    );
+    println!("flagged as spam: {}", stats.flagged);
+    println!(
+        "end-to-end latency ms: p50 {} / p90 {} / p99 {} / max {}",
+        percentile(&stats.latencies_ms, 0.50),
+        percentile(&stats.latencies_ms, 0.90),
+        percentile(&stats.latencies_ms, 0.99),
+        stats.latencies_ms.last().copied().unwrap_or(0)
+    );
+    Ok(())
+}
+
+fn synthetic_job(index: usize, spam: bool, config: &HeuristicsConfig) -> (Priority, MessageJob) {
+    let text = if spam {
+        format!(
+            "{} {SPAM_MARKER}",
+            samples::SPAM[index % samples::SPAM.len()]
+        )
+    } else {
+        samples::HAM[index % samples::HAM.len()].to_string()
+    };
+    let has_links = contains_url(&text);
+    let account_age_days = Some(if spam { 10 } else { 800 });
+    let signals = heuristics::analyze(
+        &HeuristicInput {
+            text: &text,
+            entities: &[],
+            invite_links: &[],
+            username: None,
+            account_age_days,
+            has_links,
+            has_profile_photo: None,
+            known_spam: &KnownSpam::default(),
+        },
+        config,
+    );
+    let assessment = risk::assess(
+        &RiskInput {
+            is_member: !spam,
+            has_url: has_links,
+            invite_links: 0,
+            reputation: 0,
+            signals: &signals,
+        },
+        &config.risk,
+    );
+    let language = language::detect(&text);
+    let job = MessageJob {
+        chat_id: ChatId(-1000000000001),
+        chat_title: Some("Simulation".to_string()),
+        message_id: MessageId(index as i32),
+        topic_id: None,
+        from_id: Some(6_000_000_000 + index as i64),
+        sender_chat_id: None,
+        sender_is_chat_admin: false,
+        from_display: format!("sim{index}"),
+        username: None,
+        urls: extract_urls(&text, 2),
+        text,
+        is_group_member: !spam,
+        on_probation: false,
+        member_since: None,
+        account_age_days,
+        language,
+        priority_score: assessment.score,
+        reputation: 0,
+        signals,
+        timestamp: Utc::now(),
+        attempts: 0,
+    };
+    (assessment.priority, job)
+}
+
+fn percentile(sorted: &[u64], quantile: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((sorted.len() - 1) as f64 * quantile).round() as usize;
+    sorted[rank]
+}
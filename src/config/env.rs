@@ -1,28 +1,150 @@
-use std::time::Duration;
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use thiserror::Error;
 
+use crate::i18n::{t, tf, Locale, Msg};
+
+use super::live::LiveConfig;
+
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub telegram_bot_token: String,
+    /// Updates arrive through this webhook instead of long polling when set.
+    pub webhook: Option<WebhookConfig>,
+    /// Further bots run in the same process, sharing the classifier and database.
+    pub extra_bots: Vec<ExtraBotConfig>,
     pub bot_username: Option<String>,
+    /// Language of admin logs and notices, and of chat replies without a `/language`
+    /// setting.
+    pub language: Locale,
     pub admin_user_id: Option<i64>,
     pub admin_group_id: Option<i64>,
-    pub allowed_chat_ids: Vec<i64>,
-    pub cerebras: CerebrasConfig,
+    /// Spam is forwarded here before deletion so the evidence, media included, is kept.
+    pub archive_channel_id: Option<i64>,
+    /// Deletions logged within this long of a full admin log are combined into one
+    /// message; `None` logs each deletion on its own.
+    pub admin_log_batch: Option<Duration>,
+    pub honeypot_chat_ids: Vec<i64>,
+    /// Users besides chat admins whose `/spam` reports are sent to the classifier.
+    pub trusted_reporter_ids: Vec<i64>,
+    pub ai: AiProviderConfig,
+    /// Tried in order when the primary provider fails or times out.
+    pub ai_fallbacks: Vec<AiProviderConfig>,
+    /// Stronger model that re-checks spam verdicts before anything is deleted.
+    pub ai_confirmation: Option<AiProviderConfig>,
+    /// Per-provider deadline for one batch when fallbacks are configured.
+    pub ai_timeout: Duration,
     pub directories: DirectoryConfig,
     pub logging: LoggingConfig,
     pub timezone: String,
     pub scheduler: SchedulerConfig,
-    pub web: WebContentConfig,
     pub resilience: ResilienceConfig,
     pub update: UpdateConfig,
+    pub export: ExportConfig,
+    pub backup: BackupConfig,
+    pub usage: UsageConfig,
+    pub digest: DigestConfig,
+    pub enforcement: EnforcementConfig,
+    pub chat_admins: ChatAdminConfig,
+    pub blacklist: BlacklistConfig,
+    pub reputation: ReputationConfig,
+    pub retention: RetentionConfig,
+    pub rules_file: RulesFileConfig,
+    /// Settings `/reload_config` and SIGHUP can change while the bot runs.
+    pub live: Arc<LiveConfig>,
+    pub transcription: Option<TranscriptionConfig>,
+    pub video_ocr: Option<VideoOcrConfig>,
+    pub vision: Option<VisionConfig>,
+    /// Media messages analyzed (transcribed, OCR'd or described) at once; a message
+    /// arriving while all slots are busy is screened without its media.
+    pub media_analysis_concurrency: usize,
+    pub raid: Option<RaidConfig>,
+    pub flood: Option<FloodConfig>,
+    pub probation: Option<ProbationConfig>,
+    pub captcha: Option<CaptchaConfig>,
+    pub cas: Option<CasConfig>,
+    pub url_reputation: Option<UrlReputationConfig>,
+    pub page_render: Option<PageRenderConfig>,
+    pub ai_recording: Option<AiRecordingConfig>,
+    pub prompt: PromptConfig,
+    pub bayes: Option<BayesConfig>,
+    pub shadow: Option<ShadowConfig>,
+}
+
+/// Chat-completions vendors the classifier can talk to, selected with `AI_PROVIDER`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiProviderKind {
+    Cerebras,
+    OpenAi,
+    Groq,
+    Together,
+    /// Any other OpenAI-compatible endpoint (`CUSTOM_AI_BASE_URL`), e.g. a llama.cpp server.
+    Custom,
+    /// Local Ollama server, spoken to through its native chat API.
+    Ollama,
+    /// No model at all: messages at or above the strict risk threshold count as spam.
+    Heuristic,
+}
+
+impl AiProviderKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cerebras" => Some(Self::Cerebras),
+            "openai" => Some(Self::OpenAi),
+            "groq" => Some(Self::Groq),
+            "together" => Some(Self::Together),
+            "custom" => Some(Self::Custom),
+            "ollama" => Some(Self::Ollama),
+            "heuristic" => Some(Self::Heuristic),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cerebras => "cerebras",
+            Self::OpenAi => "openai",
+            Self::Groq => "groq",
+            Self::Together => "together",
+            Self::Custom => "custom",
+            Self::Ollama => "ollama",
+            Self::Heuristic => "heuristic",
+        }
+    }
+
+    /// Prefix of the provider's `_API_KEY`, `_MODEL` and `_BASE_URL` variables.
+    pub fn env_prefix(self) -> &'static str {
+        match self {
+            Self::Cerebras => "CEREBRAS",
+            Self::OpenAi => "OPENAI",
+            Self::Groq => "GROQ",
+            Self::Together => "TOGETHER",
+            Self::Custom => "CUSTOM_AI",
+            Self::Ollama => "OLLAMA",
+            Self::Heuristic => "HEURISTIC",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct CerebrasConfig {
+pub struct AiProviderConfig {
+    pub kind: AiProviderKind,
+    /// Base URL the `/chat/completions` (or Ollama's `/api/chat`) path is appended to.
+    pub base_url: String,
     pub api_key: Option<String>,
     pub model: String,
+    /// Context window batches are chunked to fit (`<PREFIX>_CONTEXT_TOKENS`).
+    pub context_tokens: usize,
+    /// USD per million prompt and completion tokens, for cost estimates.
+    pub input_price: f64,
+    pub output_price: f64,
+}
+
+impl AiProviderConfig {
+    pub fn estimate_cost(&self, prompt_tokens: i64, completion_tokens: i64) -> f64 {
+        (prompt_tokens as f64 * self.input_price + completion_tokens as f64 * self.output_price)
+            / 1_000_000.0
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +162,31 @@ pub struct LoggingConfig {
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
     pub cron_specs: Vec<String>,
+    pub whitelist_audit_cron: Option<String>,
+    /// When temporary whitelist entries past their expiry are removed.
+    pub whitelist_expiry_cron: Option<String>,
+    /// When spam bans past `BAN_DURATION_HOURS` are lifted.
+    pub ban_expiry_cron: Option<String>,
+    /// When the database is vacuumed and analyzed and its WAL file truncated.
+    pub db_maintenance_cron: Option<String>,
+    /// When the pinned status message in the admin group is refreshed; `None` when
+    /// disabled.
+    pub status_board_cron: Option<String>,
+    pub catch_up_missed_jobs: bool,
+    pub restart_wait_for_idle: bool,
+    pub restart_max_delay: Duration,
+}
+
+/// The part of the configuration that is re-read on `/reload_config` and SIGHUP; the
+/// rest keeps its startup value until a restart.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
+    pub allowed_chat_ids: Vec<i64>,
+    /// Spam verdicts below this confidence go to the admin group for review instead
+    /// of being deleted.
+    pub ai_confidence_threshold: f32,
+    pub web: WebContentConfig,
+    pub heuristics: HeuristicsConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +194,15 @@ pub struct WebContentConfig {
     pub max_urls_per_message: usize,
     pub fetch_timeout: Duration,
     pub content_max_length: usize,
+    /// Page bodies are read up to this many bytes; the rest is dropped, and pages that
+    /// announce a larger `Content-Length` are skipped.
+    pub max_body_bytes: usize,
+    /// Lowercase MIME types worth reading; other responses are skipped.
+    pub content_types: Vec<String>,
+    /// Pages fetched at once across a batch; applies from the next start.
+    pub fetch_concurrency: usize,
+    /// Minimum gap between requests to the same domain.
+    pub domain_interval: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +210,13 @@ pub struct ResilienceConfig {
     pub network_error_threshold: u32,
     pub network_error_window: Duration,
     pub restart_cooldown: Duration,
+    /// Extra attempts for transient AI provider failures (429, 5xx, connection errors).
+    pub ai_max_retries: u32,
+    pub ai_retry_base_delay: Duration,
+    /// Consecutive failed batches that open the classification circuit breaker.
+    pub ai_breaker_threshold: u32,
+    /// How long classification pauses once the breaker opens.
+    pub ai_breaker_cooldown: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -65,8 +228,455 @@ pub struct UpdateConfig {
     pub repo_name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    pub cron: Option<String>,
+    pub dir: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupConfig {
+    pub cron: Option<String>,
+    /// Relative to the data directory.
+    pub dir: String,
+    /// Newest snapshots kept; older ones are deleted after each backup.
+    pub keep: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct UsageConfig {
+    /// When the previous day's AI usage is summarized to the admin group.
+    pub summary_cron: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    /// When the spam digest is posted to the admin group; `None` when disabled.
+    pub cron: Option<String>,
+    /// How many days back each digest looks.
+    pub period_days: u32,
+    /// Entries listed under top senders and top domains.
+    pub top: usize,
+}
+
+/// What happens to the sender after their spam message is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnforcementAction {
+    Delete,
+    /// Delete and take away the sender's permission to post for this many hours.
+    Restrict {
+        hours: u32,
+    },
+    Ban,
+}
+
+impl EnforcementAction {
+    const DEFAULT_RESTRICT_HOURS: u32 = 24;
+    /// Telegram treats restrictions ending more than 366 days ahead as permanent.
+    pub const MAX_RESTRICT_HOURS: u32 = 366 * 24;
+
+    /// Accepts `delete`, `ban`, `restrict` and `restrict:<hours>`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        let (name, hours) = match value.split_once(':') {
+            Some((name, hours)) => (name, Some(hours.trim().parse::<u32>().ok()?)),
+            None => (value.as_str(), None),
+        };
+        match (name.trim(), hours) {
+            ("delete", None) => Some(Self::Delete),
+            ("ban", None) => Some(Self::Ban),
+            ("restrict", hours) => Some(Self::Restrict {
+                hours: hours
+                    .unwrap_or(Self::DEFAULT_RESTRICT_HOURS)
+                    .clamp(1, Self::MAX_RESTRICT_HOURS),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Inverse of [`EnforcementAction::parse`], used for storage.
+    pub fn name(self) -> String {
+        match self {
+            Self::Delete => "delete".to_string(),
+            Self::Restrict { hours } => format!("restrict:{hours}"),
+            Self::Ban => "ban".to_string(),
+        }
+    }
+
+    /// The harsher of the two actions; of two restrictions, the longer one.
+    pub fn stricter(self, other: Self) -> Self {
+        let rank = |action: Self| match action {
+            Self::Delete => (0, 0),
+            Self::Restrict { hours } => (1, hours),
+            Self::Ban => (2, 0),
+        };
+        if rank(other) > rank(self) {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Description for the admin log and bot replies.
+    pub fn describe(self, locale: Locale) -> String {
+        match self {
+            Self::Delete => t(locale, Msg::ActionDelete).to_string(),
+            Self::Restrict { hours } => tf(locale, Msg::ActionRestrict, &[("hours", &hours)]),
+            Self::Ban => t(locale, Msg::ActionBan).to_string(),
+        }
+    }
+}
+
+/// Another community bot served by this process. It only handles its own chats, always
+/// long-polls, and leaves admin commands and notices to the primary bot.
+#[derive(Debug, Clone)]
+pub struct ExtraBotConfig {
+    pub name: String,
+    pub token: String,
+    pub username: Option<String>,
+    /// Chats the bot moderates, used instead of the shared whitelist.
+    pub chat_ids: Vec<i64>,
+}
+
+/// `TELEGRAM_MODE=webhook` settings.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Public HTTPS address Telegram posts updates to.
+    pub url: url::Url,
+    /// Local address the server listens on, usually behind a reverse proxy.
+    pub listen_addr: SocketAddr,
+    /// Checked against Telegram's secret-token header; generated when unset.
+    pub secret: Option<String>,
+}
+
+/// How messages from a chat's own admins and creator are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatAdminMode {
+    /// Not checked at all.
+    Skip,
+    /// Classified, but a spam verdict asks the admin group instead of deleting.
+    Report,
+    /// Checked like everyone else's.
+    Off,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatAdminConfig {
+    pub mode: ChatAdminMode,
+    /// How long a chat's admin list is reused before asking Telegram again.
+    pub cache_ttl: Duration,
+}
+
+/// Cross-chat user blacklist: listed users' messages are deleted in every moderated
+/// chat without classification.
+#[derive(Debug, Clone)]
+pub struct BlacklistConfig {
+    /// List senders whose messages are deleted as spam, once `min_deletions` of them
+    /// were.
+    pub auto_add: bool,
+    pub min_deletions: i64,
+    /// Ban newly listed users from every moderated chat, and ban listed users wherever
+    /// they post.
+    pub pre_ban: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnforcementConfig {
+    /// Applied in chats without their own `/action` policy.
+    pub default_action: EnforcementAction,
+    pub strikes: Option<StrikeConfig>,
+    /// Ban channels whose posts are deleted as spam from posting in the chat again.
+    pub ban_sender_chats: bool,
+    /// How long the in-chat notice about a deletion stays up in chats without their own
+    /// `/notice` setting; `None` posts no notice.
+    pub notice_ttl: Option<Duration>,
+    /// Tell senders privately why their message was deleted, at most once per this
+    /// interval each; `None` sends nothing.
+    pub notify_sender: Option<Duration>,
+    /// How long spam bans last before the bot lifts them; `None` keeps them forever.
+    pub ban_duration: Option<Duration>,
+}
+
+/// Escalation for repeat spammers in the same chat; a threshold of 0 skips that step.
+#[derive(Debug, Clone)]
+pub struct StrikeConfig {
+    pub restrict_at: i64,
+    pub restrict_hours: u32,
+    pub ban_at: i64,
+    /// Strikes are forgotten after this long without a new one.
+    pub ttl: Option<Duration>,
+}
+
+impl StrikeConfig {
+    /// Penalty for a sender's `count`-th strike.
+    pub fn action_for(&self, count: i64) -> EnforcementAction {
+        if self.ban_at > 0 && count >= self.ban_at {
+            EnforcementAction::Ban
+        } else if self.restrict_at > 0 && count >= self.restrict_at {
+            EnforcementAction::Restrict {
+                hours: self.restrict_hours,
+            }
+        } else {
+            EnforcementAction::Delete
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReputationConfig {
+    pub decay_cron: Option<String>,
+    pub offense_penalty: i64,
+    pub offense_ttl: Duration,
+    pub clean_period: Duration,
+    pub recovery_per_pass: i64,
+    /// Points a message the classifier cleared adds to its sender's score; 0 disables.
+    pub clean_reward: i64,
+    /// Ceiling for scores earned through clean messages.
+    pub max_score: i64,
+    /// Time a member must have been in the chat before clean messages earn anything.
+    pub min_tenure: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    pub cron: Option<String>,
+    pub spam_log_ttl: Option<Duration>,
+    pub usage_ttl: Option<Duration>,
+    pub shadow_ttl: Option<Duration>,
+    /// Applies to decided appeals only; pending ones are always kept.
+    pub appeal_ttl: Option<Duration>,
+    pub member_join_ttl: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RulesFileConfig {
+    pub path: Option<String>,
+    pub poll_interval: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct HeuristicsConfig {
+    /// Mentions per message that count as a mass mention; `0` disables the check.
+    pub mass_mention_threshold: usize,
+    /// Share of visible characters that are emoji before the message counts as emoji-heavy.
+    pub emoji_density_threshold: f32,
+    /// Custom (premium) emoji per message that count as repeated; `0` disables the check.
+    pub custom_emoji_threshold: usize,
+    /// Distinct non-allowlisted invite links that escalate a message; `0` disables the check.
+    pub invite_link_threshold: usize,
+    /// Accounts estimated younger than this (in days) get a bump when posting links.
+    pub new_account_days: i64,
+    /// How long a profile-photo lookup is reused for the same user.
+    pub profile_cache_ttl: Duration,
+    pub risk: RiskConfig,
+    /// Obvious-ham filter applied before AI classification; `None` when disabled.
+    pub prefilter: Option<PrefilterConfig>,
+}
+
+/// Messages this cheap check clears are never sent to the AI.
+#[derive(Debug, Clone)]
+pub struct PrefilterConfig {
+    /// Longest text (in characters) that can be cleared.
+    pub max_chars: usize,
+    /// Minimum time since the sender's seen join to the chat for their message to be
+    /// cleared; members whose join was never seen are never cleared.
+    pub min_member_days: i64,
+}
+
+/// Weights and thresholds for the unified risk score.
+#[derive(Debug, Clone)]
+pub struct RiskConfig {
+    /// Per-factor or per-signal overrides, keyed by factor/signal name.
+    pub weights: HashMap<String, i32>,
+    /// Scores at or above this go to the high-priority queue.
+    pub high_priority_threshold: i32,
+    /// Scores at or above this are deleted outright in strict-mode chats.
+    pub strict_threshold: i32,
+}
+
+/// Whisper-compatible speech-to-text settings; `None` in [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct TranscriptionConfig {
+    pub endpoint: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub language: Option<String>,
+    /// Longer voice messages and video notes are not downloaded.
+    pub max_duration_secs: u32,
+    pub timeout: Duration,
+}
+
+/// External tools used to read text from video frames; `None` in [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct VideoOcrConfig {
+    pub ffmpeg_path: String,
+    pub tesseract_path: String,
+    /// Tesseract language list, e.g. `kor+eng`.
+    pub languages: String,
+    pub frames: u32,
+    /// Longer videos are not downloaded.
+    pub max_duration_secs: u32,
+    pub timeout: Duration,
+}
+
+/// Multimodal model that describes photos for classification; `None` in [`AppConfig`]
+/// when no vision model is configured.
+#[derive(Debug, Clone)]
+pub struct VisionConfig {
+    /// OpenAI-compatible base URL the `/chat/completions` path is appended to.
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    /// Photos larger than this are not downloaded; the largest size within it is used.
+    pub max_bytes: u32,
+    pub timeout: Duration,
+}
+
+/// Stricter handling of users who recently joined a chat; `None` in [`AppConfig`] when
+/// disabled. The window can be overridden per chat with `/probation`.
+#[derive(Debug, Clone)]
+pub struct ProbationConfig {
+    /// A new member stays on probation until they have sent this many messages...
+    pub messages: i64,
+    /// ...and this long has passed since they joined.
+    pub window: Duration,
+    /// Replaces `ai_confidence_threshold` for their messages.
+    pub confidence_threshold: f32,
+    /// Links fetched from their messages, even when `max_urls_per_message` is lower.
+    pub max_urls: usize,
+}
+
+/// Button captcha for new members; `None` in [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct CaptchaConfig {
+    /// Members who haven't answered by then are removed from the chat.
+    pub timeout: Duration,
+}
+
+/// CAS (Combot Anti-Spam) ban list lookups; `None` in [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct CasConfig {
+    pub base_url: String,
+    pub timeout: Duration,
+    pub cache_ttl: Duration,
+    /// Ban listed senders and joiners in addition to deleting their messages.
+    pub ban: bool,
+}
+
+/// Threat-feed lookups for linked URLs; `None` in [`AppConfig`] when neither feed has a
+/// key.
+#[derive(Debug, Clone)]
+pub struct UrlReputationConfig {
+    /// Google Safe Browsing v4 API key.
+    pub safe_browsing_key: Option<String>,
+    /// abuse.ch URLhaus Auth-Key.
+    pub urlhaus_key: Option<String>,
+    pub timeout: Duration,
+    pub cache_ttl: Duration,
+}
+
+/// Headless-browser rendering for script-only pages; `None` in [`AppConfig`] without
+/// `PAGE_RENDER_ENDPOINT`.
+#[derive(Debug, Clone)]
+pub struct PageRenderConfig {
+    /// Takes a JSON `{"url": ...}` POST and answers with the rendered HTML, like
+    /// browserless' `/content`. Only accepted with `PAGE_RENDER_EGRESS_ISOLATED=true`,
+    /// since the page's own scripts run there.
+    pub endpoint: String,
+    /// Sent as a bearer `Authorization` header so it stays out of URLs and logs.
+    pub token: Option<String>,
+    pub timeout: Duration,
+}
+
+/// Coordinated multi-account attack detection; `None` in [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct RaidConfig {
+    pub window: Duration,
+    /// Distinct suspicious accounts posting similar content that make a raid.
+    pub min_accounts: usize,
+    /// Minimum Jaccard similarity (0-1) between message fingerprints.
+    pub similarity: f32,
+    pub delete: bool,
+    pub ban: bool,
+    /// Mutes the whole chat for this long when set.
+    pub lockdown: Option<Duration>,
+}
+
+/// Per-sender rate limits enforced without the classifier; `None` in [`AppConfig`] when
+/// disabled.
+#[derive(Debug, Clone)]
+pub struct FloodConfig {
+    pub window: Duration,
+    /// Messages one sender may post in a chat within the window; 0 disables the check.
+    pub max_messages: usize,
+    /// Times one sender may post the same text within the window; 0 disables the check.
+    pub max_repeats: usize,
+    /// Mutes the sender for this long in addition to deleting the message when set.
+    pub mute: Option<Duration>,
+}
+
+/// Whether classifier traffic is written to or served from a recording file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiRecordMode {
+    Record,
+    Replay,
+}
+
+/// Prompt/response recording for reproducing incidents; `None` in [`AppConfig`] when off.
+#[derive(Debug, Clone)]
+pub struct AiRecordingConfig {
+    pub mode: AiRecordMode,
+    /// JSONL file, relative to the data directory unless absolute.
+    pub file: String,
+}
+
+/// Candidate model or prompt that classifies every batch alongside the live one
+/// without acting, for comparison.
+#[derive(Debug, Clone)]
+pub struct ShadowConfig {
+    /// Provider and model under evaluation; the live one when only the prompt differs.
+    pub provider: AiProviderConfig,
+    /// Candidate system prompt template; the live prompt when unset.
+    pub prompt_file: Option<String>,
+}
+
+/// Classifier system prompt: an optional template file plus the values substituted
+/// into its `{{...}}` placeholders.
+#[derive(Debug, Clone)]
+pub struct PromptConfig {
+    /// Replaces the built-in prompt when set; reloadable with `/reload_prompt`.
+    pub file: Option<String>,
+    /// Language the model writes spam reasons in (`{{language}}`).
+    pub language: String,
+    /// Extra operator criteria appended to the spam list (`{{custom_criteria}}`).
+    pub custom_criteria: String,
+    /// Admin-confirmed spam and ham examples added per label; `0` disables few-shot.
+    pub few_shot_examples: i64,
+    /// Whole-template overrides for batches in a given language, keyed by ISO 639-1 code.
+    pub language_files: HashMap<String, String>,
+    /// Criteria overrides for batches in a given language, keyed by ISO 639-1 code.
+    pub language_criteria: HashMap<String, String>,
+}
+
+/// Offline naive-Bayes classifier tried after every AI provider; `None` in
+/// [`AppConfig`] when disabled.
+#[derive(Debug, Clone)]
+pub struct BayesConfig {
+    /// Model file, relative to the data directory unless absolute.
+    pub model_file: String,
+    pub train_cron: Option<String>,
+    /// Spam probability at or above which a message counts as spam.
+    pub spam_threshold: f64,
+    /// Spam and ham samples each required before a model is trained.
+    pub min_samples: usize,
+    /// Cleared messages kept as ham training data.
+    pub ham_samples: i64,
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("missing required environment variable: {0}")]
     Missing(&'static str),
+    #[error("invalid value for {0}: {1}")]
+    Invalid(&'static str, String),
 }
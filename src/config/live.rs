@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use super::env::LiveSettings;
+
+/// The current [`LiveSettings`], swapped as a whole when the configuration is reloaded
+/// so readers never see a half-updated mix.
+#[derive(Debug)]
+pub struct LiveConfig {
+    current: RwLock<Arc<LiveSettings>>,
+}
+
+impl LiveConfig {
+    pub fn new(settings: LiveSettings) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(settings)),
+        }
+    }
+
+    pub fn get(&self) -> Arc<LiveSettings> {
+        self.current.read().clone()
+    }
+
+    /// Re-reads `.env` over the process environment, without modifying it, and swaps in
+    /// the settings it yields.
+    pub fn reload(&self) -> Arc<LiveSettings> {
+        let settings = Arc::new(LiveSettings::from_env_file());
+        *self.current.write() = settings.clone();
+        tracing::info!(
+            target: "config",
+            allowed_chats = settings.allowed_chat_ids.len(),
+            confidence_threshold = settings.ai_confidence_threshold,
+            "live settings reloaded"
+        );
+        settings
+    }
+}
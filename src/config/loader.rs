@@ -1,51 +1,246 @@
-use std::env;
+use std::{cell::RefCell, collections::HashMap, env, sync::Arc};
 
-use super::env::{
-    AppConfig, CerebrasConfig, ConfigError, DirectoryConfig, LoggingConfig, ResilienceConfig,
-    SchedulerConfig, UpdateConfig, WebContentConfig,
+use crate::{heuristics::language, i18n::Locale};
+
+use super::{
+    env::{
+        AiProviderConfig, AiProviderKind, AiRecordMode, AiRecordingConfig, AppConfig, BackupConfig,
+        BayesConfig, BlacklistConfig, CaptchaConfig, CasConfig, ChatAdminConfig, ChatAdminMode,
+        ConfigError, DigestConfig, DirectoryConfig, EnforcementAction, EnforcementConfig,
+        ExportConfig, ExtraBotConfig, FloodConfig, HeuristicsConfig, LiveSettings, LoggingConfig,
+        PageRenderConfig, PrefilterConfig, ProbationConfig, PromptConfig, RaidConfig,
+        ReputationConfig, ResilienceConfig, RetentionConfig, RiskConfig, RulesFileConfig,
+        SchedulerConfig, ShadowConfig, StrikeConfig, TranscriptionConfig, UpdateConfig,
+        UrlReputationConfig, UsageConfig, VideoOcrConfig, VisionConfig, WebContentConfig,
+        WebhookConfig,
+    },
+    live::LiveConfig,
 };
 
 pub fn load_config() -> Result<AppConfig, ConfigError> {
     AppConfig::from_env()
 }
 
+/// Reads `<PREFIX>_API_KEY`, `<PREFIX>_MODEL`, `<PREFIX>_BASE_URL`,
+/// `<PREFIX>_CONTEXT_TOKENS` and the `<PREFIX>_INPUT_PRICE`/`_OUTPUT_PRICE` pair for the provider,
+/// falling back to the vendor's public endpoint and a sensible default model.
+pub fn load_ai_provider(kind: AiProviderKind) -> AiProviderConfig {
+    let (default_base_url, default_model) = match kind {
+        AiProviderKind::Cerebras => ("https://api.cerebras.ai/v1", "gpt-oss-120b"),
+        AiProviderKind::OpenAi => ("https://api.openai.com/v1", "gpt-4o-mini"),
+        AiProviderKind::Groq => ("https://api.groq.com/openai/v1", "llama-3.3-70b-versatile"),
+        AiProviderKind::Together => (
+            "https://api.together.xyz/v1",
+            "meta-llama/Llama-3.3-70B-Instruct-Turbo",
+        ),
+        AiProviderKind::Custom => ("http://localhost:8000/v1", "default"),
+        AiProviderKind::Ollama => ("http://localhost:11434", "llama3.1:8b"),
+        AiProviderKind::Heuristic => ("", "risk-score"),
+    };
+    let prefix = kind.env_prefix();
+    let var = |suffix: &str| {
+        var(format!("{prefix}_{suffix}"))
+            .ok()
+            .filter(|v| !v.is_empty())
+    };
+    AiProviderConfig {
+        kind,
+        base_url: var("BASE_URL")
+            .unwrap_or_else(|| default_base_url.to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        api_key: var("API_KEY"),
+        model: var("MODEL").unwrap_or_else(|| default_model.to_string()),
+        context_tokens: var("CONTEXT_TOKENS")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(8192),
+        input_price: var("INPUT_PRICE")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.0),
+        output_price: var("OUTPUT_PRICE")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.0),
+    }
+}
+
+/// Heuristic thresholds and risk weights alone, for tooling that runs without a bot token.
+pub fn load_heuristics_config() -> HeuristicsConfig {
+    HeuristicsConfig {
+        mass_mention_threshold: parse_env("MASS_MENTION_THRESHOLD").unwrap_or(5),
+        emoji_density_threshold: parse_env("EMOJI_DENSITY_THRESHOLD").unwrap_or(0.3),
+        custom_emoji_threshold: parse_env("CUSTOM_EMOJI_THRESHOLD").unwrap_or(5),
+        invite_link_threshold: parse_env("INVITE_LINK_THRESHOLD").unwrap_or(2),
+        new_account_days: parse_env("NEW_ACCOUNT_DAYS").unwrap_or(90),
+        profile_cache_ttl: std::time::Duration::from_secs(
+            parse_env("PROFILE_CACHE_TTL_SECS").unwrap_or(86_400),
+        ),
+        risk: RiskConfig {
+            weights: var("RISK_WEIGHTS")
+                .map(|value| parse_weights(&value))
+                .unwrap_or_default(),
+            high_priority_threshold: parse_env("RISK_HIGH_PRIORITY_THRESHOLD").unwrap_or(15),
+            strict_threshold: parse_env("RISK_STRICT_THRESHOLD").unwrap_or(60),
+        },
+        prefilter: parse_bool_env("PREFILTER_ENABLED")
+            .unwrap_or(true)
+            .then(|| PrefilterConfig {
+                max_chars: parse_env("PREFILTER_MAX_CHARS").unwrap_or(40),
+                min_member_days: parse_env("PREFILTER_MIN_MEMBER_DAYS").unwrap_or(30),
+            }),
+    }
+}
+
+thread_local! {
+    /// `.env` values read by an ongoing live reload, consulted before the process
+    /// environment so a reload never has to modify it.
+    static ENV_FILE: RefCell<Option<HashMap<String, String>>> = const { RefCell::new(None) };
+}
+
+/// `env::var`, preferring the `.env` values of an ongoing live reload.
+fn var(key: impl AsRef<str>) -> Result<String, env::VarError> {
+    let key = key.as_ref();
+    ENV_FILE
+        .with_borrow(|file| file.as_ref().and_then(|file| file.get(key).cloned()))
+        .map_or_else(|| env::var(key), Ok)
+}
+
+impl LiveSettings {
+    /// Settings from `.env` as it reads now, over the process environment, which is left
+    /// untouched.
+    pub(super) fn from_env_file() -> Self {
+        let file = match dotenvy::dotenv_iter() {
+            Ok(iter) => iter.filter_map(Result::ok).collect(),
+            Err(err) => {
+                tracing::debug!(target: "config", error = %err, ".env not reloaded");
+                HashMap::new()
+            }
+        };
+        ENV_FILE.set(Some(file));
+        let settings = Self::from_env();
+        ENV_FILE.set(None);
+        settings
+    }
+
+    pub(super) fn from_env() -> Self {
+        let web = WebContentConfig {
+            max_urls_per_message: var("MAX_URLS_PER_MESSAGE")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(2),
+            fetch_timeout: std::time::Duration::from_millis(
+                var("WEBPAGE_FETCH_TIMEOUT")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(10_000),
+            ),
+            content_max_length: var("WEBPAGE_CONTENT_MAX_LENGTH")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1_000),
+            max_body_bytes: parse_env("WEBPAGE_MAX_BODY_BYTES").unwrap_or(2_000_000),
+            content_types: var("WEBPAGE_CONTENT_TYPES")
+                .unwrap_or_else(|_| "text/html,application/xhtml+xml".to_string())
+                .split(',')
+                .map(|value| value.trim().to_ascii_lowercase())
+                .filter(|value| !value.is_empty())
+                .collect(),
+            fetch_concurrency: parse_env("WEBPAGE_FETCH_CONCURRENCY").unwrap_or(4),
+            domain_interval: std::time::Duration::from_millis(
+                parse_env("WEBPAGE_DOMAIN_INTERVAL_MS").unwrap_or(1_000),
+            ),
+        };
+
+        Self {
+            allowed_chat_ids: parse_chat_ids("ALLOWED_CHAT_IDS"),
+            ai_confidence_threshold: parse_env::<f32>("AI_CONFIDENCE_THRESHOLD")
+                .unwrap_or(0.8)
+                .clamp(0.0, 1.0),
+            web,
+            heuristics: load_heuristics_config(),
+        }
+    }
+}
+
 impl AppConfig {
     fn from_env() -> Result<Self, ConfigError> {
-        let telegram_bot_token = env::var("TELEGRAM_BOT_TOKEN")
-            .map_err(|_| ConfigError::Missing("TELEGRAM_BOT_TOKEN"))?;
+        let telegram_bot_token =
+            var("TELEGRAM_BOT_TOKEN").map_err(|_| ConfigError::Missing("TELEGRAM_BOT_TOKEN"))?;
+
+        let webhook = match var("TELEGRAM_MODE")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "webhook" => Some(load_webhook_config()?),
+            _ => None,
+        };
 
-        let bot_username = env::var("BOT_USERNAME").ok().filter(|v| !v.is_empty());
+        let extra_bots = load_extra_bots()?;
+        let language = var("BOT_LANGUAGE")
+            .ok()
+            .and_then(|value| Locale::parse(&value))
+            .unwrap_or_default();
+
+        let bot_username = var("BOT_USERNAME").ok().filter(|v| !v.is_empty());
         let admin_user_id = parse_int("ADMIN_USER_ID");
         let admin_group_id = parse_int("ADMIN_GROUP_ID").map(|id| if id > 0 { -id } else { id });
-        let allowed_chat_ids = env::var("ALLOWED_CHAT_IDS")
-            .ok()
+        let archive_channel_id =
+            parse_int("ARCHIVE_CHANNEL_ID").map(|id| if id > 0 { -id } else { id });
+        let admin_log_batch = Some(parse_env::<u64>("ADMIN_LOG_BATCH_SECS").unwrap_or(10))
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs);
+        let honeypot_chat_ids = parse_chat_ids("HONEYPOT_CHAT_IDS");
+        let trusted_reporter_ids = parse_chat_ids("TRUSTED_REPORTER_IDS");
+
+        let ai = load_ai_provider(match var("AI_PROVIDER") {
+            Ok(value) if !value.trim().is_empty() => AiProviderKind::parse(&value)
+                .ok_or_else(|| ConfigError::Invalid("AI_PROVIDER", value.clone()))?,
+            _ => AiProviderKind::Cerebras,
+        });
+        let ai_fallbacks = var("AI_FALLBACK_PROVIDERS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|value| !value.trim().is_empty())
             .map(|value| {
-                value
-                    .split(',')
-                    .filter_map(|part| part.trim().parse::<i64>().ok())
-                    .collect::<Vec<_>>()
+                AiProviderKind::parse(value)
+                    .ok_or_else(|| ConfigError::Invalid("AI_FALLBACK_PROVIDERS", value.to_string()))
             })
-            .unwrap_or_default();
-
-        let cerebras = CerebrasConfig {
-            api_key: env::var("CEREBRAS_API_KEY").ok().filter(|v| !v.is_empty()),
-            model: env::var("CEREBRAS_MODEL").unwrap_or_else(|_| "gpt-oss-120b".to_string()),
-        };
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|kind| *kind != ai.kind)
+            .map(load_ai_provider)
+            .collect();
+        let ai_confirmation = var("AI_CONFIRM_MODEL")
+            .ok()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .map(|model| {
+                let kind = var("AI_CONFIRM_PROVIDER")
+                    .ok()
+                    .and_then(|value| AiProviderKind::parse(&value))
+                    .unwrap_or(ai.kind);
+                AiProviderConfig {
+                    model,
+                    ..load_ai_provider(kind)
+                }
+            });
+        let ai_timeout = std::time::Duration::from_secs(parse_env("AI_TIMEOUT_SECS").unwrap_or(30));
 
         let directories = DirectoryConfig {
-            logs_dir: env::var("LOGS_DIR").unwrap_or_else(|_| "logs".to_string()),
-            data_dir: env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string()),
-            db_filename: env::var("DB_FILENAME").unwrap_or_else(|_| "whitelist.db".to_string()),
+            logs_dir: var("LOGS_DIR").unwrap_or_else(|_| "logs".to_string()),
+            data_dir: var("DATA_DIR").unwrap_or_else(|_| "data".to_string()),
+            db_filename: var("DB_FILENAME").unwrap_or_else(|_| "whitelist.db".to_string()),
         };
 
         let logging = LoggingConfig {
-            level: env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
+            level: var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
         };
 
-        let timezone = env::var("BOT_TIMEZONE").unwrap_or_else(|_| "Asia/Seoul".to_string());
+        let timezone = var("BOT_TIMEZONE").unwrap_or_else(|_| "Asia/Seoul".to_string());
 
         let scheduler = SchedulerConfig {
-            cron_specs: env::var("RESTART_CRONS")
+            cron_specs: var("RESTART_CRONS")
                 .map(|value| {
                     value
                         .split(';')
@@ -54,80 +249,585 @@ impl AppConfig {
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or_else(|_| vec!["0 0 0 * * *".to_string(), "0 0 12 * * *".to_string()]),
-        };
-
-        let web = WebContentConfig {
-            max_urls_per_message: env::var("MAX_URLS_PER_MESSAGE")
-                .ok()
-                .and_then(|v| v.parse::<usize>().ok())
-                .unwrap_or(2),
-            fetch_timeout: std::time::Duration::from_millis(
-                env::var("WEBPAGE_FETCH_TIMEOUT")
-                    .ok()
-                    .and_then(|v| v.parse::<u64>().ok())
-                    .unwrap_or(10_000),
+            whitelist_audit_cron: parse_cron_env("WHITELIST_AUDIT_CRON", "0 30 3 * * *"),
+            whitelist_expiry_cron: parse_cron_env("WHITELIST_EXPIRY_CRON", "0 */10 * * * *"),
+            ban_expiry_cron: parse_cron_env("BAN_EXPIRY_CRON", "0 */10 * * * *"),
+            db_maintenance_cron: parse_cron_env("DB_MAINTENANCE_CRON", "0 45 4 * * *"),
+            status_board_cron: parse_bool_env("STATUS_BOARD_ENABLED")
+                .unwrap_or(false)
+                .then(|| parse_cron_env("STATUS_BOARD_CRON", "0 */5 * * * *"))
+                .flatten(),
+            catch_up_missed_jobs: parse_bool_env("SCHEDULER_CATCH_UP").unwrap_or(true),
+            restart_wait_for_idle: parse_bool_env("RESTART_WAIT_FOR_IDLE").unwrap_or(true),
+            restart_max_delay: std::time::Duration::from_secs(
+                parse_env("RESTART_MAX_DELAY_SECS").unwrap_or(300),
             ),
-            content_max_length: env::var("WEBPAGE_CONTENT_MAX_LENGTH")
-                .ok()
-                .and_then(|v| v.parse::<usize>().ok())
-                .unwrap_or(1_000),
         };
 
         let resilience = ResilienceConfig {
-            network_error_threshold: env::var("NETWORK_ERROR_THRESHOLD")
+            network_error_threshold: var("NETWORK_ERROR_THRESHOLD")
                 .ok()
                 .and_then(|v| v.parse::<u32>().ok())
                 .unwrap_or(5),
             network_error_window: std::time::Duration::from_secs(
-                env::var("NETWORK_ERROR_WINDOW_SECS")
+                var("NETWORK_ERROR_WINDOW_SECS")
                     .ok()
                     .and_then(|v| v.parse::<u64>().ok())
                     .unwrap_or(60),
             ),
             restart_cooldown: std::time::Duration::from_secs(
-                env::var("EMERGENCY_RESTART_COOLDOWN_SECS")
+                var("EMERGENCY_RESTART_COOLDOWN_SECS")
                     .ok()
                     .and_then(|v| v.parse::<u64>().ok())
                     .unwrap_or(600),
             ),
+            ai_max_retries: parse_env("AI_MAX_RETRIES").unwrap_or(3),
+            ai_retry_base_delay: std::time::Duration::from_millis(
+                parse_env("AI_RETRY_BASE_MS").unwrap_or(500),
+            ),
+            ai_breaker_threshold: parse_env::<u32>("AI_BREAKER_THRESHOLD").unwrap_or(3).max(1),
+            ai_breaker_cooldown: std::time::Duration::from_secs(
+                parse_env("AI_BREAKER_COOLDOWN_SECS").unwrap_or(60),
+            ),
         };
 
         let update = UpdateConfig {
             enabled: parse_bool_env("AUTO_UPDATE_ENABLED").unwrap_or(false),
             check_on_startup: parse_bool_env("AUTO_UPDATE_CHECK_ON_STARTUP").unwrap_or(true),
             auto_restart: parse_bool_env("AUTO_UPDATE_AUTO_RESTART").unwrap_or(true),
-            repo_owner: env::var("AUTO_UPDATE_REPO_OWNER")
-                .unwrap_or_else(|_| "yldst-dev".to_string()),
-            repo_name: env::var("AUTO_UPDATE_REPO_NAME")
+            repo_owner: var("AUTO_UPDATE_REPO_OWNER").unwrap_or_else(|_| "yldst-dev".to_string()),
+            repo_name: var("AUTO_UPDATE_REPO_NAME")
                 .unwrap_or_else(|_| "fuckyou-spam-rs".to_string()),
         };
 
+        let export = ExportConfig {
+            cron: parse_cron_env("SPAM_EXPORT_CRON", ""),
+            dir: var("SPAM_EXPORT_DIR").unwrap_or_else(|_| "exports".to_string()),
+        };
+
+        let backup = BackupConfig {
+            cron: parse_cron_env("BACKUP_CRON", "0 0 5 * * *"),
+            dir: var("BACKUP_DIR").unwrap_or_else(|_| "backups".to_string()),
+            keep: parse_env::<usize>("BACKUP_KEEP").unwrap_or(7).max(1),
+        };
+
+        let usage = UsageConfig {
+            summary_cron: parse_cron_env("USAGE_SUMMARY_CRON", "0 0 9 * * *"),
+        };
+
+        let digest = DigestConfig {
+            cron: parse_bool_env("DIGEST_ENABLED")
+                .unwrap_or(false)
+                .then(|| parse_cron_env("DIGEST_CRON", "0 0 9 * * Mon"))
+                .flatten(),
+            period_days: parse_env::<u32>("DIGEST_PERIOD_DAYS").unwrap_or(7).max(1),
+            top: parse_env("DIGEST_TOP").unwrap_or(5),
+        };
+
+        let enforcement = EnforcementConfig {
+            ban_sender_chats: parse_bool_env("ENFORCEMENT_BAN_CHANNELS").unwrap_or(true),
+            ban_duration: Some(parse_env::<u64>("BAN_DURATION_HOURS").unwrap_or(0))
+                .filter(|hours| *hours > 0)
+                .map(|hours| std::time::Duration::from_secs(hours * 3600)),
+            notice_ttl: Some(parse_env::<u64>("DELETION_NOTICE_SECS").unwrap_or(0))
+                .filter(|secs| *secs > 0)
+                .map(std::time::Duration::from_secs),
+            notify_sender: parse_bool_env("NOTIFY_SENDER_ENABLED")
+                .unwrap_or(false)
+                .then(|| {
+                    std::time::Duration::from_secs(
+                        parse_env::<u64>("NOTIFY_SENDER_COOLDOWN_MINS").unwrap_or(60) * 60,
+                    )
+                }),
+            default_action: var("ENFORCEMENT_ACTION")
+                .ok()
+                .and_then(|value| EnforcementAction::parse(&value))
+                .unwrap_or(EnforcementAction::Delete),
+            strikes: parse_bool_env("STRIKES_ENABLED")
+                .unwrap_or(true)
+                .then(|| StrikeConfig {
+                    restrict_at: parse_env("STRIKE_RESTRICT_AT").unwrap_or(2),
+                    restrict_hours: parse_env::<u32>("STRIKE_RESTRICT_HOURS")
+                        .unwrap_or(24)
+                        .clamp(1, EnforcementAction::MAX_RESTRICT_HOURS),
+                    ban_at: parse_env("STRIKE_BAN_AT").unwrap_or(3),
+                    ttl: Some(parse_env::<u64>("STRIKE_TTL_DAYS").unwrap_or(30))
+                        .filter(|days| *days > 0)
+                        .map(|days| std::time::Duration::from_secs(days * 86_400)),
+                }),
+        };
+
+        let chat_admins = ChatAdminConfig {
+            mode: match var("CHAT_ADMIN_MODE")
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "report" => ChatAdminMode::Report,
+                "off" => ChatAdminMode::Off,
+                _ => ChatAdminMode::Skip,
+            },
+            cache_ttl: std::time::Duration::from_secs(
+                parse_env("CHAT_ADMIN_CACHE_SECS").unwrap_or(600),
+            ),
+        };
+
+        let blacklist = BlacklistConfig {
+            auto_add: parse_bool_env("BLACKLIST_AUTO_ADD").unwrap_or(false),
+            min_deletions: parse_env::<i64>("BLACKLIST_MIN_DELETIONS")
+                .unwrap_or(3)
+                .max(1),
+            pre_ban: parse_bool_env("BLACKLIST_PRE_BAN").unwrap_or(false),
+        };
+
+        let reputation = ReputationConfig {
+            decay_cron: parse_cron_env("REPUTATION_DECAY_CRON", "0 0 4 * * *"),
+            offense_penalty: parse_env("REPUTATION_OFFENSE_PENALTY").unwrap_or(10),
+            offense_ttl: std::time::Duration::from_secs(
+                parse_env::<u64>("REPUTATION_OFFENSE_TTL_DAYS").unwrap_or(30) * 86_400,
+            ),
+            clean_period: std::time::Duration::from_secs(
+                parse_env::<u64>("REPUTATION_CLEAN_PERIOD_DAYS").unwrap_or(7) * 86_400,
+            ),
+            recovery_per_pass: parse_env("REPUTATION_RECOVERY_PER_PASS").unwrap_or(1),
+            clean_reward: parse_env::<i64>("REPUTATION_CLEAN_REWARD")
+                .unwrap_or(1)
+                .max(0),
+            max_score: parse_env::<i64>("REPUTATION_MAX_SCORE")
+                .unwrap_or(20)
+                .max(0),
+            min_tenure: std::time::Duration::from_secs(
+                parse_env::<u64>("REPUTATION_MIN_TENURE_DAYS").unwrap_or(7) * 86_400,
+            ),
+        };
+
+        let retention = RetentionConfig {
+            cron: parse_cron_env("RETENTION_CRON", "0 30 4 * * *"),
+            spam_log_ttl: parse_days_env("SPAM_LOG_RETENTION_DAYS", 180),
+            usage_ttl: parse_days_env("USAGE_RETENTION_DAYS", 365),
+            shadow_ttl: parse_days_env("SHADOW_RETENTION_DAYS", 30),
+            appeal_ttl: parse_days_env("APPEAL_RETENTION_DAYS", 180),
+            member_join_ttl: parse_days_env("MEMBER_JOIN_RETENTION_DAYS", 30),
+        };
+
+        let rules_file = RulesFileConfig {
+            path: var("RULES_FILE")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            poll_interval: std::time::Duration::from_secs(
+                parse_env("RULES_FILE_POLL_SECS").unwrap_or(10).max(1),
+            ),
+        };
+
+        let transcription =
+            parse_bool_env("STT_ENABLED")
+                .unwrap_or(false)
+                .then(|| TranscriptionConfig {
+                    endpoint: var("STT_ENDPOINT").unwrap_or_else(|_| {
+                        "https://api.openai.com/v1/audio/transcriptions".to_string()
+                    }),
+                    api_key: var("STT_API_KEY").ok().filter(|v| !v.is_empty()),
+                    model: var("STT_MODEL").unwrap_or_else(|_| "whisper-1".to_string()),
+                    language: var("STT_LANGUAGE").ok().filter(|v| !v.is_empty()),
+                    max_duration_secs: parse_env("STT_MAX_DURATION_SECS").unwrap_or(120),
+                    timeout: std::time::Duration::from_secs(
+                        parse_env("STT_TIMEOUT_SECS").unwrap_or(60),
+                    ),
+                });
+
+        let video_ocr = parse_bool_env("VIDEO_OCR_ENABLED")
+            .unwrap_or(false)
+            .then(|| VideoOcrConfig {
+                ffmpeg_path: var("FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string()),
+                tesseract_path: var("TESSERACT_PATH").unwrap_or_else(|_| "tesseract".to_string()),
+                languages: var("VIDEO_OCR_LANGS").unwrap_or_else(|_| "kor+eng".to_string()),
+                frames: parse_env("VIDEO_OCR_FRAMES").unwrap_or(3),
+                max_duration_secs: parse_env("VIDEO_OCR_MAX_DURATION_SECS").unwrap_or(60),
+                timeout: std::time::Duration::from_secs(
+                    parse_env("VIDEO_OCR_TIMEOUT_SECS").unwrap_or(30),
+                ),
+            });
+
+        let non_empty = |key: &str| var(key).ok().filter(|v| !v.trim().is_empty());
+        let vision_endpoint = match non_empty("CEREBRAS_VISION_MODEL") {
+            Some(model) => {
+                let cerebras = load_ai_provider(AiProviderKind::Cerebras);
+                Some((cerebras.base_url, cerebras.api_key, model))
+            }
+            None => non_empty("VISION_MODEL").map(|model| {
+                (
+                    non_empty("VISION_BASE_URL")
+                        .unwrap_or_else(|| "https://api.openai.com/v1".to_string())
+                        .trim_end_matches('/')
+                        .to_string(),
+                    non_empty("VISION_API_KEY").or_else(|| non_empty("OPENAI_API_KEY")),
+                    model,
+                )
+            }),
+        };
+        let vision = vision_endpoint.map(|(base_url, api_key, model)| VisionConfig {
+            base_url,
+            api_key,
+            model,
+            max_bytes: parse_env("VISION_MAX_BYTES").unwrap_or(5 * 1024 * 1024),
+            timeout: std::time::Duration::from_secs(parse_env("VISION_TIMEOUT_SECS").unwrap_or(30)),
+        });
+
+        let probation = parse_bool_env("PROBATION_ENABLED")
+            .unwrap_or(true)
+            .then(|| ProbationConfig {
+                messages: parse_env("PROBATION_MESSAGES").unwrap_or(5),
+                window: std::time::Duration::from_secs(
+                    parse_env::<u64>("PROBATION_HOURS").unwrap_or(24) * 3600,
+                ),
+                confidence_threshold: parse_env::<f32>("PROBATION_CONFIDENCE_THRESHOLD")
+                    .unwrap_or(0.5)
+                    .clamp(0.0, 1.0),
+                max_urls: parse_env("PROBATION_MAX_URLS").unwrap_or(5),
+            });
+
+        let captcha = parse_bool_env("CAPTCHA_ENABLED")
+            .unwrap_or(false)
+            .then(|| CaptchaConfig {
+                timeout: std::time::Duration::from_secs(
+                    parse_env::<u64>("CAPTCHA_TIMEOUT_SECS")
+                        .unwrap_or(300)
+                        .max(30),
+                ),
+            });
+
+        let cas = parse_bool_env("CAS_ENABLED")
+            .unwrap_or(false)
+            .then(|| CasConfig {
+                base_url: var("CAS_API_URL").unwrap_or_else(|_| "https://api.cas.chat".to_string()),
+                timeout: std::time::Duration::from_secs(parse_env("CAS_TIMEOUT_SECS").unwrap_or(5)),
+                cache_ttl: std::time::Duration::from_secs(
+                    parse_env::<u64>("CAS_CACHE_HOURS").unwrap_or(24) * 3600,
+                ),
+                ban: parse_bool_env("CAS_BAN").unwrap_or(true),
+            });
+
+        let safe_browsing_key = var("SAFE_BROWSING_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+        let urlhaus_key = var("URLHAUS_AUTH_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+        let url_reputation =
+            (safe_browsing_key.is_some() || urlhaus_key.is_some()).then(|| UrlReputationConfig {
+                safe_browsing_key,
+                urlhaus_key,
+                timeout: std::time::Duration::from_secs(
+                    parse_env("URL_REPUTATION_TIMEOUT_SECS").unwrap_or(5),
+                ),
+                cache_ttl: std::time::Duration::from_secs(
+                    parse_env::<u64>("URL_REPUTATION_CACHE_HOURS").unwrap_or(24) * 3600,
+                ),
+            });
+        let page_render = match var("PAGE_RENDER_ENDPOINT")
+            .ok()
+            .map(|endpoint| endpoint.trim().to_string())
+            .filter(|endpoint| !endpoint.is_empty())
+        {
+            // The renderer runs the linked page's scripts, so it must not share the bot's
+            // network; the operator has to confirm that before it is used.
+            Some(_) if !parse_bool_env("PAGE_RENDER_EGRESS_ISOLATED").unwrap_or(false) => {
+                return Err(ConfigError::Invalid(
+                    "PAGE_RENDER_ENDPOINT",
+                    "requires PAGE_RENDER_EGRESS_ISOLATED=true on a renderer that can only \
+                     reach the public internet"
+                        .to_string(),
+                ));
+            }
+            Some(endpoint) => Some(PageRenderConfig {
+                endpoint,
+                token: var("PAGE_RENDER_TOKEN")
+                    .ok()
+                    .filter(|token| !token.trim().is_empty()),
+                timeout: std::time::Duration::from_secs(
+                    parse_env("PAGE_RENDER_TIMEOUT_SECS").unwrap_or(20),
+                ),
+            }),
+            None => None,
+        };
+
+        let raid = parse_bool_env("RAID_DETECTION_ENABLED")
+            .unwrap_or(true)
+            .then(|| {
+                let actions = var("RAID_ACTIONS").unwrap_or_else(|_| "delete".to_string());
+                let has_action = |name: &str| {
+                    actions
+                        .split(',')
+                        .any(|action| action.trim().eq_ignore_ascii_case(name))
+                };
+                RaidConfig {
+                    window: std::time::Duration::from_secs(
+                        parse_env("RAID_WINDOW_SECS").unwrap_or(300),
+                    ),
+                    min_accounts: parse_env::<usize>("RAID_MIN_ACCOUNTS").unwrap_or(3).max(2),
+                    similarity: parse_env("RAID_SIMILARITY").unwrap_or(0.7),
+                    delete: has_action("delete"),
+                    ban: has_action("ban"),
+                    lockdown: has_action("lockdown").then(|| {
+                        std::time::Duration::from_secs(
+                            parse_env("RAID_LOCKDOWN_SECS").unwrap_or(600),
+                        )
+                    }),
+                }
+            });
+
+        let flood = parse_bool_env("FLOOD_DETECTION_ENABLED")
+            .unwrap_or(true)
+            .then(|| FloodConfig {
+                window: std::time::Duration::from_secs(
+                    parse_env::<u64>("FLOOD_WINDOW_SECS").unwrap_or(10).max(1),
+                ),
+                max_messages: parse_env("FLOOD_MAX_MESSAGES").unwrap_or(8),
+                max_repeats: parse_env("FLOOD_MAX_REPEATS").unwrap_or(3),
+                mute: match parse_env::<u64>("FLOOD_MUTE_MINUTES").unwrap_or(60) {
+                    0 => None,
+                    minutes => Some(std::time::Duration::from_secs(minutes * 60)),
+                },
+            });
+
+        let ai_recording = var("AI_RECORD_MODE")
+            .ok()
+            .and_then(|mode| match mode.trim().to_ascii_lowercase().as_str() {
+                "record" => Some(AiRecordMode::Record),
+                "replay" => Some(AiRecordMode::Replay),
+                _ => None,
+            })
+            .map(|mode| AiRecordingConfig {
+                mode,
+                file: var("AI_RECORD_FILE").unwrap_or_else(|_| "ai_recordings.jsonl".to_string()),
+            });
+
+        let prompt = PromptConfig {
+            file: var("SYSTEM_PROMPT_FILE")
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
+            language: var("PROMPT_LANGUAGE")
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| "Korean".to_string()),
+            custom_criteria: var("PROMPT_CUSTOM_CRITERIA").unwrap_or_default(),
+            few_shot_examples: parse_env::<i64>("FEW_SHOT_EXAMPLES").unwrap_or(3).max(0),
+            language_files: per_language_env("SYSTEM_PROMPT_FILE"),
+            language_criteria: per_language_env("PROMPT_CRITERIA"),
+        };
+
+        let shadow_model = var("SHADOW_MODEL")
+            .ok()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty());
+        let shadow_prompt_file = var("SHADOW_PROMPT_FILE")
+            .ok()
+            .filter(|value| !value.trim().is_empty());
+        let shadow = (shadow_model.is_some() || shadow_prompt_file.is_some()).then(|| {
+            let provider = load_ai_provider(
+                var("SHADOW_PROVIDER")
+                    .ok()
+                    .and_then(|value| AiProviderKind::parse(&value))
+                    .unwrap_or(ai.kind),
+            );
+            ShadowConfig {
+                provider: AiProviderConfig {
+                    model: shadow_model.unwrap_or_else(|| provider.model.clone()),
+                    ..provider
+                },
+                prompt_file: shadow_prompt_file,
+            }
+        });
+
+        let bayes = parse_bool_env("BAYES_FALLBACK")
+            .unwrap_or(true)
+            .then(|| BayesConfig {
+                model_file: var("BAYES_MODEL_FILE")
+                    .unwrap_or_else(|_| "bayes_model.json".to_string()),
+                train_cron: parse_cron_env("BAYES_TRAIN_CRON", "0 15 4 * * *"),
+                spam_threshold: parse_env::<f64>("BAYES_SPAM_THRESHOLD")
+                    .unwrap_or(0.9)
+                    .clamp(0.5, 1.0),
+                min_samples: parse_env("BAYES_MIN_SAMPLES").unwrap_or(20),
+                ham_samples: parse_env("BAYES_HAM_SAMPLES").unwrap_or(2000),
+            });
+
         Ok(Self {
             telegram_bot_token,
+            webhook,
+            extra_bots,
             bot_username,
+            language,
             admin_user_id,
             admin_group_id,
-            allowed_chat_ids,
-            cerebras,
+            archive_channel_id,
+            admin_log_batch,
+            honeypot_chat_ids,
+            trusted_reporter_ids,
+            ai,
+            ai_fallbacks,
+            ai_confirmation,
+            ai_timeout,
             directories,
             logging,
             timezone,
             scheduler,
-            web,
             resilience,
             update,
+            export,
+            backup,
+            usage,
+            digest,
+            enforcement,
+            chat_admins,
+            blacklist,
+            reputation,
+            retention,
+            rules_file,
+            live: Arc::new(LiveConfig::new(LiveSettings::from_env())),
+            transcription,
+            video_ocr,
+            vision,
+            media_analysis_concurrency: parse_env("MEDIA_ANALYSIS_CONCURRENCY").unwrap_or(2).max(1),
+            raid,
+            flood,
+            probation,
+            captcha,
+            cas,
+            url_reputation,
+            page_render,
+            ai_recording,
+            prompt,
+            bayes,
+            shadow,
         })
     }
 }
 
+/// Bots named in `EXTRA_BOTS`, each configured through `BOT_<NAME>_TOKEN`,
+/// `BOT_<NAME>_USERNAME` and `BOT_<NAME>_CHAT_IDS`.
+fn load_extra_bots() -> Result<Vec<ExtraBotConfig>, ConfigError> {
+    let names = var("EXTRA_BOTS").unwrap_or_default();
+    let mut bots = Vec::new();
+    for name in names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let prefix = format!("BOT_{}", name.to_ascii_uppercase());
+        let token = var(format!("{prefix}_TOKEN"))
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| {
+                ConfigError::Invalid("EXTRA_BOTS", format!("{prefix}_TOKEN is not set"))
+            })?;
+        let chat_ids = parse_chat_ids(&format!("{prefix}_CHAT_IDS"));
+        if chat_ids.is_empty() {
+            return Err(ConfigError::Invalid(
+                "EXTRA_BOTS",
+                format!("{prefix}_CHAT_IDS is not set"),
+            ));
+        }
+        bots.push(ExtraBotConfig {
+            name: name.to_string(),
+            token: token.trim().to_string(),
+            username: var(format!("{prefix}_USERNAME"))
+                .ok()
+                .filter(|value| !value.is_empty()),
+            chat_ids,
+        });
+    }
+    Ok(bots)
+}
+
+fn load_webhook_config() -> Result<WebhookConfig, ConfigError> {
+    let url = var("WEBHOOK_URL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .ok_or(ConfigError::Missing("WEBHOOK_URL"))?;
+    let url = url::Url::parse(url.trim())
+        .map_err(|err| ConfigError::Invalid("WEBHOOK_URL", err.to_string()))?;
+    let listen_addr = var("WEBHOOK_LISTEN_ADDR").unwrap_or_else(|_| "0.0.0.0:8443".to_string());
+    let listen_addr = listen_addr
+        .trim()
+        .parse()
+        .map_err(|_| ConfigError::Invalid("WEBHOOK_LISTEN_ADDR", listen_addr.clone()))?;
+    let secret = var("WEBHOOK_SECRET")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    // Telegram only accepts these characters, and teloxide panics on anything else.
+    if let Some(secret) = &secret {
+        let valid = secret.len() <= 256
+            && secret
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-');
+        if !valid {
+            return Err(ConfigError::Invalid(
+                "WEBHOOK_SECRET",
+                "1-256 characters of A-Z, a-z, 0-9, _ and -".to_string(),
+            ));
+        }
+    }
+    Ok(WebhookConfig {
+        url,
+        listen_addr,
+        secret,
+    })
+}
+
 fn parse_int(key: &str) -> Option<i64> {
-    env::var(key)
+    var(key).ok().and_then(|value| value.parse::<i64>().ok())
+}
+
+/// Collects non-empty `<PREFIX>_<LANG>` variables (e.g. `PROMPT_CRITERIA_RU`), keyed by
+/// language code.
+fn per_language_env(prefix: &str) -> HashMap<String, String> {
+    language::CODES
+        .iter()
+        .filter_map(|code| {
+            var(format!("{prefix}_{}", code.to_ascii_uppercase()))
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (code.to_string(), value))
+        })
+        .collect()
+}
+
+/// Reads an optional cron spec; an empty value (or empty default) disables the job.
+fn parse_cron_env(key: &str, default: &str) -> Option<String> {
+    let value = var(key).unwrap_or_else(|_| default.to_string());
+    Some(value.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn parse_env<T: std::str::FromStr>(key: &str) -> Option<T> {
+    var(key)
         .ok()
-        .and_then(|value| value.parse::<i64>().ok())
+        .and_then(|value| value.trim().parse::<T>().ok())
+}
+
+/// Reads a retention period in days; `0` means "keep forever".
+fn parse_days_env(key: &str, default: u64) -> Option<std::time::Duration> {
+    let days = parse_env::<u64>(key).unwrap_or(default);
+    (days > 0).then(|| std::time::Duration::from_secs(days * 86_400))
+}
+
+/// Parses `name=weight` pairs separated by commas, skipping malformed entries.
+fn parse_weights(value: &str) -> HashMap<String, i32> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (name, weight) = pair.split_once('=')?;
+            Some((name.trim().to_string(), weight.trim().parse().ok()?))
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
 }
 
 fn parse_bool_env(key: &str) -> Option<bool> {
-    env::var(key)
+    var(key)
         .ok()
         .and_then(|value| match value.trim().to_ascii_lowercase().as_str() {
             "1" | "true" | "yes" | "on" => Some(true),
@@ -135,3 +835,15 @@ fn parse_bool_env(key: &str) -> Option<bool> {
             _ => None,
         })
 }
+
+fn parse_chat_ids(key: &str) -> Vec<i64> {
+    var(key)
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .filter_map(|part| part.trim().parse::<i64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+}
@@ -1,5 +1,13 @@
 pub mod env;
+mod live;
 mod loader;
 
-pub use env::{AppConfig, CerebrasConfig, DirectoryConfig, WebContentConfig};
-pub use loader::load_config;
+pub use env::{
+    AiProviderConfig, AiProviderKind, AiRecordMode, AppConfig, BayesConfig, CaptchaConfig,
+    CasConfig, ChatAdminMode, DirectoryConfig, EnforcementAction, ExtraBotConfig, FloodConfig,
+    HeuristicsConfig, PageRenderConfig, PrefilterConfig, PromptConfig, RaidConfig, RiskConfig,
+    TranscriptionConfig, UrlReputationConfig, VideoOcrConfig, VisionConfig, WebContentConfig,
+    WebhookConfig,
+};
+pub use live::LiveConfig;
+pub use loader::{load_config, load_heuristics_config};
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+use crate::heuristics::known_spam::domain_suffixes;
+
+/// Trusted domains whose links are never fetched for classification, including their
+/// subdomains.
+#[derive(Clone)]
+pub struct AllowedDomainRepository {
+    pool: SqlitePool,
+}
+
+impl AllowedDomainRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn contains(&self, domain: &str) -> Result<bool> {
+        for suffix in domain_suffixes(domain) {
+            let row: Option<(i64,)> =
+                query_as(r#"SELECT 1 FROM allowed_domains WHERE domain = ?1"#)
+                    .bind(suffix)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if row.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns `false` when the domain was already allowed.
+    pub async fn add(&self, domain: &str, added_by: Option<i64>) -> Result<bool> {
+        let affected =
+            query(r#"INSERT OR IGNORE INTO allowed_domains (domain, added_by) VALUES (?1, ?2)"#)
+                .bind(domain)
+                .bind(added_by)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+        Ok(affected > 0)
+    }
+
+    pub async fn remove(&self, domain: &str) -> Result<bool> {
+        let affected = query(r#"DELETE FROM allowed_domains WHERE domain = ?1"#)
+            .bind(domain)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+
+    pub async fn list(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            query_as(r#"SELECT domain FROM allowed_domains ORDER BY domain"#)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(|(domain,)| domain).collect())
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx_core::{from_row::FromRow, query::query, query_as::query_as, row::Row};
+use sqlx_sqlite::{SqlitePool, SqliteRow};
+
+/// Deletions disputed through the restore button or a user's DM appeal, waiting for
+/// an admin decision in `/review`.
+#[derive(Clone)]
+pub struct AppealRepository {
+    pool: SqlitePool,
+}
+
+/// Where a case came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppealSource {
+    /// An admin pressed "오탐 복구" on the spam log.
+    Restore,
+    /// The sender appealed in a private chat with the bot.
+    Dm,
+}
+
+impl AppealSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Restore => "restore",
+            Self::Dm => "dm",
+        }
+    }
+}
+
+/// A pending case with the deleted message it disputes.
+#[derive(Debug, Clone)]
+pub struct Appeal {
+    pub id: i64,
+    pub source: String,
+    /// The user's explanation, for DM appeals.
+    pub note: Option<String>,
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    pub user_display: String,
+    pub text: String,
+    /// Why the message was deleted, and the classifier's confidence when it decided.
+    pub reason: String,
+    pub confidence: Option<f32>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for Appeal {
+    fn from_row(row: &'r SqliteRow) -> std::result::Result<Self, sqlx_core::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            source: row.try_get("source")?,
+            note: row.try_get("note")?,
+            chat_id: row.try_get("chat_id")?,
+            user_id: row.try_get("user_id")?,
+            user_display: row.try_get("user_display")?,
+            text: row.try_get("text")?,
+            reason: row.try_get("reason")?,
+            confidence: row.try_get("confidence")?,
+        })
+    }
+}
+
+const APPEAL_COLUMNS: &str = r#"a.id, a.source, a.note, l.chat_id, l.user_id,
+    l.user_display, l.text, l.reason, l.confidence"#;
+
+impl AppealRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Opens a case for the spam log entry; returns `None` when it already has one.
+    pub async fn open(
+        &self,
+        spam_log_id: i64,
+        source: AppealSource,
+        note: Option<&str>,
+    ) -> Result<Option<i64>> {
+        let result = query(
+            r#"INSERT OR IGNORE INTO appeals (spam_log_id, source, note, status, created_at)
+                VALUES (?1, ?2, ?3, 'pending', ?4)"#,
+        )
+        .bind(spam_log_id)
+        .bind(source.as_str())
+        .bind(note)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok((result.rows_affected() > 0).then(|| result.last_insert_rowid()))
+    }
+
+    /// Oldest pending cases first.
+    pub async fn pending(&self, limit: i64) -> Result<Vec<Appeal>> {
+        let rows = query_as::<_, Appeal>(&format!(
+            r#"SELECT {APPEAL_COLUMNS}
+                FROM appeals a JOIN spam_log l ON l.id = a.spam_log_id
+                WHERE a.status = 'pending'
+                ORDER BY a.id
+                LIMIT ?1"#
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Closes a pending case and returns it; `None` when it is unknown or already
+    /// decided.
+    pub async fn decide(&self, id: i64, accepted: bool) -> Result<Option<Appeal>> {
+        let updated = query(
+            r#"UPDATE appeals SET status = ?2, decided_at = ?3
+                WHERE id = ?1 AND status = 'pending'"#,
+        )
+        .bind(id)
+        .bind(if accepted { "accepted" } else { "rejected" })
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Ok(None);
+        }
+        let row = query_as::<_, Appeal>(&format!(
+            r#"SELECT {APPEAL_COLUMNS}
+                FROM appeals a JOIN spam_log l ON l.id = a.spam_log_id
+                WHERE a.id = ?1"#
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use sqlx_core::query::query;
+use sqlx_sqlite::SqlitePool;
+
+/// Consistent snapshots of the live database, taken without stopping the bot.
+#[derive(Clone)]
+pub struct BackupRepository {
+    pool: SqlitePool,
+}
+
+impl BackupRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes a compacted copy of the database to `path`, which must not exist yet.
+    pub async fn vacuum_into(&self, path: &Path) -> Result<()> {
+        let Some(path) = path.to_str() else {
+            bail!("backup path is not valid UTF-8: {}", path.display());
+        };
+        query("VACUUM INTO ?1")
+            .bind(path)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
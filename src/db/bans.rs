@@ -0,0 +1,95 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Bans the bot applied, so timed ones can be lifted and admins can reverse any of them.
+#[derive(Clone)]
+pub struct BanRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BanRow {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub reason: String,
+    pub banned_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+type RawBan = (i64, i64, String, DateTime<Utc>, Option<DateTime<Utc>>);
+
+impl From<RawBan> for BanRow {
+    fn from((chat_id, user_id, reason, banned_at, expires_at): RawBan) -> Self {
+        Self {
+            chat_id,
+            user_id,
+            reason,
+            banned_at,
+            expires_at,
+        }
+    }
+}
+
+impl BanRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Records a ban, replacing an earlier one of the same user in the chat.
+    pub async fn record(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        reason: &str,
+        at: DateTime<Utc>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT OR REPLACE INTO bans (chat_id, user_id, reason, banned_at, expires_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .bind(reason)
+        .bind(at)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Bans whose expiry has passed; each stays recorded until it is actually lifted.
+    pub async fn expired(&self, now: DateTime<Utc>) -> Result<Vec<BanRow>> {
+        let rows: Vec<RawBan> = query_as(
+            r#"SELECT chat_id, user_id, reason, banned_at, expires_at FROM bans
+                WHERE expires_at IS NOT NULL AND expires_at <= ?1"#,
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(BanRow::from).collect())
+    }
+
+    pub async fn for_user(&self, user_id: i64) -> Result<Vec<BanRow>> {
+        let rows: Vec<RawBan> = query_as(
+            r#"SELECT chat_id, user_id, reason, banned_at, expires_at FROM bans
+                WHERE user_id = ?1 ORDER BY banned_at"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(BanRow::from).collect())
+    }
+
+    pub async fn remove(&self, chat_id: i64, user_id: i64) -> Result<bool> {
+        let affected = query(r#"DELETE FROM bans WHERE chat_id = ?1 AND user_id = ?2"#)
+            .bind(chat_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+}
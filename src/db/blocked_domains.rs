@@ -0,0 +1,88 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+use crate::heuristics::known_spam::domain_suffixes;
+
+/// Domains whose links are treated as spam, including their subdomains.
+#[derive(Clone)]
+pub struct BlockedDomainRepository {
+    pool: SqlitePool,
+}
+
+/// Source of the domains admins blocked themselves.
+pub const ADMIN_SOURCE: &str = "admin";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockedDomain {
+    pub domain: String,
+    /// `admin`, or `honeypot:<chat id>` for domains harvested from honeypot chats.
+    pub source: Option<String>,
+}
+
+impl BlockedDomain {
+    /// Blocked by an admin rather than harvested, so trusted enough to act on alone.
+    pub fn is_admin(&self) -> bool {
+        self.source.as_deref() == Some(ADMIN_SOURCE)
+    }
+}
+
+impl BlockedDomainRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the blocked entries that `domains` fall under.
+    pub async fn matching(&self, domains: &[String]) -> Result<Vec<BlockedDomain>> {
+        let mut blocked = Vec::new();
+        for domain in domains {
+            for suffix in domain_suffixes(domain) {
+                let row: Option<(String, Option<String>)> =
+                    query_as(r#"SELECT domain, source FROM blocked_domains WHERE domain = ?1"#)
+                        .bind(suffix)
+                        .fetch_optional(&self.pool)
+                        .await?;
+                if let Some((domain, source)) = row {
+                    blocked.push(BlockedDomain { domain, source });
+                    break;
+                }
+            }
+        }
+        blocked.sort();
+        blocked.dedup();
+        Ok(blocked)
+    }
+
+    /// Returns `false` when the domain was already blocked.
+    pub async fn add(&self, domain: &str, source: &str) -> Result<bool> {
+        let affected =
+            query(r#"INSERT OR IGNORE INTO blocked_domains (domain, source) VALUES (?1, ?2)"#)
+                .bind(domain)
+                .bind(source)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+        Ok(affected > 0)
+    }
+
+    pub async fn remove(&self, domain: &str) -> Result<bool> {
+        let affected = query(r#"DELETE FROM blocked_domains WHERE domain = ?1"#)
+            .bind(domain)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+
+    /// Most recently blocked first, with where each came from.
+    pub async fn list(&self, limit: i64) -> Result<Vec<(String, Option<String>)>> {
+        let rows = query_as(
+            r#"SELECT domain, source FROM blocked_domains
+                ORDER BY added_at DESC, domain LIMIT ?1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Recent CAS (Combot Anti-Spam) lookups, so each user is queried at most once per TTL.
+#[derive(Clone)]
+pub struct CasCacheRepository {
+    pool: SqlitePool,
+}
+
+impl CasCacheRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Cached verdict for `user_id` checked at or after `fresh_after`.
+    pub async fn get(&self, user_id: i64, fresh_after: DateTime<Utc>) -> Result<Option<bool>> {
+        let row: Option<(bool,)> =
+            query_as(r#"SELECT banned FROM cas_cache WHERE user_id = ?1 AND checked_at >= ?2"#)
+                .bind(user_id)
+                .bind(fresh_after)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(banned,)| banned))
+    }
+
+    pub async fn put(&self, user_id: i64, banned: bool, checked_at: DateTime<Utc>) -> Result<()> {
+        query(
+            r#"INSERT INTO cas_cache (user_id, banned, checked_at) VALUES (?1, ?2, ?3)
+                ON CONFLICT(user_id) DO UPDATE SET
+                    banned = excluded.banned,
+                    checked_at = excluded.checked_at"#,
+        )
+        .bind(user_id)
+        .bind(banned)
+        .bind(checked_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+use crate::i18n::Locale;
+
+/// Per-chat settings admins manage with bot commands, such as extra spam criteria, the
+/// action taken against spammers, the new-member probation window, reply language,
+/// dry-run mode and the deletion notice.
+#[derive(Clone)]
+pub struct ChatSettingsRepository {
+    pool: SqlitePool,
+}
+
+/// A chat's own probation window; both values at 0 turn probation off there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbationWindow {
+    pub messages: i64,
+    pub hours: i64,
+}
+
+impl ChatSettingsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn criteria(&self, chat_id: i64) -> Result<Option<String>> {
+        let row: Option<(Option<String>,)> =
+            query_as(r#"SELECT criteria FROM chat_settings WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.and_then(|(criteria,)| criteria))
+    }
+
+    /// Criteria of every listed chat that has some.
+    pub async fn criteria_for(&self, chat_ids: &[i64]) -> Result<HashMap<i64, String>> {
+        if chat_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let chat_list = chat_ids
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let rows: Vec<(i64, String)> = query_as(&format!(
+            r#"SELECT chat_id, criteria FROM chat_settings
+                WHERE criteria IS NOT NULL AND chat_id IN ({chat_list})"#
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Sets or, with `None`, clears the chat's criteria.
+    pub async fn set_criteria(&self, chat_id: i64, criteria: Option<&str>) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, criteria) VALUES (?1, ?2)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    criteria = excluded.criteria,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(criteria)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stored enforcement policy name, see `EnforcementAction::name`.
+    pub async fn action(&self, chat_id: i64) -> Result<Option<String>> {
+        let row: Option<(Option<String>,)> =
+            query_as(r#"SELECT action FROM chat_settings WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.and_then(|(action,)| action))
+    }
+
+    /// Sets or, with `None`, clears the chat's enforcement policy.
+    pub async fn set_action(&self, chat_id: i64, action: Option<&str>) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, action) VALUES (?1, ?2)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    action = excluded.action,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(action)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stored locale code, see `Locale::code`.
+    pub async fn language(&self, chat_id: i64) -> Result<Option<String>> {
+        let row: Option<(Option<String>,)> =
+            query_as(r#"SELECT language FROM chat_settings WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.and_then(|(language,)| language))
+    }
+
+    /// The chat's reply language, or `default` when it has none or loading failed.
+    pub async fn locale(&self, chat_id: i64, default: Locale) -> Locale {
+        match self.language(chat_id).await {
+            Ok(stored) => stored.as_deref().and_then(Locale::parse),
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, chat_id, "failed to load chat language");
+                None
+            }
+        }
+        .unwrap_or(default)
+    }
+
+    /// Sets or, with `None`, clears the chat's reply language.
+    pub async fn set_language(&self, chat_id: i64, language: Option<&str>) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, language) VALUES (?1, ?2)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    language = excluded.language,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(language)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Whether spam in the chat goes to the admin group for review instead of being
+    /// deleted.
+    pub async fn dry_run(&self, chat_id: i64) -> Result<bool> {
+        let row: Option<(bool,)> =
+            query_as(r#"SELECT dry_run FROM chat_settings WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some_and(|(dry_run,)| dry_run))
+    }
+
+    pub async fn set_dry_run(&self, chat_id: i64, dry_run: bool) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, dry_run) VALUES (?1, ?2)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    dry_run = excluded.dry_run,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(dry_run)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Seconds the deletion notice stays up; 0 turns it off in the chat.
+    pub async fn notice_secs(&self, chat_id: i64) -> Result<Option<i64>> {
+        let row: Option<(Option<i64>,)> =
+            query_as(r#"SELECT notice_secs FROM chat_settings WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.and_then(|(secs,)| secs))
+    }
+
+    /// Sets or, with `None`, clears how long the chat's deletion notice stays up.
+    pub async fn set_notice_secs(&self, chat_id: i64, secs: Option<i64>) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, notice_secs) VALUES (?1, ?2)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    notice_secs = excluded.notice_secs,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(secs)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn probation(&self, chat_id: i64) -> Result<Option<ProbationWindow>> {
+        let row: Option<(Option<i64>, Option<i64>)> = query_as(
+            r#"SELECT probation_messages, probation_hours FROM chat_settings WHERE chat_id = ?1"#,
+        )
+        .bind(chat_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(match row {
+            Some((Some(messages), Some(hours))) => Some(ProbationWindow { messages, hours }),
+            _ => None,
+        })
+    }
+
+    /// Sets or, with `None`, clears the chat's probation window.
+    pub async fn set_probation(&self, chat_id: i64, window: Option<ProbationWindow>) -> Result<()> {
+        query(
+            r#"INSERT INTO chat_settings (chat_id, probation_messages, probation_hours)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    probation_messages = excluded.probation_messages,
+                    probation_hours = excluded.probation_hours,
+                    updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(window.map(|window| window.messages))
+        .bind(window.map(|window| window.hours))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
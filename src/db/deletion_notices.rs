@@ -0,0 +1,101 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Notices posted after spam was removed and when each is deleted again, so a restart
+/// still cleans them up and a wave of deletions keeps one notice per chat topic.
+#[derive(Clone)]
+pub struct DeletionNoticeRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DeletionNotice {
+    pub chat_id: i64,
+    pub message_id: i32,
+    pub delete_at: DateTime<Utc>,
+}
+
+impl DeletionNoticeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Pushes back the removal of the notice still up in the chat topic; `false` when
+    /// there is none and a new one should be posted.
+    pub async fn extend(
+        &self,
+        chat_id: i64,
+        topic_id: Option<i32>,
+        delete_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = query(
+            r#"UPDATE deletion_notices SET delete_at = ?3
+                WHERE chat_id = ?1 AND topic_id IS ?2 AND delete_at > ?4"#,
+        )
+        .bind(chat_id)
+        .bind(topic_id)
+        .bind(delete_at)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        topic_id: Option<i32>,
+        delete_at: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT OR REPLACE INTO deletion_notices (chat_id, message_id, topic_id, delete_at)
+                VALUES (?1, ?2, ?3, ?4)"#,
+        )
+        .bind(chat_id)
+        .bind(message_id)
+        .bind(topic_id)
+        .bind(delete_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// When the notice is due to be deleted, if it is still tracked.
+    pub async fn delete_at(&self, chat_id: i64, message_id: i32) -> Result<Option<DateTime<Utc>>> {
+        let row: Option<(DateTime<Utc>,)> = query_as(
+            r#"SELECT delete_at FROM deletion_notices WHERE chat_id = ?1 AND message_id = ?2"#,
+        )
+        .bind(chat_id)
+        .bind(message_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(delete_at,)| delete_at))
+    }
+
+    pub async fn all(&self) -> Result<Vec<DeletionNotice>> {
+        let rows: Vec<(i64, i32, DateTime<Utc>)> =
+            query_as(r#"SELECT chat_id, message_id, delete_at FROM deletion_notices"#)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(chat_id, message_id, delete_at)| DeletionNotice {
+                chat_id,
+                message_id,
+                delete_at,
+            })
+            .collect())
+    }
+
+    pub async fn remove(&self, chat_id: i64, message_id: i32) -> Result<()> {
+        query(r#"DELETE FROM deletion_notices WHERE chat_id = ?1 AND message_id = ?2"#)
+            .bind(chat_id)
+            .bind(message_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Messages admins confirmed as spam or ham, replayed to the model as few-shot examples.
+#[derive(Clone)]
+pub struct ExampleRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LabeledExample {
+    pub text: String,
+    pub spam: bool,
+    pub reason: Option<String>,
+}
+
+impl ExampleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a labeled message. Texts sharing a fingerprint keep only the latest label.
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        text: &str,
+        spam: bool,
+        reason: Option<&str>,
+        fingerprint: Option<i64>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT INTO labeled_examples (chat_id, text, spam, reason, fingerprint)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(fingerprint) DO UPDATE SET
+                    chat_id = excluded.chat_id,
+                    text = excluded.text,
+                    spam = excluded.spam,
+                    reason = excluded.reason,
+                    added_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(chat_id)
+        .bind(text)
+        .bind(spam)
+        .bind(reason)
+        .bind(fingerprint)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent examples with the given label, those from `chat_ids` first.
+    pub async fn recent(
+        &self,
+        chat_ids: &[i64],
+        spam: bool,
+        limit: i64,
+    ) -> Result<Vec<LabeledExample>> {
+        let chat_list = chat_ids
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let rows: Vec<(String, bool, Option<String>)> = query_as(&format!(
+            r#"SELECT text, spam, reason FROM labeled_examples
+                WHERE spam = ?1
+                ORDER BY chat_id IN ({chat_list}) DESC, added_at DESC, id DESC
+                LIMIT ?2"#
+        ))
+        .bind(spam)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(text, spam, reason)| LabeledExample { text, spam, reason })
+            .collect())
+    }
+}
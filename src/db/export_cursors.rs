@@ -0,0 +1,33 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Remembers the last row id exported per source table.
+#[derive(Clone)]
+pub struct ExportCursorRepository {
+    pool: SqlitePool,
+}
+
+impl ExportCursorRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, source: &str) -> Result<i64> {
+        let row: Option<(i64,)> =
+            query_as(r#"SELECT last_id FROM export_cursors WHERE source = ?1"#)
+                .bind(source)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(id,)| id).unwrap_or(0))
+    }
+
+    pub async fn set(&self, source: &str, last_id: i64) -> Result<()> {
+        query(r#"INSERT OR REPLACE INTO export_cursors (source, last_id) VALUES (?1, ?2)"#)
+            .bind(source)
+            .bind(last_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
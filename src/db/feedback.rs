@@ -0,0 +1,111 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx_core::{from_row::FromRow, query::query, query_as::query_as, row::Row};
+use sqlx_sqlite::{SqlitePool, SqliteRow};
+
+/// Every admin verdict on a deleted message, paired with what the classifier said, kept
+/// for prompt tuning and the `eval` subcommand.
+#[derive(Clone)]
+pub struct FeedbackRepository {
+    pool: SqlitePool,
+}
+
+/// A verdict as it is recorded.
+#[derive(Debug, Clone)]
+pub struct NewFeedback<'a> {
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    pub text: &'a str,
+    /// The correct label decided by the admin.
+    pub spam: bool,
+    /// The reason the message was deleted with.
+    pub model_reason: Option<&'a str>,
+    pub confidence: Option<f32>,
+    /// `restore` or `dm` for `/review` cases, `ignore` for the spam log button.
+    pub source: &'a str,
+    pub decided_by: Option<i64>,
+    /// The `/review` case behind the verdict; deciding it replaces the row a restore
+    /// recorded instead of adding a second one.
+    pub appeal_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Feedback {
+    pub id: i64,
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    pub text: String,
+    pub spam: bool,
+    pub model_reason: Option<String>,
+    pub confidence: Option<f32>,
+    pub source: String,
+    pub decided_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for Feedback {
+    fn from_row(row: &'r SqliteRow) -> std::result::Result<Self, sqlx_core::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            chat_id: row.try_get("chat_id")?,
+            user_id: row.try_get("user_id")?,
+            text: row.try_get("text")?,
+            spam: row.try_get("spam")?,
+            model_reason: row.try_get("model_reason")?,
+            confidence: row.try_get("confidence")?,
+            source: row.try_get("source")?,
+            decided_by: row.try_get("decided_by")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl FeedbackRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a verdict, or overwrites the earlier one for the same `/review` case.
+    pub async fn record(&self, feedback: &NewFeedback<'_>) -> Result<()> {
+        query(
+            r#"INSERT INTO feedback (chat_id, user_id, text, spam, model_reason, confidence,
+                    source, decided_by, created_at, appeal_id)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                ON CONFLICT(appeal_id) DO UPDATE SET
+                    spam = excluded.spam,
+                    decided_by = excluded.decided_by,
+                    created_at = excluded.created_at"#,
+        )
+        .bind(feedback.chat_id)
+        .bind(feedback.user_id)
+        .bind(feedback.text)
+        .bind(feedback.spam)
+        .bind(feedback.model_reason)
+        .bind(feedback.confidence)
+        .bind(feedback.source)
+        .bind(feedback.decided_by)
+        .bind(Utc::now())
+        .bind(feedback.appeal_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Newest verdicts first, optionally only those recorded after `since`.
+    pub async fn recent(&self, since: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<Feedback>> {
+        let rows = query_as::<_, Feedback>(
+            r#"SELECT id, chat_id, user_id, text, spam, model_reason, confidence, source,
+                    decided_by, created_at
+                FROM feedback
+                WHERE ?1 IS NULL OR created_at >= ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2"#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Recent messages the classifier cleared, kept as ham for training the offline model.
+#[derive(Clone)]
+pub struct HamSampleRepository {
+    pool: SqlitePool,
+}
+
+impl HamSampleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores `text` and drops the oldest samples beyond `keep`.
+    pub async fn add(&self, chat_id: i64, text: &str, keep: i64) -> Result<()> {
+        query(r#"INSERT INTO ham_samples (chat_id, text) VALUES (?1, ?2)"#)
+            .bind(chat_id)
+            .bind(text)
+            .execute(&self.pool)
+            .await?;
+        query(
+            r#"DELETE FROM ham_samples
+                WHERE id <= (SELECT id FROM ham_samples ORDER BY id DESC LIMIT 1 OFFSET ?1)"#,
+        )
+        .bind(keep)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn recent_texts(&self, limit: i64) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            query_as(r#"SELECT text FROM ham_samples ORDER BY id DESC LIMIT ?1"#)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(|(text,)| text).collect())
+    }
+}
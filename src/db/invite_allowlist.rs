@@ -0,0 +1,60 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Per-chat partner channels whose invite links are not penalized.
+#[derive(Clone)]
+pub struct InviteAllowlistRepository {
+    pool: SqlitePool,
+}
+
+impl InviteAllowlistRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self, chat_id: Option<i64>) -> Result<Vec<(i64, String)>> {
+        let rows = match chat_id {
+            Some(chat_id) => {
+                query_as(
+                    r#"SELECT chat_id, target FROM invite_allowlist WHERE chat_id = ?1
+                        ORDER BY target"#,
+                )
+                .bind(chat_id)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                query_as(r#"SELECT chat_id, target FROM invite_allowlist ORDER BY chat_id, target"#)
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+        Ok(rows)
+    }
+
+    /// Returns `false` when the target was already allowed for the chat.
+    pub async fn add(&self, chat_id: i64, target: &str, added_by: Option<i64>) -> Result<bool> {
+        let affected = query(
+            r#"INSERT OR IGNORE INTO invite_allowlist (chat_id, target, added_by)
+                VALUES (?1, ?2, ?3)"#,
+        )
+        .bind(chat_id)
+        .bind(target)
+        .bind(added_by)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected > 0)
+    }
+
+    pub async fn remove(&self, chat_id: i64, target: &str) -> Result<bool> {
+        let affected = query(r#"DELETE FROM invite_allowlist WHERE chat_id = ?1 AND target = ?2"#)
+            .bind(chat_id)
+            .bind(target)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+}
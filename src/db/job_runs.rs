@@ -0,0 +1,34 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Tracks when each scheduled maintenance job last completed.
+#[derive(Clone)]
+pub struct JobRunRepository {
+    pool: SqlitePool,
+}
+
+impl JobRunRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn last_run(&self, name: &str) -> Result<Option<DateTime<Utc>>> {
+        let row: Option<(DateTime<Utc>,)> =
+            query_as(r#"SELECT last_run_at FROM job_runs WHERE name = ?1"#)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(ts,)| ts))
+    }
+
+    pub async fn record_run(&self, name: &str, at: DateTime<Utc>) -> Result<()> {
+        query(r#"INSERT OR REPLACE INTO job_runs (name, last_run_at) VALUES (?1, ?2)"#)
+            .bind(name)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+use teloxide::types::ChatPermissions;
+
+/// Chats locked down after a raid, with the permissions to restore, so a restart still
+/// lifts the lockdown and a second raid never captures the locked-down permissions.
+#[derive(Clone)]
+pub struct LockdownRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Lockdown {
+    pub chat_id: i64,
+    pub previous: ChatPermissions,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl LockdownRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn is_active(&self, chat_id: i64) -> Result<bool> {
+        let row: Option<(i64,)> =
+            query_as(r#"SELECT chat_id FROM chat_lockdowns WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some())
+    }
+
+    pub async fn start(
+        &self,
+        chat_id: i64,
+        previous: ChatPermissions,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT OR REPLACE INTO chat_lockdowns (chat_id, previous, expires_at)
+                VALUES (?1, ?2, ?3)"#,
+        )
+        .bind(chat_id)
+        .bind(serde_json::to_string(&previous)?)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn all(&self) -> Result<Vec<Lockdown>> {
+        let rows: Vec<(i64, String, DateTime<Utc>)> =
+            query_as(r#"SELECT chat_id, previous, expires_at FROM chat_lockdowns"#)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(chat_id, previous, expires_at)| Lockdown {
+                chat_id,
+                previous: serde_json::from_str(&previous)
+                    .unwrap_or_else(|_| ChatPermissions::all()),
+                expires_at,
+            })
+            .collect())
+    }
+
+    pub async fn remove(&self, chat_id: i64) -> Result<()> {
+        query(r#"DELETE FROM chat_lockdowns WHERE chat_id = ?1"#)
+            .bind(chat_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Housekeeping that keeps the database and its WAL file from growing without bound.
+#[derive(Clone)]
+pub struct MaintenanceRepository {
+    pool: SqlitePool,
+}
+
+impl MaintenanceRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns pages to the filesystem. Databases created before incremental
+    /// auto-vacuum was enabled are switched over with one full `VACUUM`; returns whether
+    /// that happened.
+    pub async fn vacuum(&self) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let (mode,): (i64,) = query_as("PRAGMA auto_vacuum").fetch_one(&mut *conn).await?;
+        if mode == 2 {
+            query("PRAGMA incremental_vacuum")
+                .execute(&mut *conn)
+                .await?;
+            return Ok(false);
+        }
+        query("PRAGMA auto_vacuum = INCREMENTAL")
+            .execute(&mut *conn)
+            .await?;
+        query("VACUUM").execute(&mut *conn).await?;
+        Ok(true)
+    }
+
+    pub async fn analyze(&self) -> Result<()> {
+        query("ANALYZE").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Copies the WAL into the database and truncates it; returns whether a reader or
+    /// writer kept the checkpoint from completing.
+    pub async fn checkpoint(&self) -> Result<bool> {
+        let (busy, _, _): (i64, i64, i64) = query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(busy != 0)
+    }
+}
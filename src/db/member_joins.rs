@@ -0,0 +1,73 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// When each member joined a chat and how many messages they have sent since, for
+/// new-member probation.
+#[derive(Clone)]
+pub struct MemberJoinRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemberActivity {
+    pub joined_at: DateTime<Utc>,
+    /// Messages sent since joining, including the current one.
+    pub messages: i64,
+}
+
+impl MemberJoinRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Starts (or restarts, after leaving and rejoining) tracking a member.
+    pub async fn record_join(&self, chat_id: i64, user_id: i64, at: DateTime<Utc>) -> Result<()> {
+        query(
+            r#"INSERT INTO member_joins (chat_id, user_id, joined_at, messages)
+                VALUES (?1, ?2, ?3, 0)
+                ON CONFLICT(chat_id, user_id) DO UPDATE SET
+                    joined_at = excluded.joined_at,
+                    messages = 0"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .bind(at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// When the member's latest join was seen, if ever.
+    pub async fn joined_at(&self, chat_id: i64, user_id: i64) -> Result<Option<DateTime<Utc>>> {
+        let row: Option<(DateTime<Utc>,)> =
+            query_as(r#"SELECT joined_at FROM member_joins WHERE chat_id = ?1 AND user_id = ?2"#)
+                .bind(chat_id)
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(joined_at,)| joined_at))
+    }
+
+    /// Counts a message from the member; `None` when their join was never seen.
+    pub async fn record_message(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<MemberActivity>> {
+        let row: Option<(DateTime<Utc>, i64)> = query_as(
+            r#"UPDATE member_joins SET messages = messages + 1
+                WHERE chat_id = ?1 AND user_id = ?2
+                RETURNING joined_at, messages"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(joined_at, messages)| MemberActivity {
+            joined_at,
+            messages,
+        }))
+    }
+}
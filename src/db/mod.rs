@@ -1,16 +1,129 @@
-use std::{path::Path, str::FromStr, time::Duration};
+use std::{path::Path, str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::Result;
-use sqlx_core::query::query;
-use sqlx_sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::{
+    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+};
 
+pub mod allowed_domains;
+pub mod appeals;
+pub mod backups;
+pub mod bans;
+pub mod blocked_domains;
+pub mod cas_cache;
+pub mod chat_settings;
+pub mod deletion_notices;
+pub mod examples;
+pub mod export_cursors;
+pub mod feedback;
+pub mod ham_samples;
+pub mod invite_allowlist;
+pub mod job_runs;
+pub mod lockdowns;
+pub mod maintenance;
+pub mod member_joins;
+pub mod recent_messages;
+pub mod reputation;
+pub mod restart_crons;
+pub mod retention;
+pub mod rules;
+pub mod shadow_results;
+pub mod spam_fingerprints;
+pub mod spam_log;
+pub mod status_messages;
+pub mod strikes;
+pub mod url_reputation_cache;
+pub mod usage;
+pub mod user_blacklist;
 pub mod whitelist;
 
+/// Every repository backed by the shared SQLite pool.
+#[derive(Clone)]
+pub struct Repositories {
+    pub whitelist: Arc<whitelist::WhitelistRepository>,
+    pub restart_crons: Arc<restart_crons::RestartCronRepository>,
+    pub job_runs: Arc<job_runs::JobRunRepository>,
+    pub spam_log: Arc<spam_log::SpamLogRepository>,
+    pub export_cursors: Arc<export_cursors::ExportCursorRepository>,
+    pub reputation: Arc<reputation::ReputationRepository>,
+    pub retention: Arc<retention::RetentionRepository>,
+    pub rules: Arc<rules::RuleRepository>,
+    pub invite_allowlist: Arc<invite_allowlist::InviteAllowlistRepository>,
+    pub user_blacklist: Arc<user_blacklist::UserBlacklistRepository>,
+    pub spam_fingerprints: Arc<spam_fingerprints::SpamFingerprintRepository>,
+    pub blocked_domains: Arc<blocked_domains::BlockedDomainRepository>,
+    pub examples: Arc<examples::ExampleRepository>,
+    pub ham_samples: Arc<ham_samples::HamSampleRepository>,
+    pub usage: Arc<usage::UsageRepository>,
+    pub chat_settings: Arc<chat_settings::ChatSettingsRepository>,
+    pub shadow_results: Arc<shadow_results::ShadowResultRepository>,
+    pub strikes: Arc<strikes::StrikeRepository>,
+    pub appeals: Arc<appeals::AppealRepository>,
+    pub member_joins: Arc<member_joins::MemberJoinRepository>,
+    pub cas_cache: Arc<cas_cache::CasCacheRepository>,
+    pub status_messages: Arc<status_messages::StatusMessageRepository>,
+    pub recent_messages: Arc<recent_messages::RecentMessageRepository>,
+    pub backups: Arc<backups::BackupRepository>,
+    pub bans: Arc<bans::BanRepository>,
+    pub feedback: Arc<feedback::FeedbackRepository>,
+    pub maintenance: Arc<maintenance::MaintenanceRepository>,
+    pub allowed_domains: Arc<allowed_domains::AllowedDomainRepository>,
+    pub url_reputation_cache: Arc<url_reputation_cache::UrlReputationCacheRepository>,
+    pub lockdowns: Arc<lockdowns::LockdownRepository>,
+    pub deletion_notices: Arc<deletion_notices::DeletionNoticeRepository>,
+}
+
+impl Repositories {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            whitelist: Arc::new(whitelist::WhitelistRepository::new(pool.clone())),
+            restart_crons: Arc::new(restart_crons::RestartCronRepository::new(pool.clone())),
+            job_runs: Arc::new(job_runs::JobRunRepository::new(pool.clone())),
+            spam_log: Arc::new(spam_log::SpamLogRepository::new(pool.clone())),
+            export_cursors: Arc::new(export_cursors::ExportCursorRepository::new(pool.clone())),
+            reputation: Arc::new(reputation::ReputationRepository::new(pool.clone())),
+            retention: Arc::new(retention::RetentionRepository::new(pool.clone())),
+            rules: Arc::new(rules::RuleRepository::new(pool.clone())),
+            invite_allowlist: Arc::new(invite_allowlist::InviteAllowlistRepository::new(
+                pool.clone(),
+            )),
+            user_blacklist: Arc::new(user_blacklist::UserBlacklistRepository::new(pool.clone())),
+            spam_fingerprints: Arc::new(spam_fingerprints::SpamFingerprintRepository::new(
+                pool.clone(),
+            )),
+            blocked_domains: Arc::new(blocked_domains::BlockedDomainRepository::new(pool.clone())),
+            examples: Arc::new(examples::ExampleRepository::new(pool.clone())),
+            ham_samples: Arc::new(ham_samples::HamSampleRepository::new(pool.clone())),
+            usage: Arc::new(usage::UsageRepository::new(pool.clone())),
+            chat_settings: Arc::new(chat_settings::ChatSettingsRepository::new(pool.clone())),
+            shadow_results: Arc::new(shadow_results::ShadowResultRepository::new(pool.clone())),
+            strikes: Arc::new(strikes::StrikeRepository::new(pool.clone())),
+            appeals: Arc::new(appeals::AppealRepository::new(pool.clone())),
+            member_joins: Arc::new(member_joins::MemberJoinRepository::new(pool.clone())),
+            cas_cache: Arc::new(cas_cache::CasCacheRepository::new(pool.clone())),
+            status_messages: Arc::new(status_messages::StatusMessageRepository::new(pool.clone())),
+            recent_messages: Arc::new(recent_messages::RecentMessageRepository::new(pool.clone())),
+            backups: Arc::new(backups::BackupRepository::new(pool.clone())),
+            bans: Arc::new(bans::BanRepository::new(pool.clone())),
+            feedback: Arc::new(feedback::FeedbackRepository::new(pool.clone())),
+            maintenance: Arc::new(maintenance::MaintenanceRepository::new(pool.clone())),
+            allowed_domains: Arc::new(allowed_domains::AllowedDomainRepository::new(pool.clone())),
+            url_reputation_cache: Arc::new(
+                url_reputation_cache::UrlReputationCacheRepository::new(pool.clone()),
+            ),
+            lockdowns: Arc::new(lockdowns::LockdownRepository::new(pool.clone())),
+            deletion_notices: Arc::new(deletion_notices::DeletionNoticeRepository::new(pool)),
+        }
+    }
+}
+
 pub async fn init_pool(db_path: &Path) -> Result<SqlitePool> {
     let options = SqliteConnectOptions::from_str(&format!("sqlite://{}", db_path.display()))?
         .create_if_missing(true)
         .busy_timeout(Duration::from_secs(5))
-        .journal_mode(SqliteJournalMode::Wal);
+        .journal_mode(SqliteJournalMode::Wal)
+        .auto_vacuum(SqliteAutoVacuum::Incremental);
 
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
@@ -30,6 +143,451 @@ pub async fn init_pool(db_path: &Path) -> Result<SqlitePool> {
     )
     .execute(&pool)
     .await?;
+    ensure_column(
+        &pool,
+        "whitelist",
+        "status",
+        "TEXT NOT NULL DEFAULT 'active'",
+    )
+    .await?;
+    ensure_column(&pool, "whitelist", "last_checked_at", "DATETIME").await?;
+    ensure_column(
+        &pool,
+        "whitelist",
+        "strict_mode",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+    ensure_column(&pool, "whitelist", "expires_at", "DATETIME").await?;
+    ensure_column(&pool, "whitelist", "note", "TEXT").await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS restart_crons (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            spec TEXT NOT NULL,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            added_by INTEGER
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS job_runs (
+            name TEXT PRIMARY KEY,
+            last_run_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS spam_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            chat_title TEXT,
+            message_id INTEGER NOT NULL,
+            user_id INTEGER,
+            user_display TEXT NOT NULL,
+            text TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            priority_score INTEGER NOT NULL,
+            sent_at DATETIME NOT NULL,
+            deleted_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    ensure_column(&pool, "spam_log", "action", "TEXT").await?;
+    query("CREATE INDEX IF NOT EXISTS idx_spam_log_user ON spam_log (user_id)")
+        .execute(&pool)
+        .await?;
+    ensure_column(&pool, "spam_log", "confidence", "REAL").await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS user_reputation (
+            user_id INTEGER PRIMARY KEY,
+            score INTEGER NOT NULL DEFAULT 0,
+            offenses INTEGER NOT NULL DEFAULT 0,
+            last_offense_at DATETIME,
+            updated_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    ensure_column(&pool, "user_reputation", "offense_aged_at", "DATETIME").await?;
+    ensure_column(&pool, "user_reputation", "last_rewarded_at", "DATETIME").await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chat_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            pattern TEXT NOT NULL,
+            action TEXT NOT NULL,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            created_by INTEGER
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS invite_allowlist (
+            chat_id INTEGER NOT NULL,
+            target TEXT NOT NULL,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            added_by INTEGER,
+            PRIMARY KEY (chat_id, target)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS user_blacklist (
+            user_id INTEGER PRIMARY KEY,
+            reason TEXT,
+            source TEXT,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS spam_fingerprints (
+            fingerprint INTEGER PRIMARY KEY,
+            sample TEXT,
+            source TEXT,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS blocked_domains (
+            domain TEXT PRIMARY KEY,
+            source TEXT,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS allowed_domains (
+            domain TEXT PRIMARY KEY,
+            added_by INTEGER,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS labeled_examples (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            spam INTEGER NOT NULL,
+            reason TEXT,
+            fingerprint INTEGER UNIQUE,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS ham_samples (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            added_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS ai_usage (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            provider TEXT NOT NULL,
+            model TEXT NOT NULL,
+            prompt_tokens INTEGER NOT NULL,
+            completion_tokens INTEGER NOT NULL,
+            cost_usd REAL NOT NULL,
+            recorded_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chat_settings (
+            chat_id INTEGER PRIMARY KEY,
+            criteria TEXT,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    ensure_column(&pool, "chat_settings", "action", "TEXT").await?;
+    ensure_column(&pool, "chat_settings", "probation_messages", "INTEGER").await?;
+    ensure_column(&pool, "chat_settings", "probation_hours", "INTEGER").await?;
+    ensure_column(&pool, "chat_settings", "language", "TEXT").await?;
+    ensure_column(
+        &pool,
+        "chat_settings",
+        "dry_run",
+        "BOOLEAN NOT NULL DEFAULT 0",
+    )
+    .await?;
+    ensure_column(&pool, "chat_settings", "notice_secs", "INTEGER").await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS strikes (
+            chat_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            count INTEGER NOT NULL DEFAULT 0,
+            last_strike_at DATETIME NOT NULL,
+            PRIMARY KEY (chat_id, user_id)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS feedback (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            user_id INTEGER,
+            text TEXT NOT NULL,
+            spam INTEGER NOT NULL,
+            model_reason TEXT,
+            confidence REAL,
+            source TEXT NOT NULL,
+            decided_by INTEGER,
+            created_at DATETIME NOT NULL,
+            appeal_id INTEGER
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    ensure_column(&pool, "feedback", "appeal_id", "INTEGER").await?;
+    query("CREATE UNIQUE INDEX IF NOT EXISTS idx_feedback_appeal ON feedback (appeal_id)")
+        .execute(&pool)
+        .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS appeals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            spam_log_id INTEGER NOT NULL UNIQUE,
+            source TEXT NOT NULL,
+            note TEXT,
+            status TEXT NOT NULL,
+            created_at DATETIME NOT NULL,
+            decided_at DATETIME
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS member_joins (
+            chat_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            joined_at DATETIME NOT NULL,
+            messages INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (chat_id, user_id)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS cas_cache (
+            user_id INTEGER PRIMARY KEY,
+            banned INTEGER NOT NULL,
+            checked_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS url_reputation_cache (
+            url TEXT PRIMARY KEY,
+            threat TEXT,
+            checked_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS shadow_results (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_id INTEGER NOT NULL,
+            message_id INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            live_spam INTEGER NOT NULL,
+            shadow_spam INTEGER NOT NULL,
+            shadow_reason TEXT,
+            recorded_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS export_cursors (
+            source TEXT PRIMARY KEY,
+            last_id INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS status_messages (
+            chat_id INTEGER PRIMARY KEY,
+            message_id INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS recent_messages (
+            user_id INTEGER NOT NULL,
+            chat_id INTEGER NOT NULL,
+            message_id INTEGER NOT NULL,
+            sent_at DATETIME NOT NULL,
+            PRIMARY KEY (user_id, chat_id, message_id)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    // Every recorded message prunes by age, which would otherwise scan the table.
+    query("CREATE INDEX IF NOT EXISTS idx_recent_messages_sent_at ON recent_messages (sent_at)")
+        .execute(&pool)
+        .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS bans (
+            chat_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+            reason TEXT NOT NULL,
+            banned_at DATETIME NOT NULL,
+            expires_at DATETIME,
+            PRIMARY KEY (chat_id, user_id)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS chat_lockdowns (
+            chat_id INTEGER PRIMARY KEY,
+            previous TEXT NOT NULL,
+            expires_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+
+    query(
+        r#"
+        CREATE TABLE IF NOT EXISTS deletion_notices (
+            chat_id INTEGER NOT NULL,
+            message_id INTEGER NOT NULL,
+            topic_id INTEGER,
+            delete_at DATETIME NOT NULL,
+            PRIMARY KEY (chat_id, message_id)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await?;
 
     Ok(pool)
 }
+
+/// Adds `column` to `table` when an older database file predates it.
+async fn ensure_column(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let columns: Vec<(String,)> =
+        query_as(&format!("SELECT name FROM pragma_table_info('{table}')"))
+            .fetch_all(pool)
+            .await?;
+    if columns.iter().any(|(name,)| name == column) {
+        return Ok(());
+    }
+    query(&format!(
+        "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+    ))
+    .execute(pool)
+    .await?;
+    tracing::info!(target: "db", table, column, "added missing column");
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Telegram only lets bots delete messages younger than this, so older ones are not
+/// worth keeping.
+const HISTORY_HOURS: i64 = 48;
+
+/// Which user sent which message in the last two days, so `/purge_user` can clean up
+/// after a spammer.
+#[derive(Clone)]
+pub struct RecentMessageRepository {
+    pool: SqlitePool,
+}
+
+impl RecentMessageRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores the message and drops entries too old to delete.
+    pub async fn record(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+        user_id: i64,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT OR IGNORE INTO recent_messages (user_id, chat_id, message_id, sent_at)
+                VALUES (?1, ?2, ?3, ?4)"#,
+        )
+        .bind(user_id)
+        .bind(chat_id)
+        .bind(message_id)
+        .bind(at)
+        .execute(&self.pool)
+        .await?;
+        query(r#"DELETE FROM recent_messages WHERE sent_at < ?1"#)
+            .bind(Utc::now() - Duration::hours(HISTORY_HOURS))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// `(chat_id, message_id)` of the user's recorded messages, in `chat_id` only when
+    /// given, oldest first.
+    pub async fn for_user(&self, user_id: i64, chat_id: Option<i64>) -> Result<Vec<(i64, i32)>> {
+        let rows = query_as(
+            r#"SELECT chat_id, message_id FROM recent_messages
+                WHERE user_id = ?1 AND (?2 IS NULL OR chat_id = ?2) AND sent_at >= ?3
+                ORDER BY chat_id, message_id"#,
+        )
+        .bind(user_id)
+        .bind(chat_id)
+        .bind(Utc::now() - Duration::hours(HISTORY_HOURS))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Forgets the user's messages, in `chat_id` only when given.
+    pub async fn forget(&self, user_id: i64, chat_id: Option<i64>) -> Result<u64> {
+        let result = query(
+            r#"DELETE FROM recent_messages WHERE user_id = ?1 AND (?2 IS NULL OR chat_id = ?2)"#,
+        )
+        .bind(user_id)
+        .bind(chat_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Per-user offense counters and reputation score shared across chats.
+#[derive(Clone)]
+pub struct ReputationRepository {
+    pool: SqlitePool,
+}
+
+/// Rows touched by a single decay pass.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DecayOutcome {
+    pub offenses_aged: u64,
+    pub scores_restored: u64,
+    pub rewards_decayed: u64,
+}
+
+impl ReputationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Current score for `user_id`; offenders are negative, users with clean history
+    /// positive and unknown users 0.
+    pub async fn score(&self, user_id: i64) -> Result<i64> {
+        let row: Option<(i64,)> =
+            query_as(r#"SELECT score FROM user_reputation WHERE user_id = ?1"#)
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(score,)| score).unwrap_or(0))
+    }
+
+    pub async fn record_offense(
+        &self,
+        user_id: i64,
+        penalty: i64,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT INTO user_reputation (user_id, score, offenses, last_offense_at, updated_at)
+                VALUES (?1, -?2, 1, ?3, ?3)
+                ON CONFLICT(user_id) DO UPDATE SET
+                    score = score - ?2,
+                    offenses = offenses + 1,
+                    last_offense_at = ?3,
+                    updated_at = ?3"#,
+        )
+        .bind(user_id)
+        .bind(penalty)
+        .bind(at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Raises the score for a message the classifier cleared, up to `max_score`, unless
+    /// the user was already rewarded after `rewarded_before`.
+    pub async fn record_clean(
+        &self,
+        user_id: i64,
+        reward: i64,
+        max_score: i64,
+        at: DateTime<Utc>,
+        rewarded_before: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT INTO user_reputation (user_id, score, offenses, last_rewarded_at, updated_at)
+                VALUES (?1, MIN(?2, ?3), 0, ?4, ?4)
+                ON CONFLICT(user_id) DO UPDATE SET
+                    score = MAX(score, MIN(score + ?2, ?3)),
+                    last_rewarded_at = ?4,
+                    updated_at = ?4
+                WHERE last_rewarded_at IS NULL OR last_rewarded_at < ?5"#,
+        )
+        .bind(user_id)
+        .bind(reward)
+        .bind(max_score)
+        .bind(at)
+        .bind(rewarded_before)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Takes back one offense and its penalty, e.g. after a false positive is confirmed.
+    pub async fn forgive_offense(&self, user_id: i64, penalty: i64) -> Result<()> {
+        query(
+            r#"UPDATE user_reputation
+                SET score = score + ?2, offenses = MAX(offenses - 1, 0),
+                    updated_at = ?3
+                WHERE user_id = ?1"#,
+        )
+        .bind(user_id)
+        .bind(penalty)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forgets one offense for users whose last offense (and last aged offense) is older
+    /// than `offense_cutoff` and moves negative scores of users clean since `clean_cutoff`
+    /// back toward zero. Positive scores of users not rewarded since `clean_cutoff` sink
+    /// toward zero the same way. Aging leaves `last_offense_at` alone so it keeps dating
+    /// the user's real last offense.
+    pub async fn decay(
+        &self,
+        now: DateTime<Utc>,
+        offense_cutoff: DateTime<Utc>,
+        clean_cutoff: DateTime<Utc>,
+        recovery: i64,
+    ) -> Result<DecayOutcome> {
+        let offenses_aged = query(
+            r#"UPDATE user_reputation
+                SET offenses = offenses - 1, offense_aged_at = ?1, updated_at = ?1
+                WHERE offenses > 0 AND last_offense_at < ?2
+                    AND (offense_aged_at IS NULL OR offense_aged_at < ?2)"#,
+        )
+        .bind(now)
+        .bind(offense_cutoff)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        let scores_restored = query(
+            r#"UPDATE user_reputation
+                SET score = MIN(0, score + ?1), updated_at = ?2
+                WHERE score < 0 AND (last_offense_at IS NULL OR last_offense_at < ?3)"#,
+        )
+        .bind(recovery)
+        .bind(now)
+        .bind(clean_cutoff)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        let rewards_decayed = query(
+            r#"UPDATE user_reputation
+                SET score = MAX(0, score - ?1), updated_at = ?2
+                WHERE score > 0 AND (last_rewarded_at IS NULL OR last_rewarded_at < ?3)"#,
+        )
+        .bind(recovery)
+        .bind(now)
+        .bind(clean_cutoff)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(DecayOutcome {
+            offenses_aged,
+            scores_restored,
+            rewards_decayed,
+        })
+    }
+}
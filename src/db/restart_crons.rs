@@ -0,0 +1,64 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// `job_runs` row recording that the `RESTART_CRONS` defaults were applied once, so an
+/// admin who removed every schedule keeps an empty table.
+const SEEDED_MARKER: &str = "restart_crons_seeded";
+
+/// Persists the restart cron specs managed through admin commands.
+#[derive(Clone)]
+pub struct RestartCronRepository {
+    pool: SqlitePool,
+}
+
+impl RestartCronRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<(i64, String)>> {
+        let rows = query_as(r#"SELECT id, spec FROM restart_crons ORDER BY id"#)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn add(&self, spec: &str, added_by: Option<i64>) -> Result<i64> {
+        let id = query(r#"INSERT INTO restart_crons (spec, added_by) VALUES (?1, ?2)"#)
+            .bind(spec)
+            .bind(added_by)
+            .execute(&self.pool)
+            .await?
+            .last_insert_rowid();
+        Ok(id)
+    }
+
+    /// Whether the defaults were applied before, on this or an earlier start.
+    pub async fn seeded(&self) -> Result<bool> {
+        let row: Option<(String,)> = query_as(r#"SELECT name FROM job_runs WHERE name = ?1"#)
+            .bind(SEEDED_MARKER)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.is_some())
+    }
+
+    pub async fn mark_seeded(&self) -> Result<()> {
+        query(r#"INSERT OR IGNORE INTO job_runs (name, last_run_at) VALUES (?1, ?2)"#)
+            .bind(SEEDED_MARKER)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove(&self, id: i64) -> Result<bool> {
+        let affected = query(r#"DELETE FROM restart_crons WHERE id = ?1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::query::query;
+use sqlx_sqlite::SqlitePool;
+
+/// Tables whose rows expire after a configured retention period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetainedTable {
+    SpamLog,
+    AiUsage,
+    ShadowResults,
+    Appeals,
+    MemberJoins,
+}
+
+impl RetainedTable {
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            RetainedTable::SpamLog => "spam_log",
+            RetainedTable::AiUsage => "ai_usage",
+            RetainedTable::ShadowResults => "shadow_results",
+            RetainedTable::Appeals => "appeals",
+            RetainedTable::MemberJoins => "member_joins",
+        }
+    }
+
+    /// Rows where this column is `NULL` are never purged.
+    fn timestamp_column(&self) -> &'static str {
+        match self {
+            RetainedTable::SpamLog => "deleted_at",
+            RetainedTable::AiUsage | RetainedTable::ShadowResults => "recorded_at",
+            RetainedTable::Appeals => "decided_at",
+            RetainedTable::MemberJoins => "joined_at",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RetentionRepository {
+    pool: SqlitePool,
+}
+
+impl RetentionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Deletes rows of `table` older than `cutoff`, returning how many were removed.
+    pub async fn purge(&self, table: RetainedTable, cutoff: DateTime<Utc>) -> Result<u64> {
+        let sql = format!(
+            "DELETE FROM {} WHERE {} < ?1",
+            table.table_name(),
+            table.timestamp_column()
+        );
+        let affected = query(&sql)
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected)
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Admin-defined per-chat moderation rules evaluated before queueing.
+#[derive(Clone)]
+pub struct RuleRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleRow {
+    pub id: i64,
+    pub chat_id: i64,
+    pub kind: String,
+    pub pattern: String,
+    pub action: String,
+}
+
+impl RuleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<RuleRow>> {
+        let rows: Vec<(i64, i64, String, String, String)> =
+            query_as(r#"SELECT id, chat_id, kind, pattern, action FROM chat_rules ORDER BY id"#)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, chat_id, kind, pattern, action)| RuleRow {
+                id,
+                chat_id,
+                kind,
+                pattern,
+                action,
+            })
+            .collect())
+    }
+
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        kind: &str,
+        pattern: &str,
+        action: &str,
+        created_by: Option<i64>,
+    ) -> Result<i64> {
+        let id = query(
+            r#"INSERT INTO chat_rules (chat_id, kind, pattern, action, created_by)
+                VALUES (?1, ?2, ?3, ?4, ?5)"#,
+        )
+        .bind(chat_id)
+        .bind(kind)
+        .bind(pattern)
+        .bind(action)
+        .bind(created_by)
+        .execute(&self.pool)
+        .await?
+        .last_insert_rowid();
+        Ok(id)
+    }
+
+    pub async fn remove(&self, id: i64) -> Result<bool> {
+        let affected = query(r#"DELETE FROM chat_rules WHERE id = ?1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Live and shadow verdicts for the same messages, for evaluating a candidate model or
+/// prompt before switching to it.
+#[derive(Clone)]
+pub struct ShadowResultRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShadowResult {
+    pub chat_id: i64,
+    pub message_id: i64,
+    pub text: String,
+    pub live_spam: bool,
+    pub shadow_spam: bool,
+    pub shadow_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShadowSummary {
+    pub total: i64,
+    pub agreed: i64,
+    /// Spam according to the shadow only.
+    pub shadow_only: i64,
+    /// Spam according to the live classifier only.
+    pub live_only: i64,
+}
+
+impl ShadowResultRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, results: &[ShadowResult]) -> Result<()> {
+        let recorded_at = Utc::now();
+        for result in results {
+            query(
+                r#"INSERT INTO shadow_results
+                    (chat_id, message_id, text, live_spam, shadow_spam, shadow_reason, recorded_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+            )
+            .bind(result.chat_id)
+            .bind(result.message_id)
+            .bind(&result.text)
+            .bind(result.live_spam)
+            .bind(result.shadow_spam)
+            .bind(&result.shadow_reason)
+            .bind(recorded_at)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn summary_since(&self, since: DateTime<Utc>) -> Result<ShadowSummary> {
+        let (total, agreed, shadow_only, live_only): (i64, i64, i64, i64) = query_as(
+            r#"SELECT COUNT(*),
+                      COALESCE(SUM(live_spam = shadow_spam), 0),
+                      COALESCE(SUM(shadow_spam AND NOT live_spam), 0),
+                      COALESCE(SUM(live_spam AND NOT shadow_spam), 0)
+                FROM shadow_results
+                WHERE recorded_at >= ?1"#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(ShadowSummary {
+            total,
+            agreed,
+            shadow_only,
+            live_only,
+        })
+    }
+
+    /// Latest messages the two classifiers disagreed on, newest first.
+    pub async fn recent_disagreements(&self, limit: i64) -> Result<Vec<ShadowResult>> {
+        let rows: Vec<(i64, i64, String, bool, bool, Option<String>)> = query_as(
+            r#"SELECT chat_id, message_id, text, live_spam, shadow_spam, shadow_reason
+                FROM shadow_results
+                WHERE live_spam != shadow_spam
+                ORDER BY id DESC
+                LIMIT ?1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(chat_id, message_id, text, live_spam, shadow_spam, shadow_reason)| ShadowResult {
+                    chat_id,
+                    message_id,
+                    text,
+                    live_spam,
+                    shadow_spam,
+                    shadow_reason,
+                },
+            )
+            .collect())
+    }
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Hashes of normalized texts confirmed as spam.
+#[derive(Clone)]
+pub struct SpamFingerprintRepository {
+    pool: SqlitePool,
+}
+
+impl SpamFingerprintRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn contains(&self, fingerprint: i64) -> Result<bool> {
+        let row: Option<(i64,)> =
+            query_as(r#"SELECT fingerprint FROM spam_fingerprints WHERE fingerprint = ?1"#)
+                .bind(fingerprint)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some())
+    }
+
+    /// Stores the fingerprint (keeping a text sample for review); returns `false` if known.
+    pub async fn add(&self, fingerprint: i64, sample: &str, source: &str) -> Result<bool> {
+        let affected = query(
+            r#"INSERT OR IGNORE INTO spam_fingerprints (fingerprint, sample, source)
+                VALUES (?1, ?2, ?3)"#,
+        )
+        .bind(fingerprint)
+        .bind(sample)
+        .bind(source)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected > 0)
+    }
+}
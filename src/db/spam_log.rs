@@ -0,0 +1,232 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx_core::{from_row::FromRow, query::query, query_as::query_as, row::Row};
+use sqlx_sqlite::{SqlitePool, SqliteRow};
+
+/// Append-only record of every spam message the bot deleted, with the action taken
+/// against the sender when the classifier made the call.
+#[derive(Clone)]
+pub struct SpamLogRepository {
+    pool: SqlitePool,
+}
+
+impl SpamLogRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(&self, entry: SpamLogEntry) -> Result<i64> {
+        let id = query(
+            r#"INSERT INTO spam_log
+                (chat_id, chat_title, message_id, user_id, user_display, text, reason,
+                 priority_score, sent_at, deleted_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+        )
+        .bind(entry.chat_id)
+        .bind(entry.chat_title)
+        .bind(entry.message_id)
+        .bind(entry.user_id)
+        .bind(entry.user_display)
+        .bind(entry.text)
+        .bind(entry.reason)
+        .bind(entry.priority_score)
+        .bind(entry.sent_at)
+        .bind(entry.deleted_at)
+        .execute(&self.pool)
+        .await?
+        .last_insert_rowid();
+        Ok(id)
+    }
+
+    /// Stores what was done to the sender once enforcement has run, and how confident
+    /// the AI was.
+    pub async fn set_outcome(&self, id: i64, action: &str, confidence: Option<f32>) -> Result<()> {
+        query(r#"UPDATE spam_log SET action = ?2, confidence = ?3 WHERE id = ?1"#)
+            .bind(id)
+            .bind(action)
+            .bind(confidence)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Texts of the most recently deleted messages, newest first.
+    pub async fn recent_texts(&self, limit: i64) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            query_as(r#"SELECT text FROM spam_log ORDER BY id DESC LIMIT ?1"#)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(|(text,)| text).collect())
+    }
+
+    pub async fn get(&self, id: i64) -> Result<Option<SpamLogRow>> {
+        let row = query_as::<_, SpamLogRow>(
+            r#"SELECT id, chat_id, chat_title, message_id, user_id, user_display, text, reason,
+                    priority_score, sent_at, deleted_at, action, confidence
+                FROM spam_log WHERE id = ?1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    /// Latest deletion of a message sent by `user_id`.
+    pub async fn latest_for_user(&self, user_id: i64) -> Result<Option<SpamLogRow>> {
+        let row = query_as::<_, SpamLogRow>(
+            r#"SELECT id, chat_id, chat_title, message_id, user_id, user_display, text, reason,
+                    priority_score, sent_at, deleted_at, action, confidence
+                FROM spam_log WHERE user_id = ?1 ORDER BY id DESC LIMIT 1"#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    /// How many of `user_id`'s messages were deleted, in any chat.
+    pub async fn count_for_user(&self, user_id: i64) -> Result<i64> {
+        let (count,): (i64,) = query_as(r#"SELECT COUNT(*) FROM spam_log WHERE user_id = ?1"#)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// How many messages were deleted since `since`, in any chat.
+    pub async fn count_since(&self, since: DateTime<Utc>) -> Result<i64> {
+        let (count,): (i64,) = query_as(r#"SELECT COUNT(*) FROM spam_log WHERE deleted_at >= ?1"#)
+            .bind(since)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// The `limit` chats with the most deletions since `since`, most first.
+    pub async fn counts_by_chat(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<SpamCount>> {
+        let rows: Vec<(i64, Option<String>, i64)> = query_as(
+            r#"SELECT chat_id, MAX(chat_title), COUNT(*)
+                FROM spam_log
+                WHERE deleted_at >= ?1
+                GROUP BY chat_id
+                ORDER BY COUNT(*) DESC
+                LIMIT ?2"#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(SpamCount::from).collect())
+    }
+
+    /// Senders with the most deletions since `since`.
+    pub async fn top_users(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<SpamCount>> {
+        let rows: Vec<(i64, Option<String>, i64)> = query_as(
+            r#"SELECT user_id, MAX(user_display), COUNT(*)
+                FROM spam_log
+                WHERE deleted_at >= ?1 AND user_id IS NOT NULL
+                GROUP BY user_id
+                ORDER BY COUNT(*) DESC
+                LIMIT ?2"#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(SpamCount::from).collect())
+    }
+
+    /// Texts deleted since `since`, newest first.
+    pub async fn texts_since(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = query_as(
+            r#"SELECT text FROM spam_log WHERE deleted_at >= ?1 ORDER BY id DESC LIMIT ?2"#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|(text,)| text).collect())
+    }
+
+    pub async fn list_after(&self, last_id: i64, limit: i64) -> Result<Vec<SpamLogRow>> {
+        let rows = query_as::<_, SpamLogRow>(
+            r#"SELECT id, chat_id, chat_title, message_id, user_id, user_display, text, reason,
+                    priority_score, sent_at, deleted_at, action, confidence
+                FROM spam_log WHERE id > ?1 ORDER BY id LIMIT ?2"#,
+        )
+        .bind(last_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
+
+/// Deletions grouped by chat or sender, with one of the titles or display names recorded.
+#[derive(Debug, Clone)]
+pub struct SpamCount {
+    pub id: i64,
+    pub label: Option<String>,
+    pub count: i64,
+}
+
+impl From<(i64, Option<String>, i64)> for SpamCount {
+    fn from((id, label, count): (i64, Option<String>, i64)) -> Self {
+        Self { id, label, count }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpamLogEntry {
+    pub chat_id: i64,
+    pub chat_title: Option<String>,
+    pub message_id: i32,
+    pub user_id: Option<i64>,
+    pub user_display: String,
+    pub text: String,
+    pub reason: String,
+    pub priority_score: i32,
+    pub sent_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpamLogRow {
+    pub id: i64,
+    pub chat_id: i64,
+    pub chat_title: Option<String>,
+    pub message_id: i32,
+    pub user_id: Option<i64>,
+    pub user_display: String,
+    pub text: String,
+    pub reason: String,
+    pub priority_score: i32,
+    pub sent_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+    /// Admin-log description of the enforcement, e.g. "deleted + banned (strike 2)";
+    /// `None` for deletions by flood, raid and review handling.
+    pub action: Option<String>,
+    pub confidence: Option<f32>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SpamLogRow {
+    fn from_row(row: &'r SqliteRow) -> std::result::Result<Self, sqlx_core::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            chat_id: row.try_get("chat_id")?,
+            chat_title: row.try_get("chat_title")?,
+            message_id: row.try_get("message_id")?,
+            user_id: row.try_get("user_id")?,
+            user_display: row.try_get("user_display")?,
+            text: row.try_get("text")?,
+            reason: row.try_get("reason")?,
+            priority_score: row.try_get("priority_score")?,
+            sent_at: row.try_get("sent_at")?,
+            deleted_at: row.try_get("deleted_at")?,
+            action: row.try_get("action")?,
+            confidence: row.try_get("confidence")?,
+        })
+    }
+}
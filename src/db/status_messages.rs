@@ -0,0 +1,33 @@
+use anyhow::Result;
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Remembers the pinned status message in each chat so restarts keep editing it.
+#[derive(Clone)]
+pub struct StatusMessageRepository {
+    pool: SqlitePool,
+}
+
+impl StatusMessageRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, chat_id: i64) -> Result<Option<i32>> {
+        let row: Option<(i32,)> =
+            query_as(r#"SELECT message_id FROM status_messages WHERE chat_id = ?1"#)
+                .bind(chat_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(id,)| id))
+    }
+
+    pub async fn set(&self, chat_id: i64, message_id: i32) -> Result<()> {
+        query(r#"INSERT OR REPLACE INTO status_messages (chat_id, message_id) VALUES (?1, ?2)"#)
+            .bind(chat_id)
+            .bind(message_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Spam strikes per sender in each chat, used to escalate penalties for repeat
+/// offenders.
+#[derive(Clone)]
+pub struct StrikeRepository {
+    pool: SqlitePool,
+}
+
+impl StrikeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Adds a strike and returns the sender's count in the chat; strikes whose latest
+    /// one is older than `expired_before` start over from one.
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        at: DateTime<Utc>,
+        expired_before: Option<DateTime<Utc>>,
+    ) -> Result<i64> {
+        let (count,): (i64,) = query_as(
+            r#"INSERT INTO strikes (chat_id, user_id, count, last_strike_at)
+                VALUES (?1, ?2, 1, ?3)
+                ON CONFLICT(chat_id, user_id) DO UPDATE SET
+                    count = CASE WHEN ?4 IS NOT NULL AND last_strike_at < ?4
+                        THEN 1 ELSE count + 1 END,
+                    last_strike_at = ?3
+                RETURNING count"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .bind(at)
+        .bind(expired_before)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Removes one strike, e.g. after an admin restores a false positive.
+    pub async fn forgive(&self, chat_id: i64, user_id: i64) -> Result<()> {
+        query(
+            r#"UPDATE strikes SET count = MAX(count - 1, 0)
+                WHERE chat_id = ?1 AND user_id = ?2"#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Recent Safe Browsing and URLhaus verdicts, so each URL is looked up at most once per
+/// TTL.
+#[derive(Clone)]
+pub struct UrlReputationCacheRepository {
+    pool: SqlitePool,
+}
+
+impl UrlReputationCacheRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Cached verdict for `url` checked at or after `fresh_after`: `Some(None)` when it
+    /// was clean, `Some(Some(threat))` when a feed listed it.
+    pub async fn get(
+        &self,
+        url: &str,
+        fresh_after: DateTime<Utc>,
+    ) -> Result<Option<Option<String>>> {
+        let row: Option<(Option<String>,)> = query_as(
+            r#"SELECT threat FROM url_reputation_cache WHERE url = ?1 AND checked_at >= ?2"#,
+        )
+        .bind(url)
+        .bind(fresh_after)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(threat,)| threat))
+    }
+
+    pub async fn put(
+        &self,
+        url: &str,
+        threat: Option<&str>,
+        checked_at: DateTime<Utc>,
+    ) -> Result<()> {
+        query(
+            r#"INSERT INTO url_reputation_cache (url, threat, checked_at) VALUES (?1, ?2, ?3)
+                ON CONFLICT(url) DO UPDATE SET
+                    threat = excluded.threat,
+                    checked_at = excluded.checked_at"#,
+        )
+        .bind(url)
+        .bind(threat)
+        .bind(checked_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
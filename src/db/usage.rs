@@ -0,0 +1,80 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{query::query, query_as::query_as};
+use sqlx_sqlite::SqlitePool;
+
+/// Token usage reported by each AI provider response, for monitoring spend.
+#[derive(Clone)]
+pub struct UsageRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UsageTotals {
+    pub provider: String,
+    pub model: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+}
+
+impl UsageRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn record(
+        &self,
+        provider: &str,
+        model: &str,
+        prompt_tokens: i64,
+        completion_tokens: i64,
+        cost_usd: f64,
+    ) -> Result<()> {
+        query(
+            r#"INSERT INTO ai_usage
+                (provider, model, prompt_tokens, completion_tokens, cost_usd, recorded_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+        )
+        .bind(provider)
+        .bind(model)
+        .bind(prompt_tokens)
+        .bind(completion_tokens)
+        .bind(cost_usd)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Per-model totals of calls recorded at or after `since`.
+    pub async fn totals_since(&self, since: DateTime<Utc>) -> Result<Vec<UsageTotals>> {
+        let rows: Vec<(String, String, i64, i64, i64, f64)> = query_as(
+            r#"SELECT provider, model, COUNT(*), SUM(prompt_tokens), SUM(completion_tokens),
+                      SUM(cost_usd)
+                FROM ai_usage
+                WHERE recorded_at >= ?1
+                GROUP BY provider, model
+                ORDER BY SUM(cost_usd) DESC, COUNT(*) DESC"#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(provider, model, calls, prompt_tokens, completion_tokens, cost_usd)| {
+                    UsageTotals {
+                        provider,
+                        model,
+                        calls,
+                        prompt_tokens,
+                        completion_tokens,
+                        cost_usd,
+                    }
+                },
+            )
+            .collect())
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx_core::{from_row::FromRow, query::query, query_as::query_as, row::Row};
+use sqlx_sqlite::{SqlitePool, SqliteRow};
+
+/// Users known to spam, matched across every chat.
+#[derive(Clone)]
+pub struct UserBlacklistRepository {
+    pool: SqlitePool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlacklistEntry {
+    pub user_id: i64,
+    pub reason: Option<String>,
+    /// `honeypot:<chat>`, `chat:<chat>` for confirmed spam, or `admin`.
+    pub source: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for BlacklistEntry {
+    fn from_row(row: &'r SqliteRow) -> std::result::Result<Self, sqlx_core::Error> {
+        Ok(Self {
+            user_id: row.try_get("user_id")?,
+            reason: row.try_get("reason")?,
+            source: row.try_get("source")?,
+            added_at: row.try_get("added_at")?,
+        })
+    }
+}
+
+impl UserBlacklistRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn contains(&self, user_id: i64) -> Result<bool> {
+        let row: Option<(i64,)> =
+            query_as(r#"SELECT user_id FROM user_blacklist WHERE user_id = ?1"#)
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some())
+    }
+
+    /// Returns `false` when the user was already listed.
+    pub async fn add(&self, user_id: i64, reason: &str, source: &str) -> Result<bool> {
+        let affected = query(
+            r#"INSERT OR IGNORE INTO user_blacklist (user_id, reason, source)
+                VALUES (?1, ?2, ?3)"#,
+        )
+        .bind(user_id)
+        .bind(reason)
+        .bind(source)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected > 0)
+    }
+
+    pub async fn remove(&self, user_id: i64) -> Result<bool> {
+        let affected = query(r#"DELETE FROM user_blacklist WHERE user_id = ?1"#)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+
+    /// Most recently listed users first.
+    pub async fn list(&self, limit: i64) -> Result<Vec<BlacklistEntry>> {
+        let rows = query_as(
+            r#"SELECT user_id, reason, source, added_at FROM user_blacklist
+                ORDER BY added_at DESC LIMIT ?1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx_core::{from_row::FromRow, query::query, query_as::query_as, row::Row};
 use sqlx_sqlite::{SqlitePool, SqliteRow};
 
+use crate::i18n::{t, Locale, Msg};
+
 #[derive(Clone)]
 pub struct WhitelistRepository {
     pool: SqlitePool,
@@ -18,15 +20,26 @@ impl WhitelistRepository {
         self.pool.close().await;
     }
 
+    /// Adds the chat or resets its existing entry; only the admin note is kept.
     pub async fn add_or_replace(&self, entry: WhitelistEntry) -> Result<bool> {
         let affected = query(
-            r#"INSERT OR REPLACE INTO whitelist (chat_id, chat_title, chat_type, added_by)
-                VALUES (?1, ?2, ?3, ?4)"#,
+            r#"INSERT INTO whitelist (chat_id, chat_title, chat_type, added_by, expires_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    chat_title = excluded.chat_title,
+                    chat_type = excluded.chat_type,
+                    added_at = CURRENT_TIMESTAMP,
+                    added_by = excluded.added_by,
+                    status = 'active',
+                    last_checked_at = NULL,
+                    strict_mode = 0,
+                    expires_at = excluded.expires_at"#,
         )
         .bind(entry.chat_id)
         .bind(entry.chat_title)
         .bind(entry.chat_type)
         .bind(entry.added_by)
+        .bind(entry.expires_at)
         .execute(&self.pool)
         .await?
         .rows_affected();
@@ -42,23 +55,131 @@ impl WhitelistRepository {
         Ok(affected > 0)
     }
 
+    /// Entries past their expiry no longer count, even before the expiry job removes
+    /// them.
     pub async fn is_allowed(&self, chat_id: i64) -> Result<bool> {
-        let result: Option<(i64,)> =
-            query_as(r#"SELECT chat_id FROM whitelist WHERE chat_id = ?1"#)
+        let result: Option<(i64,)> = query_as(
+            r#"SELECT chat_id FROM whitelist
+                WHERE chat_id = ?1 AND (expires_at IS NULL OR expires_at > ?2)"#,
+        )
+        .bind(chat_id)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(result.is_some())
+    }
+
+    pub async fn is_strict(&self, chat_id: i64) -> Result<bool> {
+        let result: Option<(bool,)> =
+            query_as(r#"SELECT strict_mode FROM whitelist WHERE chat_id = ?1"#)
                 .bind(chat_id)
                 .fetch_optional(&self.pool)
                 .await?;
-        Ok(result.is_some())
+        Ok(result.map(|(strict,)| strict).unwrap_or(false))
+    }
+
+    /// Returns `false` when the chat is not whitelisted.
+    pub async fn set_strict(&self, chat_id: i64, strict: bool) -> Result<bool> {
+        let affected = query(r#"UPDATE whitelist SET strict_mode = ?2 WHERE chat_id = ?1"#)
+            .bind(chat_id)
+            .bind(strict)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
+    }
+
+    /// Replaces the admin note (customer name, owner contact, tags); `None` clears it.
+    /// Returns `false` when the chat is not whitelisted.
+    pub async fn set_note(&self, chat_id: i64, note: Option<&str>) -> Result<bool> {
+        let affected = query(r#"UPDATE whitelist SET note = ?2 WHERE chat_id = ?1"#)
+            .bind(chat_id)
+            .bind(note)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected > 0)
     }
 
     pub async fn list(&self) -> Result<Vec<WhitelistRow>> {
         let rows = query_as::<_, WhitelistRow>(
-            r#"SELECT chat_id, chat_title, chat_type, added_at, added_by FROM whitelist ORDER BY added_at DESC"#,
+            r#"SELECT chat_id, chat_title, chat_type, added_at, added_by, status, last_checked_at,
+                    strict_mode, expires_at, note
+                FROM whitelist ORDER BY added_at DESC"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Writes an entry from a `/whitelist_import` file, replacing any existing one.
+    pub async fn restore(&self, entry: &WhitelistImport) -> Result<()> {
+        query(
+            r#"INSERT INTO whitelist
+                    (chat_id, chat_title, chat_type, added_at, added_by, strict_mode, expires_at,
+                     note)
+                VALUES (?1, ?2, ?3, COALESCE(?4, CURRENT_TIMESTAMP), ?5, ?6, ?7, ?8)
+                ON CONFLICT(chat_id) DO UPDATE SET
+                    chat_title = excluded.chat_title,
+                    chat_type = excluded.chat_type,
+                    added_at = excluded.added_at,
+                    added_by = excluded.added_by,
+                    strict_mode = excluded.strict_mode,
+                    expires_at = excluded.expires_at,
+                    note = excluded.note"#,
+        )
+        .bind(entry.chat_id)
+        .bind(&entry.chat_title)
+        .bind(&entry.chat_type)
+        .bind(entry.added_at)
+        .bind(entry.added_by)
+        .bind(entry.strict_mode)
+        .bind(entry.expires_at)
+        .bind(&entry.note)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Deletes the entries whose expiry has passed and returns them.
+    pub async fn remove_expired(&self, now: DateTime<Utc>) -> Result<Vec<WhitelistRow>> {
+        let rows = query_as::<_, WhitelistRow>(
+            r#"DELETE FROM whitelist WHERE expires_at IS NOT NULL AND expires_at <= ?1
+                RETURNING chat_id, chat_title, chat_type, added_at, added_by, status,
+                    last_checked_at, strict_mode, expires_at, note"#,
         )
+        .bind(now)
         .fetch_all(&self.pool)
         .await?;
         Ok(rows)
     }
+
+    pub async fn set_details(
+        &self,
+        chat_id: i64,
+        chat_title: Option<&str>,
+        chat_type: &str,
+    ) -> Result<()> {
+        query(r#"UPDATE whitelist SET chat_title = ?2, chat_type = ?3 WHERE chat_id = ?1"#)
+            .bind(chat_id)
+            .bind(chat_title)
+            .bind(chat_type)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_status(&self, chat_id: i64, status: WhitelistStatus) -> Result<()> {
+        query(
+            r#"UPDATE whitelist SET status = ?2, last_checked_at = CURRENT_TIMESTAMP
+                WHERE chat_id = ?1"#,
+        )
+        .bind(chat_id)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +188,66 @@ pub struct WhitelistEntry {
     pub chat_title: Option<String>,
     pub chat_type: Option<String>,
     pub added_by: Option<i64>,
+    /// `None` for a permanent entry.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// One entry read from an exported whitelist file; fields missing from the file take
+/// their defaults.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WhitelistImport {
+    pub chat_id: i64,
+    #[serde(default)]
+    pub chat_title: Option<String>,
+    #[serde(default)]
+    pub chat_type: Option<String>,
+    #[serde(default)]
+    pub added_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub added_by: Option<i64>,
+    #[serde(default)]
+    pub strict_mode: bool,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Result of the last membership audit for a whitelisted chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum WhitelistStatus {
+    Active,
+    Removed,
+    NoDeleteRights,
+}
+
+impl WhitelistStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WhitelistStatus::Active => "active",
+            WhitelistStatus::Removed => "removed",
+            WhitelistStatus::NoDeleteRights => "no_delete_rights",
+        }
+    }
+
+    pub fn label(&self, locale: Locale) -> &'static str {
+        t(
+            locale,
+            match self {
+                WhitelistStatus::Active => Msg::WhitelistActive,
+                WhitelistStatus::Removed => Msg::WhitelistRemoved,
+                WhitelistStatus::NoDeleteRights => Msg::WhitelistNoDeleteRights,
+            },
+        )
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "removed" => WhitelistStatus::Removed,
+            "no_delete_rights" => WhitelistStatus::NoDeleteRights,
+            _ => WhitelistStatus::Active,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -76,16 +257,27 @@ pub struct WhitelistRow {
     pub chat_type: Option<String>,
     pub added_at: DateTime<Utc>,
     pub added_by: Option<i64>,
+    pub status: WhitelistStatus,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub strict_mode: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
 }
 
 impl<'r> FromRow<'r, SqliteRow> for WhitelistRow {
     fn from_row(row: &'r SqliteRow) -> std::result::Result<Self, sqlx_core::Error> {
+        let status: String = row.try_get("status")?;
         Ok(Self {
             chat_id: row.try_get("chat_id")?,
             chat_title: row.try_get("chat_title")?,
             chat_type: row.try_get("chat_type")?,
             added_at: row.try_get("added_at")?,
             added_by: row.try_get("added_by")?,
+            status: WhitelistStatus::parse(&status),
+            last_checked_at: row.try_get("last_checked_at")?,
+            strict_mode: row.try_get("strict_mode")?,
+            expires_at: row.try_get("expires_at")?,
+            note: row.try_get("note")?,
         })
     }
 }
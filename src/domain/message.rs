@@ -1,17 +1,39 @@
 use chrono::{DateTime, Utc};
 use teloxide::{prelude::*, types::MessageId};
 
+use crate::heuristics::Signal;
+
 #[derive(Debug, Clone)]
 pub struct MessageJob {
     pub chat_id: ChatId,
     pub chat_title: Option<String>,
     pub message_id: MessageId,
+    /// Forum topic the message was posted in.
+    pub topic_id: Option<i32>,
     pub from_id: Option<i64>,
+    /// Channel the message was posted on behalf of; `from_id` is `None` then.
+    pub sender_chat_id: Option<i64>,
     pub from_display: String,
     pub username: Option<String>,
     pub text: String,
     pub urls: Vec<String>,
     pub is_group_member: bool,
+    /// Sender joined recently enough to be handled more strictly.
+    pub on_probation: bool,
+    /// When the sender's latest join to the chat was seen; `None` when it never was.
+    pub member_since: Option<DateTime<Utc>>,
+    /// Sender is one of the chat's admins, whose spam verdicts go to review instead of
+    /// deletion (`CHAT_ADMIN_MODE=report`).
+    pub sender_is_chat_admin: bool,
+    /// Estimated from the user ID; `None` for anonymous senders.
+    pub account_age_days: Option<i64>,
+    /// ISO 639-1 code guessed from the text's script.
+    pub language: Option<&'static str>,
     pub priority_score: i32,
+    /// Sender's score from `user_reputation`; 0 when unknown or anonymous.
+    pub reputation: i64,
+    pub signals: Vec<Signal>,
     pub timestamp: DateTime<Utc>,
+    /// Failed classification requests this job has been requeued after.
+    pub attempts: u32,
 }
@@ -7,6 +7,8 @@ pub struct WebContent {
     pub title: Option<String>,
     pub site_name: Option<String>,
     pub content: Option<String>,
+    /// Member or subscriber count shown on a Telegram channel/group preview.
+    pub members: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,13 @@ pub struct ClassificationDecision {
     pub spam: bool,
     #[serde(default)]
     pub reason: Option<String>,
+    /// Model's certainty in `spam`, 0-1. Treated as certain when the model omits it.
+    #[serde(default = "full_confidence")]
+    pub confidence: f32,
+}
+
+fn full_confidence() -> f32 {
+    1.0
 }
 
 pub type ClassificationMap = HashMap<String, ClassificationDecision>;
@@ -23,3 +32,9 @@ pub struct QueueSnapshot {
     pub high_priority: usize,
     pub normal_priority: usize,
 }
+
+impl QueueSnapshot {
+    pub fn is_empty(&self) -> bool {
+        self.high_priority == 0 && self.normal_priority == 0
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::Signal;
+
+const NEW_ACCOUNT_LINK_SCORE: i32 = 15;
+
+/// Approximate `(user_id, registration date)` anchors collected from public accounts.
+/// IDs are handed out roughly in order, so interpolating between anchors gives a usable
+/// estimate; IDs past the last anchor are extrapolated from the newest interval, so they
+/// read as recent rather than as old as that anchor.
+const ID_ANCHORS: [(i64, (i32, u32, u32)); 11] = [
+    (1_000_000, (2013, 9, 1)),
+    (100_000_000, (2015, 1, 1)),
+    (200_000_000, (2016, 5, 1)),
+    (500_000_000, (2018, 3, 1)),
+    (1_000_000_000, (2019, 10, 1)),
+    (1_500_000_000, (2020, 12, 1)),
+    (2_000_000_000, (2021, 9, 1)),
+    (5_000_000_000, (2022, 2, 1)),
+    (6_000_000_000, (2023, 1, 1)),
+    (7_000_000_000, (2024, 3, 1)),
+    (8_000_000_000, (2025, 2, 1)),
+];
+
+/// Estimates when the account with `user_id` was registered.
+pub fn estimate_created(user_id: i64) -> Option<NaiveDate> {
+    if user_id <= 0 {
+        return None;
+    }
+    let anchors: Vec<(i64, NaiveDate)> = ID_ANCHORS
+        .iter()
+        .filter_map(|&(id, (y, m, d))| NaiveDate::from_ymd_opt(y, m, d).map(|date| (id, date)))
+        .collect();
+    let (first_id, first_date) = *anchors.first()?;
+    if user_id <= first_id {
+        return Some(first_date);
+    }
+    let interpolate = |(lo_id, lo_date): (i64, NaiveDate), (hi_id, hi_date): (i64, NaiveDate)| {
+        let span_days = (hi_date - lo_date).num_days();
+        let offset = (user_id - lo_id) as f64 / (hi_id - lo_id) as f64;
+        lo_date + chrono::Duration::days((span_days as f64 * offset) as i64)
+    };
+    for pair in anchors.windows(2) {
+        if user_id <= pair[1].0 {
+            return Some(interpolate(pair[0], pair[1]));
+        }
+    }
+    let newest = anchors.windows(2).last()?;
+    Some(interpolate(newest[0], newest[1]))
+}
+
+/// Estimated account age in days at `at`, never negative.
+pub fn estimate_age_days(user_id: i64, at: DateTime<Utc>) -> Option<i64> {
+    estimate_created(user_id).map(|created| (at.date_naive() - created).num_days().max(0))
+}
+
+/// Flags very new accounts that post links.
+pub fn detect(age_days: Option<i64>, has_links: bool, max_days: i64, signals: &mut Vec<Signal>) {
+    let Some(age_days) = age_days else {
+        return;
+    };
+    if has_links && age_days <= max_days {
+        signals.push(Signal::new(
+            "new_account_link",
+            NEW_ACCOUNT_LINK_SCORE,
+            format!("신규 계정 추정(약 {age_days}일)의 링크 게시"),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_are_monotonic() {
+        let older = estimate_created(150_000_000).unwrap();
+        let newer = estimate_created(5_500_000_000).unwrap();
+        assert!(older < newer);
+        assert_eq!(estimate_created(0), None);
+    }
+
+    #[test]
+    fn ids_past_the_newest_anchor_read_as_newer() {
+        let (last_id, _) = ID_ANCHORS[ID_ANCHORS.len() - 1];
+        let newest = estimate_created(last_id).unwrap();
+        let beyond = estimate_created(last_id + 500_000_000).unwrap();
+        assert!(beyond > newest);
+        let at = Utc::now();
+        assert!(estimate_age_days(last_id + 500_000_000, at) < estimate_age_days(last_id, at));
+    }
+}
@@ -0,0 +1,46 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::Signal;
+
+const PHONE_SCORE: i32 = 12;
+const MESSENGER_SCORE: i32 = 10;
+const SOLICITATION_SCORE: i32 = 8;
+
+static PHONE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\b01[016789][\s.-]?\d{3,4}[\s.-]?\d{4}\b|\+\d{1,3}[\s.-]?\d{2,4}[\s.-]?\d{3,4}[\s.-]?\d{3,4}\b)")
+        .expect("valid phone regex")
+});
+static MESSENGER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)whats\s*app|wa\.me/|카카오\s*톡|카톡|kakao\s*(?:talk|id)|open\.kakao\.com|오픈\s*채팅|line\s*id|라인\s*(?:아이디|id)|wechat|위챗|signal\s*:",
+    )
+    .expect("valid messenger regex")
+});
+static SOLICITATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)연락\s*(?:주세요|바랍니다|부탁|주시면|하세요)|문의\s*(?:주세요|하세요|바랍니다)|갠톡|개인\s*(?:톡|메시지|메세지)\s*주세요|쪽지\s*주세요|dm\s*(?:me|주세요)|contact\s+me|message\s+me",
+    )
+    .expect("valid solicitation regex")
+});
+
+/// Flags contact details that scam messages use instead of links.
+pub fn detect(text: &str, signals: &mut Vec<Signal>) {
+    if PHONE_REGEX.is_match(text) {
+        signals.push(Signal::new("phone_number", PHONE_SCORE, "전화번호 포함"));
+    }
+    if MESSENGER_REGEX.is_match(text) {
+        signals.push(Signal::new(
+            "messenger_handle",
+            MESSENGER_SCORE,
+            "외부 메신저 연락처 유도",
+        ));
+    }
+    if SOLICITATION_REGEX.is_match(text) {
+        signals.push(Signal::new(
+            "contact_solicitation",
+            SOLICITATION_SCORE,
+            "개인 연락 요청",
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::Signal;
+
+const WALLET_SCORE: i32 = 25;
+const SEED_PHRASE_SCORE: i32 = 30;
+
+static WALLET_PATTERNS: Lazy<Vec<(&'static str, Regex)>> = Lazy::new(|| {
+    [
+        (
+            "BTC",
+            r"\b(?:[13][a-km-zA-HJ-NP-Z1-9]{25,34}|bc1[ac-hj-np-z02-9]{11,71})\b",
+        ),
+        ("ETH", r"\b0x[a-fA-F0-9]{40}\b"),
+        ("TRON", r"\bT[1-9A-HJ-NP-Za-km-z]{33}\b"),
+    ]
+    .into_iter()
+    .map(|(chain, pattern)| (chain, Regex::new(pattern).expect("valid wallet regex")))
+    .collect()
+});
+static SEED_KEYWORD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)seed\s*phrase|mnemonic|recovery\s*phrase|private\s*key|시드\s*(문구|구문)|니모닉|복구\s*(문구|구문)|개인\s*키")
+        .expect("valid seed keyword regex")
+});
+
+/// Flags wallet addresses and seed-phrase solicitations or leaks. Both are weighted
+/// signals only: donation addresses and warnings about seed-phrase scams are common in
+/// crypto communities.
+pub fn detect(text: &str, signals: &mut Vec<Signal>) {
+    let chains: Vec<&str> = WALLET_PATTERNS
+        .iter()
+        .filter(|(_, regex)| regex.is_match(text))
+        .map(|(chain, _)| *chain)
+        .collect();
+    if !chains.is_empty() {
+        signals.push(Signal::new(
+            "crypto_wallet",
+            WALLET_SCORE,
+            format!("암호화폐 지갑 주소({})", chains.join("/")),
+        ));
+    }
+
+    if SEED_KEYWORD_REGEX.is_match(text) || text.lines().any(looks_like_seed_phrase) {
+        signals.push(Signal::new(
+            "seed_phrase",
+            SEED_PHRASE_SCORE,
+            "시드 문구/개인키 관련 내용",
+        ));
+    }
+}
+
+/// A line made up of exactly 12-24 (in steps of 3) short lowercase words, as BIP-39 phrases are.
+fn looks_like_seed_phrase(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    (12..=24).contains(&words.len())
+        && words.len().is_multiple_of(3)
+        && words.iter().all(|word| {
+            (3..=8).contains(&word.len()) && word.bytes().all(|b| b.is_ascii_lowercase())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_wallets_and_seed_phrases() {
+        let mut signals = Vec::new();
+        detect(
+            "입금: 0x52908400098527886E0F7030069857D2E4169EE7",
+            &mut signals,
+        );
+        assert_eq!(signals.len(), 1);
+        assert!(signals[0].detail.contains("ETH"));
+
+        let mut signals = Vec::new();
+        detect(
+            "abandon ability able about above absent absorb abstract absurd abuse access accident",
+            &mut signals,
+        );
+        assert_eq!(signals[0].name, "seed_phrase");
+        assert!(!signals[0].strict);
+
+        let mut signals = Vec::new();
+        detect("오늘 점심 뭐 먹을까요?", &mut signals);
+        assert!(signals.is_empty());
+    }
+}
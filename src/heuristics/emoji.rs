@@ -0,0 +1,71 @@
+use teloxide::types::{MessageEntity, MessageEntityKind};
+
+use super::Signal;
+
+const EMOJI_DENSITY_SCORE: i32 = 8;
+const CUSTOM_EMOJI_SCORE: i32 = 10;
+const DECORATIVE_FRAME_SCORE: i32 = 10;
+/// Density is only meaningful once a message has a handful of emoji.
+const MIN_EMOJI_FOR_DENSITY: usize = 5;
+const MIN_FRAME_CHARS: usize = 6;
+
+/// Flags emoji-heavy, custom-emoji-heavy and box-framed messages typical of promo spam.
+pub fn detect(
+    text: &str,
+    entities: &[MessageEntity],
+    density_threshold: f32,
+    custom_emoji_threshold: usize,
+    signals: &mut Vec<Signal>,
+) {
+    let mut visible = 0usize;
+    let mut emoji = 0usize;
+    let mut frame = 0usize;
+    for ch in text.chars().filter(|ch| !ch.is_whitespace()) {
+        visible += 1;
+        if is_emoji(ch) {
+            emoji += 1;
+        } else if is_frame_char(ch) {
+            frame += 1;
+        }
+    }
+
+    if emoji >= MIN_EMOJI_FOR_DENSITY && emoji as f32 / visible as f32 >= density_threshold {
+        signals.push(Signal::new(
+            "emoji_density",
+            EMOJI_DENSITY_SCORE,
+            format!("이모지 과다({emoji}/{visible})"),
+        ));
+    }
+
+    let custom = entities
+        .iter()
+        .filter(|entity| matches!(entity.kind, MessageEntityKind::CustomEmoji { .. }))
+        .count();
+    if custom_emoji_threshold > 0 && custom >= custom_emoji_threshold {
+        signals.push(Signal::new(
+            "custom_emoji",
+            CUSTOM_EMOJI_SCORE,
+            format!("프리미엄 이모지 반복({custom}개)"),
+        ));
+    }
+
+    if frame >= MIN_FRAME_CHARS {
+        signals.push(Signal::new(
+            "decorative_frame",
+            DECORATIVE_FRAME_SCORE,
+            "장식용 유니코드 테두리",
+        ));
+    }
+}
+
+fn is_emoji(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF
+    )
+}
+
+/// Box-drawing, block elements and geometric shapes used to frame promo text (▛▜▙▟ ━ ■).
+fn is_frame_char(ch: char) -> bool {
+    matches!(ch as u32, 0x2500..=0x25FF)
+}
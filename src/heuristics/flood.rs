@@ -0,0 +1,160 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::config::FloodConfig;
+
+use super::known_spam::text_fingerprint;
+
+/// Why a sender was caught flooding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloodVerdict {
+    /// More messages than allowed within the window.
+    Rate { count: usize },
+    /// The same text posted repeatedly within the window.
+    Repeat { count: usize },
+}
+
+impl FloodVerdict {
+    pub fn reason(&self, window: Duration) -> String {
+        match self {
+            Self::Rate { count } => {
+                format!("도배: {}초 안에 메시지 {count}개", window.as_secs())
+            }
+            Self::Repeat { count } => {
+                format!("도배: {}초 안에 같은 메시지 {count}회", window.as_secs())
+            }
+        }
+    }
+}
+
+struct RecentPost {
+    fingerprint: Option<i64>,
+    seen_at: Instant,
+}
+
+/// Sliding-window message counter per chat member, cheap enough to run on every message
+/// before it is queued.
+pub struct FloodDetector {
+    config: FloodConfig,
+    senders: Mutex<HashMap<(i64, i64), VecDeque<RecentPost>>>,
+}
+
+impl FloodDetector {
+    pub fn new(config: FloodConfig) -> Self {
+        Self {
+            config,
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &FloodConfig {
+        &self.config
+    }
+
+    pub fn observe(&self, chat_id: i64, user_id: i64, text: &str) -> Option<FloodVerdict> {
+        self.observe_at(chat_id, user_id, text, Instant::now())
+    }
+
+    fn observe_at(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        text: &str,
+        now: Instant,
+    ) -> Option<FloodVerdict> {
+        let window = self.config.window;
+        let mut senders = self.senders.lock();
+        // Drop idle senders so the map only holds people active within the window.
+        senders.retain(|_, posts| {
+            posts
+                .back()
+                .is_some_and(|post| now.duration_since(post.seen_at) < window)
+        });
+        let posts = senders.entry((chat_id, user_id)).or_default();
+        while posts
+            .front()
+            .is_some_and(|post| now.duration_since(post.seen_at) >= window)
+        {
+            posts.pop_front();
+        }
+
+        let fingerprint = text_fingerprint(text);
+        posts.push_back(RecentPost {
+            fingerprint,
+            seen_at: now,
+        });
+
+        if self.config.max_repeats > 0 {
+            if let Some(fingerprint) = fingerprint {
+                let count = posts
+                    .iter()
+                    .filter(|post| post.fingerprint == Some(fingerprint))
+                    .count();
+                if count > self.config.max_repeats {
+                    return Some(FloodVerdict::Repeat { count });
+                }
+            }
+        }
+        if self.config.max_messages > 0 && posts.len() > self.config.max_messages {
+            return Some(FloodVerdict::Rate { count: posts.len() });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> FloodDetector {
+        FloodDetector::new(FloodConfig {
+            window: Duration::from_secs(10),
+            max_messages: 4,
+            max_repeats: 2,
+            mute: None,
+        })
+    }
+
+    #[test]
+    fn repeated_text_trips_before_the_rate_limit() {
+        let detector = detector();
+        let start = Instant::now();
+        for _ in 0..2 {
+            assert_eq!(
+                detector.observe_at(-1, 7, "무료 코인 받아가세요 지금 바로", start),
+                None
+            );
+        }
+        assert_eq!(
+            detector.observe_at(-1, 7, "무료 코인 받아가세요 지금 바로", start),
+            Some(FloodVerdict::Repeat { count: 3 })
+        );
+        // Another sender in the same chat has their own window.
+        assert_eq!(
+            detector.observe_at(-1, 8, "무료 코인 받아가세요 지금 바로", start),
+            None
+        );
+    }
+
+    #[test]
+    fn rate_limit_slides_with_the_window() {
+        let detector = detector();
+        let start = Instant::now();
+        for (index, text) in ["하나", "둘", "셋", "넷"].iter().enumerate() {
+            let at = start + Duration::from_secs(index as u64);
+            assert_eq!(detector.observe_at(-1, 7, text, at), None);
+        }
+        assert_eq!(
+            detector.observe_at(-1, 7, "다섯", start + Duration::from_secs(5)),
+            Some(FloodVerdict::Rate { count: 5 })
+        );
+        assert_eq!(
+            detector.observe_at(-1, 7, "여섯", start + Duration::from_secs(12)),
+            None
+        );
+    }
+}
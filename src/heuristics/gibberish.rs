@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use super::Signal;
+
+const RANDOM_USERNAME_SCORE: i32 = 8;
+const KEYBOARD_MASH_SCORE: i32 = 6;
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Flags random-string usernames and keyboard-mash messages used to probe moderation.
+pub fn detect(text: &str, username: Option<&str>, signals: &mut Vec<Signal>) {
+    if username.is_some_and(looks_random) {
+        signals.push(Signal::new(
+            "random_username",
+            RANDOM_USERNAME_SCORE,
+            "무작위 문자열 사용자명",
+        ));
+    }
+    if looks_like_mash(text) {
+        signals.push(Signal::new(
+            "keyboard_mash",
+            KEYBOARD_MASH_SCORE,
+            "의미 없는 문자열(키보드 난타)",
+        ));
+    }
+}
+
+/// Shannon entropy in bits per character.
+fn entropy(value: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut total = 0usize;
+    for ch in value.chars() {
+        *counts.entry(ch).or_default() += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn longest_consonant_run(value: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in value.chars() {
+        if ch.is_ascii_alphabetic() && !"aeiouy".contains(ch.to_ascii_lowercase()) {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+fn looks_random(username: &str) -> bool {
+    let lowered = username.to_ascii_lowercase();
+    if lowered.len() < 8 {
+        return false;
+    }
+    let letters = lowered.chars().filter(char::is_ascii_alphabetic).count();
+    let digits = lowered.chars().filter(char::is_ascii_digit).count();
+    let transitions = lowered
+        .as_bytes()
+        .windows(2)
+        .filter(|pair| pair[0].is_ascii_digit() != pair[1].is_ascii_digit())
+        .count();
+    entropy(&lowered) >= 3.0
+        && (longest_consonant_run(&lowered) >= 5
+            || (letters >= 3 && digits >= 3 && transitions >= 4))
+}
+
+fn looks_like_mash(text: &str) -> bool {
+    let compact: String = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if compact.is_empty() || compact.chars().count() > 60 {
+        return false;
+    }
+    // Korean mash is usually bare consonant jamo, e.g. "ㅁㄴㅇㄹㅁㄴㅇ"; laughter
+    // ("ㅋㅋㅋ", "ㅎㅎ") is ordinary chat and must not count.
+    let jamo: Vec<char> = compact
+        .chars()
+        .filter(|ch| ('\u{3131}'..='\u{314E}').contains(ch) && !matches!(ch, 'ㅋ' | 'ㅎ'))
+        .collect();
+    let mut distinct = jamo.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if jamo.len() >= 4 && distinct.len() >= 3 && jamo.len() * 2 >= compact.chars().count() {
+        return true;
+    }
+    let lowered = compact.to_ascii_lowercase();
+    if !lowered.chars().all(|ch| ch.is_ascii_alphabetic()) || lowered.len() < 6 {
+        return false;
+    }
+    // Four adjacent keys occur in ordinary words ("property", "liberty"), so they only
+    // count when typed twice; five in a row do on their own.
+    let row_run = KEYBOARD_ROWS.iter().any(|row| {
+        let has_run = |len: usize, times: usize| {
+            row.as_bytes().windows(len).any(|window| {
+                let window = std::str::from_utf8(window).unwrap_or_default();
+                lowered.matches(window).count() >= times
+            })
+        };
+        has_run(5, 1) || has_run(4, 2)
+    });
+    row_run || longest_consonant_run(&lowered) >= 6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_random_strings_only() {
+        assert!(looks_random("xk3jq9zv7w2"));
+        assert!(!looks_random("minsu_kim"));
+        assert!(looks_like_mash("asdfasdf"));
+        assert!(looks_like_mash("ㅁㄴㅇㄹㅁㄴㅇ"));
+        assert!(!looks_like_mash("hello everyone"));
+        assert!(!looks_like_mash("ㅋㅋㅋㅋㅋㅎㅎ"));
+    }
+
+    #[test]
+    fn real_words_are_not_mash() {
+        assert!(looks_like_mash("sdfghjk"));
+        assert!(looks_like_mash("zxcvbn"));
+        for word in [
+            "property",
+            "liberty",
+            "Puberty",
+            "poverty",
+            "typewriter",
+            "strengths",
+            "sweaters",
+        ] {
+            assert!(!looks_like_mash(word), "{word}");
+        }
+    }
+}
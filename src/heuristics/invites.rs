@@ -0,0 +1,102 @@
+use std::collections::BTreeSet;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use teloxide::types::{MessageEntity, MessageEntityKind};
+
+use super::Signal;
+
+const INVITE_LINK_SCORE: i32 = 25;
+
+static INVITE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(?:https?://)?(?:t\.me|telegram\.me|telegram\.dog)/([A-Za-z0-9_+\-]+)(?:/([A-Za-z0-9_\-]+))?",
+    )
+    .expect("valid invite regex")
+});
+
+/// Distinct channels/invites linked from the message text and its hidden text links.
+pub fn invite_targets(text: &str, entities: &[MessageEntity]) -> Vec<String> {
+    let mut targets = BTreeSet::new();
+    let linked = entities.iter().filter_map(|entity| match &entity.kind {
+        MessageEntityKind::TextLink { url } => Some(url.as_str()),
+        _ => None,
+    });
+    for haystack in std::iter::once(text).chain(linked) {
+        targets.extend(INVITE_REGEX.captures_iter(haystack).filter_map(target_from));
+    }
+    targets.into_iter().collect()
+}
+
+/// Normalizes admin input such as `@partner`, `t.me/partner` or `https://t.me/+hash`.
+pub fn normalize_target(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if let Some(captures) = INVITE_REGEX.captures(raw) {
+        return target_from(captures);
+    }
+    let name = raw.trim_start_matches('@');
+    (!name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_'))
+    .then(|| name.to_ascii_lowercase())
+}
+
+/// Usernames are case-insensitive; private invite hashes are not. Private-channel
+/// links (`t.me/c/<id>/...`) keep the channel ID so different channels stay apart.
+fn target_from(captures: Captures<'_>) -> Option<String> {
+    let first = captures.get(1)?.as_str();
+    if first.eq_ignore_ascii_case("joinchat") {
+        return captures.get(2).map(|hash| format!("+{}", hash.as_str()));
+    }
+    if first.eq_ignore_ascii_case("c") {
+        return captures
+            .get(2)
+            .map(|id| id.as_str())
+            .filter(|id| id.bytes().all(|b| b.is_ascii_digit()))
+            .map(|id| format!("c/{id}"));
+    }
+    if first.starts_with('+') {
+        return Some(first.to_string());
+    }
+    Some(first.to_ascii_lowercase())
+}
+
+/// Escalates messages carrying several invite links that are not allowlisted for the chat.
+pub fn detect(invite_links: &[String], threshold: usize, signals: &mut Vec<Signal>) {
+    if threshold > 0 && invite_links.len() >= threshold {
+        signals.push(Signal::new(
+            "invite_links",
+            INVITE_LINK_SCORE,
+            format!("텔레그램 초대 링크 {}개", invite_links.len()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invite_targets_normalize_links() {
+        let targets = invite_targets(
+            "https://t.me/c/2485256729/1/205 t.me/MyChannel https://t.me/mychannel/5 t.me/joinchat/AbC t.me/+XyZ",
+            &[],
+        );
+        assert_eq!(targets, vec!["+AbC", "+XyZ", "c/2485256729", "mychannel"]);
+        assert_eq!(normalize_target("@MyChannel").as_deref(), Some("mychannel"));
+    }
+
+    #[test]
+    fn private_channel_links_keep_their_id() {
+        let targets = invite_targets(
+            "https://t.me/c/2485256729/1/205 t.me/c/1111111111/7 t.me/c/2485256729/9",
+            &[],
+        );
+        assert_eq!(targets, vec!["c/1111111111", "c/2485256729"]);
+        assert_eq!(
+            normalize_target("https://t.me/c/2485256729/1").as_deref(),
+            Some("c/2485256729")
+        );
+    }
+}
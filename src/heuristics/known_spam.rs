@@ -0,0 +1,202 @@
+use teloxide::types::MessageEntity;
+use url::Url;
+
+use super::Signal;
+use crate::{db::blocked_domains::BlockedDomain, telegram::utils::extract_message_urls};
+
+const BLACKLISTED_USER_SCORE: i32 = 50;
+const KNOWN_TEXT_SCORE: i32 = 40;
+const BLOCKED_DOMAIN_SCORE: i32 = 40;
+/// Domains harvested from honeypots are only a hint: spammers link popular sites too.
+const HARVESTED_DOMAIN_SCORE: i32 = 20;
+/// Normalized texts shorter than this are too generic to fingerprint.
+const MIN_FINGERPRINT_CHARS: usize = 12;
+/// Platforms too widely used to ever block automatically.
+const NEVER_BLOCK: [&str; 8] = [
+    "t.me",
+    "telegram.me",
+    "telegram.dog",
+    "youtube.com",
+    "youtu.be",
+    "google.com",
+    "naver.com",
+    "kakao.com",
+];
+
+/// What the blacklist, fingerprint store and blocked-domain table know about a message.
+#[derive(Debug, Default, Clone)]
+pub struct KnownSpam {
+    pub blacklisted_user: bool,
+    pub known_text: bool,
+    pub blocked_domains: Vec<BlockedDomain>,
+}
+
+pub fn detect(known: &KnownSpam, signals: &mut Vec<Signal>) {
+    if known.blacklisted_user {
+        signals.push(
+            Signal::new(
+                "blacklisted_user",
+                BLACKLISTED_USER_SCORE,
+                "블랙리스트에 등록된 사용자",
+            )
+            .strict(),
+        );
+    }
+    if known.known_text {
+        signals.push(
+            Signal::new("known_spam_text", KNOWN_TEXT_SCORE, "이미 확인된 스팸 문구").strict(),
+        );
+    }
+    let (admin, harvested): (Vec<_>, Vec<_>) = known
+        .blocked_domains
+        .iter()
+        .partition(|blocked| blocked.is_admin());
+    if !admin.is_empty() {
+        signals.push(
+            Signal::new(
+                "blocked_domain",
+                BLOCKED_DOMAIN_SCORE,
+                format!("차단된 도메인({})", domain_names(&admin)),
+            )
+            .strict(),
+        );
+    }
+    if !harvested.is_empty() {
+        signals.push(Signal::new(
+            "honeypot_domain",
+            HARVESTED_DOMAIN_SCORE,
+            format!("허니팟에서 수집된 도메인({})", domain_names(&harvested)),
+        ));
+    }
+}
+
+fn domain_names(domains: &[&BlockedDomain]) -> String {
+    domains
+        .iter()
+        .map(|blocked| blocked.domain.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Stable FNV-1a hash of the text with case, spacing, digits and punctuation removed.
+pub fn text_fingerprint(text: &str) -> Option<i64> {
+    let normalized: String = text
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if normalized.chars().count() < MIN_FINGERPRINT_CHARS {
+        return None;
+    }
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in normalized.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    Some(hash as i64)
+}
+
+/// Distinct lowercase hosts (without `www.`) linked from the text or its hidden text links.
+pub fn link_domains(text: &str, entities: &[MessageEntity]) -> Vec<String> {
+    let urls = extract_message_urls(text, entities, usize::MAX);
+    let mut domains: Vec<String> = urls.iter().filter_map(|raw| url_domain(raw)).collect();
+    domains.sort();
+    domains.dedup();
+    domains
+}
+
+/// Lowercase host of a URL without `www.`.
+pub fn url_domain(raw: &str) -> Option<String> {
+    let url = Url::parse(raw).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+/// Reads a domain typed by an admin, bare or as a link.
+pub fn normalize_domain(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let domain = if raw.contains("://") {
+        url_domain(raw)?
+    } else {
+        url_domain(&format!("http://{raw}"))?
+    };
+    (domain.contains('.') && !domain.ends_with('.')).then_some(domain)
+}
+
+/// The domain and each parent domain down to two labels, so a listed domain also
+/// covers its subdomains.
+pub fn domain_suffixes(domain: &str) -> Vec<&str> {
+    let mut suffixes = vec![domain];
+    let mut rest = domain;
+    while let Some((_, parent)) = rest.split_once('.') {
+        if !parent.contains('.') {
+            break;
+        }
+        suffixes.push(parent);
+        rest = parent;
+    }
+    suffixes
+}
+
+/// Whether a domain harvested from a honeypot may be added to the blocklist.
+pub fn is_blockable(domain: &str) -> bool {
+    !NEVER_BLOCK
+        .iter()
+        .any(|safe| domain == *safe || domain.ends_with(&format!(".{safe}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_ignores_spacing_digits_and_case() {
+        let a = text_fingerprint("지금 가입하면 USDT 100 지급! 선착순 모집");
+        let b = text_fingerprint("지금가입하면 usdt 500 지급 -- 선착순  모집!!");
+        assert!(a.is_some());
+        assert_eq!(a, b);
+        assert_eq!(text_fingerprint("안녕하세요"), None);
+    }
+
+    #[test]
+    fn domains_are_normalized_and_cover_subdomains() {
+        assert_eq!(
+            normalize_domain("https://WWW.Example.com/path"),
+            Some("example.com".to_string())
+        );
+        assert_eq!(
+            normalize_domain("gist.github.com"),
+            Some("gist.github.com".to_string())
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(
+            domain_suffixes("a.b.example.com"),
+            vec!["a.b.example.com", "b.example.com", "example.com"]
+        );
+        assert_eq!(domain_suffixes("example.com"), vec!["example.com"]);
+    }
+
+    #[test]
+    fn only_admin_blocked_domains_are_strict() {
+        let known = KnownSpam {
+            blocked_domains: vec![
+                BlockedDomain {
+                    domain: "scam.example".to_string(),
+                    source: Some("admin".to_string()),
+                },
+                BlockedDomain {
+                    domain: "github.com".to_string(),
+                    source: Some("honeypot:-100".to_string()),
+                },
+            ],
+            ..KnownSpam::default()
+        };
+        let mut signals = Vec::new();
+        detect(&known, &mut signals);
+        let strict: Vec<_> = signals.iter().map(|s| (s.name, s.strict)).collect();
+        assert_eq!(
+            strict,
+            vec![("blocked_domain", true), ("honeypot_domain", false)]
+        );
+    }
+}
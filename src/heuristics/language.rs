@@ -0,0 +1,85 @@
+/// Letters needed before a script-based guess is trusted.
+const MIN_LETTERS: usize = 4;
+
+/// Codes [`detect`] can return.
+pub const CODES: [&str; 5] = ["ko", "ru", "ja", "zh", "en"];
+
+/// Guesses the message language from its dominant script, returning an ISO 639-1 code.
+/// Latin text is assumed to be English; mixed or letter-poor text yields `None`. Links
+/// and mentions are ignored.
+pub fn detect(text: &str) -> Option<&'static str> {
+    let mut counts = [0usize; 5];
+    // Links and mentions are Latin whatever language the message is in.
+    let words = text
+        .split_whitespace()
+        .filter(|word| !word.contains("://") && !word.contains("t.me/") && !word.starts_with('@'));
+    for ch in words.flat_map(str::chars).filter(|ch| ch.is_alphabetic()) {
+        let script = match ch {
+            '가'..='힣' | 'ㄱ'..='ㆎ' => 0,
+            'а'..='я' | 'А'..='Я' | 'ё' | 'Ё' => 1,
+            'ぁ'..='ゟ' | '゠'..='ヿ' => 2,
+            '一'..='鿿' => 3,
+            'a'..='z' | 'A'..='Z' => 4,
+            _ => continue,
+        };
+        counts[script] += 1;
+    }
+    let total: usize = counts.iter().sum();
+    if total < MIN_LETTERS {
+        return None;
+    }
+    // Kana anywhere means Japanese, even when kanji outnumber it.
+    if counts[2] > 0 && counts[2] + counts[3] >= total / 2 {
+        return Some("ja");
+    }
+    let (script, count) = counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, count)| **count)
+        .expect("non-empty counts");
+    if *count * 2 < total {
+        return None;
+    }
+    Some(CODES[script])
+}
+
+/// Korean name shown in admin logs.
+pub fn display_name(code: &str) -> &'static str {
+    match code {
+        "ko" => "한국어",
+        "ru" => "러시아어",
+        "ja" => "일본어",
+        "zh" => "중국어",
+        "en" => "영어",
+        _ => "알 수 없음",
+    }
+}
+
+/// English name substituted into the classifier prompt.
+pub fn english_name(code: &str) -> &'static str {
+    match code {
+        "ko" => "Korean",
+        "ru" => "Russian",
+        "ja" => "Japanese",
+        "zh" => "Chinese",
+        "en" => "English",
+        _ => "an unknown language",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_dominant_script() {
+        assert_eq!(
+            detect("무료 리딩방 입장하세요 https://t.me/abc"),
+            Some("ko")
+        );
+        assert_eq!(detect("Заработок от 5000 в день, пиши в ЛС"), Some("ru"));
+        assert_eq!(detect("DM me for crypto recovery"), Some("en"));
+        assert_eq!(detect("今日は良い天気ですね"), Some("ja"));
+        assert_eq!(detect("ok"), None);
+    }
+}
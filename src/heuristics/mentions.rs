@@ -0,0 +1,31 @@
+use teloxide::types::{MessageEntity, MessageEntityKind};
+
+use super::Signal;
+
+const MASS_MENTION_SCORE: i32 = 30;
+
+/// Flags messages that ping many users at once, the usual shape of a raid.
+pub fn detect(entities: &[MessageEntity], threshold: usize, signals: &mut Vec<Signal>) {
+    if threshold == 0 {
+        return;
+    }
+    let mentions = entities
+        .iter()
+        .filter(|entity| {
+            matches!(
+                entity.kind,
+                MessageEntityKind::Mention | MessageEntityKind::TextMention { .. }
+            )
+        })
+        .count();
+    if mentions >= threshold {
+        signals.push(
+            Signal::new(
+                "mass_mention",
+                MASS_MENTION_SCORE,
+                format!("다수 사용자 멘션({mentions}명)"),
+            )
+            .strict(),
+        );
+    }
+}
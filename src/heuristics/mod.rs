@@ -0,0 +1,95 @@
+pub mod account_age;
+pub mod contact;
+pub mod crypto;
+pub mod emoji;
+pub mod flood;
+pub mod gibberish;
+pub mod invites;
+pub mod known_spam;
+pub mod language;
+pub mod mentions;
+pub mod profile;
+pub mod raid;
+pub mod risk;
+
+use teloxide::types::MessageEntity;
+
+use crate::config::HeuristicsConfig;
+
+/// A cheap, local spam indicator found before the message reaches the classifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signal {
+    /// Stable identifier used in logs.
+    pub name: &'static str,
+    pub score: i32,
+    /// Short Korean description included in the classification prompt.
+    pub detail: String,
+    /// Whether chats in strict mode delete the message without asking the classifier.
+    pub strict: bool,
+}
+
+impl Signal {
+    pub fn new(name: &'static str, score: i32, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            score,
+            detail: detail.into(),
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
+/// The parts of an incoming message the detectors look at.
+pub struct HeuristicInput<'a> {
+    pub text: &'a str,
+    pub entities: &'a [MessageEntity],
+    /// Invite targets left after removing the chat's allowlisted partners.
+    pub invite_links: &'a [String],
+    pub username: Option<&'a str>,
+    /// Estimated from the sender's user ID, see [`account_age`].
+    pub account_age_days: Option<i64>,
+    /// Whether the message carries any URL or invite link.
+    pub has_links: bool,
+    /// Only looked up for link posters without a username; `None` when unknown.
+    pub has_profile_photo: Option<bool>,
+    pub known_spam: &'a known_spam::KnownSpam,
+}
+
+pub fn analyze(input: &HeuristicInput<'_>, config: &HeuristicsConfig) -> Vec<Signal> {
+    let mut signals = Vec::new();
+    known_spam::detect(input.known_spam, &mut signals);
+    crypto::detect(input.text, &mut signals);
+    contact::detect(input.text, &mut signals);
+    mentions::detect(input.entities, config.mass_mention_threshold, &mut signals);
+    invites::detect(
+        input.invite_links,
+        config.invite_link_threshold,
+        &mut signals,
+    );
+    account_age::detect(
+        input.account_age_days,
+        input.has_links,
+        config.new_account_days,
+        &mut signals,
+    );
+    profile::detect(
+        input.username.is_some(),
+        input.has_profile_photo,
+        input.has_links,
+        &mut signals,
+    );
+    gibberish::detect(input.text, input.username, &mut signals);
+    emoji::detect(
+        input.text,
+        input.entities,
+        config.emoji_density_threshold,
+        config.custom_emoji_threshold,
+        &mut signals,
+    );
+    signals
+}
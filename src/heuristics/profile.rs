@@ -0,0 +1,19 @@
+use super::Signal;
+
+const BARE_PROFILE_SCORE: i32 = 12;
+
+/// Flags link posters with neither a username nor a profile photo.
+pub fn detect(
+    has_username: bool,
+    has_profile_photo: Option<bool>,
+    has_links: bool,
+    signals: &mut Vec<Signal>,
+) {
+    if has_links && !has_username && has_profile_photo == Some(false) {
+        signals.push(Signal::new(
+            "bare_profile",
+            BARE_PROFILE_SCORE,
+            "사용자명·프로필 사진 없는 계정의 링크 게시",
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::{config::RaidConfig, domain::MessageJob};
+
+/// Normalized texts shorter than this are too generic to fingerprint.
+const MIN_FINGERPRINT_CHARS: usize = 8;
+/// Per-chat history cap so a busy chat cannot grow the buffer unbounded.
+const MAX_RECENT_PER_CHAT: usize = 200;
+
+pub type Fingerprint = HashSet<u64>;
+
+struct RecentPost {
+    job: MessageJob,
+    fingerprint: Fingerprint,
+    suspicious: bool,
+    seen_at: Instant,
+}
+
+struct ActiveRaid {
+    fingerprint: Fingerprint,
+    until: Instant,
+}
+
+#[derive(Default)]
+struct ChatActivity {
+    recent: Vec<RecentPost>,
+    joins: HashMap<i64, Instant>,
+    raids: Vec<ActiveRaid>,
+}
+
+/// Outcome of feeding a message into the detector.
+pub enum RaidVerdict {
+    /// Nothing coordinated going on.
+    None,
+    /// A new raid was detected; `jobs` holds every participating message including this one.
+    Detected { jobs: Vec<MessageJob> },
+    /// The message repeats the content of a raid that is still being handled.
+    FollowUp,
+}
+
+/// Detects several distinct, typically new accounts posting near-identical content in a
+/// chat within a short window.
+pub struct RaidDetector {
+    config: RaidConfig,
+    chats: Mutex<HashMap<i64, ChatActivity>>,
+}
+
+impl RaidDetector {
+    pub fn new(config: RaidConfig) -> Self {
+        Self {
+            config,
+            chats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn record_join(&self, chat_id: i64, user_id: i64) {
+        let mut chats = self.chats.lock();
+        let activity = chats.entry(chat_id).or_default();
+        activity.joins.insert(user_id, Instant::now());
+    }
+
+    /// `is_new_account` comes from the account-age estimate.
+    pub fn observe(&self, job: &MessageJob, is_new_account: bool) -> RaidVerdict {
+        let (Some(user_id), Some(fingerprint)) = (job.from_id, fingerprint(&job.text)) else {
+            return RaidVerdict::None;
+        };
+        let now = Instant::now();
+        let window = self.config.window;
+        let mut chats = self.chats.lock();
+        let activity = chats.entry(job.chat_id.0).or_default();
+        prune(activity, now, window);
+
+        if activity
+            .raids
+            .iter()
+            .any(|raid| similarity(&raid.fingerprint, &fingerprint) >= self.config.similarity)
+        {
+            return RaidVerdict::FollowUp;
+        }
+
+        let joined_recently = activity.joins.contains_key(&user_id);
+        let suspicious = joined_recently || !job.is_group_member || is_new_account;
+        let similar: Vec<usize> = activity
+            .recent
+            .iter()
+            .enumerate()
+            .filter(|(_, post)| post.job.from_id != Some(user_id))
+            .filter(|(_, post)| {
+                similarity(&post.fingerprint, &fingerprint) >= self.config.similarity
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut accounts: HashSet<i64> = similar
+            .iter()
+            .filter(|&&index| activity.recent[index].suspicious)
+            .filter_map(|&index| activity.recent[index].job.from_id)
+            .collect();
+        if suspicious {
+            accounts.insert(user_id);
+        }
+
+        if accounts.len() >= self.config.min_accounts {
+            let mut jobs: Vec<MessageJob> = Vec::with_capacity(similar.len() + 1);
+            for index in similar.into_iter().rev() {
+                jobs.push(activity.recent.remove(index).job);
+            }
+            jobs.push(job.clone());
+            activity.raids.push(ActiveRaid {
+                fingerprint,
+                until: now + window,
+            });
+            return RaidVerdict::Detected { jobs };
+        }
+
+        if activity.recent.len() >= MAX_RECENT_PER_CHAT {
+            activity.recent.remove(0);
+        }
+        activity.recent.push(RecentPost {
+            job: job.clone(),
+            fingerprint,
+            suspicious,
+            seen_at: now,
+        });
+        RaidVerdict::None
+    }
+}
+
+fn prune(activity: &mut ChatActivity, now: Instant, window: Duration) {
+    activity
+        .recent
+        .retain(|post| now.duration_since(post.seen_at) < window);
+    activity
+        .joins
+        .retain(|_, joined_at| now.duration_since(*joined_at) < window);
+    activity.raids.retain(|raid| raid.until > now);
+}
+
+/// Hashed character trigrams of the text with case, spacing, digits and punctuation removed,
+/// so trivially varied copies still collide.
+pub fn fingerprint(text: &str) -> Option<Fingerprint> {
+    let normalized: Vec<char> = text
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if normalized.len() < MIN_FINGERPRINT_CHARS {
+        return None;
+    }
+    Some(
+        normalized
+            .windows(3)
+            .map(|gram| {
+                let mut hasher = DefaultHasher::new();
+                gram.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect(),
+    )
+}
+
+/// Jaccard similarity of two fingerprints.
+pub fn similarity(a: &Fingerprint, b: &Fingerprint) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_ignore_trivial_variations() {
+        let a = fingerprint("지금 바로 가입하세요!! 수익 보장 123").unwrap();
+        let b = fingerprint("지금바로 가입하세요 수익보장 456").unwrap();
+        let c = fingerprint("오늘 회의는 세 시에 시작합니다").unwrap();
+        assert!(similarity(&a, &b) > 0.9);
+        assert!(similarity(&a, &c) < 0.2);
+        assert!(fingerprint("ㅋㅋㅋ").is_none());
+    }
+}
@@ -0,0 +1,170 @@
+use super::Signal;
+use crate::{config::RiskConfig, tasks::queue::Priority};
+
+/// Built-in factor weights; signals default to their own score.
+const DEFAULT_WEIGHTS: [(&str, i32); 5] = [
+    ("base", 1),
+    ("non_member", 10),
+    ("url", 5),
+    ("invite_link", 20),
+    // Percent of the sender's reputation subtracted from the score: offenders add
+    // points, users with a clean history take some off.
+    ("reputation", 50),
+];
+
+fn weight(config: &RiskConfig, name: &str, default: i32) -> i32 {
+    config.weights.get(name).copied().unwrap_or(default)
+}
+
+fn base_weight(config: &RiskConfig, name: &str) -> i32 {
+    let default = DEFAULT_WEIGHTS
+        .iter()
+        .find(|(factor, _)| *factor == name)
+        .map(|(_, weight)| *weight)
+        .unwrap_or_default();
+    weight(config, name, default)
+}
+
+/// Everything known about a message before classification.
+pub struct RiskInput<'a> {
+    pub is_member: bool,
+    pub has_url: bool,
+    pub invite_links: usize,
+    /// Reputation score from `user_reputation`; offenders are negative, users with a
+    /// clean history positive.
+    pub reputation: i64,
+    pub signals: &'a [Signal],
+}
+
+#[derive(Debug, Clone)]
+pub struct RiskAssessment {
+    pub score: i32,
+    pub priority: Priority,
+    /// Whether strict-mode chats should delete without asking the classifier.
+    pub strict: bool,
+    /// Contributing factors with their points, for logs and the prompt.
+    pub factors: Vec<(String, i32)>,
+}
+
+pub fn assess(input: &RiskInput<'_>, config: &RiskConfig) -> RiskAssessment {
+    let mut factors: Vec<(String, i32)> = Vec::new();
+    let mut add = |name: &str, points: i32| {
+        if points != 0 {
+            factors.push((name.to_string(), points));
+        }
+    };
+
+    add("base", base_weight(config, "base"));
+    if !input.is_member {
+        add("non_member", base_weight(config, "non_member"));
+    }
+    if input.has_url {
+        add("url", base_weight(config, "url"));
+    }
+    if input.invite_links > 0 {
+        add("invite_link", base_weight(config, "invite_link"));
+    }
+    add(
+        "reputation",
+        (-input.reputation * i64::from(base_weight(config, "reputation")) / 100) as i32,
+    );
+    for signal in input.signals {
+        add(signal.name, weight(config, signal.name, signal.score));
+    }
+
+    let score = factors.iter().map(|(_, points)| points).sum();
+    let priority = if score >= config.high_priority_threshold {
+        Priority::High
+    } else {
+        Priority::Normal
+    };
+    // A strict signal whose weight `RISK_WEIGHTS` lowered below its own score (e.g. to 0)
+    // only adds its points.
+    let strict =
+        input.signals.iter().any(|signal| {
+            signal.strict && weight(config, signal.name, signal.score) >= signal.score
+        }) || score >= config.strict_threshold;
+    RiskAssessment {
+        score,
+        priority,
+        strict,
+        factors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn weights_override_defaults_and_signals() {
+        let config = RiskConfig {
+            weights: HashMap::from([
+                ("non_member".to_string(), 3),
+                ("crypto_wallet".to_string(), 0),
+            ]),
+            high_priority_threshold: 15,
+            strict_threshold: 60,
+        };
+        let signals = [Signal::new("crypto_wallet", 25, "")];
+        let assessment = assess(
+            &RiskInput {
+                is_member: false,
+                has_url: true,
+                invite_links: 0,
+                reputation: -20,
+                signals: &signals,
+            },
+            &config,
+        );
+        // base 1 + non_member 3 + url 5 + reputation 10
+        assert_eq!(assessment.score, 19);
+        assert_eq!(assessment.priority, Priority::High);
+        assert!(!assessment.strict);
+    }
+
+    #[test]
+    fn lowered_weights_disarm_strict_signals() {
+        let signals = [Signal::new("blocked_domain", 50, "").strict()];
+        let input = RiskInput {
+            is_member: true,
+            has_url: true,
+            invite_links: 0,
+            reputation: 0,
+            signals: &signals,
+        };
+        let mut config = RiskConfig {
+            weights: HashMap::new(),
+            high_priority_threshold: 15,
+            strict_threshold: 60,
+        };
+        assert!(assess(&input, &config).strict);
+        config.weights.insert("blocked_domain".to_string(), 0);
+        assert!(!assess(&input, &config).strict);
+        config.weights.insert("blocked_domain".to_string(), 80);
+        assert!(assess(&input, &config).strict);
+    }
+
+    #[test]
+    fn good_reputation_lowers_the_score() {
+        let config = RiskConfig {
+            weights: HashMap::new(),
+            high_priority_threshold: 15,
+            strict_threshold: 60,
+        };
+        let assessment = assess(
+            &RiskInput {
+                is_member: true,
+                has_url: true,
+                invite_links: 0,
+                reputation: 10,
+                signals: &[],
+            },
+            &config,
+        );
+        // base 1 + url 5 - reputation 5
+        assert_eq!(assessment.score, 1);
+    }
+}
@@ -0,0 +1,711 @@
+//! Message catalog for replies in chats, admin logs and admin-group notices.
+//!
+//! Templates use `{name}` placeholders filled by [`tf`]; each Korean and English entry
+//! must use the same ones.
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Ko,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Ko, Locale::En];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ko" | "kr" | "korean" => Some(Self::Ko),
+            "en" | "english" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Ko => "ko",
+            Self::En => "en",
+        }
+    }
+}
+
+macro_rules! catalog {
+    ($($name:ident => $ko:expr, $en:expr;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Msg {
+            $($name,)*
+        }
+
+        impl Msg {
+            #[cfg(test)]
+            const ALL: &'static [Msg] = &[$(Msg::$name,)*];
+
+            fn text(self, locale: Locale) -> &'static str {
+                match (self, locale) {
+                    $(
+                        (Msg::$name, Locale::Ko) => $ko,
+                        (Msg::$name, Locale::En) => $en,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    // General commands
+    CommandsHeader => "사용 가능한 명령어:", "Available commands:";
+    CommandStart => "봇 소개 및 시작", "About this bot";
+    CommandHelp => "도움말", "Show help";
+    CommandStatus => "봇 상태 확인", "Show bot status";
+    CommandChatid => "현재 채팅 ID 확인", "Show this chat's ID";
+    CommandPing => "응답 속도 측정", "Measure response time";
+    CommandSpam => "답장한 메시지를 스팸으로 신고 (채팅방 관리자·신뢰 사용자)",
+        "Report the replied-to message as spam (chat admins and trusted users)";
+    CommandAppeal => "삭제된 내 메시지에 이의 신청 (개인 대화)",
+        "Appeal the deletion of your message (private chat)";
+    CommandRequestWhitelist => "이 그룹의 화이트리스트 등록 요청 (그룹 관리자)",
+        "Ask the bot admin to whitelist this group (group admins)";
+
+    // Admin command menu
+    CommandWhitelistAdd => "그룹을 화이트리스트에 추가 (기간 지정 가능)",
+        "Whitelist a group (optionally for a period)";
+    CommandWhitelistRemove => "화이트리스트에서 제거", "Remove a group from the whitelist";
+    CommandWhitelistList => "화이트리스트 목록", "List the whitelist";
+    CommandWhitelistNote => "화이트리스트 항목 메모·태그 설정", "Set the note or tag of a whitelist entry";
+    CommandWhitelistExport => "화이트리스트 파일로 내보내기", "Export the whitelist as a file";
+    CommandWhitelistImport => "내보낸 화이트리스트 파일 가져오기", "Import an exported whitelist file";
+    CommandRestartCronList => "재시작 스케줄 목록", "List the restart schedules";
+    CommandRestartCronAdd => "재시작 스케줄 추가", "Add a restart schedule";
+    CommandRestartCronRemove => "재시작 스케줄 삭제", "Remove a restart schedule";
+    CommandRuleAdd => "채팅방 규칙 추가", "Add a chat rule";
+    CommandRuleList => "채팅방 규칙 목록", "List the chat rules";
+    CommandRuleRemove => "채팅방 규칙 삭제", "Remove a chat rule";
+    CommandSettings => "채팅방 설정 버튼 패널", "Chat settings button panel";
+    CommandStrictMode => "채팅방 엄격 모드 설정", "Set strict mode for a chat";
+    CommandCriteria => "채팅방별 추가 스팸 기준 설정", "Set extra spam criteria for a chat";
+    CommandAction => "채팅방별 스팸 발송자 조치 설정", "Set the action against spammers for a chat";
+    CommandProbation => "채팅방별 신규 멤버 관찰 기간 설정", "Set the new-member probation for a chat";
+    CommandNotice => "채팅방별 스팸 삭제 알림 설정", "Set the deletion notice for a chat";
+    CommandLanguage => "채팅방별 응답 언어 설정", "Set the reply language for a chat";
+    CommandReview => "오탐 신고·이의 신청 검토", "Review false-positive reports and appeals";
+    CommandBlacklistAdd => "전역 블랙리스트에 사용자 추가", "Add a user to the global blacklist";
+    CommandBlacklistRemove => "전역 블랙리스트에서 사용자 제거", "Remove a user from the global blacklist";
+    CommandBlacklistList => "전역 블랙리스트 목록", "List the global blacklist";
+    CommandUnban => "봇이 적용한 차단 해제", "Lift bans applied by the bot";
+    CommandDomainBlock => "도메인 차단 (AI 검사 없이 삭제)", "Block a domain (deleted without an AI check)";
+    CommandDomainBlockRemove => "도메인 차단 해제", "Unblock a domain";
+    CommandDomainAllow => "도메인 허용 (링크 내용 가져오지 않음)", "Allow a domain (links are not fetched)";
+    CommandDomainAllowRemove => "도메인 허용 해제", "Stop allowing a domain";
+    CommandDomainList => "차단·허용 도메인 목록", "List the blocked and allowed domains";
+    CommandFeedbackExport => "오탐 피드백 내보내기 (JSONL)", "Export false-positive feedback (JSONL)";
+    CommandPurgeUser => "사용자의 최근 메시지 일괄 삭제", "Delete a user's recent messages";
+    CommandLogs => "최근 로그 보기", "Show the recent logs";
+    CommandBackupNow => "DB 즉시 백업", "Back up the database now";
+    CommandRestart => "봇 즉시 재시작", "Restart the bot now";
+    CommandUpdate => "최신 릴리스로 즉시 업데이트", "Update to the latest release now";
+    CommandVersion => "실행 중인 버전과 업데이트 확인", "Show the running version and check for updates";
+    CommandQueue => "분류 대기 중인 메시지 목록", "List the messages waiting for classification";
+    CommandTestSpam => "텍스트를 조치 없이 분류해 보기", "Classify text without acting on it";
+    CommandInviteAllow => "파트너 초대 링크 허용", "Allow a partner invite link";
+    CommandInviteAllowList => "허용된 초대 링크 목록", "List the allowed invite links";
+    CommandInviteAllowRemove => "초대 링크 허용 해제", "Stop allowing an invite link";
+    CommandReloadPrompt => "AI 시스템 프롬프트 다시 불러오기", "Reload the AI system prompt";
+    CommandReloadConfig => "설정(.env) 다시 불러오기", "Reload the settings (.env)";
+    CommandTwoStageStats => "2단계 분류 불일치 통계", "Two-stage classification disagreement stats";
+    CommandShadowStats => "섀도 모델·프롬프트 비교 결과", "Shadow model and prompt comparison";
+    CommandUsage => "AI 호출·토큰 사용량과 예상 비용", "AI calls, token usage and estimated cost";
+    CommandSyncCommands => "봇 명령어 동기화", "Sync the bot commands";
+    StartGreeting => "안녕하세요! 스팸 감지 봇입니다.\n현재 그룹 상태: {status}",
+        "Hi! I'm a spam detection bot.\nStatus in this group: {status}";
+    GroupActive => "활성화됨", "active";
+    GroupInactive => "비활성화됨", "inactive";
+    BotStatus => "봇 상태\n- 높은 우선순위: {high}\n- 일반 우선순위: {normal}",
+        "Bot status\n- High priority: {high}\n- Normal priority: {normal}";
+    CurrentChatId => "현재 채팅 ID: {chat_id}", "Current chat ID: {chat_id}";
+    PingMeasuring => "Pong 측정 중...", "Measuring pong...";
+    PingResult => "Pong! 응답 속도: {secs}초", "Pong! Response time: {secs}s";
+    AdminOnly => "이 명령어는 관리자만 사용할 수 있습니다.",
+        "Only the bot admin can use this command.";
+
+    // Reports and appeals
+    SpamReplyRequired => "신고할 메시지에 답장으로 /spam 을 보내주세요.",
+        "Reply to the message you want to report with /spam.";
+    SpamReportNotAllowed => "채팅방 관리자나 신뢰 사용자만 /spam 으로 신고할 수 있습니다.",
+        "Only chat admins and trusted users can report with /spam.";
+    AppealPrivateOnly => "이의 신청은 봇과의 개인 대화에서 보내주세요.",
+        "Please send appeals in a private chat with the bot.";
+    AppealNoRecord => "삭제된 메시지 기록이 없습니다.", "There is no deleted message of yours on record.";
+    AppealFailed => "이의 신청 처리 중 오류가 발생했습니다.",
+        "Something went wrong while filing your appeal.";
+    AppealReceived => "이의 신청이 접수되었습니다. 관리자가 검토 후 결과를 알려드립니다.",
+        "Your appeal has been filed. An admin will review it and let you know.";
+    AppealDuplicate => "이 메시지에 대한 이의 신청이 이미 접수되어 있습니다.",
+        "An appeal for this message has already been filed.";
+    AppealAccepted => "이의 신청이 승인되었습니다. 삭제된 메시지는 스팸이 아닌 것으로 기록되었습니다.",
+        "Your appeal was accepted. The deleted message has been recorded as not spam.";
+    AppealRejected => "이의 신청이 검토 후 기각되었습니다.", "Your appeal was reviewed and rejected.";
+    AppealNotice => "오탐 이의 신청 #{id}\n채팅방: {chat}\n사용자: {user}\n삭제된 메시지: {text}\n사유: {note}\n/review 로 처리하세요.",
+        "False-positive appeal #{id}\nChat: {chat}\nUser: {user}\nDeleted message: {text}\nNote: {note}\nHandle it with /review.";
+    RestoredMessage => "관리자가 오탐으로 확인해 복구한 메시지입니다.\n보낸 사람: {sender}\n\n{text}",
+        "An admin restored this message after confirming it was not spam.\nSent by: {sender}\n\n{text}";
+    NoneLabel => "없음", "none";
+
+    // Whitelist requests
+    WhitelistRequestSent => "봇 관리자에게 화이트리스트 등록을 요청했습니다. 승인되면 이 채팅방에 알려드립니다.",
+        "Asked the bot admin to whitelist this group. You'll be told here once it's approved.";
+    WhitelistRequestPending => "이미 등록 요청이 접수되어 검토 중입니다.",
+        "A request for this group is already waiting for review.";
+    WhitelistRequestFailed => "등록 요청을 보내지 못했습니다. 잠시 후 다시 시도하세요.",
+        "The request could not be sent. Please try again later.";
+    WhitelistRequestUnavailable => "이 봇은 화이트리스트 등록 요청을 받지 않습니다.",
+        "This bot does not take whitelist requests.";
+    WhitelistRequestGroupOnly => "그룹에서만 사용할 수 있는 명령어입니다.",
+        "This command only works in groups.";
+    WhitelistRequestAdminOnly => "그룹 관리자만 등록을 요청할 수 있습니다.",
+        "Only group admins can request whitelisting.";
+    WhitelistAlreadyActive => "이미 화이트리스트에 등록된 그룹입니다.",
+        "This group is already whitelisted.";
+    WhitelistJoinRequested => "이 그룹은 아직 화이트리스트에 없어 스팸 감시가 꺼져 있습니다. 봇 관리자에게 등록을 요청했습니다.",
+        "This group is not whitelisted yet, so spam filtering is off. The bot admin has been asked to whitelist it.";
+    WhitelistApproved => "이 그룹이 화이트리스트에 등록되어 스팸 감시를 시작합니다.",
+        "This group has been whitelisted and spam filtering is now on.";
+    WhitelistRejected => "화이트리스트 등록 요청이 거절되었습니다.",
+        "The whitelist request for this group was rejected.";
+
+    // Moderation notices
+    SpamDeletedNotice => "스팸 메시지가 삭제되었습니다.", "A spam message was deleted.";
+    SenderNotice => "{chat}에 보낸 메시지가 스팸으로 판단되어 삭제되었습니다.\n사유: {reason}\n\n잘못 삭제되었다면 이 대화에서 /appeal [설명] 으로 이의 신청할 수 있습니다.",
+        "Your message in {chat} was deleted as spam.\nReason: {reason}\n\nIf this was a mistake, you can appeal here with /appeal [explanation].";
+
+    // Captcha
+    CaptchaWelcome => "{user} 님, 환영합니다! {minutes}분 안에 '{choice}' 버튼을 눌러 주세요. 확인 전까지는 메시지를 보낼 수 없습니다.",
+        "Welcome, {user}! Press the '{choice}' button within {minutes} min. You can't send messages until then.";
+    CaptchaApple => "사과", "apple";
+    CaptchaCar => "자동차", "car";
+    CaptchaDog => "강아지", "dog";
+    CaptchaStar => "별", "star";
+    CaptchaInvalid => "잘못된 요청입니다.", "Invalid request.";
+    CaptchaNotYou => "새로 참여한 본인만 누를 수 있습니다.", "Only the new member can press this.";
+    CaptchaExpired => "만료된 확인 요청입니다.", "This check has expired.";
+    CaptchaWrong => "틀렸습니다. 채팅방에서 내보내졌으며 다시 참여해 시도할 수 있습니다.",
+        "Wrong answer. You were removed from the chat and can rejoin to try again.";
+    CaptchaPassed => "확인되었습니다. 환영합니다!", "Verified. Welcome!";
+
+    // Chat settings panel
+    SettingsLoadFailed => "채팅방 설정을 불러오지 못했습니다.", "Failed to load the chat settings.";
+    SettingsPanel => "채팅방 {chat_id} 설정\n\n- 스팸 조치: {action}\n- 드라이런(삭제 대신 검토 요청): {dry_run}\n- 엄격 모드: {strict}\n- 응답 언어: {language}",
+        "Settings for chat {chat_id}\n\n- Spam action: {action}\n- Dry run (review instead of deleting): {dry_run}\n- Strict mode: {strict}\n- Reply language: {language}";
+    SettingOn => "켜짐", "on";
+    SettingOff => "꺼짐", "off";
+    SettingDefault => "기본값", "Default";
+    SettingDefaultValue => "기본값({value})", "default ({value})";
+    SettingNotWhitelisted => "화이트리스트에 없음", "not whitelisted";
+    SettingActionDelete => "삭제", "Delete";
+    SettingActionRestrict => "삭제 + 제한", "Delete + restrict";
+    SettingActionBan => "삭제 + 차단", "Delete + ban";
+    SettingDryRunOn => "드라이런 켜기", "Turn dry run on";
+    SettingDryRunOff => "드라이런 끄기", "Turn dry run off";
+    SettingStrictOn => "엄격 모드 켜기", "Turn strict mode on";
+    SettingStrictOff => "엄격 모드 끄기", "Turn strict mode off";
+    SettingInvalid => "잘못된 요청입니다.", "Invalid request.";
+    SettingFailed => "설정 중 오류가 발생했습니다.", "Failed to save the setting.";
+    SettingSaved => "설정을 저장했습니다.", "Setting saved.";
+
+    // Admin logs
+    SpamLog => "<b>스팸 삭제 로그</b>\n\n채팅방: {chat}\n채팅방 ID: {chat_id}\n사용자: {user}\n사용자 ID: {user_id}\n{link}{archive}{language}{confidence}메시지 전송 시각: {sent_at}\n삭제 완료 시각: {deleted_at}\n조치: {outcome}\n\n스팸 메시지:\n<pre>{text}</pre>\n삭제 사유:\n<pre>{reason}</pre>",
+        "<b>Spam deleted</b>\n\nChat: {chat}\nChat ID: {chat_id}\nUser: {user}\nUser ID: {user_id}\n{link}{archive}{language}{confidence}Sent at: {sent_at}\nDeleted at: {deleted_at}\nAction: {outcome}\n\nMessage:\n<pre>{text}</pre>\nReason:\n<pre>{reason}</pre>";
+    AdminLogBatch => "<b>스팸 {count}건 추가 삭제</b> (최근 {secs}초)",
+        "<b>{count} more spam messages deleted</b> (last {secs}s)";
+    AdminLogBatchEntry => " {chat} · {user} ({user_id}) · {outcome}\n{text}\n",
+        " {chat} · {user} ({user_id}) · {outcome}\n{text}\n";
+    AdminLogBatchMore => "\n외 {count}건", "\nand {count} more";
+    DetectedLanguage => "감지 언어: {language}\n", "Detected language: {language}\n";
+    MessageLink => "위치: <a href=\"{url}\">채팅방에서 보기</a>\n",
+        "Location: <a href=\"{url}\">view in chat</a>\n";
+    ArchivedCopy => "보관본: <a href=\"{url}\">아카이브에서 보기</a>\n",
+        "Archived copy: <a href=\"{url}\">view in archive</a>\n";
+    AiConfidence => "AI 확신도: {confidence}%\n", "AI confidence: {confidence}%\n";
+    SpamLogRestore => "오탐 복구", "Restore";
+    SpamLogBan => "차단", "Ban";
+    SpamLogIgnore => "무시", "Dismiss";
+    ReviewRequest => "<b>스팸 검토 요청</b> (확신도 {confidence}%)\n\n채팅방: {chat}\n채팅방 ID: {chat_id}\n사용자: {user}\n사용자 ID: {user_id}\n\n메시지:\n<pre>{text}</pre>\n판정 사유:\n<pre>{reason}</pre>",
+        "<b>Spam review requested</b> ({confidence}% confident)\n\nChat: {chat}\nChat ID: {chat_id}\nUser: {user}\nUser ID: {user_id}\n\nMessage:\n<pre>{text}</pre>\nVerdict reason:\n<pre>{reason}</pre>";
+    ReviewDelete => "삭제", "Delete";
+    ReviewKeep => "유지", "Keep";
+    ReviewBan => "유저 밴", "Ban user";
+    ActionDelete => "삭제", "deleted";
+    ActionRestrict => "삭제 + {hours}시간 제한", "deleted + restricted for {hours}h";
+    ActionBan => "삭제 + 차단", "deleted + banned";
+    BanDuration => " ({hours}시간 후 해제)", " (lifted after {hours}h)";
+    ActionFailed => "삭제 ({action} 실패: 권한을 확인하세요)",
+        "deleted ({action} failed: check the bot's permissions)";
+    ActionBanChannel => "삭제 + 채널 차단", "deleted + channel banned";
+    ActionBanChannelFailed => "삭제 (채널 차단 실패: 권한을 확인하세요)",
+        "deleted (channel ban failed: check the bot's permissions)";
+    StrikeCount => " (누적 {count}회)", " (strike {count})";
+
+    // Admin-group notices
+    WhitelistRequest => "<b>화이트리스트 등록 요청</b>\n\n채팅방: {chat}\n채팅방 ID: {chat_id}\n요청자: {user} ({user_id})",
+        "<b>Whitelist request</b>\n\nChat: {chat}\nChat ID: {chat_id}\nRequested by: {user} ({user_id})";
+    WhitelistRequestApprove => "승인", "Approve";
+    WhitelistAutoAdded => "관리자가 봇을 추가한 그룹을 화이트리스트에 자동 등록했습니다.\n\n채팅방: {chat}\n채팅방 ID: {chat_id}",
+        "Whitelisted a group the admin added the bot to.\n\nChat: {chat}\nChat ID: {chat_id}";
+    WhitelistRequestReject => "거절", "Reject";
+    BotStarted => "스팸 감지 봇이 시작되었습니다.", "Spam detection bot started.";
+    BotStopped => "스팸 감지 봇이 종료되었습니다.", "Spam detection bot stopped.";
+    RestartStarting => "자동 재부팅을 시작합니다.\n현재 시각: {time}",
+        "Restarting automatically.\nCurrent time: {time}";
+    RestartNoExecutable => "자동 재부팅 실패: 실행 파일 경로를 찾을 수 없습니다.",
+        "Automatic restart failed: the executable path could not be found.";
+    RestartSpawnFailed => "자동 재부팅 실패: 새 프로세스를 시작할 수 없습니다.",
+        "Automatic restart failed: the new process could not be started.";
+    NetworkRestart => "텔레그램 업데이트 리스너가 최근 {secs}초 동안 {count}회 연속으로 {kind}를 보고했습니다.",
+        "The Telegram update listener reported {kind} {count} times in a row over the last {secs}s.";
+    NetworkTimeout => "요청 타임아웃", "request timeouts";
+    NetworkConnect => "TCP 연결 실패", "TCP connection failures";
+    NetworkOther => "기타 네트워크 오류", "other network errors";
+    NetworkLastUrl => "\n- 마지막 요청 URL: {url}", "\n- Last request URL: {url}";
+    NetworkDetail => "\n- reqwest 상세: {detail}\n- teloxide 오류: {error}",
+        "\n- reqwest detail: {detail}\n- teloxide error: {error}";
+    NetworkRestartNow => "\n네트워크가 복구되지 않아 즉시 봇을 재시작합니다.",
+        "\nThe network has not recovered, so the bot restarts now.";
+    ConfigReloaded => "SIGHUP 신호로 설정을 다시 불러왔습니다.\n- 허용 채팅방: {chats}개\n- AI 신뢰도 기준: {threshold}",
+        "Configuration reloaded on SIGHUP.\n- Allowed chats: {chats}\n- AI confidence threshold: {threshold}";
+    AiRecovered => "AI 분류가 복구되어 대기 중이던 메시지 처리를 재개했습니다.",
+        "AI classification has recovered; queued messages are being processed again.";
+    AiBreakerOpen => "AI 분류가 연속으로 실패하여 {secs}초 동안 일시 중지합니다. 메시지는 대기열에 보관되며 자동으로 재시도합니다.",
+        "AI classification keeps failing and is paused for {secs}s. Messages stay queued and are retried automatically.";
+    RuleFlagged => "규칙 {rule} ({pattern})에 일치하는 메시지가 감지되었습니다.\n채팅방: {chat} ({chat_id})\n사용자: {user}\n내용: {text}",
+        "A message matched rule {rule} ({pattern}).\nChat: {chat} ({chat_id})\nUser: {user}\nText: {text}";
+    CasJoinerBanned => "CAS 차단 목록에 있는 사용자 {user} ({user_id})를 입장 즉시 차단했습니다.\n채팅방: {chat}",
+        "Banned {user} ({user_id}) on joining because they are on the CAS ban list.\nChat: {chat}";
+    FloodAlert => "<b>도배 감지</b>\n\n채팅방: {chat} ({chat_id})\n사용자: {user} ({user_id})\n사유: {reason}\n조치: {minutes}분 채팅 금지\n\n<pre>{text}</pre>",
+        "<b>Flood detected</b>\n\nChat: {chat} ({chat_id})\nUser: {user} ({user_id})\nReason: {reason}\nAction: muted for {minutes} min\n\n<pre>{text}</pre>";
+    RaidAlert => "<b>조직적 스팸 공격 감지</b>\n\n채팅방: {chat} ({chat_id})\n계정 {accounts}개가 유사한 메시지 {messages}개를 게시했습니다.\n조치: {actions}\n\n계정:",
+        "<b>Coordinated spam raid detected</b>\n\nChat: {chat} ({chat_id})\n{accounts} accounts posted {messages} similar messages.\nActions: {actions}\n\nAccounts:";
+    RaidExample => "\n\n메시지 예시:\n<pre>{text}</pre>", "\n\nExample message:\n<pre>{text}</pre>";
+    RaidDeleted => "메시지 {count}개 삭제", "{count} messages deleted";
+    RaidBanned => "계정 {count}개 밴", "{count} accounts banned";
+    RaidLockdown => "채팅방 {minutes}분 잠금", "chat locked for {minutes} min";
+    UsageDaily => "AI 사용량 일일 요약", "Daily AI usage summary";
+    UsageLastDay => "최근 24시간", "last 24 hours";
+    UsageTotals => "\n[{period}] 호출 {calls}회, 입력 {prompt} / 출력 {completion} 토큰, 예상 비용 ${cost}",
+        "\n[{period}] {calls} calls, {prompt} input / {completion} output tokens, estimated cost ${cost}";
+    UsageModel => "\n- {model}: {calls}회, {prompt} / {completion} 토큰, ${cost}",
+        "\n- {model}: {calls} calls, {prompt} / {completion} tokens, ${cost}";
+    DigestTitle => "<b>스팸 요약 (최근 {days}일)</b>\n삭제된 스팸: {total}건",
+        "<b>Spam digest (last {days} days)</b>\nSpam deleted: {total}";
+    DigestPeriod => "최근 {days}일", "last {days} days";
+    DigestChats => "\n\n채팅방별 삭제:", "\n\nDeleted per chat:";
+    DigestOtherChats => "\n- 그 외 채팅방: {count}건", "\n- Other chats: {count}";
+    DigestUsers => "\n\n상위 발송자:", "\n\nTop senders:";
+    DigestDomains => "\n\n상위 도메인:", "\n\nTop domains:";
+    DigestRow => "\n- {name}: {count}건", "\n- {name}: {count}";
+    DigestQueue => "\n\n대기열: 우선 {high}건, 일반 {normal}건",
+        "\n\nQueue: {high} high priority, {normal} normal";
+    DigestAiPaused => "\nAI 분류가 일시 중지된 상태입니다.", "\nAI classification is currently paused.";
+    StatusBoard => "<b>봇 상태</b>\n\n- 대기열: 우선 {high}건, 일반 {normal}건\n- AI API: {api}{process}\n\n<i>{updated} 갱신</i>",
+        "<b>Bot status</b>\n\n- Queue: {high} high priority, {normal} normal\n- AI API: {api}{process}\n\n<i>Updated {updated}</i>";
+    StatusUptimeLine => "\n- 가동 시간: {uptime}", "\n- Uptime: {uptime}";
+    ProcessStatus => "\n- 메모리(RSS): {rss}MB\n- CPU: {cpu}%", "\n- Memory (RSS): {rss} MB\n- CPU: {cpu}%";
+    StatusClassification => "\n- 처리량: 분당 {rate}건\n- 마지막 AI 분류 성공: {last}",
+        "\n- Throughput: {rate} messages/min\n- Last successful AI classification: {last}";
+    StatusUptime => "{days}일 {hours}시간 {minutes}분", "{days}d {hours}h {minutes}m";
+    StatusNeverClassified => "시작 후 없음", "none since startup";
+    StatusApiHealthy => "정상", "healthy";
+    StatusApiPaused => "연속 실패로 일시 중지됨", "paused after repeated failures";
+    WhitelistAuditFound => "화이트리스트 점검 결과 문제가 발견되었습니다.\n",
+        "The whitelist check found problems.\n";
+    WhitelistExpired => "기간이 만료된 그룹을 화이트리스트에서 제거했습니다.\n",
+        "Whitelist entries past their expiry were removed.\n";
+    WhitelistExpiredRow => "\n- {chat} (ID: {chat_id})", "\n- {chat} (ID: {chat_id})";
+    BansLifted => "기간이 끝난 차단을 해제했습니다.\n", "Bans past their duration were lifted.\n";
+    BansLiftedRow => "\n- 사용자 {user_id} (채팅 {chat_id})", "\n- user {user_id} (chat {chat_id})";
+    BansLiftFailedRow => "\n- 사용자 {user_id} (채팅 {chat_id}): 해제 실패",
+        "\n- user {user_id} (chat {chat_id}): unban failed";
+    WhitelistActive => "정상", "OK";
+    WhitelistRemoved => "봇이 그룹에서 제거됨", "bot was removed from the group";
+    WhitelistNoDeleteRights => "메시지 삭제 권한 없음", "no permission to delete messages";
+    Untitled => "(제목 없음)", "(untitled)";
+    RetentionPurged => "보관 기간이 지난 기록을 정리했습니다.\n",
+        "Records past their retention period were removed.\n";
+    RetentionRow => "\n- {table}: {count}건", "\n- {table}: {count} rows";
+    UpdateInstalled => "자동 업데이트 완료\n- 이전 버전: v{old}\n- 신규 버전: v{new}",
+        "Automatic update installed\n- Previous version: v{old}\n- New version: v{new}";
+    UpdateRestarting => "\n새 바이너리로 곧 재시작합니다.", "\nRestarting with the new binary shortly.";
+    UpdateRestartManually => "\n프로세스를 재시작하면 변경 내용이 적용됩니다.",
+        "\nRestart the process to apply the update.";
+
+    // Admin commands
+    UsageHint => "사용법: {usage}", "Usage: {usage}";
+    InvalidGroupId => "올바른 그룹 ID를 입력하세요. 예: {command} -1001234567890",
+        "Enter a valid group ID, e.g. {command} -1001234567890";
+    InvalidTtl => "올바르지 않은 기간입니다: {value}\n예: /whitelist_add -1001234567890 30d (m/h/d/w 단위)",
+        "Invalid duration: {value}\nExample: /whitelist_add -1001234567890 30d (units m/h/d/w)";
+    WhitelistAddUsage => "그룹 ID가 필요합니다. 예: /whitelist_add -1001234567890 [30d]",
+        "A group ID is required, e.g. /whitelist_add -1001234567890 [30d]";
+    WhitelistRemoveUsage => "그룹 ID가 필요합니다. 예: /whitelist_remove -1001234567890",
+        "A group ID is required, e.g. /whitelist_remove -1001234567890";
+    CronSpecRequired => "크론 표현식이 필요합니다. 예: /restart_cron_add 0 0 3 * * *",
+        "A cron expression is required, e.g. /restart_cron_add 0 0 3 * * *";
+    CronIndexRequired => "삭제할 번호가 필요합니다. 예: /restart_cron_remove 1",
+        "The number to remove is required, e.g. /restart_cron_remove 1";
+    RuleAddUsage => "사용법: /rule_add <chat_id> <regex|keyword> <delete|flag|boost|ignore> <패턴>",
+        "Usage: /rule_add <chat_id> <regex|keyword> <delete|flag|boost|ignore> <pattern>";
+    RuleRemoved => "규칙 #{rule_id}이 삭제되었습니다.", "Rule #{rule_id} was removed.";
+    RuleNotFound => "해당 ID의 규칙이 없습니다.", "There is no rule with that ID.";
+    RuleRemoveFailed => "규칙 삭제 중 오류가 발생했습니다.", "Failed to remove the rule.";
+    RuleIdRequired => "삭제할 규칙 ID가 필요합니다. 예: /rule_remove 3",
+        "The rule ID to remove is required, e.g. /rule_remove 3";
+    WhitelistNoteUsage => "사용법: /whitelist_note <chat_id> <메모 | clear>",
+        "Usage: /whitelist_note <chat_id> <note | clear>";
+    CriteriaUsage => "사용법: /criteria <chat_id> [기준 내용 | clear]",
+        "Usage: /criteria <chat_id> [criteria | clear]";
+    ActionUsage => "사용법: /action <chat_id> [delete | restrict [시간] | ban | clear]",
+        "Usage: /action <chat_id> [delete | restrict [hours] | ban | clear]";
+    ProbationUsage => "사용법: /probation <chat_id> [<메시지 수> <시간> | off | clear]",
+        "Usage: /probation <chat_id> [<messages> <hours> | off | clear]";
+    NoticeUsage => "사용법: /notice <chat_id> [초 | off | clear]",
+        "Usage: /notice <chat_id> [seconds | off | clear]";
+    InviteAllowUsage => "사용법: {command} <chat_id> <@채널 또는 t.me 링크>",
+        "Usage: {command} <chat_id> <@channel or t.me link>";
+    BlacklistAddUsage => "사용법: /blacklist_add <user_id> [사유]",
+        "Usage: /blacklist_add <user_id> [reason]";
+    RestartRequestedWhenIdle => "재시작을 요청했습니다. 처리 중인 메시지가 끝나면 재시작합니다.",
+        "Restart requested. The bot restarts once the messages in progress are done.";
+    RestartRequested => "재시작을 요청했습니다.", "Restart requested.";
+    TestSpamUsage => "사용법: /test_spam <텍스트> 또는 메시지에 답장하며 /test_spam",
+        "Usage: /test_spam <text>, or reply to a message with /test_spam";
+    DomainUsage => "사용법: {command} <도메인>", "Usage: {command} <domain>";
+    CommandsSynced => "봇 명령어 동기화를 완료했습니다.", "Bot commands synced.";
+    ReloadConfigReply => "설정을 다시 불러왔습니다.\n- 허용 채팅방(ALLOWED_CHAT_IDS): {allowed}개\n- AI 신뢰도 기준: {confidence}\n- 메시지당 최대 URL: {max_urls}개\n- 위험 점수 기준: 우선 처리 {high_priority} / 엄격 모드 {strict}\n- 사전 필터: {prefilter}\n\n그 밖의 설정은 재시작해야 적용됩니다.",
+        "Settings reloaded.\n- Allowed chats (ALLOWED_CHAT_IDS): {allowed}\n- AI confidence threshold: {confidence}\n- Max URLs per message: {max_urls}\n- Risk score thresholds: priority {high_priority} / strict mode {strict}\n- Prefilter: {prefilter}\n\nOther settings take effect after a restart.";
+    PromptReloaded => "시스템 프롬프트를 다시 불러왔습니다: {paths}", "System prompt reloaded: {paths}";
+    PromptBuiltin => "SYSTEM_PROMPT_FILE이 설정되지 않아 내장 프롬프트를 사용합니다.",
+        "SYSTEM_PROMPT_FILE is not set, so the built-in prompt is used.";
+    PromptReloadFailed => "프롬프트를 불러오지 못해 기존 프롬프트를 유지합니다: {error}",
+        "Could not load the prompt; keeping the current one: {error}";
+    TwoStageStats => "2단계 분류 통계 (시작 이후)\n- 1차 모델 스팸 판정: {flagged}건\n- 확인 모델 동의: {confirmed}건\n- 확인 모델 반려: {overturned}건\n- 불일치율: {rate}%",
+        "Two-stage classification (since start)\n- Flagged by the first model: {flagged}\n- Confirmed: {confirmed}\n- Overturned: {overturned}\n- Disagreement rate: {rate}%";
+    TwoStageDisabled => "AI_CONFIRM_MODEL이 설정되지 않아 2단계 분류를 사용하지 않습니다.",
+        "AI_CONFIRM_MODEL is not set, so two-stage classification is off.";
+    ShadowDisabled => "SHADOW_MODEL 또는 SHADOW_PROMPT_FILE이 설정되지 않아 섀도 분류를 사용하지 않습니다.",
+        "Neither SHADOW_MODEL nor SHADOW_PROMPT_FILE is set, so shadow classification is off.";
+    ShadowStats => "섀도 분류 비교 (최근 7일, 모델 {model}, 프롬프트 {prompt})\n- 비교한 메시지: {total}건\n- 일치: {agreed}건 ({agreed_rate}%)\n- 섀도만 스팸: {shadow_only}건 ({shadow_rate}%)\n- 운영 모델만 스팸: {live_only}건 ({live_rate}%)",
+        "Shadow comparison (last 7 days, model {model}, prompt {prompt})\n- Messages compared: {total}\n- Agreed: {agreed} ({agreed_rate}%)\n- Spam for the shadow only: {shadow_only} ({shadow_rate}%)\n- Spam for the live model only: {live_only} ({live_rate}%)";
+    ShadowLivePrompt => "운영 프롬프트", "live prompt";
+    ShadowRecentDisagreements => "\n\n최근 불일치:", "\n\nRecent disagreements:";
+    ShadowOnlySpam => "섀도만 스팸 ({reason})", "shadow only ({reason})";
+    NoReason => "사유 없음", "no reason";
+    LiveOnlySpam => "운영 모델만 스팸", "live model only";
+    ShadowLoadFailed => "섀도 분류 결과를 불러오지 못했습니다.", "Failed to load the shadow results.";
+    UsageHeader => "AI 사용량", "AI usage";
+    UsageLoadFailed => "사용량을 불러오지 못했습니다.", "Failed to load the usage.";
+    WhitelistAdded => "그룹 (ID: {chat_id})이 화이트리스트에 추가되었습니다.",
+        "Group (ID: {chat_id}) was added to the whitelist.";
+    WhitelistExpires => "\n만료: {at}", "\nExpires: {at}";
+    WhitelistExists => "이미 등록된 그룹입니다.", "That group is already whitelisted.";
+    WhitelistAddFailed => "화이트리스트 추가 중 오류가 발생했습니다.", "Failed to add the group to the whitelist.";
+    GroupNotFound => "해당 그룹을 찾을 수 없습니다. 봇이 그룹에 추가되어 있는지 확인하세요.",
+        "Could not find that group. Check that the bot is a member.";
+    AdminActionOnly => "관리자만 실행할 수 있습니다.", "Only the bot admin can do this.";
+    InvalidRequest => "잘못된 요청입니다.", "Invalid request.";
+    InvalidChatId => "chat_id 형식이 올바르지 않습니다.", "Invalid chat_id.";
+    InvalidUserId => "user_id 형식이 올바르지 않습니다.", "Invalid user_id.";
+    BanDone => "밴 완료", "Banned.";
+    BanFailed => "밴 실패: 권한 또는 네트워크 오류", "Ban failed: missing rights or a network error.";
+    ReviewEmpty => "검토 대기 중인 오탐 신고가 없습니다.", "No false-positive cases are waiting for review.";
+    ReviewListHeader => "검토 대기 중인 오탐 신고 (/review <id> <accept|reject>):",
+        "False-positive cases waiting for review (/review <id> <accept|reject>):";
+    ReviewSourceAppeal => "사용자 이의 신청", "user appeal";
+    ReviewSourceRestore => "관리자 복구", "admin restore";
+    ReviewListEntry => "\n\n#{id} [{source}] 채팅방 {chat_id} / {user}\n{text}",
+        "\n\n#{id} [{source}] chat {chat_id} / {user}\n{text}";
+    ReviewListNote => "\n사유: {note}", "\nNote: {note}";
+    ReviewLoadFailed => "오탐 신고 목록을 불러오지 못했습니다.", "Failed to load the false-positive cases.";
+    ReviewNotFound => "대기 중인 오탐 신고 #{id}가 없습니다.", "There is no pending case #{id}.";
+    ReviewDecideFailed => "오탐 신고 처리 중 오류가 발생했습니다.", "Failed to close the case.";
+    ReviewAccepted => "오탐 신고 #{id}를 승인했습니다.", "Accepted case #{id}.";
+    ReviewRejected => "오탐 신고 #{id}를 기각했습니다.", "Rejected case #{id}.";
+    SpamLogNotFound => "스팸 로그를 찾을 수 없습니다.", "Spam log entry not found.";
+    RestoreFailed => "복구 실패: 권한 또는 네트워크 오류", "Restore failed: missing rights or a network error.";
+    Restored => "메시지를 복구했습니다. /review 에서 오탐 여부를 확정하세요.",
+        "Message restored. Confirm the false positive with /review.";
+    RestoredNotQueued => "메시지를 복구했지만 검토 대기열 등록에 실패했습니다.",
+        "Message restored, but it could not be queued for review.";
+    SenderUnknown => "발송자 정보가 없습니다.", "The sender is unknown.";
+    RecordedFalsePositive => "오탐으로 기록했습니다.", "Recorded as a false positive.";
+    RequestGroupNotFound => "해당 그룹을 찾을 수 없습니다. 봇이 그룹에 남아 있는지 확인하세요.",
+        "Could not find that group. Check that the bot is still a member.";
+    RequestApproved => "화이트리스트에 추가했습니다.", "Added to the whitelist.";
+    RequestRejected => "요청을 거절했습니다.", "Request rejected.";
+    ReviewDeleted => "삭제 완료", "Deleted.";
+    ReviewDeleteFailed => "삭제 실패: 이미 삭제되었거나 권한이 없습니다.",
+        "Delete failed: already deleted or missing rights.";
+    ReviewKept => "메시지를 유지합니다.", "Message kept.";
+    WhitelistEntryRemoved => "그룹 (ID: {chat_id})이 화이트리스트에서 제거되었습니다.",
+        "Group (ID: {chat_id}) was removed from the whitelist.";
+    WhitelistMissing => "화이트리스트에 등록되지 않은 그룹입니다.", "That group is not whitelisted.";
+    WhitelistRemoveFailed => "화이트리스트 제거 중 오류가 발생했습니다.",
+        "Failed to remove the group from the whitelist.";
+    WhitelistEmpty => "화이트리스트가 비어있습니다.", "The whitelist is empty.";
+    WhitelistListHeader => "화이트리스트 목록:\n\n", "Whitelist:\n\n";
+    WhitelistListEntry => "{index}. ID: {chat_id}\n   저장된 이름: {title}\n   등록일: {added}\n",
+        "{index}. ID: {chat_id}\n   Saved name: {title}\n   Added: {added}\n";
+    UntitledChat => "(제목 없음)", "(untitled)";
+    WhitelistListStatus => "   상태: {status}\n", "   Status: {status}\n";
+    WhitelistListStrict => "   엄격 모드: 켜짐\n", "   Strict mode: on\n";
+    WhitelistListNote => "   메모: {note}\n", "   Note: {note}\n";
+    WhitelistListExpires => "   만료: {at}\n", "   Expires: {at}\n";
+    WhitelistListFailed => "화이트리스트 조회 중 오류가 발생했습니다.", "Failed to load the whitelist.";
+    WhitelistExported => "화이트리스트 {count}개", "{count} whitelist entries";
+    WhitelistExportFailed => "화이트리스트 내보내기 중 오류가 발생했습니다.", "Failed to export the whitelist.";
+    FeedbackEmpty => "기록된 오탐 피드백이 없습니다.", "No false-positive feedback has been recorded.";
+    FeedbackExported => "오탐 피드백 {count}건", "{count} feedback records";
+    FeedbackExportFailed => "오탐 피드백 내보내기 중 오류가 발생했습니다.", "Failed to export the feedback.";
+    WhitelistImportUsage => "/whitelist_export 로 받은 JSON 또는 CSV 파일에 캡션이나 답장으로 /whitelist_import 를 보내주세요.",
+        "Send /whitelist_import as the caption of, or a reply to, a JSON or CSV file from /whitelist_export.";
+    FileReadFailed => "파일을 읽지 못했습니다: {error}", "Could not read the file: {error}";
+    WhitelistImported => "화이트리스트 {count}개를 복원했습니다.", "Restored {count} whitelist entries.";
+    WhitelistImportExpired => "\n만료되어 건너뜀: {count}개", "\nSkipped as expired: {count}";
+    WhitelistImportFailed => "\n실패: {count}개", "\nFailed: {count}";
+    NoteCleared => "채팅방 {chat_id}의 메모를 삭제했습니다.", "Removed the note of chat {chat_id}.";
+    NoteSaved => "채팅방 {chat_id}의 메모를 저장했습니다.", "Saved the note of chat {chat_id}.";
+    ChatNotWhitelisted => "화이트리스트에 없는 채팅방입니다.", "That chat is not whitelisted.";
+    NoteFailed => "메모 저장 중 오류가 발생했습니다.", "Failed to save the note.";
+    CriteriaShow => "채팅방 {chat_id}의 추가 스팸 기준:\n{criteria}",
+        "Extra spam criteria of chat {chat_id}:\n{criteria}";
+    CriteriaNone => "채팅방 {chat_id}에 설정된 추가 스팸 기준이 없습니다.",
+        "Chat {chat_id} has no extra spam criteria.";
+    CriteriaLoadFailed => "추가 스팸 기준을 불러오지 못했습니다.", "Failed to load the extra spam criteria.";
+    CriteriaCleared => "채팅방 {chat_id}의 추가 스팸 기준을 삭제했습니다.",
+        "Removed the extra spam criteria of chat {chat_id}.";
+    CriteriaSaved => "채팅방 {chat_id}의 추가 스팸 기준을 저장했습니다.",
+        "Saved the extra spam criteria of chat {chat_id}.";
+    CriteriaFailed => "추가 스팸 기준 저장 중 오류가 발생했습니다.", "Failed to save the extra spam criteria.";
+    ChatActionShow => "채팅방 {chat_id}의 스팸 조치: {action}", "Spam action of chat {chat_id}: {action}";
+    ChatActionDefault => "채팅방 {chat_id}은 기본 스팸 조치를 사용합니다: {action}",
+        "Chat {chat_id} uses the default spam action: {action}";
+    ChatActionLoadFailed => "스팸 조치 설정을 불러오지 못했습니다.", "Failed to load the spam action.";
+    ChatActionCleared => "채팅방 {chat_id}의 스팸 조치를 기본값({action})으로 되돌렸습니다.",
+        "Reset the spam action of chat {chat_id} to the default ({action}).";
+    ChatActionSet => "채팅방 {chat_id}의 스팸 조치를 '{action}'(으)로 설정했습니다.",
+        "Set the spam action of chat {chat_id} to '{action}'.";
+    ChatActionFailed => "스팸 조치 설정 중 오류가 발생했습니다.", "Failed to save the spam action.";
+    ChatActionInvalid => "조치는 delete, restrict [시간], ban, clear 중 하나여야 합니다.",
+        "The action must be one of delete, restrict [hours], ban or clear.";
+    ProbationDisabled => "PROBATION_ENABLED=false 로 신규 멤버 관찰이 꺼져 있습니다.",
+        "New-member probation is off (PROBATION_ENABLED=false).";
+    Disabled => "사용 안 함", "off";
+    ProbationWindowDesc => "참여 후 메시지 {messages}개 또는 {hours}시간",
+        "{messages} messages or {hours} hours after joining";
+    ProbationShow => "채팅방 {chat_id}의 신규 멤버 관찰 기간: {window}",
+        "Probation of new members in chat {chat_id}: {window}";
+    ProbationDefault => "채팅방 {chat_id}은 기본 관찰 기간을 사용합니다: {window}",
+        "Chat {chat_id} uses the default probation: {window}";
+    ProbationLoadFailed => "관찰 기간 설정을 불러오지 못했습니다.", "Failed to load the probation setting.";
+    ProbationSet => "채팅방 {chat_id}의 신규 멤버 관찰 기간을 '{window}'(으)로 설정했습니다.",
+        "Set the probation of new members in chat {chat_id} to '{window}'.";
+    ProbationFailed => "관찰 기간 설정 중 오류가 발생했습니다.", "Failed to save the probation setting.";
+    LanguageShow => "채팅방 {chat_id}의 응답 언어: {code}", "Reply language of chat {chat_id}: {code}";
+    LanguageDefault => "채팅방 {chat_id}은 기본 응답 언어를 사용합니다: {code}",
+        "Chat {chat_id} uses the default reply language: {code}";
+    LanguageLoadFailed => "응답 언어 설정을 불러오지 못했습니다.", "Failed to load the reply language.";
+    LanguageSet => "채팅방 {chat_id}의 응답 언어를 '{code}'(으)로 설정했습니다.",
+        "Set the reply language of chat {chat_id} to '{code}'.";
+    LanguageCleared => "채팅방 {chat_id}의 응답 언어를 기본값({code})으로 되돌렸습니다.",
+        "Reset the reply language of chat {chat_id} to the default ({code}).";
+    LanguageFailed => "응답 언어 설정 중 오류가 발생했습니다.", "Failed to save the reply language.";
+    NoticeAfter => "{secs}초 후 자동 삭제", "deleted after {secs}s";
+    NoticeShow => "채팅방 {chat_id}의 삭제 알림: {notice}", "Deletion notice of chat {chat_id}: {notice}";
+    NoticeDefault => "채팅방 {chat_id}은 기본 삭제 알림 설정을 사용합니다: {notice}",
+        "Chat {chat_id} uses the default deletion notice: {notice}";
+    NoticeLoadFailed => "삭제 알림 설정을 불러오지 못했습니다.", "Failed to load the deletion notice setting.";
+    NoticeSet => "채팅방 {chat_id}의 삭제 알림을 '{notice}'(으)로 설정했습니다.",
+        "Set the deletion notice of chat {chat_id} to '{notice}'.";
+    NoticeFailed => "삭제 알림 설정 중 오류가 발생했습니다.", "Failed to save the deletion notice setting.";
+    StrictModeOn => "채팅방 {chat_id}의 엄격 모드가 켜졌습니다.", "Strict mode is now on for chat {chat_id}.";
+    StrictModeOff => "채팅방 {chat_id}의 엄격 모드가 꺼졌습니다.", "Strict mode is now off for chat {chat_id}.";
+    StrictModeFailed => "엄격 모드 설정 중 오류가 발생했습니다.", "Failed to update strict mode.";
+    InviteAllowed => "채팅방 {chat_id}에서 {target} 초대 링크를 허용합니다.",
+        "Invite links to {target} are now allowed in chat {chat_id}.";
+    InviteAlreadyAllowed => "이미 허용된 초대 링크입니다.", "That invite link is already allowed.";
+    InviteAllowFailed => "초대 링크 허용 중 오류가 발생했습니다.", "Failed to allow the invite link.";
+    InviteDisallowed => "채팅방 {chat_id}의 {target} 초대 링크 허용을 해제했습니다.",
+        "Invite links to {target} are no longer allowed in chat {chat_id}.";
+    InviteNotAllowed => "허용 목록에 없는 초대 링크입니다.", "That invite link is not on the allowlist.";
+    InviteDisallowFailed => "초대 링크 허용 해제 중 오류가 발생했습니다.",
+        "Failed to remove the invite link from the allowlist.";
+    InviteListEmpty => "허용된 초대 링크가 없습니다.", "No invite links are allowed.";
+    InviteListHeader => "허용된 초대 링크 목록:\n", "Allowed invite links:\n";
+    InviteListFailed => "초대 링크 목록 조회 중 오류가 발생했습니다.", "Failed to load the allowed invite links.";
+    DomainBlocked => "{domain} 도메인을 차단합니다. 이 도메인(하위 도메인 포함) 링크가 있는 메시지는 AI 검사 없이 삭제됩니다.",
+        "Blocked {domain}. Messages linking it or its subdomains are deleted without an AI check.";
+    DomainAlreadyBlocked => "이미 차단된 도메인입니다.", "That domain is already blocked.";
+    DomainBlockFailed => "도메인 차단 중 오류가 발생했습니다.", "Failed to block the domain.";
+    DomainAllowed => "{domain} 도메인을 허용합니다. 이 도메인(하위 도메인 포함) 링크는 내용을 가져오지 않고 차단 목록에도 추가되지 않습니다.",
+        "Allowed {domain}. Links to it or its subdomains are never fetched or added to the blocklist.";
+    DomainAlreadyAllowed => "이미 허용된 도메인입니다.", "That domain is already allowed.";
+    DomainAllowFailed => "도메인 허용 중 오류가 발생했습니다.", "Failed to allow the domain.";
+    DomainUnblocked => "{domain} 도메인을 차단 목록에서 제거했습니다.", "Removed {domain} from the blocklist.";
+    DomainUnallowed => "{domain} 도메인을 허용 목록에서 제거했습니다.", "Removed {domain} from the allowlist.";
+    DomainNotBlocked => "차단 목록에 없는 도메인입니다.", "That domain is not on the blocklist.";
+    DomainNotAllowed => "허용 목록에 없는 도메인입니다.", "That domain is not on the allowlist.";
+    DomainRemoveFailed => "도메인 제거 중 오류가 발생했습니다.", "Failed to remove the domain.";
+    DomainListBlocked => "차단된 도메인 (최근 {limit}개):", "Blocked domains (latest {limit}):";
+    DomainListAllowed => "\n\n허용된 도메인:", "\n\nAllowed domains:";
+    DomainListNone => "\n없음", "\nnone";
+    DomainListFailed => "도메인 목록 조회 중 오류가 발생했습니다.", "Failed to load the domain lists.";
+    BlacklistAdminReason => "관리자 등록", "added by an admin";
+    BlacklistAdded => "사용자 {user_id}를 블랙리스트에 추가했습니다.", "Added user {user_id} to the blacklist.";
+    BlacklistPreBanned => "\n채팅방 {count}곳에서 미리 차단했습니다.", "\nBanned ahead of time in {count} chats.";
+    BlacklistExists => "이미 블랙리스트에 있는 사용자입니다.", "That user is already blacklisted.";
+    BlacklistAddFailed => "블랙리스트 추가 중 오류가 발생했습니다.", "Failed to blacklist the user.";
+    BlacklistRemoved => "사용자 {user_id}를 블랙리스트에서 제거했습니다. 이미 적용된 차단은 /unban {user_id} 로 해제하세요.",
+        "Removed user {user_id} from the blacklist. Lift bans already applied with /unban {user_id}.";
+    BlacklistMissing => "블랙리스트에 없는 사용자입니다.", "That user is not blacklisted.";
+    BlacklistRemoveFailed => "블랙리스트 제거 중 오류가 발생했습니다.",
+        "Failed to remove the user from the blacklist.";
+    BansLoadFailed => "차단 기록을 불러오는 중 오류가 발생했습니다.", "Failed to load the ban records.";
+    UnbanDetail => "{reason}, {at} 차단", "{reason}, banned {at}";
+    UnbanDetailExpires => ", {at} 해제 예정", ", expires {at}";
+    UnbanNoRecords => "기록된 차단이 없습니다. 차단된 채팅방을 지정하려면 /unban <user_id> <chat_id>",
+        "No bans are recorded. To name the chat the user is banned in: /unban <user_id> <chat_id>";
+    UnbanHeader => "사용자 {user_id}의 차단 해제 결과:", "Unban results for user {user_id}:";
+    UnbanLifted => "해제", "lifted";
+    UnbanFailed => "실패 (권한을 확인하세요)", "failed (check the bot rights)";
+    VersionInfo => "버전: {version}\n빌드 대상: {target}\n커밋: {commit}",
+        "Version: {version}\nBuild target: {target}\nCommit: {commit}";
+    VersionUpdateAvailable => "\n최신 릴리스: {latest} (업데이트 가능)",
+        "\nLatest release: {latest} (update available)";
+    VersionUpToDate => "\n최신 릴리스: {latest} (최신 버전 사용 중)", "\nLatest release: {latest} (up to date)";
+    VersionCheckFailed => "\n최신 릴리스 확인에 실패했습니다.", "\nFailed to check the latest release.";
+    BackupSaved => "DB 백업을 저장했습니다.\n- 파일: {path}\n- 크기: {size}MB",
+        "Database backup saved.\n- File: {path}\n- Size: {size}MB";
+    BackupFailed => "DB 백업 중 오류가 발생했습니다: {error}", "Database backup failed: {error}";
+    LogFileMissing => "로그 파일을 찾을 수 없습니다.", "No log file was found.";
+    LogFileEmpty => "로그 파일이 비어있습니다.", "The log file is empty.";
+    LogFileReadFailed => "로그 파일을 읽는 중 오류가 발생했습니다.", "Failed to read the log file.";
+    LogTail => "최근 로그 {lines}줄", "Last {lines} log lines";
+    AlreadyLatest => "이미 최신 버전({current})을 사용 중입니다.", "Already on the latest version ({current}).";
+    ReleaseCheckFailed => "최신 릴리스 확인에 실패했습니다.", "Failed to check the latest release.";
+    UpdateDownloading => "새 버전 {latest}을 내려받아 설치하는 중입니다…", "Downloading and installing {latest}…";
+    UpdateNothing => "이미 최신 버전을 사용 중입니다.", "Already on the latest version.";
+    UpdateDoneRestarting => "{old} → {new} 설치를 마쳤습니다. 새 버전으로 재시작합니다.",
+        "Installed {old} → {new}. Restarting into the new version.";
+    UpdateDoneRestartManually => "{old} → {new} 설치를 마쳤습니다. 적용하려면 프로세스를 재시작하세요.",
+        "Installed {old} → {new}. Restart the process to apply it.";
+    UpdateFailed => "업데이트에 실패했습니다: {error}", "Update failed: {error}";
+    QueueEmpty => "대기열이 비어있습니다.", "The queue is empty.";
+    QueueHeader => "대기열: 우선 {high}건, 일반 {normal}건", "Queue: {high} priority, {normal} normal";
+    QueuePriority => "우선", "priority";
+    QueueNormal => "일반", "normal";
+    QueueEntry => "\n\n[{label}] {chat} ({chat_id})\n사용자: {user} ({user_id})\n위험 점수: {score} / 대기 {secs}초\n{text}",
+        "\n\n[{label}] {chat} ({chat_id})\nUser: {user} ({user_id})\nRisk score: {score} / waiting {secs}s\n{text}";
+    QueueMore => "\n\n외 {count}건", "\n\nand {count} more";
+    TestSpamResult => "분류 결과: {verdict}\n확신도: {confidence}\n사유: {reason}\n\n테스트 분류이므로 아무 조치도 하지 않았습니다.",
+        "Verdict: {verdict}\nConfidence: {confidence}\nReason: {reason}\n\nThis was a test, so nothing was done.";
+    VerdictSpam => "스팸", "spam";
+    VerdictHam => "정상", "not spam";
+    TestSpamNoVerdict => "모델이 이 메시지에 대한 판정을 반환하지 않았습니다.",
+        "The model returned no verdict for this message.";
+    TestSpamFailed => "분류 요청에 실패했습니다: {error}", "The classification request failed: {error}";
+    PurgeLoadFailed => "최근 메시지 기록 조회 중 오류가 발생했습니다.", "Failed to load the recent messages.";
+    PurgeDone => "사용자 {user_id}의 최근 메시지 {deleted}개를 채팅방 {chats}곳에서 삭제했습니다.",
+        "Deleted {deleted} recent messages of user {user_id} in {chats} chats.";
+    PurgeFailedCount => "\n{count}개는 삭제하지 못했습니다 (이미 삭제됐거나 권한 없음).",
+        "\n{count} could not be deleted (already gone or missing rights).";
+    PurgeBanned => "\n채팅방 {count}곳에서 밴했습니다.", "\nBanned in {count} chats.";
+    BlacklistEmpty => "블랙리스트가 비어있습니다.", "The blacklist is empty.";
+    BlacklistListHeader => "블랙리스트 (최근 {limit}명까지):\n", "Blacklist (latest {limit}):\n";
+    BlacklistListFailed => "블랙리스트 조회 중 오류가 발생했습니다.", "Failed to load the blacklist.";
+    RuleAdded => "규칙 #{rule_id}이 추가되었습니다.\n채팅 ID: {chat_id}\n종류: {kind}\n동작: {action}\n패턴: {pattern}",
+        "Rule #{rule_id} added.\nChat ID: {chat_id}\nKind: {kind}\nAction: {action}\nPattern: {pattern}";
+    RuleAddFailed => "규칙을 추가할 수 없습니다: {error}", "Could not add the rule: {error}";
+    RuleListEmpty => "등록된 규칙이 없습니다.", "No rules are set.";
+    RuleListHeader => "규칙 목록:\n", "Rules:\n";
+    RuleListFailed => "규칙 조회 중 오류가 발생했습니다.", "Failed to load the rules.";
+    CronListEmpty => "등록된 재시작 스케줄이 없습니다.", "No restart schedules are set.";
+    CronListHeader => "재시작 스케줄 목록:\n", "Restart schedules:\n";
+    CronAdded => "재시작 스케줄 {index}번이 추가되었습니다: {spec}", "Added restart schedule {index}: {spec}";
+    CronAddFailed => "크론 표현식이 올바르지 않거나 저장에 실패했습니다. 형식: 초 분 시 일 월 요일",
+        "The cron expression is invalid or could not be saved. Format: sec min hour day month weekday";
+    CronRemoved => "재시작 스케줄 {index}번이 삭제되었습니다: {spec}", "Removed restart schedule {index}: {spec}";
+    CronNotFound => "해당 번호의 재시작 스케줄이 없습니다.", "There is no restart schedule with that number.";
+    CronRemoveFailed => "재시작 스케줄 삭제 중 오류가 발생했습니다.", "Failed to remove the restart schedule.";
+}
+
+pub fn t(locale: Locale, msg: Msg) -> &'static str {
+    msg.text(locale)
+}
+
+/// The template with each `{name}` placeholder replaced by its value. Values are not
+/// scanned again, so user text containing braces is inserted as is.
+pub fn tf(locale: Locale, msg: Msg, args: &[(&str, &(dyn Display + Sync))]) -> String {
+    let mut rest = msg.text(locale);
+    let mut text = String::with_capacity(rest.len());
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                text.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn translations_use_the_same_placeholders() {
+        for msg in Msg::ALL {
+            assert_eq!(
+                placeholders(msg.text(Locale::Ko)),
+                placeholders(msg.text(Locale::En)),
+                "{msg:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn tf_fills_every_placeholder() {
+        let text = tf(Locale::En, Msg::BotStatus, &[("high", &2), ("normal", &5)]);
+        assert_eq!(text, "Bot status\n- High priority: 2\n- Normal priority: 5");
+
+        let text = tf(
+            Locale::En,
+            Msg::RestoredMessage,
+            &[("sender", &"{text}"), ("text", &"hi")],
+        );
+        assert!(text.ends_with("Sent by: {text}\n\nhi"));
+    }
+}
@@ -1,4 +1,8 @@
-use std::io;
+use std::{
+    fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
 
 use anyhow::Result;
 use once_cell::sync::OnceCell;
@@ -6,6 +10,11 @@ use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 use crate::{config::AppConfig, infrastructure::directories::ResolvedPaths};
 
+/// Prefix of the daily log files; `tracing_appender` appends the date.
+const LOG_FILE_PREFIX: &str = "bot.log";
+/// How far back from the end of the log file a tail reads.
+const TAIL_MAX_BYTES: u64 = 1024 * 1024;
+
 static INIT: OnceCell<()> = OnceCell::new();
 static GUARD: OnceCell<tracing_appender::non_blocking::WorkerGuard> = OnceCell::new();
 
@@ -15,7 +24,7 @@ pub fn init_tracing(config: &AppConfig, paths: &ResolvedPaths) -> Result<()> {
             .or_else(|_| EnvFilter::try_new(&config.logging.level))
             .unwrap_or_else(|_| EnvFilter::new("info"));
 
-        let file_appender = tracing_appender::rolling::daily(&paths.logs_dir, "bot.log");
+        let file_appender = tracing_appender::rolling::daily(&paths.logs_dir, LOG_FILE_PREFIX);
         let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
         let _ = GUARD.set(guard);
 
@@ -40,3 +49,52 @@ pub fn init_tracing(config: &AppConfig, paths: &ResolvedPaths) -> Result<()> {
     })?;
     Ok(())
 }
+
+/// The log file currently written to: the newest daily file in `logs_dir`.
+pub fn current_log_file(logs_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(logs_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .max()
+}
+
+/// The last `lines` lines of the file, looking at most [`TAIL_MAX_BYTES`] back.
+pub fn tail(path: &Path, lines: usize) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(TAIL_MAX_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let mut all: Vec<&str> = text.lines().collect();
+    if start > 0 && !all.is_empty() {
+        // The first line was cut by the seek.
+        all.remove(0);
+    }
+    let skip = all.len().saturating_sub(lines);
+    Ok(all[skip..].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_reads_the_newest_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bot.log.2026-01-01"), "old\n").unwrap();
+        fs::write(dir.path().join("bot.log.2026-01-02"), "a\nb\nc\n").unwrap();
+        fs::write(dir.path().join("other.txt"), "x\n").unwrap();
+
+        let current = current_log_file(dir.path()).unwrap();
+        assert!(current.ends_with("bot.log.2026-01-02"));
+        assert_eq!(tail(&current, 2).unwrap(), "b\nc");
+    }
+}
@@ -2,5 +2,6 @@ pub mod directories;
 pub mod instance_guard;
 pub mod logging;
 pub mod notifier;
+pub mod process_stats;
 pub mod shutdown;
 pub mod updater;
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sysinfo::{Pid, System};
+
+/// Kept between samples: sysinfo reports CPU usage since the previous refresh.
+static SYSTEM: Lazy<Mutex<System>> = Lazy::new(|| Mutex::new(System::new()));
+
+/// Set by the first [`sample`], which runs at startup.
+static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Resource usage of the bot process.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessStats {
+    pub rss_bytes: u64,
+    /// Share of one core used since the previous sample; may exceed 100.
+    pub cpu_percent: f32,
+}
+
+/// Refreshes and reads this process's stats; `None` when the platform does not expose
+/// them. The first call only sets the CPU baseline and reports 0% CPU.
+pub fn sample() -> Option<ProcessStats> {
+    Lazy::force(&STARTED_AT);
+    let pid = Pid::from_u32(std::process::id());
+    let mut system = SYSTEM.lock();
+    if !system.refresh_process(pid) {
+        return None;
+    }
+    let process = system.process(pid)?;
+    Some(ProcessStats {
+        rss_bytes: process.memory(),
+        cpu_percent: process.cpu_usage(),
+    })
+}
+
+/// How long the bot has been running; available even where [`sample`] is not.
+pub fn uptime() -> Duration {
+    STARTED_AT.elapsed()
+}
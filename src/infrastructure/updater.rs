@@ -1,8 +1,94 @@
-use anyhow::Result;
+use std::time::Duration;
 
-use crate::{config::AppConfig, infrastructure::directories::ResolvedPaths};
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use semver::Version;
+use serde::Deserialize;
+
+use crate::{
+    config::AppConfig,
+    infrastructure::directories::{self, ResolvedPaths},
+};
 
 const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+/// Target triple the binary was built for, set by `build.rs`.
+pub const BUILD_TARGET: &str = env!("BUILD_TARGET");
+/// Commit the binary was built from, or `unknown` outside a git checkout.
+pub const GIT_COMMIT: &str = env!("GIT_COMMIT");
+
+pub enum UpdateStatus {
+    UpToDate,
+    Installed {
+        new_version: Version,
+        old_version: Version,
+    },
+}
+
+#[derive(Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+fn http_client() -> Result<Client> {
+    Ok(Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(20))
+        .build()?)
+}
+
+async fn fetch_latest_release(client: &Client, config: &AppConfig) -> Result<ReleaseResponse> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases/latest",
+        config.update.repo_owner, config.update.repo_name
+    );
+    let response = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json::<ReleaseResponse>().await?)
+}
+
+fn parse_version(tag: &str) -> Result<Version> {
+    let normalized = tag.trim_start_matches('v');
+    Version::parse(normalized).with_context(|| format!("잘못된 버전 태그: {}", tag))
+}
+
+/// The running version and the newest GitHub release's version.
+pub async fn check_latest_version(config: &AppConfig) -> Result<(Version, Version)> {
+    let release = fetch_latest_release(&http_client()?, config).await?;
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+    Ok((current, parse_version(&release.tag_name)?))
+}
+
+/// Installs the latest release right away, for `/update`; restarting into it is left
+/// to the caller.
+pub async fn apply_update_now(config: &AppConfig) -> Result<UpdateStatus> {
+    if cfg!(debug_assertions) {
+        bail!("디버그 빌드에서는 자동 업데이트를 사용할 수 없습니다");
+    }
+
+    #[cfg(unix)]
+    {
+        let paths = directories::ensure_directories(&config.directories)?;
+        return unix::try_apply_update(&http_client()?, config, &paths).await;
+    }
+
+    #[cfg(not(unix))]
+    {
+        bail!("자동 업데이트는 현재 Unix 계열 환경에서만 지원됩니다")
+    }
+}
 
 pub async fn auto_update_on_startup(config: &AppConfig, paths: &ResolvedPaths) -> Result<()> {
     if !config.update.enabled || !config.update.check_on_startup {
@@ -36,33 +122,29 @@ mod unix {
         ffi::{OsStr, OsString},
         fs,
         path::{Path, PathBuf},
-        time::Duration,
     };
 
     use anyhow::{anyhow, Context, Result};
     use flate2::read::GzDecoder;
     use reqwest::Client;
     use semver::Version;
-    use serde::Deserialize;
     use teloxide::Bot;
     use tempfile::{Builder as TempDirBuilder, TempDir};
     use tokio::io::AsyncWriteExt;
 
     use crate::{
         config::AppConfig,
+        i18n::{t, tf, Msg},
         infrastructure::{directories::ResolvedPaths, notifier::notify_admin_group},
     };
 
-    use super::USER_AGENT;
+    use super::{fetch_latest_release, http_client, parse_version, UpdateStatus};
 
     pub(super) async fn auto_update_on_startup(
         config: &AppConfig,
         paths: &ResolvedPaths,
     ) -> Result<()> {
-        let client = Client::builder()
-            .user_agent(USER_AGENT)
-            .timeout(Duration::from_secs(20))
-            .build()?;
+        let client = http_client()?;
 
         match try_apply_update(&client, config, paths).await? {
             UpdateStatus::UpToDate => Ok(()),
@@ -98,26 +180,6 @@ mod unix {
         }
     }
 
-    #[derive(Deserialize)]
-    struct ReleaseResponse {
-        tag_name: String,
-        assets: Vec<ReleaseAsset>,
-    }
-
-    #[derive(Deserialize)]
-    struct ReleaseAsset {
-        name: String,
-        browser_download_url: String,
-    }
-
-    enum UpdateStatus {
-        UpToDate,
-        Installed {
-            new_version: Version,
-            old_version: Version,
-        },
-    }
-
     #[derive(Clone, Copy)]
     struct PlatformPackage {
         asset_name: &'static str,
@@ -163,7 +225,7 @@ mod unix {
         }
     }
 
-    async fn try_apply_update(
+    pub(super) async fn try_apply_update(
         client: &Client,
         config: &AppConfig,
         paths: &ResolvedPaths,
@@ -204,25 +266,6 @@ mod unix {
         })
     }
 
-    async fn fetch_latest_release(client: &Client, config: &AppConfig) -> Result<ReleaseResponse> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/releases/latest",
-            config.update.repo_owner, config.update.repo_name
-        );
-        let response = client
-            .get(url)
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await?
-            .error_for_status()?;
-        Ok(response.json::<ReleaseResponse>().await?)
-    }
-
-    fn parse_version(tag: &str) -> Result<Version> {
-        let normalized = tag.trim_start_matches('v');
-        Version::parse(normalized).with_context(|| format!("잘못된 버전 태그: {}", tag))
-    }
-
     fn prepare_workspace(paths: &ResolvedPaths) -> Result<TempDir> {
         let updates_dir = paths.data_dir.join("updates");
         fs::create_dir_all(&updates_dir)
@@ -343,17 +386,20 @@ mod unix {
             return;
         }
 
-        let summary = if will_restart {
-            format!(
-                "자동 업데이트 완료\n- 이전 버전: v{}\n- 신규 버전: v{}\n새 바이너리로 곧 재시작합니다.",
-                old_version, new_version
-            )
-        } else {
-            format!(
-                "자동 업데이트 완료\n- 이전 버전: v{}\n- 신규 버전: v{}\n프로세스를 재시작하면 변경 내용이 적용됩니다.",
-                old_version, new_version
-            )
-        };
+        let locale = config.language;
+        let mut summary = tf(
+            locale,
+            Msg::UpdateInstalled,
+            &[("old", old_version), ("new", new_version)],
+        );
+        summary.push_str(t(
+            locale,
+            if will_restart {
+                Msg::UpdateRestarting
+            } else {
+                Msg::UpdateRestartManually
+            },
+        ));
 
         let bot = Bot::new(&config.telegram_bot_token);
         notify_admin_group(&bot, config, &summary).await;
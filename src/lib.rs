@@ -0,0 +1,18 @@
+pub mod ai;
+pub mod app;
+pub mod cas;
+pub mod cli;
+pub mod config;
+pub mod db;
+pub mod domain;
+pub mod heuristics;
+pub mod i18n;
+pub mod infrastructure;
+pub mod media;
+pub mod rules;
+pub mod tasks;
+pub mod telegram;
+#[cfg(all(test, feature = "testing"))]
+mod testing;
+pub mod url_reputation;
+pub mod web_content;
@@ -1,20 +1,17 @@
-mod ai;
-mod app;
-mod config;
-mod db;
-mod domain;
-mod infrastructure;
-mod tasks;
-mod telegram;
-mod web_content;
-
 use anyhow::Result;
-use infrastructure::{directories, instance_guard, logging, shutdown, updater};
+use fuckyou_spam_rust::{
+    app, cli, config,
+    infrastructure::{directories, instance_guard, logging, shutdown, updater},
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
+    if let Some(command) = cli::Subcommand::from_args() {
+        return command.run().await;
+    }
+
     let config = config::load_config()?;
     let paths = directories::ensure_directories(&config.directories)?;
     logging::init_tracing(&config, &paths)?;
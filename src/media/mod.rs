@@ -0,0 +1,19 @@
+pub mod ocr;
+pub mod transcription;
+pub mod vision;
+
+use anyhow::{Context, Result};
+use teloxide::{net::Download, prelude::*, types::FileId};
+
+/// Downloads a Telegram file into memory.
+pub async fn download(bot: &Bot, file_id: &FileId) -> Result<Vec<u8>> {
+    let file = bot
+        .get_file(file_id.clone())
+        .await
+        .context("get_file failed")?;
+    let mut bytes = Vec::with_capacity(file.size as usize);
+    bot.download_file(&file.path, &mut bytes)
+        .await
+        .context("file download failed")?;
+    Ok(bytes)
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashSet, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use tokio::process::Command;
+
+use crate::config::VideoOcrConfig;
+
+/// Samples frames from short videos with `ffmpeg` and reads overlaid text with `tesseract`.
+pub struct VideoOcr {
+    config: VideoOcrConfig,
+}
+
+impl VideoOcr {
+    pub fn new(config: VideoOcrConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn max_duration_secs(&self) -> u32 {
+        self.config.max_duration_secs
+    }
+
+    /// Returns the distinct text lines found across the sampled frames.
+    pub async fn extract_text(&self, video: Vec<u8>, duration_secs: u32) -> Result<String> {
+        tokio::time::timeout(self.config.timeout, self.run(video, duration_secs))
+            .await
+            .map_err(|_| anyhow!("video OCR timed out after {:?}", self.config.timeout))?
+    }
+
+    async fn run(&self, video: Vec<u8>, duration_secs: u32) -> Result<String> {
+        let workdir = tempfile::tempdir().context("failed to create OCR temp dir")?;
+        let input = workdir.path().join("input.mp4");
+        tokio::fs::write(&input, video).await?;
+
+        let frames = self.config.frames.max(1);
+        let mut seen = HashSet::new();
+        let mut lines = Vec::new();
+        for index in 0..frames {
+            // Spread samples evenly, avoiding the very first and last frame.
+            let at = f64::from(duration_secs.max(1)) * (f64::from(index) + 0.5) / f64::from(frames);
+            let frame = workdir.path().join(format!("frame_{index}.png"));
+            if !self.grab_frame(&input, at, &frame).await? {
+                continue;
+            }
+            for line in self.read_text(&frame).await?.lines() {
+                let line = line.trim();
+                if line.chars().count() >= 2 && seen.insert(line.to_string()) {
+                    lines.push(line.to_string());
+                }
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    async fn grab_frame(&self, input: &Path, at_secs: f64, output: &Path) -> Result<bool> {
+        let status = Command::new(&self.config.ffmpeg_path)
+            .args(["-loglevel", "error", "-ss", &format!("{at_secs:.2}"), "-i"])
+            .arg(input)
+            .args(["-frames:v", "1", "-y"])
+            .arg(output)
+            .kill_on_drop(true)
+            .status()
+            .await
+            .with_context(|| format!("failed to run {}", self.config.ffmpeg_path))?;
+        Ok(status.success() && output.exists())
+    }
+
+    async fn read_text(&self, frame: &Path) -> Result<String> {
+        let output = Command::new(&self.config.tesseract_path)
+            .arg(frame)
+            .arg("stdout")
+            .args(["-l", &self.config.languages])
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("failed to run {}", self.config.tesseract_path))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "tesseract failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
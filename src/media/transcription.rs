@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use reqwest::{multipart, Client};
+use serde::Deserialize;
+
+use crate::config::TranscriptionConfig;
+
+/// Speech-to-text client for any Whisper-compatible `/audio/transcriptions` endpoint
+/// (OpenAI, or a local whisper.cpp / faster-whisper server).
+#[derive(Clone)]
+pub struct Transcriber {
+    http: Client,
+    config: TranscriptionConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+impl Transcriber {
+    pub fn new(http: Client, config: TranscriptionConfig) -> Self {
+        Self { http, config }
+    }
+
+    pub fn max_duration_secs(&self) -> u32 {
+        self.config.max_duration_secs
+    }
+
+    pub async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String> {
+        let mut form = multipart::Form::new()
+            .part(
+                "file",
+                multipart::Part::bytes(audio).file_name(file_name.to_string()),
+            )
+            .text("model", self.config.model.clone())
+            .text("response_format", "json");
+        if let Some(language) = &self.config.language {
+            form = form.text("language", language.clone());
+        }
+
+        let mut request = self
+            .http
+            .post(&self.config.endpoint)
+            .timeout(self.config.timeout)
+            .multipart(form);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?;
+        if let Err(err) = response.error_for_status_ref() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(err).context(format!("transcription error {status}: {error_text}"));
+        }
+        let body: TranscriptionResponse = response
+            .json()
+            .await
+            .context("failed to parse transcription response")?;
+        Ok(body.text.trim().to_string())
+    }
+}
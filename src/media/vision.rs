@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::VisionConfig;
+
+const INSTRUCTIONS: &str = "Transcribe every piece of visible text in this image verbatim \
+(including URLs, @handles and phone numbers), then describe in one sentence what the image \
+shows, e.g. an advertisement, a profit screenshot, a QR code or a chat screenshot. \
+Answer in plain text, at most 600 characters.";
+
+/// Describes images with a multimodal model behind an OpenAI-compatible
+/// `/chat/completions` endpoint, so image-only messages can be classified as text.
+#[derive(Clone)]
+pub struct ImageDescriber {
+    http: Client,
+    config: VisionConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionResponse {
+    choices: Vec<CompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionChoice {
+    message: CompletionMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionMessage {
+    content: Option<String>,
+}
+
+impl ImageDescriber {
+    pub fn new(http: Client, config: VisionConfig) -> Self {
+        Self { http, config }
+    }
+
+    /// Largest photo size the describer downloads.
+    pub fn max_bytes(&self) -> u32 {
+        self.config.max_bytes
+    }
+
+    pub async fn describe(&self, image: Vec<u8>) -> Result<String> {
+        let data_url = format!("data:image/jpeg;base64,{}", STANDARD.encode(image));
+        let body = json!({
+            "model": self.config.model,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": INSTRUCTIONS },
+                    { "type": "image_url", "image_url": { "url": data_url } }
+                ]
+            }],
+            "temperature": 0.0,
+            "max_completion_tokens": 400
+        });
+
+        let mut request = self
+            .http
+            .post(format!("{}/chat/completions", self.config.base_url))
+            .timeout(self.config.timeout)
+            .json(&body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?;
+        if let Err(err) = response.error_for_status_ref() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(err).context(format!("vision model error {status}: {error_text}"));
+        }
+        let body: CompletionResponse = response
+            .json()
+            .await
+            .context("failed to parse vision model response")?;
+        Ok(body
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Timelike;
+use chrono_tz::Tz;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use url::Url;
+
+use crate::domain::MessageJob;
+
+use super::RuleAction;
+
+/// Top-level layout of the rules file:
+///
+/// ```toml
+/// [[rule]]
+/// name = "stock pump"
+/// chats = [-1001234567890]     # optional, all whitelisted chats when omitted
+/// text_regex = "종목|타점"
+/// domains = ["t.me"]
+/// sender = { member = false, has_username = false }
+/// hours = "22-6"               # local hour window in BOT_TIMEZONE
+/// action = "delete"            # delete | flag | boost | ignore
+/// ```
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RawFileRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawFileRule {
+    name: String,
+    #[serde(default)]
+    chats: Vec<i64>,
+    text_regex: Option<String>,
+    #[serde(default)]
+    domains: Vec<String>,
+    #[serde(default)]
+    sender: SenderCondition,
+    hours: Option<String>,
+    action: String,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SenderCondition {
+    pub member: Option<bool>,
+    pub has_username: Option<bool>,
+}
+
+/// Half-open local hour window; `start > end` wraps around midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HourWindow {
+    start: u32,
+    end: u32,
+}
+
+impl HourWindow {
+    fn parse(value: &str) -> Result<Self> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| anyhow!("hours must look like \"22-6\": {value}"))?;
+        let start: u32 = start.trim().parse()?;
+        let end: u32 = end.trim().parse()?;
+        if start > 23 || end > 24 {
+            return Err(anyhow!("hours out of range: {value}"));
+        }
+        Ok(Self { start, end })
+    }
+
+    fn contains(&self, hour: u32) -> bool {
+        if self.start <= self.end {
+            hour >= self.start && hour < self.end
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+/// A compiled rule from the rules file; every present condition must match.
+#[derive(Debug, Clone)]
+pub struct FileRule {
+    pub name: String,
+    pub action: RuleAction,
+    chats: Vec<i64>,
+    text_regex: Option<Regex>,
+    domains: Vec<String>,
+    sender: SenderCondition,
+    hours: Option<HourWindow>,
+}
+
+impl FileRule {
+    pub fn pattern(&self) -> String {
+        self.text_regex
+            .as_ref()
+            .map(|regex| regex.as_str().to_string())
+            .unwrap_or_else(|| self.domains.join(","))
+    }
+
+    pub fn matches(&self, job: &MessageJob, tz: Tz) -> bool {
+        if !self.chats.is_empty() && !self.chats.contains(&job.chat_id.0) {
+            return false;
+        }
+        if let Some(regex) = &self.text_regex {
+            if !regex.is_match(&job.text) {
+                return false;
+            }
+        }
+        if !self.domains.is_empty() && !job.urls.iter().any(|url| self.matches_domain(url)) {
+            return false;
+        }
+        if let Some(member) = self.sender.member {
+            if job.is_group_member != member {
+                return false;
+            }
+        }
+        if let Some(has_username) = self.sender.has_username {
+            if job.username.is_some() != has_username {
+                return false;
+            }
+        }
+        if let Some(window) = self.hours {
+            if !window.contains(job.timestamp.with_timezone(&tz).hour()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_domain(&self, raw_url: &str) -> bool {
+        let Some(host) = Url::parse(raw_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            return false;
+        };
+        self.domains
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")))
+    }
+}
+
+pub fn parse_rules(source: &str) -> Result<Vec<FileRule>> {
+    let file: RulesFile = toml::from_str(source)?;
+    file.rules
+        .into_iter()
+        .map(|raw| {
+            let action = RuleAction::parse(&raw.action)
+                .ok_or_else(|| anyhow!("rule '{}': unknown action {}", raw.name, raw.action))?;
+            let text_regex = raw
+                .text_regex
+                .as_deref()
+                .map(|pattern| RegexBuilder::new(pattern).size_limit(1 << 20).build())
+                .transpose()
+                .with_context(|| format!("rule '{}': invalid text_regex", raw.name))?;
+            let hours = raw
+                .hours
+                .as_deref()
+                .map(HourWindow::parse)
+                .transpose()
+                .with_context(|| format!("rule '{}': invalid hours", raw.name))?;
+            Ok(FileRule {
+                name: raw.name,
+                action,
+                chats: raw.chats,
+                text_regex,
+                domains: raw
+                    .domains
+                    .into_iter()
+                    .map(|domain| domain.trim().to_ascii_lowercase())
+                    .collect(),
+                sender: raw.sender,
+                hours,
+            })
+        })
+        .collect()
+}
+
+pub fn load_rules_file(path: &Path) -> Result<Vec<FileRule>> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read rules file {}", path.display()))?;
+    parse_rules(&source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hour_window_wraps_midnight() {
+        let window = HourWindow::parse("22-6").unwrap();
+        assert!(window.contains(23));
+        assert!(window.contains(3));
+        assert!(!window.contains(12));
+    }
+
+    #[test]
+    fn parse_rules_rejects_unknown_action() {
+        let source = r#"
+            [[rule]]
+            name = "bad"
+            text_regex = "spam"
+            action = "explode"
+        "#;
+        assert!(parse_rules(source).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+pub mod dsl;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Result};
+use chrono_tz::Tz;
+use parking_lot::RwLock;
+use regex::{Regex, RegexBuilder};
+use tokio::{task::JoinHandle, time::sleep};
+
+use crate::{
+    db::rules::RuleRepository, domain::MessageJob, infrastructure::shutdown::ShutdownListener,
+};
+
+use dsl::FileRule;
+
+/// How a rule pattern is matched against message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    Regex,
+    Keyword,
+}
+
+impl RuleKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "regex" => Some(RuleKind::Regex),
+            "keyword" => Some(RuleKind::Keyword),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Regex => "regex",
+            RuleKind::Keyword => "keyword",
+        }
+    }
+}
+
+/// What happens to a message that matches a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    /// Delete immediately without asking the classifier.
+    Delete,
+    /// Notify the admin group, then classify as usual.
+    Flag,
+    /// Queue with high priority and a score bonus.
+    Boost,
+    /// Never queue the message.
+    Ignore,
+}
+
+impl RuleAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "delete" => Some(RuleAction::Delete),
+            "flag" => Some(RuleAction::Flag),
+            "boost" => Some(RuleAction::Boost),
+            "ignore" => Some(RuleAction::Ignore),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleAction::Delete => "delete",
+            RuleAction::Flag => "flag",
+            RuleAction::Boost => "boost",
+            RuleAction::Ignore => "ignore",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Regex(Regex),
+    Keyword(String),
+}
+
+impl Matcher {
+    fn build(kind: RuleKind, pattern: &str) -> Result<Self> {
+        match kind {
+            RuleKind::Regex => Ok(Matcher::Regex(
+                RegexBuilder::new(pattern)
+                    .size_limit(1 << 20)
+                    .build()
+                    .map_err(|err| anyhow!("invalid regex: {err}"))?,
+            )),
+            RuleKind::Keyword => Ok(Matcher::Keyword(pattern.to_lowercase())),
+        }
+    }
+
+    fn is_match(&self, text: &str, lowered: &str) -> bool {
+        match self {
+            Matcher::Regex(regex) => regex.is_match(text),
+            Matcher::Keyword(keyword) => lowered.contains(keyword.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    id: i64,
+    pattern: String,
+    action: RuleAction,
+    matcher: Matcher,
+}
+
+/// The first rule that matched a message; chat rules win over file rules.
+#[derive(Debug, Clone)]
+pub struct RuleMatch {
+    /// Human-readable rule reference, e.g. `#3` or `'stock pump'`.
+    pub label: String,
+    pub pattern: String,
+    pub action: RuleAction,
+}
+
+/// In-memory cache of compiled per-chat rules backed by the `chat_rules` table,
+/// plus the declarative rules loaded from `RULES_FILE`.
+pub struct RuleEngine {
+    repository: Arc<RuleRepository>,
+    rules: RwLock<HashMap<i64, Vec<CompiledRule>>>,
+    file_rules: RwLock<Vec<FileRule>>,
+    timezone: Tz,
+}
+
+impl RuleEngine {
+    pub async fn load(repository: Arc<RuleRepository>, timezone: Tz) -> Result<Arc<Self>> {
+        let engine = Arc::new(Self {
+            repository,
+            rules: RwLock::new(HashMap::new()),
+            file_rules: RwLock::new(Vec::new()),
+            timezone,
+        });
+        engine.reload().await?;
+        Ok(engine)
+    }
+
+    /// Loads the rules file now and re-reads it whenever its mtime changes.
+    /// A file that fails to parse is logged and the previous rules stay active.
+    pub fn watch_file(
+        self: Arc<Self>,
+        path: PathBuf,
+        poll_interval: Duration,
+        mut shutdown: ShutdownListener,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut last_modified: Option<SystemTime> = None;
+            loop {
+                let modified = tokio::fs::metadata(&path)
+                    .await
+                    .and_then(|meta| meta.modified())
+                    .ok();
+                if modified.is_some() && modified != last_modified {
+                    last_modified = modified;
+                    self.reload_file(&path).await;
+                }
+                tokio::select! {
+                    _ = sleep(poll_interval) => {}
+                    _ = shutdown.notified() => break,
+                }
+            }
+        })
+    }
+
+    async fn reload_file(&self, path: &Path) {
+        let target = path.to_path_buf();
+        match tokio::task::spawn_blocking(move || dsl::load_rules_file(&target)).await {
+            Ok(Ok(rules)) => {
+                tracing::info!(
+                    target: "rules",
+                    path = %path.display(),
+                    count = rules.len(),
+                    "rules file loaded"
+                );
+                *self.file_rules.write() = rules;
+            }
+            Ok(Err(err)) => {
+                tracing::warn!(
+                    target: "rules",
+                    path = %path.display(),
+                    error = format!("{err:#}"),
+                    "rules file rejected; keeping previous rules"
+                );
+            }
+            Err(err) => {
+                tracing::error!(target: "rules", error = %err, "rules file loader panicked");
+            }
+        }
+    }
+
+    pub async fn reload(&self) -> Result<()> {
+        let rows = self.repository.list().await?;
+        let mut compiled: HashMap<i64, Vec<CompiledRule>> = HashMap::new();
+        for row in rows {
+            let (Some(kind), Some(action)) =
+                (RuleKind::parse(&row.kind), RuleAction::parse(&row.action))
+            else {
+                tracing::warn!(target: "rules", rule_id = row.id, "skipping rule with unknown kind/action");
+                continue;
+            };
+            match Matcher::build(kind, &row.pattern) {
+                Ok(matcher) => compiled.entry(row.chat_id).or_default().push(CompiledRule {
+                    id: row.id,
+                    pattern: row.pattern,
+                    action,
+                    matcher,
+                }),
+                Err(err) => {
+                    tracing::warn!(target: "rules", rule_id = row.id, error = %err, "skipping invalid rule");
+                }
+            }
+        }
+        *self.rules.write() = compiled;
+        Ok(())
+    }
+
+    /// Validates and stores a new rule, returning its id.
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        kind: RuleKind,
+        action: RuleAction,
+        pattern: &str,
+        created_by: Option<i64>,
+    ) -> Result<i64> {
+        Matcher::build(kind, pattern)?;
+        let id = self
+            .repository
+            .add(chat_id, kind.as_str(), pattern, action.as_str(), created_by)
+            .await?;
+        self.reload().await?;
+        Ok(id)
+    }
+
+    pub async fn remove(&self, id: i64) -> Result<bool> {
+        let removed = self.repository.remove(id).await?;
+        if removed {
+            self.reload().await?;
+        }
+        Ok(removed)
+    }
+
+    pub fn evaluate(&self, job: &MessageJob) -> Option<RuleMatch> {
+        self.evaluate_chat_rules(job.chat_id.0, &job.text)
+            .or_else(|| self.evaluate_file_rules(job))
+    }
+
+    fn evaluate_chat_rules(&self, chat_id: i64, text: &str) -> Option<RuleMatch> {
+        let rules = self.rules.read();
+        let chat_rules = rules.get(&chat_id)?;
+        let lowered = text.to_lowercase();
+        chat_rules
+            .iter()
+            .find(|rule| rule.matcher.is_match(text, &lowered))
+            .map(|rule| RuleMatch {
+                label: format!("#{}", rule.id),
+                pattern: rule.pattern.clone(),
+                action: rule.action,
+            })
+    }
+
+    fn evaluate_file_rules(&self, job: &MessageJob) -> Option<RuleMatch> {
+        self.file_rules
+            .read()
+            .iter()
+            .find(|rule| rule.matches(job, self.timezone))
+            .map(|rule| RuleMatch {
+                label: format!("'{}'", rule.name),
+                pattern: rule.pattern(),
+                action: rule.action,
+            })
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode},
+};
+
+use crate::{
+    config::AppConfig,
+    i18n::{t, tf, Locale, Msg},
+};
+
+/// Most entries listed in one combined log; the rest are only counted.
+const MAX_LISTED: usize = 15;
+
+/// One deletion held for the combined log.
+pub struct BatchEntry {
+    pub summary: String,
+    /// Spam log row the entry's buttons act on.
+    pub log_id: Option<i64>,
+    pub has_sender: bool,
+}
+
+#[derive(Default)]
+struct Window {
+    started: Option<Instant>,
+    pending: Vec<BatchEntry>,
+}
+
+/// Restore/ban/dismiss buttons for a spam log entry (`spamlog:<action>:<log id>`); in a
+/// combined log the labels carry the entry's number.
+pub fn spam_log_buttons(
+    locale: Locale,
+    log_id: i64,
+    has_sender: bool,
+    index: Option<usize>,
+) -> Vec<InlineKeyboardButton> {
+    let button = |label: Msg, action: &str| {
+        let label = match index {
+            Some(index) => format!("{index}. {}", t(locale, label)),
+            None => t(locale, label).to_string(),
+        };
+        InlineKeyboardButton::callback(label, format!("spamlog:{action}:{log_id}"))
+    };
+    let mut buttons = vec![button(Msg::SpamLogRestore, "restore")];
+    if has_sender {
+        buttons.push(button(Msg::SpamLogBan, "ban"));
+    }
+    buttons.push(button(Msg::SpamLogIgnore, "ignore"));
+    buttons
+}
+
+/// Keeps spam waves from flooding the admin group: the first deletion in a window is
+/// logged in full, later ones are collected and posted as one combined message when
+/// the window ends.
+pub struct AdminLogBatcher {
+    bot: Bot,
+    config: Arc<AppConfig>,
+    window: Duration,
+    state: Arc<Mutex<Window>>,
+}
+
+impl AdminLogBatcher {
+    pub fn new(bot: Bot, config: Arc<AppConfig>, window: Duration) -> Self {
+        Self {
+            bot,
+            config,
+            window,
+            state: Arc::new(Mutex::new(Window::default())),
+        }
+    }
+
+    /// `true` when the full log should be sent now; otherwise `entry` is held for the
+    /// combined message.
+    pub fn admit(&self, entry: BatchEntry) -> bool {
+        let mut state = self.state.lock();
+        let elapsed = state.started.map(|started| started.elapsed());
+        match elapsed {
+            Some(elapsed) if elapsed < self.window => {
+                state.pending.push(entry);
+                if state.pending.len() == 1 {
+                    self.schedule_flush(self.window - elapsed);
+                }
+                false
+            }
+            _ => {
+                state.started = Some(Instant::now());
+                true
+            }
+        }
+    }
+
+    /// Posts the held entries once `delay` has passed, and keeps batching while the
+    /// wave lasts by starting a new window.
+    fn schedule_flush(&self, delay: Duration) {
+        let bot = self.bot.clone();
+        let config = self.config.clone();
+        let state = self.state.clone();
+        let window = self.window;
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let pending = {
+                let mut state = state.lock();
+                state.started = Some(Instant::now());
+                std::mem::take(&mut state.pending)
+            };
+            if pending.is_empty() {
+                return;
+            }
+            let locale = config.language;
+            let mut text = tf(
+                locale,
+                Msg::AdminLogBatch,
+                &[("count", &pending.len()), ("secs", &window.as_secs())],
+            );
+            text.push_str("\n<blockquote expandable>");
+            let mut rows = Vec::new();
+            for (index, entry) in pending.iter().take(MAX_LISTED).enumerate() {
+                text.push_str(&format!("\n{}.", index + 1));
+                text.push_str(&entry.summary);
+                if let Some(log_id) = entry.log_id {
+                    rows.push(spam_log_buttons(
+                        locale,
+                        log_id,
+                        entry.has_sender,
+                        Some(index + 1),
+                    ));
+                }
+            }
+            if pending.len() > MAX_LISTED {
+                text.push_str(&tf(
+                    locale,
+                    Msg::AdminLogBatchMore,
+                    &[("count", &(pending.len() - MAX_LISTED))],
+                ));
+            }
+            text.push_str("</blockquote>");
+            let Some(admin_group_id) = config.admin_group_id.filter(|id| *id != 0) else {
+                return;
+            };
+            let mut request = bot
+                .send_message(ChatId(admin_group_id), text)
+                .parse_mode(ParseMode::Html);
+            if !rows.is_empty() {
+                request = request.reply_markup(InlineKeyboardMarkup::new(rows));
+            }
+            match request.await {
+                Ok(_) => tracing::info!(
+                    target: "processor",
+                    count = pending.len(),
+                    "combined admin log sent"
+                ),
+                Err(err) => tracing::error!(
+                    target: "processor",
+                    error = %err,
+                    admin_group_id,
+                    "failed to send combined admin log"
+                ),
+            }
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::{config::AppConfig, db::backups::BackupRepository, tasks::scheduler::JobTask};
+
+const FILE_PREFIX: &str = "backup-";
+const FILE_SUFFIX: &str = ".sqlite";
+
+/// Snapshots the database into `BACKUP_DIR` and keeps only the newest `BACKUP_KEEP`
+/// files.
+pub struct DatabaseBackup {
+    backups: Arc<BackupRepository>,
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl DatabaseBackup {
+    pub fn new(backups: Arc<BackupRepository>, config: &AppConfig) -> Self {
+        Self {
+            backups,
+            dir: Path::new(&config.directories.data_dir).join(&config.backup.dir),
+            keep: config.backup.keep,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let backup = self.clone();
+            Box::pin(async move {
+                if let Err(err) = backup.run().await {
+                    tracing::error!(target: "scheduler", error = %format!("{err:#}"), "database backup failed");
+                }
+            })
+        })
+    }
+
+    /// Takes a snapshot and returns its path and size in bytes.
+    pub async fn run(&self) -> Result<(PathBuf, u64)> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let path = self.dir.join(format!(
+            "{FILE_PREFIX}{}{FILE_SUFFIX}",
+            Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        self.backups.vacuum_into(&path).await?;
+        let size = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        let removed = self.rotate()?;
+        tracing::info!(
+            target: "scheduler",
+            path = %path.display(),
+            size,
+            removed,
+            "database backup written"
+        );
+        Ok((path, size))
+    }
+
+    /// Deletes the oldest snapshots beyond `keep`; names sort by their timestamp.
+    fn rotate(&self) -> Result<usize> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+                    })
+            })
+            .collect();
+        if files.len() <= self.keep {
+            return Ok(0);
+        }
+        files.sort();
+        let excess = files.len() - self.keep;
+        for path in &files[..excess] {
+            if let Err(err) = fs::remove_file(path) {
+                tracing::warn!(target: "scheduler", error = %err, path = %path.display(), "failed to remove old backup");
+            }
+        }
+        Ok(excess)
+    }
+}
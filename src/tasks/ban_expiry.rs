@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::bans::BanRepository,
+    i18n::{t, tf, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::{processor::MessageProcessor, scheduler::JobTask},
+};
+
+/// Lifts spam bans once `BAN_DURATION_HOURS` has passed and tells the admin group; bans
+/// that fail to lift are retried on the next run.
+pub struct BanExpiry {
+    bot: Bot,
+    bans: Arc<BanRepository>,
+    processor: Arc<MessageProcessor>,
+    config: Arc<AppConfig>,
+}
+
+impl BanExpiry {
+    pub fn new(
+        bot: Bot,
+        bans: Arc<BanRepository>,
+        processor: Arc<MessageProcessor>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            bot,
+            bans,
+            processor,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let expiry = self.clone();
+            Box::pin(async move {
+                if let Err(err) = expiry.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "ban expiry failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let expired = self.bans.expired(Utc::now()).await?;
+        if expired.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(target: "scheduler", count = expired.len(), "expired bans found");
+
+        let locale = self.config.language;
+        let mut message = t(locale, Msg::BansLifted).to_string();
+        for row in &expired {
+            let lifted = self.processor.lift_ban(row.chat_id, row.user_id).await;
+            message.push_str(&tf(
+                locale,
+                if lifted {
+                    Msg::BansLiftedRow
+                } else {
+                    Msg::BansLiftFailedRow
+                },
+                &[("user_id", &row.user_id), ("chat_id", &row.chat_id)],
+            ));
+        }
+        notify_admin_group(&self.bot, self.config.as_ref(), &message).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::{
+    ai::bayes::{BayesClassifier, BayesModel},
+    config::BayesConfig,
+    db::Repositories,
+    tasks::scheduler::JobTask,
+};
+
+/// Most recent samples of each label a model is trained on.
+const MAX_SAMPLES: i64 = 5000;
+
+/// Retrains the offline classifier from the spam log, admin-labeled examples and
+/// cleared messages.
+pub struct BayesTrainer {
+    db: Repositories,
+    classifier: Arc<BayesClassifier>,
+    config: BayesConfig,
+}
+
+impl BayesTrainer {
+    pub fn new(db: Repositories, classifier: Arc<BayesClassifier>, config: BayesConfig) -> Self {
+        Self {
+            db,
+            classifier,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let trainer = self.clone();
+            Box::pin(async move {
+                if let Err(err) = trainer.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "offline model training failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut spam = self.db.spam_log.recent_texts(MAX_SAMPLES).await?;
+        let mut ham = self.db.ham_samples.recent_texts(MAX_SAMPLES).await?;
+        for (label, texts) in [(true, &mut spam), (false, &mut ham)] {
+            texts.extend(
+                self.db
+                    .examples
+                    .recent(&[], label, MAX_SAMPLES)
+                    .await?
+                    .into_iter()
+                    .map(|example| example.text),
+            );
+        }
+
+        if spam.len() < self.config.min_samples || ham.len() < self.config.min_samples {
+            tracing::info!(
+                target: "scheduler",
+                spam = spam.len(),
+                ham = ham.len(),
+                required = self.config.min_samples,
+                "not enough samples to train the offline model yet"
+            );
+            return Ok(());
+        }
+
+        let model = BayesModel::train(
+            spam.iter().map(String::as_str),
+            ham.iter().map(String::as_str),
+        );
+        self.classifier.replace(model)?;
+        tracing::info!(
+            target: "scheduler",
+            spam = spam.len(),
+            ham = ham.len(),
+            path = %self.classifier.path().display(),
+            "offline model trained"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    tripped: bool,
+}
+
+/// Stops classification after repeated failures and lets a trial batch through once the
+/// cooldown has passed; one success closes it again.
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// `true` while batches should stay queued.
+    pub fn is_open(&self, now: Instant) -> bool {
+        self.state
+            .lock()
+            .open_until
+            .is_some_and(|until| now < until)
+    }
+
+    /// Returns `true` when this success recovered a tripped breaker.
+    pub fn record_success(&self) -> bool {
+        let mut state = self.state.lock();
+        let recovered = state.tripped;
+        *state = BreakerState::default();
+        recovered
+    }
+
+    /// Returns `true` when this failure newly opened the breaker.
+    pub fn record_failure(&self, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures < self.threshold {
+            return false;
+        }
+        let newly_tripped = !state.tripped;
+        state.tripped = true;
+        state.open_until = Some(now + self.cooldown);
+        newly_tripped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_and_closes_on_success() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(!breaker.record_failure(now));
+        assert!(!breaker.is_open(now));
+        assert!(breaker.record_failure(now));
+        assert!(breaker.is_open(now + Duration::from_secs(30)));
+        assert!(!breaker.is_open(now + Duration::from_secs(61)));
+        // A failed trial batch reopens without reporting a new trip.
+        assert!(!breaker.record_failure(now + Duration::from_secs(61)));
+        assert!(breaker.is_open(now + Duration::from_secs(90)));
+        assert!(breaker.record_success());
+        assert!(!breaker.is_open(now + Duration::from_secs(90)));
+        assert!(!breaker.record_success());
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+
+use crate::{config::AppConfig, db::maintenance::MaintenanceRepository, tasks::scheduler::JobTask};
+
+/// Vacuums and analyzes the database, then truncates its WAL file, logging the size on
+/// disk before and after.
+pub struct DatabaseMaintenance {
+    maintenance: Arc<MaintenanceRepository>,
+    db_path: PathBuf,
+}
+
+impl DatabaseMaintenance {
+    pub fn new(maintenance: Arc<MaintenanceRepository>, config: &AppConfig) -> Self {
+        Self {
+            maintenance,
+            db_path: Path::new(&config.directories.data_dir).join(&config.directories.db_filename),
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let maintenance = self.clone();
+            Box::pin(async move {
+                if let Err(err) = maintenance.run().await {
+                    tracing::error!(target: "scheduler", error = %format!("{err:#}"), "database maintenance failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let (db_before, wal_before) = self.sizes();
+        let full_vacuum = self.maintenance.vacuum().await?;
+        self.maintenance.analyze().await?;
+        // Last, so the pages written by the vacuum are checkpointed too.
+        let busy = self.maintenance.checkpoint().await?;
+        let (db_after, wal_after) = self.sizes();
+        tracing::info!(
+            target: "scheduler",
+            db_before,
+            wal_before,
+            db_after,
+            wal_after,
+            full_vacuum,
+            checkpoint_busy = busy,
+            "database maintenance finished"
+        );
+        Ok(())
+    }
+
+    /// Bytes in the database file and its WAL file.
+    fn sizes(&self) -> (u64, u64) {
+        let size = |path: &Path| fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+        let mut wal = self.db_path.clone().into_os_string();
+        wal.push("-wal");
+        (size(&self.db_path), size(Path::new(&wal)))
+    }
+}
@@ -0,0 +1,162 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::{spam_log::SpamCount, Repositories},
+    heuristics::known_spam::link_domains,
+    i18n::{t, tf, Locale, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::{processor::MessageProcessor, scheduler::JobTask, usage_summary::format_usage},
+    telegram::utils::escape_html,
+};
+
+/// Most recent deletions scanned for link domains in one digest.
+const DOMAIN_SCAN_LIMIT: i64 = 5_000;
+/// Chats listed by name; the rest are summed into one line so the digest stays under
+/// Telegram's message length limit.
+const CHAT_LIST_LIMIT: i64 = 20;
+
+/// Posts deletions per chat, the top senders and link domains, AI usage and queue
+/// health for the configured period to the admin group.
+pub struct SpamDigest {
+    bot: Bot,
+    repos: Repositories,
+    processor: Arc<MessageProcessor>,
+    config: Arc<AppConfig>,
+}
+
+impl SpamDigest {
+    pub fn new(
+        bot: Bot,
+        repos: Repositories,
+        processor: Arc<MessageProcessor>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            bot,
+            repos,
+            processor,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let digest = self.clone();
+            Box::pin(async move {
+                if let Err(err) = digest.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "spam digest failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let days = self.config.digest.period_days;
+        let top = self.config.digest.top;
+        let since = Utc::now() - Duration::days(i64::from(days));
+        let total = self.repos.spam_log.count_since(since).await?;
+        let chats = self
+            .repos
+            .spam_log
+            .counts_by_chat(since, CHAT_LIST_LIMIT)
+            .await?;
+        let users = self.repos.spam_log.top_users(since, top as i64).await?;
+        let texts = self
+            .repos
+            .spam_log
+            .texts_since(since, DOMAIN_SCAN_LIMIT)
+            .await?;
+        let usage = self.repos.usage.totals_since(since).await?;
+        tracing::info!(target: "scheduler", days, total, "spam digest prepared");
+
+        let locale = self.config.language;
+        let mut text = tf(
+            locale,
+            Msg::DigestTitle,
+            &[("days", &days), ("total", &total)],
+        );
+        if !chats.is_empty() {
+            text.push_str(t(locale, Msg::DigestChats));
+            push_counts(&mut text, locale, &chats, |chat| {
+                chat.label
+                    .clone()
+                    .unwrap_or_else(|| t(locale, Msg::Untitled).to_string())
+            });
+            let others = total - chats.iter().map(|chat| chat.count).sum::<i64>();
+            if others > 0 {
+                text.push_str(&tf(locale, Msg::DigestOtherChats, &[("count", &others)]));
+            }
+        }
+        if !users.is_empty() {
+            text.push_str(t(locale, Msg::DigestUsers));
+            push_counts(&mut text, locale, &users, |user| {
+                user.label.clone().unwrap_or_default()
+            });
+        }
+        let domains = top_domains(&texts, top);
+        if !domains.is_empty() {
+            text.push_str(t(locale, Msg::DigestDomains));
+            for (domain, count) in &domains {
+                text.push_str(&tf(
+                    locale,
+                    Msg::DigestRow,
+                    &[("name", &escape_html(domain)), ("count", count)],
+                ));
+            }
+        }
+        if !usage.is_empty() {
+            let period = tf(locale, Msg::DigestPeriod, &[("days", &days)]);
+            text.push('\n');
+            text.push_str(&format_usage(locale, &period, &usage));
+        }
+        let queue = self.processor.queue_snapshot();
+        text.push_str(&tf(
+            locale,
+            Msg::DigestQueue,
+            &[
+                ("high", &queue.high_priority),
+                ("normal", &queue.normal_priority),
+            ],
+        ));
+        if self.processor.ai_paused() {
+            text.push_str(t(locale, Msg::DigestAiPaused));
+        }
+        notify_admin_group(&self.bot, self.config.as_ref(), &text).await;
+        Ok(())
+    }
+}
+
+fn push_counts(
+    text: &mut String,
+    locale: Locale,
+    counts: &[SpamCount],
+    label: impl Fn(&SpamCount) -> String,
+) {
+    for entry in counts {
+        let name = format!("{} ({})", escape_html(&label(entry)), entry.id);
+        text.push_str(&tf(
+            locale,
+            Msg::DigestRow,
+            &[("name", &name), ("count", &entry.count)],
+        ));
+    }
+}
+
+/// Link domains by the number of messages that contained them, most first.
+fn top_domains(texts: &[String], limit: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for text in texts {
+        for domain in link_domains(text, &[]) {
+            *counts.entry(domain).or_default() += 1;
+        }
+    }
+    let mut domains: Vec<(String, usize)> = counts.into_iter().collect();
+    domains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    domains.truncate(limit);
+    domains
+}
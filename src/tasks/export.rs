@@ -0,0 +1,146 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use flate2::{write::GzEncoder, Compression};
+
+use crate::{
+    db::{export_cursors::ExportCursorRepository, spam_log::SpamLogRepository},
+    tasks::scheduler::JobTask,
+};
+
+const SPAM_LOG_SOURCE: &str = "spam_log";
+const EXPORT_PAGE_SIZE: i64 = 1_000;
+
+/// Periodically writes spam-log rows added since the previous run to gzip-compressed JSONL files.
+pub struct SpamLogExporter {
+    spam_log: Arc<SpamLogRepository>,
+    cursors: Arc<ExportCursorRepository>,
+    export_dir: PathBuf,
+}
+
+impl SpamLogExporter {
+    pub fn new(
+        spam_log: Arc<SpamLogRepository>,
+        cursors: Arc<ExportCursorRepository>,
+        export_dir: PathBuf,
+    ) -> Self {
+        Self {
+            spam_log,
+            cursors,
+            export_dir,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let exporter = self.clone();
+            Box::pin(async move {
+                if let Err(err) = exporter.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "spam log export failed");
+                }
+            })
+        })
+    }
+
+    /// Streams new rows page by page into a `.part` file that is renamed into place once
+    /// complete, so memory stays bounded by one page and a failed run leaves no partial
+    /// export behind.
+    pub async fn run(&self) -> Result<()> {
+        let file_name = format!(
+            "{}-{}.jsonl.gz",
+            SPAM_LOG_SOURCE,
+            Utc::now().format("%Y%m%dT%H%M%S")
+        );
+        let path = self.export_dir.join(file_name);
+        let part = path.with_extension("gz.part");
+
+        let written = self.write_pages(&part).await;
+        let (last_id, count) = match written {
+            Ok(Some(done)) => done,
+            Ok(None) => {
+                tracing::debug!(target: "scheduler", "no new spam log rows to export");
+                return Ok(());
+            }
+            Err(err) => {
+                let _ = fs::remove_file(&part);
+                return Err(err);
+            }
+        };
+        fs::rename(&part, &path)
+            .with_context(|| format!("failed to move export into {}", path.display()))?;
+        self.cursors.set(SPAM_LOG_SOURCE, last_id).await?;
+
+        tracing::info!(
+            target: "scheduler",
+            rows = count,
+            path = %path.display(),
+            "spam log rows exported"
+        );
+        Ok(())
+    }
+
+    /// Writes every row after the cursor to `path`, returning the last exported ID and the
+    /// row count, or `None` (creating no file) when there was nothing new.
+    async fn write_pages(&self, path: &Path) -> Result<Option<(i64, usize)>> {
+        let mut last_id = self.cursors.get(SPAM_LOG_SOURCE).await?;
+        let mut encoder: Option<GzEncoder<File>> = None;
+        let mut count = 0;
+        loop {
+            let rows = self.spam_log.list_after(last_id, EXPORT_PAGE_SIZE).await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id;
+            let lines = rows
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<_>, _>>()?;
+            count += lines.len();
+            let current = encoder.take();
+            let path = path.to_path_buf();
+            encoder = Some(
+                tokio::task::spawn_blocking(move || {
+                    let current = match current {
+                        Some(current) => current,
+                        None => create_gzip(&path)?,
+                    };
+                    write_lines(current, &lines)
+                })
+                .await??,
+            );
+            if (rows.len() as i64) < EXPORT_PAGE_SIZE {
+                break;
+            }
+        }
+
+        let Some(encoder) = encoder else {
+            return Ok(None);
+        };
+        tokio::task::spawn_blocking(move || encoder.finish()).await??;
+        Ok(Some((last_id, count)))
+    }
+}
+
+fn create_gzip(path: &Path) -> Result<GzEncoder<File>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create export dir {}", parent.display()))?;
+    }
+    let file = File::create(path)
+        .with_context(|| format!("failed to create export file {}", path.display()))?;
+    Ok(GzEncoder::new(file, Compression::default()))
+}
+
+fn write_lines(mut encoder: GzEncoder<File>, lines: &[String]) -> Result<GzEncoder<File>> {
+    for line in lines {
+        encoder.write_all(line.as_bytes())?;
+        encoder.write_all(b"\n")?;
+    }
+    Ok(encoder)
+}
@@ -1,3 +1,17 @@
+pub mod admin_log;
+pub mod backup;
+pub mod ban_expiry;
+pub mod bayes_training;
+pub mod circuit_breaker;
+pub mod db_maintenance;
+pub mod digest;
+pub mod export;
 pub mod processor;
 pub mod queue;
+pub mod reputation_decay;
+pub mod retention;
 pub mod scheduler;
+pub mod status_board;
+pub mod usage_summary;
+pub mod whitelist_audit;
+pub mod whitelist_expiry;
@@ -1,45 +1,204 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
-use teloxide::{prelude::*, types::ParseMode};
+use parking_lot::Mutex;
+use teloxide::{
+    prelude::*,
+    types::{ChatPermissions, MessageId, ParseMode, ThreadId},
+};
 use tokio::{task::JoinHandle, time::sleep};
 
 use crate::{
-    ai::CerebrasClient,
-    config::AppConfig,
-    domain::{ClassificationDecision, ClassificationMap, MessageJob, WebContent},
-    infrastructure::shutdown::ShutdownListener,
-    tasks::queue::MessageQueue,
+    ai::{prompt::SystemPrompt, shadow::ShadowClassifier, ClassificationProvider, PromptEntry},
+    config::{AppConfig, EnforcementAction, PrefilterConfig},
+    db::{
+        deletion_notices::{DeletionNotice, DeletionNoticeRepository},
+        examples::LabeledExample,
+        shadow_results::ShadowResult,
+        spam_log::SpamLogEntry,
+        Repositories,
+    },
+    domain::{ClassificationDecision, ClassificationMap, MessageJob, QueueSnapshot, WebContent},
+    heuristics::{known_spam::url_domain, language},
+    i18n::{t, tf, Msg},
+    infrastructure::{notifier::notify_admin_group, shutdown::ShutdownListener},
+    tasks::{
+        admin_log::{spam_log_buttons, AdminLogBatcher, BatchEntry},
+        circuit_breaker::CircuitBreaker,
+        queue::{MessageQueue, Priority},
+    },
+    telegram::utils::{contains_url, escape_html, message_link},
     web_content::WebContentFetcher,
 };
 
 const DEFAULT_REASON: &str = "모델이 사유를 제공하지 않았습니다.";
+/// Low-confidence verdicts nobody has acted on are forgotten after this long.
+const REVIEW_TTL_HOURS: i64 = 24;
+/// Longest message excerpt in a combined admin log entry.
+const BATCH_EXCERPT_CHARS: usize = 80;
+/// How far back the classification throughput is averaged.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(600);
+/// Longest example text quoted in the few-shot section.
+const EXAMPLE_MAX_CHARS: usize = 300;
+/// Failed classification requests after which a job is dropped instead of requeued.
+const MAX_CLASSIFY_ATTEMPTS: u32 = 5;
+
+/// What [`MessageProcessor::remove_spam`] left behind.
+pub struct RemovedSpam {
+    pub deleted_at: DateTime<Utc>,
+    /// Spam log row id; `None` when recording the log failed.
+    pub log_id: Option<i64>,
+    /// Link to the copy in `ARCHIVE_CHANNEL_ID`.
+    pub archive_link: Option<String>,
+}
+
+/// A spam verdict held back for an admin decision because the model was unsure, the
+/// sender administers the chat or the chat is in dry-run mode.
+pub struct PendingReview {
+    pub job: MessageJob,
+    pub reason: String,
+    pub confidence: f32,
+}
+
+/// Prompt entries classified in one request, with the chat's own criteria when the group
+/// holds a single chat that has some.
+struct PromptGroup {
+    criteria: Option<String>,
+    entries: Vec<PromptEntry>,
+}
 
 pub struct MessageProcessor {
     queue: Arc<MessageQueue<MessageJob>>,
     bot: Bot,
-    cerebras: Arc<CerebrasClient>,
+    classifier: Arc<dyn ClassificationProvider>,
+    system_prompt: Arc<SystemPrompt>,
     web_fetcher: Arc<WebContentFetcher>,
     config: Arc<AppConfig>,
+    db: Repositories,
+    busy: AtomicBool,
+    breaker: CircuitBreaker,
+    reviews: Mutex<HashMap<(i64, i32), PendingReview>>,
+    admin_logs: Option<AdminLogBatcher>,
+    /// When the AI last returned a verdict.
+    last_classified: Mutex<Option<DateTime<Utc>>>,
+    /// Messages in each classified group over the last [`THROUGHPUT_WINDOW`].
+    classified: Mutex<VecDeque<(Instant, usize)>>,
+    /// When each sender was last told about a deletion (`NOTIFY_SENDER_ENABLED`).
+    notified_senders: Mutex<HashMap<i64, Instant>>,
+    shadow: Option<Arc<ShadowClassifier>>,
+    /// Bots other than `bot` that moderate some chats, keyed by chat ID.
+    chat_bots: HashMap<i64, Bot>,
 }
 
 impl MessageProcessor {
     pub fn new(
         queue: Arc<MessageQueue<MessageJob>>,
         bot: Bot,
-        cerebras: Arc<CerebrasClient>,
+        classifier: Arc<dyn ClassificationProvider>,
+        system_prompt: Arc<SystemPrompt>,
         web_fetcher: Arc<WebContentFetcher>,
         config: Arc<AppConfig>,
+        db: Repositories,
     ) -> Self {
+        let admin_logs = config
+            .admin_log_batch
+            .map(|window| AdminLogBatcher::new(bot.clone(), config.clone(), window));
+        let breaker = CircuitBreaker::new(
+            config.resilience.ai_breaker_threshold,
+            config.resilience.ai_breaker_cooldown,
+        );
         Self {
             queue,
             bot,
-            cerebras,
+            classifier,
+            system_prompt,
             web_fetcher,
             config,
+            db,
+            busy: AtomicBool::new(false),
+            breaker,
+            reviews: Mutex::new(HashMap::new()),
+            admin_logs,
+            last_classified: Mutex::new(None),
+            classified: Mutex::new(VecDeque::new()),
+            notified_senders: Mutex::new(HashMap::new()),
+            shadow: None,
+            chat_bots: HashMap::new(),
+        }
+    }
+
+    /// Acts in `chat_ids` through `bot` instead of the primary bot.
+    pub fn with_chat_bot(mut self, chat_ids: &[i64], bot: Bot) -> Self {
+        for chat_id in chat_ids {
+            self.chat_bots.insert(*chat_id, bot.clone());
+        }
+        self
+    }
+
+    /// The bot that moderates `chat_id`.
+    pub fn bot_for(&self, chat_id: ChatId) -> &Bot {
+        self.chat_bots.get(&chat_id.0).unwrap_or(&self.bot)
+    }
+
+    /// Also runs `shadow` on every batch, recording how its verdicts compare.
+    pub fn with_shadow(mut self, shadow: ShadowClassifier) -> Self {
+        self.shadow = Some(Arc::new(shadow));
+        self
+    }
+
+    /// Returns `true` while a batch is being classified or acted upon.
+    pub fn is_busy(&self) -> bool {
+        self.busy.load(Ordering::SeqCst)
+    }
+
+    pub fn queue_snapshot(&self) -> QueueSnapshot {
+        self.queue.snapshot()
+    }
+
+    /// `true` while the circuit breaker holds classification back.
+    pub fn ai_paused(&self) -> bool {
+        self.breaker.is_open(Instant::now())
+    }
+
+    /// When the AI last returned a verdict since startup.
+    pub fn last_classified(&self) -> Option<DateTime<Utc>> {
+        *self.last_classified.lock()
+    }
+
+    /// Messages classified per minute, averaged over the last ten minutes.
+    pub fn messages_per_minute(&self) -> f64 {
+        let mut classified = self.classified.lock();
+        prune_classified(&mut classified);
+        let total: usize = classified.iter().map(|(_, count)| count).sum();
+        total as f64 / (THROUGHPUT_WINDOW.as_secs_f64() / 60.0)
+    }
+
+    pub fn system_prompt(&self) -> &SystemPrompt {
+        &self.system_prompt
+    }
+
+    /// Removes and returns the review waiting on the given message, if any.
+    pub fn take_review(&self, chat_id: i64, message_id: i32) -> Option<PendingReview> {
+        self.reviews.lock().remove(&(chat_id, message_id))
+    }
+
+    /// Classifies whatever is queued right now, for driving the pipeline from tests.
+    #[cfg(all(test, feature = "testing"))]
+    pub async fn process_pending(&self, shutdown: &mut ShutdownListener) -> Result<()> {
+        let batch = self.queue.drain_ordered();
+        if batch.is_empty() {
+            return Ok(());
         }
+        self.handle_batch(batch, shutdown).await
     }
 
     pub fn spawn(self: Arc<Self>, mut shutdown: ShutdownListener) -> JoinHandle<()> {
@@ -56,8 +215,18 @@ impl MessageProcessor {
                 break;
             }
 
+            if self.breaker.is_open(Instant::now()) {
+                tokio::select! {
+                    _ = sleep(Duration::from_millis(500)) => {}
+                    _ = shutdown.notified() => break,
+                }
+                continue;
+            }
+
+            self.busy.store(true, Ordering::SeqCst);
             let batch = self.queue.drain_ordered();
             if batch.is_empty() {
+                self.busy.store(false, Ordering::SeqCst);
                 tokio::select! {
                     _ = sleep(Duration::from_millis(500)) => {}
                     _ = shutdown.notified() => break,
@@ -67,6 +236,7 @@ impl MessageProcessor {
             if let Err(err) = self.handle_batch(batch, shutdown).await {
                 tracing::error!(target: "processor", error = %err, "failed to handle batch");
             }
+            self.busy.store(false, Ordering::SeqCst);
         }
         tracing::info!(target: "processor", "message processor stopped");
         Ok(())
@@ -78,9 +248,11 @@ impl MessageProcessor {
         shutdown: &mut ShutdownListener,
     ) -> Result<()> {
         tracing::info!(target: "processor", total = batch.len(), "processing batch");
+        let batch = self.prefilter(batch);
         let mut prompt_entries = Vec::with_capacity(batch.len());
         let mut lookup: HashMap<String, MessageJob> = HashMap::new();
 
+        let mut jobs = Vec::with_capacity(batch.len());
         for job in batch {
             if shutdown.is_triggered() {
                 tracing::info!(
@@ -89,65 +261,393 @@ impl MessageProcessor {
                 );
                 return Ok(());
             }
+            let urls = self.fetchable_urls(&job).await;
+            jobs.push((job, urls));
+        }
 
-            let member_flag = if job.is_group_member {
-                "멤버"
-            } else {
-                "비멤버"
-            };
-            let username = job.username.as_deref().unwrap_or("-");
-            let mut entry = format!(
-                "{}: [{} | {} | {}] [우선순위: {}] {}",
-                job.message_id.0,
-                job.from_display,
-                username,
-                member_flag,
-                job.priority_score,
-                job.text
-            );
-
-            for url in &job.urls {
-                let content = tokio::select! {
-                    res = self.web_fetcher.fetch(url) => res,
-                    _ = shutdown.notified() => {
-                        tracing::info!(
-                            target: "processor",
-                            url = %url,
-                            "shutdown requested during web fetch; aborting batch"
-                        );
-                        return Ok(());
-                    }
-                }?;
+        let pages = tokio::select! {
+            pages = self.web_fetcher.fetch_all(
+                jobs.iter().flat_map(|(_, urls)| urls.iter().map(String::as_str)),
+            ) => pages,
+            _ = shutdown.notified() => {
+                tracing::info!(
+                    target: "processor",
+                    "shutdown requested during web fetch; aborting batch"
+                );
+                return Ok(());
+            }
+        };
 
-                if let Some(content) = content {
-                    entry.push_str("\n웹페이지 정보 (");
-                    entry.push_str(url);
-                    entry.push_str("):\n");
-                    entry.push_str(&format_web_content(&content));
+        for (job, urls) in jobs {
+            let mut entry = prompt_entry(&job);
+            for url in &urls {
+                if let Some(content) = pages.get(url) {
+                    push_web_content(&mut entry.rendered, url, content);
                 }
             }
 
-            lookup.insert(job.message_id.0.to_string(), job);
-            prompt_entries.push(entry);
+            let chat_id = job.chat_id.0;
+            lookup.insert(entry.id.clone(), job);
+            prompt_entries.push((chat_id, entry));
         }
 
         if prompt_entries.is_empty() {
             return Ok(());
         }
 
-        let prompt = prompt_entries.join("\n\n");
-        let classification = tokio::select! {
-            res = self.cerebras.classify(&prompt) => res,
-            _ = shutdown.notified() => {
-                tracing::info!(
+        for PromptGroup { criteria, entries } in self.group_by_criteria(prompt_entries).await {
+            let group: HashMap<String, MessageJob> = entries
+                .iter()
+                .filter_map(|entry| lookup.remove_entry(&entry.id))
+                .collect();
+            if self.breaker.is_open(Instant::now()) {
+                self.requeue(group.into_values());
+                continue;
+            }
+            let system = self
+                .system_for(&self.system_prompt, &group, criteria.as_deref())
+                .await;
+            let result = tokio::select! {
+                res = self.classifier.classify(&system, &entries) => res,
+                _ = shutdown.notified() => {
+                    tracing::info!(
+                        target: "processor",
+                        "shutdown requested during classify call; aborting batch"
+                    );
+                    return Ok(());
+                }
+            };
+            if let (Some(shadow), Ok(classification)) = (&self.shadow, &result) {
+                self.run_shadow(shadow, &group, criteria.as_deref(), entries, classification)
+                    .await;
+            }
+            self.finish_group(result, group).await?;
+        }
+        Ok(())
+    }
+
+    /// The job's links outside allowlisted domains, which are trusted and never fetched.
+    async fn fetchable_urls(&self, job: &MessageJob) -> Vec<String> {
+        let mut urls = Vec::with_capacity(job.urls.len());
+        for url in &job.urls {
+            let allowed = match url_domain(url) {
+                Some(domain) => self
+                    .db
+                    .allowed_domains
+                    .contains(&domain)
+                    .await
+                    .unwrap_or_else(|err| {
+                        tracing::warn!(target: "db", error = %err, "failed to check allowed domains");
+                        false
+                    }),
+                None => false,
+            };
+            if !allowed {
+                urls.push(url.clone());
+            }
+        }
+        urls
+    }
+
+    /// Classifies `job` on its own with the live prompt and the chat's criteria, without
+    /// acting on the verdict; `None` when the model returned none for it.
+    pub async fn classify_only(&self, job: MessageJob) -> Result<Option<ClassificationDecision>> {
+        let mut entry = prompt_entry(&job);
+        let urls = self.fetchable_urls(&job).await;
+        let pages = self
+            .web_fetcher
+            .fetch_all(urls.iter().map(String::as_str))
+            .await;
+        for url in &urls {
+            if let Some(content) = pages.get(url) {
+                push_web_content(&mut entry.rendered, url, content);
+            }
+        }
+        let chat_id = job.chat_id.0;
+        let criteria = self
+            .db
+            .chat_settings
+            .criteria_for(&[chat_id])
+            .await?
+            .remove(&chat_id);
+        let message_id = entry.id.clone();
+        let lookup = HashMap::from([(message_id.clone(), job)]);
+        let system = self
+            .system_for(&self.system_prompt, &lookup, criteria.as_deref())
+            .await;
+        let mut verdicts = self
+            .classifier
+            .classify(&system, std::slice::from_ref(&entry))
+            .await?;
+        Ok(verdicts.remove(&message_id))
+    }
+
+    /// Splits the batch so each chat with admin-defined criteria is classified on its
+    /// own with those criteria; every other chat shares one request.
+    async fn group_by_criteria(&self, entries: Vec<(i64, PromptEntry)>) -> Vec<PromptGroup> {
+        let mut chat_ids: Vec<i64> = entries.iter().map(|(chat_id, _)| *chat_id).collect();
+        chat_ids.sort_unstable();
+        chat_ids.dedup();
+        let criteria = match self.db.chat_settings.criteria_for(&chat_ids).await {
+            Ok(criteria) => criteria,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to load chat criteria");
+                HashMap::new()
+            }
+        };
+        let mut shared = Vec::new();
+        let mut by_chat: HashMap<i64, Vec<PromptEntry>> = HashMap::new();
+        for (chat_id, entry) in entries {
+            if criteria.contains_key(&chat_id) {
+                by_chat.entry(chat_id).or_default().push(entry);
+            } else {
+                shared.push(entry);
+            }
+        }
+        let mut groups: Vec<PromptGroup> = by_chat
+            .into_iter()
+            .map(|(chat_id, entries)| PromptGroup {
+                criteria: criteria.get(&chat_id).cloned(),
+                entries,
+            })
+            .collect();
+        if !shared.is_empty() {
+            groups.push(PromptGroup {
+                criteria: None,
+                entries: shared,
+            });
+        }
+        groups
+    }
+
+    /// System prompt for one request: the rendered template, the chat's own criteria
+    /// and few-shot examples.
+    async fn system_for(
+        &self,
+        template: &SystemPrompt,
+        lookup: &HashMap<String, MessageJob>,
+        chat_criteria: Option<&str>,
+    ) -> String {
+        let mut chat_titles: Vec<&str> = lookup
+            .values()
+            .filter_map(|job| job.chat_title.as_deref())
+            .collect();
+        chat_titles.sort_unstable();
+        chat_titles.dedup();
+        let mut system = template.render(&chat_titles, dominant_language(lookup.values()));
+        if let Some(criteria) = chat_criteria {
+            system.push_str(&format!(
+                "\n\nAdditional spam criteria set by this chat's admins. They apply to every message below:\n{criteria}"
+            ));
+        }
+        let mut chat_ids: Vec<i64> = lookup.values().map(|job| job.chat_id.0).collect();
+        chat_ids.sort_unstable();
+        chat_ids.dedup();
+        system.push_str(&self.few_shot_section(&chat_ids).await);
+        system
+    }
+
+    /// Classifies the same prompt with the shadow in the background and records where it
+    /// agrees with the live verdicts.
+    async fn run_shadow(
+        &self,
+        shadow: &Arc<ShadowClassifier>,
+        lookup: &HashMap<String, MessageJob>,
+        chat_criteria: Option<&str>,
+        entries: Vec<PromptEntry>,
+        live: &ClassificationMap,
+    ) {
+        let template = shadow.prompt.as_deref().unwrap_or(&self.system_prompt);
+        let system = self.system_for(template, lookup, chat_criteria).await;
+        let jobs: Vec<(String, i64, String, bool)> = lookup
+            .iter()
+            .map(|(message_id, job)| {
+                let live_spam = live.get(message_id).is_some_and(|decision| decision.spam);
+                (
+                    message_id.clone(),
+                    job.chat_id.0,
+                    job.text.clone(),
+                    live_spam,
+                )
+            })
+            .collect();
+        let shadow = shadow.clone();
+        let results_repo = self.db.shadow_results.clone();
+        tokio::spawn(async move {
+            let verdicts = match shadow.provider.classify(&system, &entries).await {
+                Ok(verdicts) => verdicts,
+                Err(err) => {
+                    tracing::warn!(target: "processor", error = %format!("{err:#}"), "shadow classification failed");
+                    return;
+                }
+            };
+            let results: Vec<ShadowResult> = jobs
+                .into_iter()
+                .map(|(message_id, chat_id, text, live_spam)| {
+                    let verdict = verdicts.get(&message_id);
+                    ShadowResult {
+                        chat_id,
+                        message_id: message_id.parse().unwrap_or_default(),
+                        text,
+                        live_spam,
+                        shadow_spam: verdict.is_some_and(|decision| decision.spam),
+                        shadow_reason: verdict.and_then(|decision| decision.reason.clone()),
+                    }
+                })
+                .collect();
+            tracing::info!(
+                target: "processor",
+                compared = results.len(),
+                disagreements = results
+                    .iter()
+                    .filter(|result| result.live_spam != result.shadow_spam)
+                    .count(),
+                "shadow classification recorded"
+            );
+            if let Err(err) = results_repo.record(&results).await {
+                tracing::warn!(target: "db", error = %err, "failed to record shadow results");
+            }
+        });
+    }
+
+    async fn finish_group(
+        &self,
+        result: Result<ClassificationMap>,
+        lookup: HashMap<String, MessageJob>,
+    ) -> Result<()> {
+        match result {
+            Ok(classification) => {
+                *self.last_classified.lock() = Some(Utc::now());
+                {
+                    let mut classified = self.classified.lock();
+                    classified.push_back((Instant::now(), lookup.len()));
+                    prune_classified(&mut classified);
+                }
+                if self.breaker.record_success() {
+                    tracing::info!(target: "processor", "AI classification recovered; breaker closed");
+                    notify_admin_group(
+                        &self.bot,
+                        self.config.as_ref(),
+                        t(self.config.language, Msg::AiRecovered),
+                    )
+                    .await;
+                }
+                self.apply_classification(classification, lookup).await
+            }
+            Err(err) => {
+                let (retry, exhausted): (Vec<_>, Vec<_>) = lookup
+                    .into_values()
+                    .map(|mut job| {
+                        job.attempts += 1;
+                        job
+                    })
+                    .partition(|job| job.attempts < MAX_CLASSIFY_ATTEMPTS);
+                let requeued = retry.len();
+                self.requeue(retry.into_iter());
+                tracing::error!(
                     target: "processor",
-                    "shutdown requested during Cerebras classify call; aborting batch"
+                    error = %format!("{err:#}"),
+                    requeued,
+                    dropped = exhausted.len(),
+                    "classification failed; batch requeued"
                 );
-                return Ok(());
+                for job in exhausted {
+                    tracing::warn!(
+                        target: "processor",
+                        chat_id = job.chat_id.0,
+                        message_id = job.message_id.0,
+                        attempts = job.attempts,
+                        "dropping message after repeated classification failures"
+                    );
+                }
+                if self.breaker.record_failure(Instant::now()) {
+                    let cooldown = self.breaker.cooldown();
+                    tracing::warn!(
+                        target: "processor",
+                        cooldown_secs = cooldown.as_secs(),
+                        "AI classification keeps failing; breaker opened"
+                    );
+                    notify_admin_group(
+                        &self.bot,
+                        self.config.as_ref(),
+                        &tf(
+                            self.config.language,
+                            Msg::AiBreakerOpen,
+                            &[("secs", &cooldown.as_secs())],
+                        ),
+                    )
+                    .await;
+                }
+                Ok(())
             }
-        }?;
+        }
+    }
+
+    /// Minimum confidence for deleting without review; lower for members on probation.
+    fn confidence_threshold(&self, job: &MessageJob) -> f32 {
+        match &self.config.probation {
+            Some(probation) if job.on_probation => probation.confidence_threshold,
+            _ => self.config.live.get().ai_confidence_threshold,
+        }
+    }
+
+    /// Drops messages that are obviously ham so they never reach the classifier.
+    fn prefilter(&self, batch: Vec<MessageJob>) -> Vec<MessageJob> {
+        let live = self.config.live.get();
+        let Some(config) = &live.heuristics.prefilter else {
+            return batch;
+        };
+        let total = batch.len();
+        let remaining: Vec<MessageJob> = batch
+            .into_iter()
+            .filter(|job| !is_obvious_ham(job, config))
+            .collect();
+        if remaining.len() < total {
+            tracing::debug!(
+                target: "processor",
+                skipped = total - remaining.len(),
+                "obvious ham skipped before classification"
+            );
+        }
+        remaining
+    }
+
+    /// Recent admin-confirmed examples, preferring the batch's own chats; empty when
+    /// disabled or nothing has been labeled yet.
+    async fn few_shot_section(&self, chat_ids: &[i64]) -> String {
+        let limit = self.config.prompt.few_shot_examples;
+        if limit == 0 {
+            return String::new();
+        }
+        let mut examples = Vec::new();
+        for spam in [true, false] {
+            match self.db.examples.recent(chat_ids, spam, limit).await {
+                Ok(rows) => examples.extend(rows),
+                Err(err) => {
+                    tracing::warn!(target: "db", error = %err, "failed to load few-shot examples");
+                }
+            }
+        }
+        format_examples(&examples)
+    }
 
-        self.apply_classification(classification, lookup).await
+    /// Puts jobs back so they are classified once the provider answers again.
+    fn requeue(&self, jobs: impl Iterator<Item = MessageJob>) {
+        let threshold = self
+            .config
+            .live
+            .get()
+            .heuristics
+            .risk
+            .high_priority_threshold;
+        for job in jobs {
+            let priority = if job.priority_score >= threshold {
+                Priority::High
+            } else {
+                Priority::Normal
+            };
+            self.queue.push(priority, job);
+        }
     }
 
     async fn apply_classification(
@@ -155,8 +655,20 @@ impl MessageProcessor {
         classification: ClassificationMap,
         mut lookup: HashMap<String, MessageJob>,
     ) -> Result<()> {
-        for (message_id, ClassificationDecision { spam, reason }) in classification {
+        for (
+            message_id,
+            ClassificationDecision {
+                spam,
+                reason,
+                confidence,
+            },
+        ) in classification
+        {
             if !spam {
+                if let Some(job) = lookup.remove(&message_id) {
+                    self.keep_ham_sample(&job).await;
+                    self.reward_clean(&job).await;
+                }
                 continue;
             }
             if let Some(job) = lookup.remove(&message_id) {
@@ -165,7 +677,16 @@ impl MessageProcessor {
                     .map(str::trim)
                     .filter(|s| !s.is_empty())
                     .unwrap_or(DEFAULT_REASON);
-                if let Err(err) = self.delete_spam(&job, reason_text).await {
+                if job.sender_is_chat_admin || confidence < self.confidence_threshold(&job) {
+                    self.request_review(PendingReview {
+                        job,
+                        reason: reason_text.to_string(),
+                        confidence,
+                    })
+                    .await;
+                    continue;
+                }
+                if let Err(err) = self.act_on_spam(&job, reason_text, Some(confidence)).await {
                     tracing::error!(
                         target: "processor",
                         error = %err,
@@ -179,8 +700,546 @@ impl MessageProcessor {
         Ok(())
     }
 
-    async fn delete_spam(&self, job: &MessageJob, reason: &str) -> Result<()> {
-        self.bot
+    /// Keeps a cleared message as training data for the offline classifier.
+    async fn keep_ham_sample(&self, job: &MessageJob) {
+        let Some(bayes) = &self.config.bayes else {
+            return;
+        };
+        if job.text.trim().is_empty() {
+            return;
+        }
+        if let Err(err) = self
+            .db
+            .ham_samples
+            .add(job.chat_id.0, &job.text, bayes.ham_samples)
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, "failed to store ham sample");
+        }
+    }
+
+    /// Rewards a cleared message at most once a day, and only from members past probation
+    /// and `REPUTATION_MIN_TENURE_DAYS`; members whose join was never seen count as
+    /// long-standing.
+    async fn reward_clean(&self, job: &MessageJob) {
+        let reputation = &self.config.reputation;
+        let Some(user_id) = job.from_id else {
+            return;
+        };
+        if reputation.clean_reward == 0
+            || job.reputation >= reputation.max_score
+            || !job.is_group_member
+            || job.on_probation
+        {
+            return;
+        }
+        let now = Utc::now();
+        let tenure = chrono::Duration::from_std(reputation.min_tenure).unwrap_or_default();
+        match self.db.member_joins.joined_at(job.chat_id.0, user_id).await {
+            Ok(Some(joined_at)) if now - joined_at < tenure => return,
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to load member join");
+                return;
+            }
+        }
+        if let Err(err) = self
+            .db
+            .reputation
+            .record_clean(
+                user_id,
+                reputation.clean_reward,
+                reputation.max_score,
+                now,
+                now - chrono::Duration::days(1),
+            )
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, user_id, "failed to reward clean message");
+        }
+    }
+
+    /// Deletes the message and acts against its sender, or in a dry-run chat sends it to
+    /// the admin group for review instead.
+    pub async fn delete_spam(&self, job: &MessageJob, reason: &str) -> Result<()> {
+        self.act_on_spam(job, reason, None).await
+    }
+
+    /// [`MessageProcessor::delete_spam`] with the classifier's confidence, shown in the
+    /// admin log when known.
+    async fn act_on_spam(
+        &self,
+        job: &MessageJob,
+        reason: &str,
+        confidence: Option<f32>,
+    ) -> Result<()> {
+        if self.dry_run(job.chat_id.0).await {
+            self.request_review(PendingReview {
+                job: job.clone(),
+                reason: reason.to_string(),
+                confidence: confidence.unwrap_or(1.0),
+            })
+            .await;
+            return Ok(());
+        }
+        let removed = self.remove_confirmed(job, reason).await?;
+        self.blacklist_sender(job, reason).await;
+        let mut action = self.enforcement_action(job.chat_id.0).await;
+        let strikes = self.record_strike(job, removed.deleted_at).await;
+        if let (Some(count), Some(config)) = (strikes, &self.config.enforcement.strikes) {
+            action = action.stricter(config.action_for(count));
+        }
+        let mut outcome = self.enforce(job, action).await;
+        if let Some(count) = strikes {
+            outcome.push_str(&tf(
+                self.config.language,
+                Msg::StrikeCount,
+                &[("count", &count)],
+            ));
+        }
+        if let Some(log_id) = removed.log_id {
+            if let Err(err) = self
+                .db
+                .spam_log
+                .set_outcome(log_id, &outcome, confidence)
+                .await
+            {
+                tracing::warn!(target: "processor", error = %err, log_id, "failed to record spam outcome");
+            }
+        }
+        self.send_admin_log(job, &removed, reason, confidence, &outcome)
+            .await;
+        self.post_deletion_notice(job).await;
+        self.notify_sender(job, reason).await;
+        Ok(())
+    }
+
+    /// Privately tells the sender why their message was deleted and how to appeal,
+    /// unless they were told within the cooldown.
+    async fn notify_sender(&self, job: &MessageJob, reason: &str) {
+        let (Some(cooldown), Some(user_id)) = (self.config.enforcement.notify_sender, job.from_id)
+        else {
+            return;
+        };
+        {
+            let mut notified = self.notified_senders.lock();
+            notified.retain(|_, at| at.elapsed() < cooldown);
+            if notified.contains_key(&user_id) {
+                return;
+            }
+            notified.insert(user_id, Instant::now());
+        }
+        let locale = self
+            .db
+            .chat_settings
+            .locale(job.chat_id.0, self.config.language)
+            .await;
+        let text = tf(
+            locale,
+            Msg::SenderNotice,
+            &[
+                (
+                    "chat",
+                    &job.chat_title
+                        .as_deref()
+                        .unwrap_or(t(locale, Msg::Untitled)),
+                ),
+                ("reason", &reason),
+            ],
+        );
+        if let Err(err) = self
+            .bot_for(job.chat_id)
+            .send_message(ChatId(user_id), text)
+            .await
+        {
+            // Users who never started the bot cannot be messaged.
+            tracing::debug!(target: "processor", error = %err, user_id, "failed to notify spam sender");
+        }
+    }
+
+    /// Tells the chat a spam message was removed and deletes the notice again after the
+    /// chat's `/notice` time or `DELETION_NOTICE_SECS`. While a notice is still up in
+    /// the topic, further deletions only keep it up longer instead of posting another.
+    async fn post_deletion_notice(&self, job: &MessageJob) {
+        let chat_id = job.chat_id.0;
+        let ttl = match self.db.chat_settings.notice_secs(chat_id).await {
+            Ok(Some(secs)) => (secs > 0).then(|| Duration::from_secs(secs as u64)),
+            Ok(None) => self.config.enforcement.notice_ttl,
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, chat_id, "failed to load notice setting");
+                self.config.enforcement.notice_ttl
+            }
+        };
+        let Some(ttl) = ttl else {
+            return;
+        };
+        let delete_at = Utc::now() + chrono::Duration::from_std(ttl).unwrap_or_default();
+        match self
+            .db
+            .deletion_notices
+            .extend(chat_id, job.topic_id, delete_at)
+            .await
+        {
+            Ok(true) => return,
+            Ok(false) => {}
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, chat_id, "failed to extend deletion notice");
+            }
+        }
+        let locale = self
+            .db
+            .chat_settings
+            .locale(chat_id, self.config.language)
+            .await;
+        let bot = self.bot_for(job.chat_id).clone();
+        let mut request = bot.send_message(job.chat_id, t(locale, Msg::SpamDeletedNotice));
+        if let Some(topic) = job.topic_id {
+            request = request.message_thread_id(ThreadId(MessageId(topic)));
+        }
+        let notice = match request.await {
+            Ok(notice) => notice,
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, chat_id, "failed to post deletion notice");
+                return;
+            }
+        };
+        let notice = DeletionNotice {
+            chat_id,
+            message_id: notice.id.0,
+            delete_at,
+        };
+        if let Err(err) = self
+            .db
+            .deletion_notices
+            .add(chat_id, notice.message_id, job.topic_id, delete_at)
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, chat_id, "failed to save deletion notice");
+        }
+        schedule_notice_removal(bot, self.db.deletion_notices.clone(), notice);
+    }
+
+    /// Picks up the removal of notices posted before a restart; overdue ones go at once.
+    pub async fn resume_deletion_notices(&self) {
+        match self.db.deletion_notices.all().await {
+            Ok(notices) => {
+                for notice in notices {
+                    let bot = self.bot_for(ChatId(notice.chat_id)).clone();
+                    schedule_notice_removal(bot, self.db.deletion_notices.clone(), notice);
+                }
+            }
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to load deletion notices")
+            }
+        }
+    }
+
+    /// Whether the chat only sends spam for review instead of deleting or punishing.
+    pub async fn dry_run(&self, chat_id: i64) -> bool {
+        match self.db.chat_settings.dry_run(chat_id).await {
+            Ok(dry_run) => dry_run,
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, chat_id, "failed to load dry-run setting");
+                false
+            }
+        }
+    }
+
+    /// Lists a sender with `BLACKLIST_MIN_DELETIONS` deleted messages in the cross-chat
+    /// blacklist, so a single false positive never bans anyone network-wide.
+    async fn blacklist_sender(&self, job: &MessageJob, reason: &str) {
+        let Some(user_id) = job.from_id else {
+            return;
+        };
+        if !self.config.blacklist.auto_add || self.config.admin_user_id == Some(user_id) {
+            return;
+        }
+        match self.db.spam_log.count_for_user(user_id).await {
+            Ok(count) if count >= self.config.blacklist.min_deletions => {}
+            Ok(_) => return,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to count sender deletions");
+                return;
+            }
+        }
+        self.blacklist_user(user_id, reason, &format!("chat:{}", job.chat_id.0))
+            .await;
+    }
+
+    /// Lists the user in the cross-chat blacklist and, with `BLACKLIST_PRE_BAN`, bans a
+    /// newly listed user from every moderated chat.
+    pub async fn blacklist_user(&self, user_id: i64, reason: &str, source: &str) {
+        match self.db.user_blacklist.add(user_id, reason, source).await {
+            Ok(true) => {
+                tracing::info!(target: "processor", user_id, source, "user blacklisted");
+                if self.config.blacklist.pre_ban {
+                    self.ban_everywhere(user_id).await;
+                }
+            }
+            Ok(false) => {}
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to blacklist sender");
+            }
+        }
+    }
+
+    /// Bans the user from every whitelisted chat and every chat an extra bot moderates;
+    /// returns how many bans succeeded.
+    pub async fn ban_everywhere(&self, user_id: i64) -> usize {
+        let mut chat_ids: Vec<i64> = self.config.live.get().allowed_chat_ids.clone();
+        chat_ids.extend(self.chat_bots.keys());
+        match self.db.whitelist.list().await {
+            Ok(rows) => chat_ids.extend(rows.into_iter().map(|row| row.chat_id)),
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to list whitelisted chats");
+            }
+        }
+        chat_ids.sort_unstable();
+        chat_ids.dedup();
+
+        let mut banned = 0;
+        for chat_id in chat_ids
+            .into_iter()
+            .filter(|chat_id| *chat_id < 0 && self.config.admin_group_id != Some(*chat_id))
+        {
+            let chat_id = ChatId(chat_id);
+            match self
+                .bot_for(chat_id)
+                .ban_chat_member(chat_id, UserId(user_id as u64))
+                .await
+            {
+                Ok(_) => {
+                    banned += 1;
+                    self.record_ban(chat_id.0, user_id, "blacklist", false)
+                        .await;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        target: "processor",
+                        error = %err,
+                        chat_id = chat_id.0,
+                        user_id,
+                        "failed to pre-ban blacklisted user"
+                    );
+                }
+            }
+        }
+        tracing::info!(target: "processor", user_id, banned, "blacklisted user pre-banned");
+        banned
+    }
+
+    /// Remembers a ban the bot applied; `timed` ones expire after `BAN_DURATION_HOURS`.
+    /// Returns when the ban will be lifted, if ever.
+    pub async fn record_ban(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        reason: &str,
+        timed: bool,
+    ) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        let expires_at = self
+            .config
+            .enforcement
+            .ban_duration
+            .filter(|_| timed)
+            .and_then(|duration| chrono::Duration::from_std(duration).ok())
+            .map(|duration| now + duration);
+        if let Err(err) = self
+            .db
+            .bans
+            .record(chat_id, user_id, reason, now, expires_at)
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, chat_id, user_id, "failed to record ban");
+        }
+        expires_at
+    }
+
+    /// Unbans the user from the chat without kicking members who are not banned, and
+    /// drops the ban record once that succeeded so a failed unban is retried.
+    pub async fn lift_ban(&self, chat_id: i64, user_id: i64) -> bool {
+        let chat = ChatId(chat_id);
+        let result = self
+            .bot_for(chat)
+            .unban_chat_member(chat, UserId(user_id as u64))
+            .only_if_banned(true)
+            .await;
+        match result {
+            Ok(_) => {
+                tracing::info!(target: "processor", chat_id, user_id, "ban lifted");
+                if let Err(err) = self.db.bans.remove(chat_id, user_id).await {
+                    tracing::warn!(target: "db", error = %err, chat_id, user_id, "failed to remove ban record");
+                }
+                true
+            }
+            Err(err) => {
+                tracing::warn!(
+                    target: "processor",
+                    error = %err,
+                    chat_id,
+                    user_id,
+                    "failed to lift ban"
+                );
+                false
+            }
+        }
+    }
+
+    /// Adds a strike for the sender and returns their count in the chat, when strikes
+    /// are enabled.
+    async fn record_strike(&self, job: &MessageJob, at: DateTime<Utc>) -> Option<i64> {
+        let config = self.config.enforcement.strikes.as_ref()?;
+        let user_id = job.from_id?;
+        let expired_before = config
+            .ttl
+            .and_then(|ttl| chrono::Duration::from_std(ttl).ok())
+            .map(|ttl| at - ttl);
+        match self
+            .db
+            .strikes
+            .add(job.chat_id.0, user_id, at, expired_before)
+            .await
+        {
+            Ok(count) => Some(count),
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, user_id, "failed to record strike");
+                None
+            }
+        }
+    }
+
+    /// The chat's own `/action` policy, or the configured default.
+    async fn enforcement_action(&self, chat_id: i64) -> EnforcementAction {
+        match self.db.chat_settings.action(chat_id).await {
+            Ok(stored) => stored.as_deref().and_then(EnforcementAction::parse),
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, chat_id, "failed to load chat action");
+                None
+            }
+        }
+        .unwrap_or(self.config.enforcement.default_action)
+    }
+
+    /// Applies `action` to the sender of an already deleted message and describes what
+    /// was done for the admin log.
+    async fn enforce(&self, job: &MessageJob, action: EnforcementAction) -> String {
+        if let (None, Some(sender_chat_id)) = (job.from_id, job.sender_chat_id) {
+            return self.ban_sender_chat(job, sender_chat_id).await;
+        }
+        let result = match (action, job.from_id) {
+            (EnforcementAction::Restrict { hours }, Some(user_id)) => self
+                .bot_for(job.chat_id)
+                .restrict_chat_member(
+                    job.chat_id,
+                    UserId(user_id as u64),
+                    ChatPermissions::empty(),
+                )
+                .until_date(Utc::now() + chrono::Duration::hours(i64::from(hours)))
+                .await
+                .map(|_| ()),
+            (EnforcementAction::Ban, Some(user_id)) => self
+                .bot_for(job.chat_id)
+                .ban_chat_member(job.chat_id, UserId(user_id as u64))
+                .await
+                .map(|_| ()),
+            _ => return EnforcementAction::Delete.describe(self.config.language),
+        };
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    target: "processor",
+                    chat_id = job.chat_id.0,
+                    user_id = job.from_id,
+                    action = %action.name(),
+                    "enforcement action applied"
+                );
+                let locale = self.config.language;
+                let mut outcome = action.describe(locale);
+                if let (EnforcementAction::Ban, Some(user_id)) = (action, job.from_id) {
+                    let expires_at = self.record_ban(job.chat_id.0, user_id, "spam", true).await;
+                    if let (Some(_), Some(duration)) =
+                        (expires_at, self.config.enforcement.ban_duration)
+                    {
+                        let hours = duration.as_secs() / 3600;
+                        outcome.push_str(&tf(locale, Msg::BanDuration, &[("hours", &hours)]));
+                    }
+                }
+                outcome
+            }
+            Err(err) => {
+                tracing::warn!(
+                    target: "processor",
+                    error = %err,
+                    chat_id = job.chat_id.0,
+                    user_id = job.from_id,
+                    action = %action.name(),
+                    "failed to apply enforcement action"
+                );
+                let locale = self.config.language;
+                tf(
+                    locale,
+                    Msg::ActionFailed,
+                    &[("action", &action.describe(locale))],
+                )
+            }
+        }
+    }
+
+    /// Channels can't be restricted, so spam posted as one bans the channel itself from
+    /// the chat instead, whatever the chat's policy.
+    async fn ban_sender_chat(&self, job: &MessageJob, sender_chat_id: i64) -> String {
+        if !self.config.enforcement.ban_sender_chats {
+            return EnforcementAction::Delete.describe(self.config.language);
+        }
+        match self
+            .bot_for(job.chat_id)
+            .ban_chat_sender_chat(job.chat_id, ChatId(sender_chat_id))
+            .await
+        {
+            Ok(_) => {
+                tracing::info!(
+                    target: "processor",
+                    chat_id = job.chat_id.0,
+                    sender_chat_id,
+                    "spam sender channel banned"
+                );
+                t(self.config.language, Msg::ActionBanChannel).to_string()
+            }
+            Err(err) => {
+                tracing::warn!(
+                    target: "processor",
+                    error = %err,
+                    chat_id = job.chat_id.0,
+                    sender_chat_id,
+                    "failed to ban spam sender channel"
+                );
+                t(self.config.language, Msg::ActionBanChannelFailed).to_string()
+            }
+        }
+    }
+
+    /// Archives and deletes the message and records it in the spam log and the sender's
+    /// reputation, without notifying the admin group. In a dry-run chat the message is
+    /// only sent for review and `None` is returned.
+    pub async fn remove_spam(&self, job: &MessageJob, reason: &str) -> Result<Option<RemovedSpam>> {
+        if self.dry_run(job.chat_id.0).await {
+            self.request_review(PendingReview {
+                job: job.clone(),
+                reason: reason.to_string(),
+                confidence: 1.0,
+            })
+            .await;
+            return Ok(None);
+        }
+        self.remove_confirmed(job, reason).await.map(Some)
+    }
+
+    /// [`MessageProcessor::remove_spam`] regardless of dry-run, for deletions an admin
+    /// confirmed or the caller already checked.
+    pub async fn remove_confirmed(&self, job: &MessageJob, reason: &str) -> Result<RemovedSpam> {
+        let archive_link = self.archive(job).await;
+        self.bot_for(job.chat_id)
             .delete_message(job.chat_id, job.message_id)
             .await
             .with_context(|| format!("failed to delete message {}", job.message_id.0))?;
@@ -193,23 +1252,105 @@ impl MessageProcessor {
             "spam message deleted"
         );
 
+        let deleted_at = Utc::now();
+        let log_id = match self
+            .db
+            .spam_log
+            .record(SpamLogEntry {
+                chat_id: job.chat_id.0,
+                chat_title: job.chat_title.clone(),
+                message_id: job.message_id.0,
+                user_id: job.from_id,
+                user_display: job.from_display.clone(),
+                text: job.text.clone(),
+                reason: reason.to_string(),
+                priority_score: job.priority_score,
+                sent_at: job.timestamp,
+                deleted_at,
+            })
+            .await
+        {
+            Ok(id) => Some(id),
+            Err(err) => {
+                tracing::warn!(target: "processor", error = %err, "failed to record spam log");
+                None
+            }
+        };
+        if let Some(user_id) = job.from_id {
+            if let Err(err) = self
+                .db
+                .reputation
+                .record_offense(user_id, self.config.reputation.offense_penalty, deleted_at)
+                .await
+            {
+                tracing::warn!(target: "processor", error = %err, user_id, "failed to record offense");
+            }
+        }
+        Ok(RemovedSpam {
+            deleted_at,
+            log_id,
+            archive_link,
+        })
+    }
+
+    /// Forwards the message to the archive channel, or copies it when the chat does not
+    /// allow forwarding, and returns a link to the archived message.
+    async fn archive(&self, job: &MessageJob) -> Option<String> {
+        let channel = ChatId(self.config.archive_channel_id?);
+        let bot = self.bot_for(job.chat_id);
+        let archived = match bot
+            .forward_message(channel, job.chat_id, job.message_id)
+            .await
+        {
+            Ok(message) => Ok(message.id),
+            Err(_) => bot.copy_message(channel, job.chat_id, job.message_id).await,
+        };
+        match archived {
+            Ok(message_id) => message_link(channel.0, None, message_id.0),
+            Err(err) => {
+                tracing::warn!(
+                    target: "processor",
+                    error = %err,
+                    chat_id = job.chat_id.0,
+                    message_id = job.message_id.0,
+                    "failed to archive spam message"
+                );
+                None
+            }
+        }
+    }
+
+    async fn send_admin_log(
+        &self,
+        job: &MessageJob,
+        removed: &RemovedSpam,
+        reason: &str,
+        confidence: Option<f32>,
+        outcome: &str,
+    ) {
+        if let Some(batcher) = &self.admin_logs {
+            let entry = BatchEntry {
+                summary: self.format_batch_entry(job, outcome),
+                log_id: removed.log_id,
+                has_sender: job.from_id.is_some(),
+            };
+            if !batcher.admit(entry) {
+                return;
+            }
+        }
         if let Some(admin_group_id) = self.config.admin_group_id {
             if admin_group_id != 0 {
-                let deleted_at = Utc::now();
-                let formatted = self.format_admin_log(job, deleted_at, Some(reason));
+                let formatted = self.format_admin_log(job, removed, reason, confidence, outcome);
                 let mut request = self
                     .bot
                     .send_message(ChatId(admin_group_id), formatted)
                     .parse_mode(ParseMode::Html);
 
-                if let Some(user_id) = job.from_id {
-                    let markup = teloxide::types::InlineKeyboardMarkup::new(vec![vec![
-                        teloxide::types::InlineKeyboardButton::callback(
-                            "유저 밴",
-                            format!("ban:{}:{}", job.chat_id.0, user_id),
-                        ),
-                    ]]);
-                    request = request.reply_markup(markup);
+                if let Some(log_id) = removed.log_id {
+                    let buttons =
+                        spam_log_buttons(self.config.language, log_id, job.from_id.is_some(), None);
+                    request = request
+                        .reply_markup(teloxide::types::InlineKeyboardMarkup::new(vec![buttons]));
                 }
 
                 if let Err(err) = request.await {
@@ -224,15 +1365,116 @@ impl MessageProcessor {
                 }
             }
         }
+    }
 
-        Ok(())
+    /// Leaves the message in place and asks the admin group to decide.
+    async fn request_review(&self, review: PendingReview) {
+        let job = &review.job;
+        tracing::info!(
+            target: "processor",
+            chat_id = job.chat_id.0,
+            message_id = job.message_id.0,
+            confidence = review.confidence,
+            "spam verdict sent for review"
+        );
+        let Some(admin_group_id) = self.config.admin_group_id.filter(|id| *id != 0) else {
+            return;
+        };
+
+        let locale = self.config.language;
+        let mut buttons = vec![
+            teloxide::types::InlineKeyboardButton::callback(
+                t(locale, Msg::ReviewDelete),
+                format!("review:delete:{}:{}", job.chat_id.0, job.message_id.0),
+            ),
+            teloxide::types::InlineKeyboardButton::callback(
+                t(locale, Msg::ReviewKeep),
+                format!("review:keep:{}:{}", job.chat_id.0, job.message_id.0),
+            ),
+        ];
+        if let Some(user_id) = job.from_id {
+            buttons.push(teloxide::types::InlineKeyboardButton::callback(
+                t(locale, Msg::ReviewBan),
+                format!("ban:{}:{}", job.chat_id.0, user_id),
+            ));
+        }
+        let text = tf(
+            locale,
+            Msg::ReviewRequest,
+            &[
+                ("confidence", &format!("{:.0}", review.confidence * 100.0)),
+                (
+                    "chat",
+                    &escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
+                ),
+                ("chat_id", &job.chat_id.0),
+                ("user", &escape_html(&job.from_display)),
+                (
+                    "user_id",
+                    &job.from_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ),
+                ("text", &escape_html(&job.text)),
+                ("reason", &escape_html(&review.reason)),
+            ],
+        );
+        let result = self
+            .bot
+            .send_message(ChatId(admin_group_id), text)
+            .parse_mode(ParseMode::Html)
+            .reply_markup(teloxide::types::InlineKeyboardMarkup::new(vec![buttons]))
+            .await;
+        if let Err(err) = result {
+            tracing::error!(
+                target: "processor",
+                error = %err,
+                admin_group_id,
+                chat_id = job.chat_id.0,
+                message_id = job.message_id.0,
+                "failed to send spam review request"
+            );
+            return;
+        }
+
+        let key = (job.chat_id.0, job.message_id.0);
+        let cutoff = Utc::now() - chrono::Duration::hours(REVIEW_TTL_HOURS);
+        let mut reviews = self.reviews.lock();
+        reviews.retain(|_, pending| pending.job.timestamp > cutoff);
+        reviews.insert(key, review);
+    }
+
+    /// One deletion's line in a combined admin log.
+    fn format_batch_entry(&self, job: &MessageJob, outcome: &str) -> String {
+        let text: String = job.text.chars().take(BATCH_EXCERPT_CHARS).collect();
+        tf(
+            self.config.language,
+            Msg::AdminLogBatchEntry,
+            &[
+                (
+                    "chat",
+                    &escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
+                ),
+                ("user", &escape_html(&job.from_display)),
+                (
+                    "user_id",
+                    &job.from_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ),
+                ("outcome", &escape_html(outcome)),
+                ("text", &escape_html(&text)),
+            ],
+        )
     }
 
     fn format_admin_log(
         &self,
         job: &MessageJob,
-        deleted_at: DateTime<Utc>,
-        reason: Option<&str>,
+        removed: &RemovedSpam,
+        reason: &str,
+        confidence: Option<f32>,
+        outcome: &str,
     ) -> String {
         let tz: Tz = self
             .config
@@ -240,33 +1482,228 @@ impl MessageProcessor {
             .parse()
             .unwrap_or(chrono_tz::Asia::Seoul);
         let sent_time = job.timestamp.with_timezone(&tz);
-        let deleted_time = deleted_at.with_timezone(&tz);
+        let deleted_time = removed.deleted_at.with_timezone(&tz);
         let user_id = job
             .from_id
             .map(|id| id.to_string())
             .unwrap_or_else(|| "unknown".to_string());
-        format!(
-            "<b>스팸 삭제 로그</b>\n\n\
-             채팅방: {}\n\
-             채팅방 ID: {}\n\
-             사용자: {}\n\
-             사용자 ID: {}\n\
-             메시지 전송 시각: {}\n\
-             삭제 완료 시각: {}\n\n\
-             스팸 메시지:\n<pre>{}</pre>\n\
-             삭제 사유:\n<pre>{}</pre>",
-            escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
-            job.chat_id.0,
-            escape_html(&job.from_display),
-            escape_html(&user_id),
-            sent_time.format("%Y-%m-%d %H:%M:%S"),
-            deleted_time.format("%Y-%m-%d %H:%M:%S"),
-            escape_html(&job.text),
-            escape_html(reason.unwrap_or(DEFAULT_REASON))
+        let locale = self.config.language;
+        let language = job
+            .language
+            .map(|code| {
+                tf(
+                    locale,
+                    Msg::DetectedLanguage,
+                    &[("language", &language::display_name(code))],
+                )
+            })
+            .unwrap_or_default();
+        let link = message_link(job.chat_id.0, job.topic_id, job.message_id.0)
+            .map(|url| tf(locale, Msg::MessageLink, &[("url", &url)]))
+            .unwrap_or_default();
+        let archive = removed
+            .archive_link
+            .as_ref()
+            .map(|url| tf(locale, Msg::ArchivedCopy, &[("url", url)]))
+            .unwrap_or_default();
+        let confidence = confidence
+            .map(|confidence| {
+                tf(
+                    locale,
+                    Msg::AiConfidence,
+                    &[("confidence", &format!("{:.0}", confidence * 100.0))],
+                )
+            })
+            .unwrap_or_default();
+        tf(
+            locale,
+            Msg::SpamLog,
+            &[
+                (
+                    "chat",
+                    &escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
+                ),
+                ("chat_id", &job.chat_id.0),
+                ("user", &escape_html(&job.from_display)),
+                ("user_id", &escape_html(&user_id)),
+                ("link", &link),
+                ("archive", &archive),
+                ("language", &language),
+                ("confidence", &confidence),
+                ("sent_at", &sent_time.format("%Y-%m-%d %H:%M:%S")),
+                ("deleted_at", &deleted_time.format("%Y-%m-%d %H:%M:%S")),
+                ("outcome", &escape_html(outcome)),
+                ("text", &escape_html(&job.text)),
+                ("reason", &escape_html(reason)),
+            ],
         )
     }
 }
 
+/// Appends a linked page's summary under the prompt entry it was found in.
+fn push_web_content(entry: &mut String, url: &str, content: &WebContent) {
+    entry.push_str("\n웹페이지 정보 (");
+    entry.push_str(url);
+    entry.push_str("):\n");
+    entry.push_str(&format_web_content(content));
+}
+
+/// The job as a classifier entry, its ID and text kept apart from the rendered prompt.
+pub fn prompt_entry(job: &MessageJob) -> PromptEntry {
+    PromptEntry {
+        id: job.message_id.0.to_string(),
+        text: job.text.clone(),
+        risk_score: job.priority_score,
+        rendered: format_prompt_entry(job),
+    }
+}
+
+/// Formats a job's prompt line and metadata; linked page summaries are appended by the
+/// caller.
+pub fn format_prompt_entry(job: &MessageJob) -> String {
+    let member_flag = if job.is_group_member {
+        "멤버"
+    } else {
+        "비멤버"
+    };
+    let username = job.username.as_deref().unwrap_or("-");
+    let mut entry = format!(
+        "{}: [{} | {} | {}] [위험 점수: {}] {}",
+        job.message_id.0, job.from_display, username, member_flag, job.priority_score, job.text
+    );
+    if let Some(days) = job.account_age_days {
+        entry.push_str(&format!("\n추정 계정 나이: 약 {days}일"));
+    }
+    if job.on_probation {
+        entry.push_str("\n최근 참여한 신규 멤버");
+    }
+    if job.reputation < 0 {
+        entry.push_str(&format!(
+            "\n평판 점수: {} (과거 스팸으로 삭제된 이력 있음)",
+            job.reputation
+        ));
+    } else if job.reputation > 0 {
+        entry.push_str(&format!(
+            "\n평판 점수: +{} (정상 메시지 이력 있음)",
+            job.reputation
+        ));
+    }
+    if job.sender_chat_id.is_some() {
+        entry.push_str("\n사용자가 아닌 채널 명의로 게시됨");
+    }
+    if !job.signals.is_empty() {
+        let details: Vec<&str> = job
+            .signals
+            .iter()
+            .map(|signal| signal.detail.as_str())
+            .collect();
+        entry.push_str(&format!("\n감지된 신호: {}", details.join(", ")));
+    }
+    entry
+}
+
+/// Deletes the notice once it is due, waiting longer while later deletions in the chat
+/// keep pushing its removal back.
+fn schedule_notice_removal(
+    bot: Bot,
+    notices: Arc<DeletionNoticeRepository>,
+    notice: DeletionNotice,
+) {
+    tokio::spawn(async move {
+        let mut delete_at = notice.delete_at;
+        loop {
+            sleep((delete_at - Utc::now()).to_std().unwrap_or_default()).await;
+            match notices.delete_at(notice.chat_id, notice.message_id).await {
+                Ok(Some(later)) if later > delete_at => delete_at = later,
+                Ok(_) => break,
+                Err(err) => {
+                    tracing::warn!(target: "db", error = %err, "failed to read deletion notice");
+                    break;
+                }
+            }
+        }
+        let chat_id = ChatId(notice.chat_id);
+        if let Err(err) = bot
+            .delete_message(chat_id, MessageId(notice.message_id))
+            .await
+        {
+            tracing::debug!(target: "processor", error = %err, "failed to remove deletion notice");
+        }
+        if let Err(err) = notices.remove(notice.chat_id, notice.message_id).await {
+            tracing::warn!(target: "db", error = %err, "failed to clear deletion notice");
+        }
+    });
+}
+
+/// Drops throughput samples older than [`THROUGHPUT_WINDOW`].
+fn prune_classified(classified: &mut VecDeque<(Instant, usize)>) {
+    while classified
+        .front()
+        .is_some_and(|(at, _)| at.elapsed() > THROUGHPUT_WINDOW)
+    {
+        classified.pop_front();
+    }
+}
+
+/// Most common detected language in the batch, if any message had one.
+fn dominant_language<'a>(jobs: impl Iterator<Item = &'a MessageJob>) -> Option<&'static str> {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for language in jobs.filter_map(|job| job.language) {
+        *counts.entry(language).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(language, count)| (*count, std::cmp::Reverse(*language)))
+        .map(|(language, _)| language)
+}
+
+/// Short plain text from a long-standing member past probation, with no links, mentions
+/// or heuristic signals. Tenure counts from the join the bot saw, not the account's age,
+/// since aged accounts are cheap to buy.
+fn is_obvious_ham(job: &MessageJob, config: &PrefilterConfig) -> bool {
+    job.is_group_member
+        && !job.on_probation
+        && job.urls.is_empty()
+        && job.signals.is_empty()
+        && job.member_since.is_some_and(|since| {
+            job.timestamp - since >= chrono::Duration::days(config.min_member_days)
+        })
+        && job.text.chars().count() <= config.max_chars
+        && !contains_url(&job.text)
+        && !job.text.contains('@')
+        && !job.text.to_lowercase().contains("t.me/")
+}
+
+fn format_examples(examples: &[LabeledExample]) -> String {
+    if examples.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from(
+        "\n\nMessages previously reviewed by this community's admins. Follow the same judgement for similar messages:",
+    );
+    for example in examples {
+        let text: String = example
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(EXAMPLE_MAX_CHARS)
+            .collect();
+        let verdict = if example.spam {
+            format!(
+                r#"{{"spam": true, "reason": {}}}"#,
+                serde_json::to_string(example.reason.as_deref().unwrap_or(DEFAULT_REASON))
+                    .unwrap_or_default()
+            )
+        } else {
+            r#"{"spam": false, "reason": null}"#.to_string()
+        };
+        out.push_str(&format!("\n- {text}\n  → {verdict}"));
+    }
+    out
+}
+
 fn format_web_content(content: &WebContent) -> String {
     let mut out = String::new();
     if let Some(title) = &content.title {
@@ -284,20 +1721,54 @@ fn format_web_content(content: &WebContent) -> String {
         out.push_str(text);
         out.push('\n');
     }
+    if let Some(members) = &content.members {
+        out.push_str("구성원: ");
+        out.push_str(members);
+        out.push('\n');
+    }
     out
 }
 
-fn escape_html(text: &str) -> String {
-    let mut escaped = String::with_capacity(text.len());
-    for ch in text.chars() {
-        match ch {
-            '&' => escaped.push_str("&amp;"),
-            '<' => escaped.push_str("&lt;"),
-            '>' => escaped.push_str("&gt;"),
-            '"' => escaped.push_str("&quot;"),
-            '\'' => escaped.push_str("&#39;"),
-            _ => escaped.push(ch),
-        }
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::MessageJobBuilder;
+
+    #[test]
+    fn only_short_plain_messages_from_tenured_members_are_obvious_ham() {
+        let config = PrefilterConfig {
+            max_chars: 20,
+            min_member_days: 30,
+        };
+        let job = |text: &str| MessageJobBuilder::new(-100, 1).from(1, "user").text(text);
+        assert!(is_obvious_ham(
+            &job("오늘 회의 몇 시인가요?").joined_days_ago(90).build(),
+            &config
+        ));
+        assert!(!is_obvious_ham(
+            &job("오늘 회의 몇 시인가요?")
+                .joined_days_ago(90)
+                .non_member()
+                .build(),
+            &config
+        ));
+        assert!(!is_obvious_ham(
+            &job("오늘 회의 몇 시인가요?").joined_days_ago(3).build(),
+            &config
+        ));
+        assert!(!is_obvious_ham(
+            &job("오늘 회의 몇 시인가요?").build(),
+            &config
+        ));
+        assert!(!is_obvious_ham(
+            &job("여기 보세요 t.me/abc").joined_days_ago(90).build(),
+            &config
+        ));
+        assert!(!is_obvious_ham(
+            &job("수익 인증합니다 매일 오전 열시에 무료로 공개합니다")
+                .joined_days_ago(90)
+                .build(),
+            &config
+        ));
     }
-    escaped
 }
@@ -4,7 +4,7 @@ use parking_lot::Mutex;
 
 use crate::domain::types::QueueSnapshot;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Priority {
     High,
     Normal,
@@ -16,6 +16,12 @@ pub struct MessageQueue<T> {
     normal: Mutex<VecDeque<T>>,
 }
 
+impl<T> Default for MessageQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> MessageQueue<T> {
     pub fn new() -> Self {
         Self {
@@ -40,6 +46,20 @@ impl<T> MessageQueue<T> {
         drained
     }
 
+    /// Up to `limit` queued values in the order they would be drained, left in place.
+    pub fn peek(&self, limit: usize) -> Vec<(Priority, T)>
+    where
+        T: Clone,
+    {
+        let high = self.high.lock();
+        let normal = self.normal.lock();
+        high.iter()
+            .map(|value| (Priority::High, value.clone()))
+            .chain(normal.iter().map(|value| (Priority::Normal, value.clone())))
+            .take(limit)
+            .collect()
+    }
+
     pub fn snapshot(&self) -> QueueSnapshot {
         QueueSnapshot {
             high_priority: self.high.lock().len(),
@@ -47,3 +67,22 @@ impl<T> MessageQueue<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peek_lists_high_priority_first_without_draining() {
+        let queue = MessageQueue::new();
+        queue.push(Priority::Normal, 1);
+        queue.push(Priority::High, 2);
+        queue.push(Priority::Normal, 3);
+
+        assert_eq!(
+            queue.peek(2),
+            vec![(Priority::High, 2), (Priority::Normal, 1)]
+        );
+        assert_eq!(queue.drain_ordered(), vec![2, 1, 3]);
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::{config::AppConfig, db::reputation::ReputationRepository, tasks::scheduler::JobTask};
+
+/// Ages out old offenses and restores reputation for users who stayed clean.
+pub struct ReputationDecay {
+    reputation: Arc<ReputationRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl ReputationDecay {
+    pub fn new(reputation: Arc<ReputationRepository>, config: Arc<AppConfig>) -> Self {
+        Self { reputation, config }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let decay = self.clone();
+            Box::pin(async move {
+                if let Err(err) = decay.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "reputation decay failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let cfg = &self.config.reputation;
+        let now = Utc::now();
+        let outcome = self
+            .reputation
+            .decay(
+                now,
+                now - chrono::Duration::from_std(cfg.offense_ttl)?,
+                now - chrono::Duration::from_std(cfg.clean_period)?,
+                cfg.recovery_per_pass,
+            )
+            .await?;
+        tracing::info!(
+            target: "scheduler",
+            offenses_aged = outcome.offenses_aged,
+            scores_restored = outcome.scores_restored,
+            rewards_decayed = outcome.rewards_decayed,
+            "reputation decay pass finished"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use chrono::Utc;
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::retention::{RetainedTable, RetentionRepository},
+    i18n::{t, tf, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::scheduler::JobTask,
+};
+
+/// Deletes log and archive rows that outlived their configured retention period.
+pub struct RetentionEnforcer {
+    bot: Bot,
+    retention: Arc<RetentionRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl RetentionEnforcer {
+    pub fn new(bot: Bot, retention: Arc<RetentionRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            bot,
+            retention,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let enforcer = self.clone();
+            Box::pin(async move {
+                if let Err(err) = enforcer.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "retention purge failed");
+                }
+            })
+        })
+    }
+
+    fn policies(&self) -> Vec<(RetainedTable, Duration)> {
+        let cfg = &self.config.retention;
+        [
+            (RetainedTable::SpamLog, cfg.spam_log_ttl),
+            (RetainedTable::AiUsage, cfg.usage_ttl),
+            (RetainedTable::ShadowResults, cfg.shadow_ttl),
+            (RetainedTable::Appeals, cfg.appeal_ttl),
+            (RetainedTable::MemberJoins, cfg.member_join_ttl),
+        ]
+        .into_iter()
+        .filter_map(|(table, ttl)| ttl.map(|ttl| (table, ttl)))
+        .collect()
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let now = Utc::now();
+        let mut removed = Vec::new();
+        for (table, ttl) in self.policies() {
+            let cutoff = now - chrono::Duration::from_std(ttl)?;
+            let count = self.retention.purge(table, cutoff).await?;
+            tracing::info!(
+                target: "scheduler",
+                table = table.table_name(),
+                removed = count,
+                "retention purge finished"
+            );
+            if count > 0 {
+                removed.push((table, count));
+            }
+        }
+
+        if !removed.is_empty() {
+            let locale = self.config.language;
+            let mut message = t(locale, Msg::RetentionPurged).to_string();
+            for (table, count) in removed {
+                message.push_str(&tf(
+                    locale,
+                    Msg::RetentionRow,
+                    &[("table", &table.table_name()), ("count", &count)],
+                ));
+            }
+            notify_admin_group(&self.bot, self.config.as_ref(), &message).await;
+        }
+        Ok(())
+    }
+}
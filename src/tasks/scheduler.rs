@@ -1,29 +1,276 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::Result;
+use chrono::Utc;
+use cron::Schedule;
+use futures::future::BoxFuture;
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Instant},
+};
 use tokio_cron_scheduler::{Job, JobScheduler};
+use uuid::Uuid;
+
+use crate::db::{job_runs::JobRunRepository, restart_crons::RestartCronRepository};
 
 pub type RestartCallback = Arc<dyn Fn() + Send + Sync>;
+pub type JobTask = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+pub type IdleProbe = Arc<dyn Fn() -> bool + Send + Sync>;
+
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+struct ScheduledRestart {
+    id: i64,
+    spec: String,
+    job_id: Uuid,
+}
+
+/// Owns the restart cron jobs so admins can change them while the scheduler is running.
+///
+/// Specs are persisted in `restart_crons`; the `RESTART_CRONS` env value only seeds the
+/// table on the first start.
+pub struct RestartScheduleManager {
+    scheduler: JobScheduler,
+    repository: Arc<RestartCronRepository>,
+    callback: RestartCallback,
+    jobs: Mutex<Vec<ScheduledRestart>>,
+}
+
+impl RestartScheduleManager {
+    pub async fn load(
+        scheduler: JobScheduler,
+        repository: Arc<RestartCronRepository>,
+        defaults: &[String],
+        callback: RestartCallback,
+    ) -> Result<Arc<Self>> {
+        let rows = repository.list().await?;
+        // Defaults only seed the table once; an admin who later removes every schedule
+        // keeps it empty. They are scheduled before they are stored, so an invalid one
+        // never lands in the table.
+        let seeded = repository.seeded().await?;
+        let rows = if rows.is_empty() && !seeded {
+            defaults.iter().map(|spec| (None, spec.clone())).collect()
+        } else {
+            rows.into_iter()
+                .map(|(id, spec)| (Some(id), spec))
+                .collect::<Vec<_>>()
+        };
+
+        let mut jobs = Vec::with_capacity(rows.len());
+        for (id, spec) in rows {
+            match build_restart_job(&spec, callback.clone()) {
+                Ok(job) => {
+                    let job_id = scheduler.add(job).await?;
+                    let id = match id {
+                        Some(id) => id,
+                        None => repository.add(&spec, None).await?,
+                    };
+                    tracing::info!(target: "scheduler", cron = %spec, "restart job registered");
+                    jobs.push(ScheduledRestart { id, spec, job_id });
+                }
+                Err(err) => {
+                    tracing::error!(
+                        target: "scheduler",
+                        cron = %spec,
+                        error = %err,
+                        "restart cron is invalid; skipping"
+                    );
+                }
+            }
+        }
+        if !seeded {
+            repository.mark_seeded().await?;
+        }
+
+        Ok(Arc::new(Self {
+            scheduler,
+            repository,
+            callback,
+            jobs: Mutex::new(jobs),
+        }))
+    }
+
+    /// Restarts without waiting for a scheduled run, for `/restart` and after `/update`
+    /// installed a new binary.
+    pub fn restart_now(&self) {
+        (self.callback)();
+    }
+
+    pub async fn list(&self) -> Vec<String> {
+        self.jobs
+            .lock()
+            .await
+            .iter()
+            .map(|job| job.spec.clone())
+            .collect()
+    }
+
+    /// Validates, schedules, and persists `spec`; it is only stored once the scheduler
+    /// accepted it. Returns the 1-based index of the new entry.
+    pub async fn add(&self, spec: &str, added_by: Option<i64>) -> Result<usize> {
+        let job = build_restart_job(spec, self.callback.clone())?;
+        let mut jobs = self.jobs.lock().await;
+        let job_id = self.scheduler.add(job).await?;
+        let id = match self.repository.add(spec, added_by).await {
+            Ok(id) => id,
+            Err(err) => {
+                if let Err(remove_err) = self.scheduler.remove(&job_id).await {
+                    tracing::error!(
+                        target: "scheduler",
+                        cron = %spec,
+                        error = %remove_err,
+                        "failed to unschedule restart job that could not be saved"
+                    );
+                }
+                return Err(err);
+            }
+        };
+        jobs.push(ScheduledRestart {
+            id,
+            spec: spec.to_string(),
+            job_id,
+        });
+        tracing::info!(target: "scheduler", cron = %spec, "restart job added at runtime");
+        Ok(jobs.len())
+    }
+
+    /// Removes the entry at the 1-based `index`, returning its spec when it existed.
+    pub async fn remove(&self, index: usize) -> Result<Option<String>> {
+        let mut jobs = self.jobs.lock().await;
+        if index == 0 || index > jobs.len() {
+            return Ok(None);
+        }
+        let job = jobs.remove(index - 1);
+        self.scheduler.remove(&job.job_id).await?;
+        self.repository.remove(job.id).await?;
+        tracing::info!(target: "scheduler", cron = %job.spec, "restart job removed at runtime");
+        Ok(Some(job.spec))
+    }
+}
 
-pub async fn configure_restart_jobs(
-    cron_specs: &[String],
+/// Wraps `callback` so it only fires once `is_idle` reports no pending work,
+/// giving up on waiting after `max_delay`.
+pub fn wait_for_idle(
     callback: RestartCallback,
-) -> Result<JobScheduler> {
-    let scheduler = JobScheduler::new().await?;
-    for spec in cron_specs {
-        let label = spec.clone();
+    is_idle: IdleProbe,
+    max_delay: Duration,
+) -> RestartCallback {
+    Arc::new(move || {
+        let callback = callback.clone();
+        let is_idle = is_idle.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            while !is_idle() {
+                if started.elapsed() >= max_delay {
+                    tracing::warn!(
+                        target: "scheduler",
+                        waited_secs = max_delay.as_secs(),
+                        "bot still busy after max restart delay; restarting anyway"
+                    );
+                    break;
+                }
+                sleep(IDLE_POLL_INTERVAL).await;
+            }
+            if started.elapsed() >= IDLE_POLL_INTERVAL {
+                tracing::info!(
+                    target: "scheduler",
+                    waited_secs = started.elapsed().as_secs(),
+                    "restart delayed until idle"
+                );
+            }
+            callback();
+        });
+    })
+}
+
+fn build_restart_job(spec: &str, callback: RestartCallback) -> Result<Job> {
+    let label = spec.to_string();
+    let job = Job::new_async(spec, move |_id, _l| {
         let cb = callback.clone();
-        let job = Job::new_async(spec.as_str(), move |_id, _l| {
-            let cb = cb.clone();
-            let cron_label = label.clone();
+        let cron_label = label.clone();
+        Box::pin(async move {
+            tracing::info!(target: "scheduler", cron = %cron_label, "restart job triggered");
+            cb();
+        })
+    })?;
+    Ok(job)
+}
+
+/// Registers named maintenance jobs and remembers when each one last ran, so a tick
+/// missed while the bot was offline can be caught up on startup.
+#[derive(Clone)]
+pub struct JobRegistry {
+    scheduler: JobScheduler,
+    runs: Arc<JobRunRepository>,
+    catch_up: bool,
+}
+
+impl JobRegistry {
+    pub fn new(scheduler: JobScheduler, runs: Arc<JobRunRepository>, catch_up: bool) -> Self {
+        Self {
+            scheduler,
+            runs,
+            catch_up,
+        }
+    }
+
+    /// Registers `task` to run on every tick of `spec`.
+    pub async fn register(&self, name: &'static str, spec: &str, task: JobTask) -> Result<()> {
+        let schedule = Schedule::from_str(spec)?;
+        let runs = self.runs.clone();
+        let scheduled_task = task.clone();
+        let job = Job::new_async(spec, move |_id, _l| {
+            let task = scheduled_task.clone();
+            let runs = runs.clone();
             Box::pin(async move {
-                tracing::info!(target: "scheduler", cron = %cron_label, "restart job triggered");
-                cb();
+                tracing::info!(target: "scheduler", job = name, "scheduled job triggered");
+                run_and_record(name, task, &runs).await;
             })
         })?;
-        scheduler.add(job).await?;
-        tracing::info!(target: "scheduler", cron = %spec, "restart job registered");
+        self.scheduler.add(job).await?;
+        tracing::info!(target: "scheduler", job = name, cron = %spec, "scheduled job registered");
+
+        self.catch_up_if_missed(name, &schedule, task).await
+    }
+
+    async fn catch_up_if_missed(
+        &self,
+        name: &'static str,
+        schedule: &Schedule,
+        task: JobTask,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let Some(last_run) = self.runs.last_run(name).await? else {
+            // First time we see this job: start tracking from now instead of firing immediately.
+            self.runs.record_run(name, now).await?;
+            return Ok(());
+        };
+
+        let missed = schedule
+            .after(&last_run)
+            .next()
+            .is_some_and(|due| due <= now);
+        if !missed || !self.catch_up {
+            return Ok(());
+        }
+
+        tracing::info!(
+            target: "scheduler",
+            job = name,
+            last_run = %last_run,
+            "missed scheduled run detected; running catch-up"
+        );
+        let runs = self.runs.clone();
+        tokio::spawn(async move {
+            run_and_record(name, task, &runs).await;
+        });
+        Ok(())
+    }
+}
+
+async fn run_and_record(name: &'static str, task: JobTask, runs: &JobRunRepository) {
+    task().await;
+    if let Err(err) = runs.record_run(name, Utc::now()).await {
+        tracing::warn!(target: "scheduler", job = name, error = %err, "failed to record job run");
     }
-    scheduler.start().await?;
-    Ok(scheduler)
 }
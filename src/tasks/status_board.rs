@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use chrono_tz::Tz;
+use teloxide::{
+    prelude::*,
+    types::{MessageId, ParseMode},
+};
+
+use crate::{
+    config::AppConfig,
+    db::status_messages::StatusMessageRepository,
+    i18n::{t, tf, Locale, Msg},
+    infrastructure::process_stats,
+    tasks::{processor::MessageProcessor, scheduler::JobTask},
+};
+
+/// Keeps one pinned message in the admin group up to date with queue depth, AI API
+/// health and the process metrics from [`process_status`].
+pub struct StatusBoard {
+    bot: Bot,
+    messages: Arc<StatusMessageRepository>,
+    processor: Arc<MessageProcessor>,
+    config: Arc<AppConfig>,
+}
+
+impl StatusBoard {
+    pub fn new(
+        bot: Bot,
+        messages: Arc<StatusMessageRepository>,
+        processor: Arc<MessageProcessor>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            bot,
+            messages,
+            processor,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let board = self.clone();
+            Box::pin(async move {
+                if let Err(err) = board.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "status board update failed");
+                }
+            })
+        })
+    }
+
+    /// Edits the pinned message, or posts and pins a new one when there is none yet or
+    /// it can no longer be edited.
+    pub async fn run(&self) -> Result<()> {
+        let Some(admin_group_id) = self.config.admin_group_id.filter(|id| *id != 0) else {
+            return Ok(());
+        };
+        let chat_id = ChatId(admin_group_id);
+        let text = self.render();
+
+        if let Some(message_id) = self.messages.get(admin_group_id).await? {
+            let edited = self
+                .bot
+                .edit_message_text(chat_id, MessageId(message_id), text.clone())
+                .parse_mode(ParseMode::Html)
+                .await;
+            match edited {
+                Ok(_) => return Ok(()),
+                Err(err) if err.to_string().contains("message is not modified") => return Ok(()),
+                Err(err) => {
+                    tracing::warn!(
+                        target: "scheduler",
+                        error = %err,
+                        message_id,
+                        "status message could not be edited; posting a new one"
+                    );
+                }
+            }
+        }
+
+        let sent = self
+            .bot
+            .send_message(chat_id, text)
+            .parse_mode(ParseMode::Html)
+            .disable_notification(true)
+            .await?;
+        self.messages.set(admin_group_id, sent.id.0).await?;
+        if let Err(err) = self
+            .bot
+            .pin_chat_message(chat_id, sent.id)
+            .disable_notification(true)
+            .await
+        {
+            tracing::warn!(target: "scheduler", error = %err, "failed to pin status message");
+        }
+        tracing::info!(target: "scheduler", message_id = sent.id.0, "status message posted");
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        let locale = self.config.language;
+        let tz: Tz = self
+            .config
+            .timezone
+            .parse()
+            .unwrap_or(chrono_tz::Asia::Seoul);
+        let api = if self.processor.ai_paused() {
+            Msg::StatusApiPaused
+        } else {
+            Msg::StatusApiHealthy
+        };
+        let queue = self.processor.queue_snapshot();
+        tf(
+            locale,
+            Msg::StatusBoard,
+            &[
+                ("high", &queue.high_priority),
+                ("normal", &queue.normal_priority),
+                ("api", &t(locale, api)),
+                ("process", &process_status(locale, tz, &self.processor)),
+                (
+                    "updated",
+                    &Utc::now().with_timezone(&tz).format("%Y-%m-%d %H:%M:%S"),
+                ),
+            ],
+        )
+    }
+}
+
+/// Uptime, memory, CPU, classification throughput and the last successful AI call, as
+/// lines appended to `/status` and the pinned status message. Memory and CPU are left
+/// out when the platform does not report process stats.
+pub fn process_status(locale: Locale, tz: Tz, processor: &MessageProcessor) -> String {
+    let uptime = process_stats::uptime().as_secs() / 60;
+    let uptime = tf(
+        locale,
+        Msg::StatusUptime,
+        &[
+            ("days", &(uptime / 1440)),
+            ("hours", &(uptime / 60 % 24)),
+            ("minutes", &(uptime % 60)),
+        ],
+    );
+    let last = processor
+        .last_classified()
+        .map(|at| {
+            at.with_timezone(&tz)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| t(locale, Msg::StatusNeverClassified).to_string());
+    let mut status = tf(locale, Msg::StatusUptimeLine, &[("uptime", &uptime)]);
+    if let Some(stats) = process_stats::sample() {
+        status.push_str(&tf(
+            locale,
+            Msg::ProcessStatus,
+            &[
+                (
+                    "rss",
+                    &format!("{:.1}", stats.rss_bytes as f64 / (1024.0 * 1024.0)),
+                ),
+                ("cpu", &format!("{:.1}", stats.cpu_percent)),
+            ],
+        ));
+    }
+    status.push_str(&tf(
+        locale,
+        Msg::StatusClassification,
+        &[
+            ("rate", &format!("{:.1}", processor.messages_per_minute())),
+            ("last", &last),
+        ],
+    ));
+    status
+}
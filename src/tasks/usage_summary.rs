@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::usage::{UsageRepository, UsageTotals},
+    i18n::{t, tf, Locale, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::scheduler::JobTask,
+};
+
+/// Posts the last day's AI calls, tokens and estimated cost to the admin group.
+pub struct UsageSummary {
+    bot: Bot,
+    usage: Arc<UsageRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl UsageSummary {
+    pub fn new(bot: Bot, usage: Arc<UsageRepository>, config: Arc<AppConfig>) -> Self {
+        Self { bot, usage, config }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let summary = self.clone();
+            Box::pin(async move {
+                if let Err(err) = summary.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "usage summary failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let totals = self
+            .usage
+            .totals_since(Utc::now() - Duration::hours(24))
+            .await?;
+        tracing::info!(
+            target: "scheduler",
+            calls = totals.iter().map(|row| row.calls).sum::<i64>(),
+            "usage summary prepared"
+        );
+        if totals.is_empty() {
+            return Ok(());
+        }
+        let locale = self.config.language;
+        let summary = format!(
+            "{}{}",
+            t(locale, Msg::UsageDaily),
+            format_usage(locale, t(locale, Msg::UsageLastDay), &totals)
+        );
+        notify_admin_group(&self.bot, self.config.as_ref(), &summary).await;
+        Ok(())
+    }
+}
+
+/// Totals over all models followed by a line per model.
+pub fn format_usage(locale: Locale, period: &str, totals: &[UsageTotals]) -> String {
+    let sum = totals.iter().fold(UsageTotals::default(), |mut sum, row| {
+        sum.calls += row.calls;
+        sum.prompt_tokens += row.prompt_tokens;
+        sum.completion_tokens += row.completion_tokens;
+        sum.cost_usd += row.cost_usd;
+        sum
+    });
+    let mut text = tf(
+        locale,
+        Msg::UsageTotals,
+        &[
+            ("period", &period),
+            ("calls", &sum.calls),
+            ("prompt", &sum.prompt_tokens),
+            ("completion", &sum.completion_tokens),
+            ("cost", &format!("{:.4}", sum.cost_usd)),
+        ],
+    );
+    for row in totals {
+        text.push_str(&tf(
+            locale,
+            Msg::UsageModel,
+            &[
+                ("model", &format!("{}/{}", row.provider, row.model)),
+                ("calls", &row.calls),
+                ("prompt", &row.prompt_tokens),
+                ("completion", &row.completion_tokens),
+                ("cost", &format!("{:.4}", row.cost_usd)),
+            ],
+        ));
+    }
+    text
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::whitelist::{WhitelistRepository, WhitelistStatus},
+    i18n::{t, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::scheduler::JobTask,
+    telegram::utils::escape_html,
+};
+
+/// Verifies that the bot is still present (with delete rights) in every whitelisted chat,
+/// and refreshes the stored title and type of chats that changed them.
+pub struct WhitelistAuditor {
+    bot: Bot,
+    whitelist: Arc<WhitelistRepository>,
+    config: Arc<AppConfig>,
+}
+
+/// What one audit learned about a chat; `details` is `None` when `get_chat` failed.
+struct ChatCheck {
+    status: WhitelistStatus,
+    details: Option<(Option<String>, String)>,
+}
+
+struct StaleChat {
+    chat_id: i64,
+    title: Option<String>,
+    status: WhitelistStatus,
+}
+
+impl WhitelistAuditor {
+    pub fn new(bot: Bot, whitelist: Arc<WhitelistRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            bot,
+            whitelist,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let auditor = self.clone();
+            Box::pin(async move {
+                if let Err(err) = auditor.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "whitelist audit failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let me = self.bot.get_me().await?;
+        let rows = self.whitelist.list().await?;
+        let mut stale = Vec::new();
+        let mut refreshed = 0;
+
+        for row in &rows {
+            let check = self.check_chat(ChatId(row.chat_id), me.id).await;
+            let Some(ChatCheck { status, details }) = check else {
+                continue;
+            };
+            if let Some((title, chat_type)) = details {
+                if title != row.chat_title || Some(&chat_type) != row.chat_type.as_ref() {
+                    self.whitelist
+                        .set_details(row.chat_id, title.as_deref(), &chat_type)
+                        .await?;
+                    refreshed += 1;
+                }
+            }
+            self.whitelist.set_status(row.chat_id, status).await?;
+            if status != WhitelistStatus::Active && status != row.status {
+                stale.push(StaleChat {
+                    chat_id: row.chat_id,
+                    title: row.chat_title.clone(),
+                    status,
+                });
+            }
+        }
+
+        tracing::info!(
+            target: "scheduler",
+            checked = rows.len(),
+            refreshed,
+            newly_stale = stale.len(),
+            "whitelist audit finished"
+        );
+
+        if !stale.is_empty() {
+            let locale = self.config.language;
+            let mut message = t(locale, Msg::WhitelistAuditFound).to_string();
+            for chat in &stale {
+                message.push_str(&format!(
+                    "\n- {} (ID: {}): {}",
+                    escape_html(
+                        chat.title
+                            .as_deref()
+                            .unwrap_or_else(|| t(locale, Msg::Untitled))
+                    ),
+                    chat.chat_id,
+                    chat.status.label(locale)
+                ));
+            }
+            notify_admin_group(&self.bot, self.config.as_ref(), &message).await;
+        }
+        Ok(())
+    }
+
+    /// Returns `None` when the check failed for a reason unrelated to membership
+    /// (e.g. a network error), so the previous status is kept untouched.
+    async fn check_chat(&self, chat_id: ChatId, bot_id: UserId) -> Option<ChatCheck> {
+        let details = match self.bot.get_chat(chat_id).await {
+            Ok(chat) => Some((
+                chat.title().map(|t| t.to_string()),
+                format!("{:?}", chat.kind),
+            )),
+            Err(teloxide::RequestError::Api(_)) => {
+                return Some(ChatCheck {
+                    status: WhitelistStatus::Removed,
+                    details: None,
+                });
+            }
+            Err(err) => {
+                tracing::warn!(target: "scheduler", error = %err, chat_id = chat_id.0, "get_chat failed");
+                return None;
+            }
+        };
+
+        let status = match self.bot.get_chat_member(chat_id, bot_id).await {
+            Ok(member) if !member.is_present() => WhitelistStatus::Removed,
+            Ok(member) if !member.can_delete_messages() => WhitelistStatus::NoDeleteRights,
+            Ok(_) => WhitelistStatus::Active,
+            Err(teloxide::RequestError::Api(_)) => WhitelistStatus::Removed,
+            Err(err) => {
+                tracing::warn!(
+                    target: "scheduler",
+                    error = %err,
+                    chat_id = chat_id.0,
+                    "get_chat_member failed"
+                );
+                return None;
+            }
+        };
+        Some(ChatCheck { status, details })
+    }
+}
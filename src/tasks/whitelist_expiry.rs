@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use teloxide::prelude::*;
+
+use crate::{
+    config::AppConfig,
+    db::whitelist::WhitelistRepository,
+    i18n::{t, tf, Msg},
+    infrastructure::notifier::notify_admin_group,
+    tasks::scheduler::JobTask,
+    telegram::utils::escape_html,
+};
+
+/// Removes temporary whitelist entries once they expire and tells the admin group.
+pub struct WhitelistExpiry {
+    bot: Bot,
+    whitelist: Arc<WhitelistRepository>,
+    config: Arc<AppConfig>,
+}
+
+impl WhitelistExpiry {
+    pub fn new(bot: Bot, whitelist: Arc<WhitelistRepository>, config: Arc<AppConfig>) -> Self {
+        Self {
+            bot,
+            whitelist,
+            config,
+        }
+    }
+
+    pub fn into_task(self: Arc<Self>) -> JobTask {
+        Arc::new(move || {
+            let expiry = self.clone();
+            Box::pin(async move {
+                if let Err(err) = expiry.run().await {
+                    tracing::error!(target: "scheduler", error = %err, "whitelist expiry failed");
+                }
+            })
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let expired = self.whitelist.remove_expired(Utc::now()).await?;
+        if expired.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(target: "scheduler", count = expired.len(), "expired whitelist entries removed");
+
+        let locale = self.config.language;
+        let mut message = t(locale, Msg::WhitelistExpired).to_string();
+        for row in &expired {
+            message.push_str(&tf(
+                locale,
+                Msg::WhitelistExpiredRow,
+                &[
+                    (
+                        "chat",
+                        &escape_html(
+                            row.chat_title
+                                .as_deref()
+                                .unwrap_or_else(|| t(locale, Msg::Untitled)),
+                        ),
+                    ),
+                    ("chat_id", &row.chat_id),
+                ],
+            ));
+        }
+        notify_admin_group(&self.bot, self.config.as_ref(), &message).await;
+        Ok(())
+    }
+}
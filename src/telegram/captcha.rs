@@ -0,0 +1,190 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use teloxide::{
+    prelude::*,
+    types::{ChatPermissions, InlineKeyboardButton, InlineKeyboardMarkup, MessageId, User},
+};
+
+use crate::{
+    config::CaptchaConfig,
+    i18n::{t, tf, Locale, Msg},
+};
+
+use super::utils::{format_user_display, user_to_i64};
+
+/// Buttons offered to a new member, with the names the challenge asks for.
+const CHOICES: [(&str, Msg); 4] = [
+    ("🍎", Msg::CaptchaApple),
+    ("🚗", Msg::CaptchaCar),
+    ("🐶", Msg::CaptchaDog),
+    ("⭐", Msg::CaptchaStar),
+];
+
+/// How long the new member's mute outlasts the captcha timeout.
+const MUTE_GRACE_SECS: i64 = 60;
+
+struct Challenge {
+    message_id: MessageId,
+    answer: usize,
+}
+
+/// Mutes new members until they press the button the challenge names; members who press
+/// a wrong button or don't answer before the timeout are removed from the chat. The
+/// mute expires shortly after the timeout on its own, so a restart that loses the
+/// pending challenge never leaves anyone muted for good.
+pub struct CaptchaGate {
+    config: CaptchaConfig,
+    pending: Mutex<HashMap<(i64, i64), Challenge>>,
+}
+
+impl CaptchaGate {
+    pub fn new(config: CaptchaConfig) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn challenge(
+        self: &Arc<Self>,
+        bot: &Bot,
+        chat_id: ChatId,
+        user: &User,
+        locale: Locale,
+    ) {
+        let user_id = user_to_i64(user);
+        if user.is_bot || self.pending.lock().contains_key(&(chat_id.0, user_id)) {
+            return;
+        }
+        let until = chrono::Utc::now()
+            + chrono::Duration::from_std(self.config.timeout).unwrap_or_default()
+            + chrono::Duration::seconds(MUTE_GRACE_SECS);
+        if let Err(err) = bot
+            .restrict_chat_member(chat_id, user.id, ChatPermissions::empty())
+            .until_date(until)
+            .await
+        {
+            tracing::warn!(target: "telegram", error = %err, user_id, "failed to mute new member");
+            return;
+        }
+
+        let answer = fastrand::usize(..CHOICES.len());
+        let buttons = CHOICES
+            .iter()
+            .enumerate()
+            .map(|(index, (emoji, _))| {
+                InlineKeyboardButton::callback(
+                    *emoji,
+                    format!("captcha:{}:{user_id}:{index}", chat_id.0),
+                )
+            })
+            .collect::<Vec<_>>();
+        let text = tf(
+            locale,
+            Msg::CaptchaWelcome,
+            &[
+                ("user", &format_user_display(user)),
+                ("minutes", &self.config.timeout.as_secs().div_ceil(60)),
+                ("choice", &t(locale, CHOICES[answer].1)),
+            ],
+        );
+        let sent = match bot
+            .send_message(chat_id, text)
+            .reply_markup(InlineKeyboardMarkup::new(vec![buttons]))
+            .await
+        {
+            Ok(sent) => sent,
+            Err(err) => {
+                tracing::warn!(target: "telegram", error = %err, user_id, "failed to send captcha");
+                return;
+            }
+        };
+        self.pending.lock().insert(
+            (chat_id.0, user_id),
+            Challenge {
+                message_id: sent.id,
+                answer,
+            },
+        );
+        tracing::info!(target: "telegram", chat_id = chat_id.0, user_id, "captcha sent");
+
+        let gate = self.clone();
+        let bot = bot.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(gate.config.timeout).await;
+            gate.expire(&bot, chat_id, user_id).await;
+        });
+    }
+
+    /// Removes a member who never answered.
+    async fn expire(&self, bot: &Bot, chat_id: ChatId, user_id: i64) {
+        let Some(challenge) = self.pending.lock().remove(&(chat_id.0, user_id)) else {
+            return;
+        };
+        remove_unverified(bot, chat_id, user_id, challenge).await;
+    }
+
+    /// Handles a press on `captcha:<chat>:<user>:<choice>` and returns the message shown
+    /// to the presser.
+    pub async fn answer(&self, bot: &Bot, presser: i64, data: &str) -> Msg {
+        let ids: Option<Vec<i64>> = data.split(':').map(|part| part.parse().ok()).collect();
+        let Some(&[chat_id, user_id, choice]) = ids.as_deref() else {
+            return Msg::CaptchaInvalid;
+        };
+        if presser != user_id {
+            return Msg::CaptchaNotYou;
+        }
+        let Some(challenge) = self.pending.lock().remove(&(chat_id, user_id)) else {
+            return Msg::CaptchaExpired;
+        };
+        let chat_id = ChatId(chat_id);
+        if challenge.answer as i64 != choice {
+            remove_unverified(bot, chat_id, user_id, challenge).await;
+            return Msg::CaptchaWrong;
+        }
+
+        let permissions = match bot.get_chat(chat_id).await {
+            Ok(chat) => chat.permissions().unwrap_or_else(ChatPermissions::all),
+            Err(err) => {
+                tracing::warn!(target: "telegram", error = %err, "failed to read chat permissions");
+                ChatPermissions::all()
+            }
+        };
+        if let Err(err) = bot
+            .restrict_chat_member(chat_id, UserId(user_id as u64), permissions)
+            .await
+        {
+            tracing::warn!(target: "telegram", error = %err, user_id, "failed to unmute verified member");
+        }
+        if let Err(err) = bot.delete_message(chat_id, challenge.message_id).await {
+            tracing::debug!(target: "telegram", error = %err, "failed to delete captcha");
+        }
+        tracing::info!(target: "telegram", chat_id = chat_id.0, user_id, "captcha passed");
+        Msg::CaptchaPassed
+    }
+}
+
+/// Removes a member who failed the captcha; banning and unbanning lets them rejoin.
+async fn remove_unverified(bot: &Bot, chat_id: ChatId, user_id: i64, challenge: Challenge) {
+    let user = UserId(user_id as u64);
+    match bot.ban_chat_member(chat_id, user).await {
+        Ok(_) => {
+            if let Err(err) = bot.unban_chat_member(chat_id, user).await {
+                tracing::warn!(target: "telegram", error = %err, user_id, "failed to unban after captcha");
+            }
+            tracing::info!(
+                target: "telegram",
+                chat_id = chat_id.0,
+                user_id,
+                "unverified member removed"
+            );
+        }
+        Err(err) => {
+            tracing::warn!(target: "telegram", error = %err, user_id, "failed to remove unverified member")
+        }
+    }
+    if let Err(err) = bot.delete_message(chat_id, challenge.message_id).await {
+        tracing::debug!(target: "telegram", error = %err, "failed to delete captcha");
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use teloxide::prelude::*;
+
+type AdminSet = Arc<HashSet<UserId>>;
+
+/// Caches each chat's administrators and creator, so exempting them costs one
+/// `getChatAdministrators` call per chat and TTL.
+pub struct ChatAdminCache {
+    ttl: Duration,
+    chats: Mutex<HashMap<i64, (AdminSet, Instant)>>,
+}
+
+impl ChatAdminCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            chats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `user_id` administers the chat; `false` when the lookup failed, which
+    /// is not cached.
+    pub async fn contains(&self, bot: &Bot, chat_id: ChatId, user_id: UserId) -> bool {
+        if let Some((admins, checked_at)) = self.chats.lock().get(&chat_id.0).cloned() {
+            if checked_at.elapsed() < self.ttl {
+                return admins.contains(&user_id);
+            }
+        }
+
+        let admins: AdminSet = match bot.get_chat_administrators(chat_id).await {
+            Ok(members) => Arc::new(members.into_iter().map(|member| member.user.id).collect()),
+            Err(err) => {
+                tracing::warn!(
+                    target: "telegram",
+                    error = %err,
+                    chat_id = chat_id.0,
+                    "get_chat_administrators failed"
+                );
+                return false;
+            }
+        };
+        let is_admin = admins.contains(&user_id);
+        self.chats
+            .lock()
+            .insert(chat_id.0, (admins, Instant::now()));
+        is_admin
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::Utc;
+use teloxide::{prelude::*, types::ChatPermissions};
+
+use crate::{
+    domain::MessageJob,
+    heuristics::flood::FloodVerdict,
+    i18n::{tf, Msg},
+    infrastructure::notifier::notify_admin_group,
+    telegram::utils::escape_html,
+};
+
+use super::types::AppState;
+
+/// Deletes a flooding sender's message and, when configured, mutes them; a dry-run chat
+/// only gets a review request. Returns false when the sender is a chat admin and the
+/// message should go through the usual checks.
+pub async fn respond(bot: &Bot, state: &AppState, job: &MessageJob, verdict: FloodVerdict) -> bool {
+    let (Some(floods), Some(user_id)) = (&state.floods, job.from_id) else {
+        return false;
+    };
+    let user = UserId(user_id as u64);
+    if state.is_admin_user(user_id) {
+        return false;
+    }
+    if state.chat_admins.contains(bot, job.chat_id, user).await {
+        return false;
+    }
+
+    let config = floods.config();
+    let reason = verdict.reason(config.window);
+    tracing::info!(target: "telegram", chat_id = job.chat_id.0, user_id, ?verdict, "flood detected");
+    match state.processor.remove_spam(job, &reason).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return true,
+        Err(err) => {
+            tracing::warn!(target: "telegram", error = %err, "failed to delete flood message");
+        }
+    }
+
+    let Some(mute) = config.mute else {
+        return true;
+    };
+    let until = Utc::now() + chrono::Duration::seconds(mute.as_secs() as i64);
+    match bot
+        .restrict_chat_member(job.chat_id, user, ChatPermissions::empty())
+        .until_date(until)
+        .await
+    {
+        Ok(_) => {
+            let message = tf(
+                state.config.language,
+                Msg::FloodAlert,
+                &[
+                    (
+                        "chat",
+                        &escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
+                    ),
+                    ("chat_id", &job.chat_id.0),
+                    ("user", &escape_html(&job.from_display)),
+                    ("user_id", &user_id),
+                    ("reason", &escape_html(&reason)),
+                    ("minutes", &mute.as_secs().div_ceil(60)),
+                    ("text", &escape_html(&job.text)),
+                ],
+            );
+            notify_admin_group(&state.admin_bot, state.config.as_ref(), &message).await;
+        }
+        Err(err) => {
+            tracing::warn!(target: "telegram", error = %err, user_id, "failed to mute flooding sender")
+        }
+    }
+    true
+}
@@ -1,31 +1,93 @@
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use futures::future::BoxFuture;
 use parking_lot::Mutex;
 use teloxide::{
-    dispatching::Dispatcher,
-    error_handlers::ErrorHandler,
+    dispatching::{DefaultKey, Dispatcher},
+    error_handlers::{ErrorHandler, LoggingErrorHandler},
     prelude::*,
-    types::{BotCommandScope, CallbackQuery, ChatId, Message, Recipient, UserId},
-    update_listeners,
-    utils::command::BotCommands,
+    types::{
+        AllowedUpdate, BotCommandScope, CallbackQuery, CallbackQueryId, ChatId, ChatMemberUpdated,
+        InlineKeyboardButton, InlineKeyboardButtonKind, InlineKeyboardMarkup, InputFile,
+        MaybeInaccessibleMessage, Message, MessageId, Recipient, UserId,
+    },
+    update_listeners::{self, webhooks, UpdateListener},
 };
 use tokio::time::{Duration, Instant};
 
 use crate::{
-    config::AppConfig,
-    db::whitelist::{WhitelistEntry, WhitelistRepository},
+    cas::CasClient,
+    config::{AppConfig, ChatAdminMode, EnforcementAction, WebhookConfig},
+    db::{
+        appeals::AppealSource,
+        blocked_domains::ADMIN_SOURCE,
+        chat_settings::ProbationWindow,
+        feedback::NewFeedback,
+        member_joins::MemberActivity,
+        whitelist::{WhitelistEntry, WhitelistStatus},
+    },
     domain::MessageJob,
-    infrastructure::{notifier::notify_admin_group, shutdown::ShutdownListener},
-    tasks::{queue::MessageQueue, scheduler::RestartCallback},
+    heuristics::{
+        self, account_age,
+        invites::{invite_targets, normalize_target},
+        known_spam::{normalize_domain, text_fingerprint},
+        language,
+        raid::RaidVerdict,
+        risk::{self, RiskInput},
+        HeuristicInput,
+    },
+    i18n::{t, tf, Locale, Msg},
+    infrastructure::{
+        logging,
+        notifier::notify_admin_group,
+        shutdown::ShutdownListener,
+        updater::{self, UpdateStatus},
+    },
+    media,
+    rules::{RuleAction, RuleKind},
+    tasks::{
+        backup::DatabaseBackup, queue::Priority, scheduler::RestartCallback,
+        status_board::process_status, usage_summary::format_usage,
+    },
 };
 
 use super::{
-    types::{is_group_member, AppState, BotResult, GeneralCommand, QueueSnapshotProvider},
-    utils::{admin_command_list, calc_priority, extract_urls, format_user_display, user_to_i64},
+    flood, honeypot, raid,
+    types::{is_group_member, AppState, BotResult, GeneralCommand},
+    utils::{
+        admin_command_list, contains_url, escape_html, extract_message_urls, format_chat_display,
+        format_user_display, general_command_list, general_help, message_sender, parse_ttl,
+        split_args, topic_id, user_to_i64,
+    },
+    whitelist_file::{self, FileFormat},
+    whitelist_request,
 };
 
+const RULE_BOOST_SCORE: i32 = 20;
+const CAS_REASON: &str = "CAS(Combot Anti-Spam) 차단 목록에 등록된 사용자";
+const BLACKLIST_REASON: &str = "다른 채팅방에서 스팸이 확인된 블랙리스트 사용자";
+/// Stands in for the text of media nothing could be read from; never stored as an example.
+const MEDIA_PLACEHOLDER: &str = "[미디어 메시지]";
+/// Most recent entries shown by `/blacklist_list`.
+const BLACKLIST_LIST_LIMIT: i64 = 50;
+/// Lines `/logs` sends when no count is given, and the most it sends.
+const LOGS_DEFAULT_LINES: usize = 50;
+const LOGS_MAX_LINES: usize = 2_000;
+/// Longest log tail sent as a message; longer ones are uploaded as a document.
+const LOGS_INLINE_CHARS: usize = 4_000;
+/// Most jobs listed by `/queue`.
+const QUEUE_LIST_LIMIT: usize = 20;
+/// Longest message excerpt per job in `/queue`.
+const QUEUE_EXCERPT_CHARS: usize = 60;
+/// Most message IDs Telegram accepts in one `deleteMessages` call.
+const PURGE_CHUNK: usize = 100;
+/// Most verdicts one `/feedback_export` file holds.
+const FEEDBACK_EXPORT_LIMIT: i64 = 10_000;
+/// Most recently blocked domains listed by `/domain_list`.
+const DOMAIN_LIST_LIMIT: i64 = 50;
+
 pub struct TelegramService {
     bot: Bot,
     state: Arc<AppState>,
@@ -47,12 +109,15 @@ enum NetworkIssueKind {
 }
 
 impl NetworkIssueKind {
-    fn label(&self) -> &'static str {
-        match self {
-            NetworkIssueKind::Timeout => "요청 타임아웃",
-            NetworkIssueKind::Connection => "TCP 연결 실패",
-            NetworkIssueKind::Other => "기타 네트워크 오류",
-        }
+    fn label(&self, locale: Locale) -> &'static str {
+        t(
+            locale,
+            match self {
+                NetworkIssueKind::Timeout => Msg::NetworkTimeout,
+                NetworkIssueKind::Connection => Msg::NetworkConnect,
+                NetworkIssueKind::Other => Msg::NetworkOther,
+            },
+        )
     }
 }
 
@@ -136,7 +201,7 @@ impl UpdateListenerWatchdog {
 
             tracing::error!(
                 target: "telegram",
-                issue = info.kind.label(),
+                issue = info.kind.label(Locale::En),
                 url = info.url.as_deref(),
                 consecutive,
                 error = %error,
@@ -187,17 +252,25 @@ impl UpdateListenerWatchdog {
         consecutive: u32,
         elapsed: std::time::Duration,
     ) -> String {
-        let elapsed_secs = elapsed.as_secs();
-        let mut message = format!(
-            "텔레그램 업데이트 리스너가 최근 {elapsed_secs}초 동안 {consecutive}회 연속으로 {kind}를 보고했습니다.",
-            kind = info.kind.label()
+        let locale = self.config.language;
+        let mut message = tf(
+            locale,
+            Msg::NetworkRestart,
+            &[
+                ("secs", &elapsed.as_secs()),
+                ("count", &consecutive),
+                ("kind", &info.kind.label(locale)),
+            ],
         );
         if let Some(url) = info.url.as_deref() {
-            message.push_str(&format!("\n- 마지막 요청 URL: {url}"));
+            message.push_str(&tf(locale, Msg::NetworkLastUrl, &[("url", &url)]));
         }
-        message.push_str(&format!("\n- reqwest 상세: {}", info.detail));
-        message.push_str(&format!("\n- teloxide 오류: {error}"));
-        message.push_str("\n네트워크가 복구되지 않아 즉시 봇을 재시작합니다.");
+        message.push_str(&tf(
+            locale,
+            Msg::NetworkDetail,
+            &[("detail", &info.detail), ("error", &error)],
+        ));
+        message.push_str(t(locale, Msg::NetworkRestartNow));
         message
     }
 }
@@ -210,24 +283,21 @@ impl ErrorHandler<teloxide::RequestError> for UpdateListenerWatchdog {
     }
 }
 
+/// Update kinds the handlers use; `chat_member` has to be requested explicitly.
+fn allowed_updates() -> Vec<AllowedUpdate> {
+    vec![
+        AllowedUpdate::Message,
+        AllowedUpdate::CallbackQuery,
+        AllowedUpdate::ChatMember,
+        AllowedUpdate::MyChatMember,
+    ]
+}
+
 impl TelegramService {
-    pub fn new(
-        bot: Bot,
-        config: Arc<AppConfig>,
-        whitelist: Arc<WhitelistRepository>,
-        queue: Arc<MessageQueue<MessageJob>>,
-        queue_snapshot: QueueSnapshotProvider,
-        restart_callback: RestartCallback,
-    ) -> Self {
-        let state = Arc::new(AppState {
-            config,
-            whitelist,
-            queue,
-            queue_snapshot,
-        });
+    pub fn new(bot: Bot, state: AppState, restart_callback: RestartCallback) -> Self {
         Self {
             bot,
-            state,
+            state: Arc::new(state),
             restart_callback,
         }
     }
@@ -235,7 +305,11 @@ impl TelegramService {
     pub async fn run(&self, mut shutdown: ShutdownListener) -> Result<()> {
         self.sync_commands().await?;
         let me = self.bot.get_me().await?;
-        if let Some(expected_username) = &self.state.config.bot_username {
+        let expected_username = match &self.state.extra_bot {
+            Some(extra) => extra.username.as_ref(),
+            None => self.state.config.bot_username.as_ref(),
+        };
+        if let Some(expected_username) = expected_username {
             if me.username.as_deref() != Some(expected_username.as_str()) {
                 tracing::warn!(
                     target: "telegram",
@@ -261,10 +335,14 @@ impl TelegramService {
             .branch(dptree::endpoint(Self::on_plain_message));
 
         let callback_handler = Update::filter_callback_query().endpoint(Self::on_callback_query);
+        let member_handler = Update::filter_chat_member().endpoint(Self::on_chat_member);
+        let my_member_handler = Update::filter_my_chat_member().endpoint(Self::on_my_chat_member);
 
         let handler = dptree::entry()
             .branch(message_handler)
-            .branch(callback_handler);
+            .branch(callback_handler)
+            .branch(member_handler)
+            .branch(my_member_handler);
 
         let mut dispatcher = Dispatcher::builder(self.bot.clone(), handler)
             .dependencies(dptree::deps![self.state.clone()])
@@ -273,19 +351,82 @@ impl TelegramService {
             })
             .build();
 
-        let listener = update_listeners::Polling::builder(self.bot.clone())
-            .timeout(Duration::from_secs(3))
-            .delete_webhook()
-            .await
-            .build();
-        let watchdog = UpdateListenerWatchdog::new(
-            self.bot.clone(),
-            self.state.config.clone(),
-            self.restart_callback.clone(),
+        let webhook = match &self.state.extra_bot {
+            Some(_) => None,
+            None => self.state.config.webhook.clone(),
+        };
+        match webhook {
+            Some(webhook) => {
+                let listener = self.webhook_listener(webhook).await?;
+                let error_handler = LoggingErrorHandler::with_custom_text("webhook listener error");
+                Self::dispatch(&mut dispatcher, listener, error_handler, &mut shutdown).await;
+            }
+            None => {
+                let listener = update_listeners::Polling::builder(self.bot.clone())
+                    .timeout(Duration::from_secs(3))
+                    .allowed_updates(allowed_updates())
+                    .delete_webhook()
+                    .await
+                    .build();
+                let watchdog = UpdateListenerWatchdog::new(
+                    self.bot.clone(),
+                    self.state.config.clone(),
+                    self.restart_callback.clone(),
+                );
+                Self::dispatch(&mut dispatcher, listener, watchdog, &mut shutdown).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers the webhook with Telegram and serves it until the dispatcher stops.
+    async fn webhook_listener(
+        &self,
+        config: WebhookConfig,
+    ) -> Result<impl UpdateListener<Err = Infallible>> {
+        let mut options = webhooks::Options::new(config.listen_addr, config.url.clone());
+        if let Some(secret) = config.secret {
+            options = options.secret_token(secret);
+        }
+        let secret = options.get_or_gen_secret_token().to_string();
+        self.bot
+            .set_webhook(config.url.clone())
+            .secret_token(secret)
+            .allowed_updates(allowed_updates())
+            .await?;
+
+        let (listener, stop_flag, router) = webhooks::axum_no_setup(options);
+        let server = tokio::net::TcpListener::bind(config.listen_addr).await?;
+        tracing::info!(
+            target: "telegram",
+            address = %config.listen_addr,
+            url = %config.url,
+            "웹훅 수신 시작"
         );
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(server, router)
+                .with_graceful_shutdown(stop_flag)
+                .await
+            {
+                tracing::error!(target: "telegram", error = %err, "webhook server failed");
+            }
+        });
+        Ok(listener)
+    }
 
+    async fn dispatch<L, Eh>(
+        dispatcher: &mut Dispatcher<Bot, teloxide::RequestError, DefaultKey>,
+        listener: L,
+        error_handler: Arc<Eh>,
+        shutdown: &mut ShutdownListener,
+    ) where
+        L: UpdateListener + Send,
+        L::Err: std::fmt::Debug,
+        Eh: ErrorHandler<L::Err> + Send + Sync,
+    {
         let shutdown_token = dispatcher.shutdown_token();
-        let mut dispatcher_future = Box::pin(dispatcher.dispatch_with_listener(listener, watchdog));
+        let mut dispatcher_future =
+            Box::pin(dispatcher.dispatch_with_listener(listener, error_handler));
         let mut dispatcher_finished = false;
 
         tokio::select! {
@@ -304,12 +445,15 @@ impl TelegramService {
         if !dispatcher_finished {
             dispatcher_future.await;
         }
-
-        Ok(())
     }
 
-    async fn on_plain_message(bot: Bot, msg: Message, state: Arc<AppState>) -> BotResult<()> {
-        if let Some(text) = msg.text() {
+    pub(crate) async fn on_plain_message(
+        bot: Bot,
+        msg: Message,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        // A document's caption can carry a command too, e.g. `/whitelist_import`.
+        if let Some(text) = msg.text().or_else(|| msg.document().and(msg.caption())) {
             if Self::maybe_handle_admin_command(&bot, &msg, text, state.clone()).await? {
                 return Ok(());
             }
@@ -319,22 +463,154 @@ impl TelegramService {
             return Ok(());
         }
 
+        if state.is_honeypot(msg.chat.id.0) {
+            if msg.new_chat_members().is_none() {
+                honeypot::harvest(&bot, &state, &msg).await;
+            }
+            return Ok(());
+        }
+
         if !state.is_chat_allowed(msg.chat.id.0).await {
             return Ok(());
         }
 
-        let text = msg
+        if let Some(members) = msg.new_chat_members() {
+            for member in members {
+                if let Some(raids) = &state.raids {
+                    raids.record_join(msg.chat.id.0, user_to_i64(member));
+                }
+                Self::record_member_join(&state, msg.chat.id.0, user_to_i64(member), msg.date)
+                    .await;
+            }
+            return Ok(());
+        }
+
+        // Anonymous admins post as the group itself, and the linked channel's posts are
+        // forwarded into its discussion group automatically; neither needs checking.
+        if msg.is_automatic_forward()
+            || msg
+                .sender_chat
+                .as_ref()
+                .is_some_and(|sender| sender.id == msg.chat.id)
+        {
+            return Ok(());
+        }
+
+        let chat_admin_mode = state.config.chat_admins.mode;
+        let sender_is_chat_admin = match message_sender(&msg) {
+            Some(user) if chat_admin_mode != ChatAdminMode::Off => {
+                state.chat_admins.contains(&bot, msg.chat.id, user.id).await
+            }
+            _ => false,
+        };
+        if sender_is_chat_admin && chat_admin_mode == ChatAdminMode::Skip {
+            return Ok(());
+        }
+
+        if let Some(user) = message_sender(&msg) {
+            Self::record_recent_message(&state, &msg, user_to_i64(user)).await;
+        }
+
+        if let (Some(user), false) = (message_sender(&msg), sender_is_chat_admin) {
+            let user_id = user_to_i64(user);
+            if !state.is_admin_user(user_id) && Self::is_blacklisted(&state, user_id).await {
+                Self::remove_blacklisted(&bot, &state, &msg).await;
+                return Ok(());
+            }
+        }
+
+        if let (Some(cas), Some(user), false) =
+            (&state.cas, message_sender(&msg), sender_is_chat_admin)
+        {
+            let user_id = user_to_i64(user);
+            if !state.is_admin_user(user_id) && cas.is_banned(user_id).await {
+                Self::remove_cas_listed(&bot, &state, cas, &msg).await;
+                return Ok(());
+            }
+        }
+
+        // Transcription, OCR and vision can take many seconds, and updates from one chat
+        // are handled in order, so they run in the background instead of holding up the
+        // chat's later messages.
+        if Self::has_analyzable_media(&msg, &state) {
+            match state.media_slots.clone().try_acquire_owned() {
+                Ok(permit) => {
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        let chat_id = msg.chat.id.0;
+                        if let Err(err) =
+                            Self::screen_message(bot, msg, state, sender_is_chat_admin, true).await
+                        {
+                            tracing::warn!(
+                                target: "telegram",
+                                error = %err,
+                                chat_id,
+                                "failed to screen media message"
+                            );
+                        }
+                    });
+                    return Ok(());
+                }
+                Err(_) => {
+                    tracing::debug!(
+                        target: "telegram",
+                        chat_id = msg.chat.id.0,
+                        "media analysis slots busy; screening without the media"
+                    );
+                }
+            }
+        }
+        Self::screen_message(bot, msg, state, sender_is_chat_admin, false).await
+    }
+
+    /// Runs the message through the rules, heuristics and deleting checks, then queues
+    /// it for the classifier; `analyze_media` adds transcripts, video text and photo
+    /// descriptions to the text first.
+    async fn screen_message(
+        bot: Bot,
+        msg: Message,
+        state: Arc<AppState>,
+        sender_is_chat_admin: bool,
+        analyze_media: bool,
+    ) -> BotResult<()> {
+        let mut text = msg
             .text()
             .or_else(|| msg.caption())
             .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .unwrap_or_else(|| "[미디어 메시지]".to_string());
+            .unwrap_or_default();
+        if analyze_media {
+            if let Some((label, transcript)) = Self::transcribe_speech(&bot, &msg, &state).await {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[{label}] {transcript}"));
+            }
+            if let Some(overlay) = Self::read_video_text(&bot, &msg, &state).await {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[영상 속 텍스트] {overlay}"));
+            }
+            if let Some(description) = Self::describe_photo(&bot, &msg, &state).await {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[이미지 내용] {description}"));
+            }
+        }
+        if text.is_empty() {
+            text = MEDIA_PLACEHOLDER.to_string();
+        }
 
-        let from = msg.from.as_ref();
+        let from = message_sender(&msg);
+        let sender_chat = msg.sender_chat.as_ref();
         let from_display = from
             .map(format_user_display)
+            .or_else(|| sender_chat.map(format_chat_display))
             .unwrap_or_else(|| "Unknown".to_string());
-        let username = from.and_then(|u| u.username.clone());
+        let username = from
+            .and_then(|u| u.username.clone())
+            .or_else(|| sender_chat.and_then(|chat| chat.username().map(str::to_string)));
         let raw_user_id = from.map(|u| u.id);
         let from_id = from.map(user_to_i64);
 
@@ -343,212 +619,3956 @@ impl TelegramService {
         } else {
             false
         };
+        let activity = match from_id {
+            Some(user_id) => Self::member_activity(&state, msg.chat.id.0, user_id).await,
+            None => None,
+        };
+        let on_probation = match activity {
+            Some(activity) => Self::on_probation(&state, msg.chat.id.0, activity, msg.date).await,
+            None => false,
+        };
 
-        let (priority, priority_score) = calc_priority(&text, is_group_member);
-        let urls = extract_urls(&text, state.config.web.max_urls_per_message);
-        let job = MessageJob {
+        let entities = msg
+            .entities()
+            .or_else(|| msg.caption_entities())
+            .unwrap_or_default();
+        let invite_links = state
+            .unlisted_invites(msg.chat.id.0, invite_targets(&text, entities))
+            .await;
+        let live = state.config.live.get();
+        let max_urls = match &state.config.probation {
+            Some(probation) if on_probation => {
+                probation.max_urls.max(live.web.max_urls_per_message)
+            }
+            _ => live.web.max_urls_per_message,
+        };
+        let urls = extract_message_urls(&text, entities, max_urls);
+        let account_age_days = from_id.and_then(|id| account_age::estimate_age_days(id, msg.date));
+        let has_links = !urls.is_empty() || !invite_links.is_empty();
+        // Only link posters without a username can trip the bare-profile check.
+        let has_profile_photo = match raw_user_id {
+            Some(user_id) if has_links && username.is_none() => {
+                state.profiles.has_photo(&bot, user_id).await
+            }
+            _ => None,
+        };
+        let known_spam = state.known_spam(from_id, &text, entities).await;
+        let signals = heuristics::analyze(
+            &HeuristicInput {
+                text: &text,
+                entities,
+                invite_links: &invite_links,
+                username: username.as_deref(),
+                account_age_days,
+                has_links,
+                has_profile_photo,
+                known_spam: &known_spam,
+            },
+            &live.heuristics,
+        );
+        let reputation = match from_id {
+            Some(user_id) => state
+                .db
+                .reputation
+                .score(user_id)
+                .await
+                .unwrap_or_else(|err| {
+                    tracing::warn!(target: "db", error = %err, "failed to read user reputation");
+                    0
+                }),
+            None => 0,
+        };
+        let assessment = risk::assess(
+            &RiskInput {
+                is_member: is_group_member,
+                has_url: contains_url(&text),
+                invite_links: invite_links.len(),
+                reputation,
+                signals: &signals,
+            },
+            &live.heuristics.risk,
+        );
+        tracing::debug!(
+            target: "telegram",
+            score = assessment.score,
+            factors = ?assessment.factors,
+            "risk assessed"
+        );
+        let mut priority = if on_probation {
+            Priority::High
+        } else {
+            assessment.priority
+        };
+        let language = language::detect(&text);
+        let mut job = MessageJob {
             chat_id: msg.chat.id,
             chat_title: msg.chat.title().map(|t| t.to_string()),
             message_id: msg.id,
+            topic_id: topic_id(&msg),
             from_id,
+            sender_chat_id: sender_chat.map(|chat| chat.id.0),
+            sender_is_chat_admin,
             from_display,
             username,
             text,
             urls,
             is_group_member,
-            priority_score,
+            on_probation,
+            member_since: activity.map(|activity| activity.joined_at),
+            account_age_days,
+            language,
+            priority_score: assessment.score,
+            reputation,
+            signals,
             timestamp: msg.date,
+            attempts: 0,
         };
 
+        // Admins' messages only ever reach the classifier, never the deleting checks.
+        if job.sender_is_chat_admin {
+            state.queue.push(priority, job);
+            return Ok(());
+        }
+
+        if let Some(rule_match) = state.rules.evaluate(&job) {
+            match rule_match.action {
+                RuleAction::Ignore => return Ok(()),
+                RuleAction::Delete => {
+                    let reason = format!("관리자 규칙 {} 일치", rule_match.label);
+                    if let Err(err) = state.processor.delete_spam(&job, &reason).await {
+                        tracing::error!(
+                            target: "rules",
+                            error = %err,
+                            rule = %rule_match.label,
+                            "failed to delete message matched by rule"
+                        );
+                    }
+                    return Ok(());
+                }
+                RuleAction::Flag => {
+                    let notice = tf(
+                        state.config.language,
+                        Msg::RuleFlagged,
+                        &[
+                            ("rule", &escape_html(&rule_match.label)),
+                            ("pattern", &escape_html(&rule_match.pattern)),
+                            (
+                                "chat",
+                                &escape_html(job.chat_title.as_deref().unwrap_or("Unknown")),
+                            ),
+                            ("chat_id", &job.chat_id.0),
+                            ("user", &escape_html(&job.from_display)),
+                            ("text", &escape_html(&job.text)),
+                        ],
+                    );
+                    notify_admin_group(&state.admin_bot, state.config.as_ref(), &notice).await;
+                }
+                RuleAction::Boost => {
+                    priority = Priority::High;
+                    job.priority_score += RULE_BOOST_SCORE;
+                }
+            }
+        }
+
+        // Honeypot-harvested domains only feed the risk score; a spammer could plant
+        // popular sites there.
+        let domains: Vec<&str> = known_spam
+            .blocked_domains
+            .iter()
+            .filter(|blocked| blocked.is_admin())
+            .map(|blocked| blocked.domain.as_str())
+            .collect();
+        if !domains.is_empty() {
+            let reason = format!("차단된 도메인({})", domains.join(", "));
+            if let Err(err) = state.processor.delete_spam(&job, &reason).await {
+                tracing::error!(target: "telegram", error = %err, "failed to delete message linking a blocked domain");
+            }
+            return Ok(());
+        }
+
+        if let (Some(checker), false) = (&state.url_reputation, job.urls.is_empty()) {
+            if let Some(found) = checker.first_threat(&job.urls).await {
+                let reason = format!("위협 목록에 등록된 링크({}): {}", found.threat, found.url);
+                if let Err(err) = state.processor.delete_spam(&job, &reason).await {
+                    tracing::error!(target: "telegram", error = %err, "failed to delete message linking a listed URL");
+                }
+                return Ok(());
+            }
+        }
+
+        if let (Some(floods), Some(user_id)) = (&state.floods, job.from_id) {
+            if let Some(verdict) = floods.observe(job.chat_id.0, user_id, &job.text) {
+                if flood::respond(&bot, &state, &job, verdict).await {
+                    return Ok(());
+                }
+            }
+        }
+
+        if let (Some(raids), Some(raid_config)) = (&state.raids, &state.config.raid) {
+            let is_new_account = job
+                .account_age_days
+                .is_some_and(|days| days <= live.heuristics.new_account_days);
+            match raids.observe(&job, is_new_account) {
+                RaidVerdict::Detected { jobs } => {
+                    raid::respond(&bot, &state, raid_config, jobs).await;
+                    return Ok(());
+                }
+                RaidVerdict::FollowUp => {
+                    raid::respond_follow_up(&bot, &state, raid_config, &job).await;
+                    return Ok(());
+                }
+                RaidVerdict::None => {}
+            }
+        }
+
+        if let Some(reason) = Self::strict_mode_reason(&state, &job, assessment.strict).await {
+            if let Err(err) = state.processor.delete_spam(&job, &reason).await {
+                tracing::error!(
+                    target: "telegram",
+                    error = %err,
+                    "failed to delete message in strict mode"
+                );
+            }
+            return Ok(());
+        }
+
         state.queue.push(priority, job);
         Ok(())
     }
 
-    async fn on_command(
+    /// Starts probation tracking and the captcha when someone joins, seen through
+    /// `chat_member` updates (which Telegram only sends while the bot is a chat admin).
+    async fn on_chat_member(
         bot: Bot,
-        msg: Message,
-        cmd: GeneralCommand,
+        update: ChatMemberUpdated,
         state: Arc<AppState>,
     ) -> BotResult<()> {
-        match cmd {
-            GeneralCommand::Start => {
-                let allowed = state.is_chat_allowed(msg.chat.id.0).await;
-                bot.send_message(
-                    msg.chat.id,
-                    format!(
-                        "안녕하세요! 스팸 감지 봇입니다.\n현재 그룹 상태: {}",
-                        if allowed {
-                            "활성화됨"
-                        } else {
-                            "비활성화됨"
-                        }
-                    ),
-                )
-                .await?
-            }
-            GeneralCommand::Help => {
-                bot.send_message(msg.chat.id, GeneralCommand::descriptions().to_string())
-                    .await?
-            }
-            GeneralCommand::Status => {
-                let snapshot = (state.queue_snapshot)();
-                bot.send_message(
-                    msg.chat.id,
-                    format!(
-                        "봇 상태\n- 높은 우선순위: {}\n- 일반 우선순위: {}",
-                        snapshot.high_priority, snapshot.normal_priority
-                    ),
-                )
-                .await?
-            }
-            GeneralCommand::Chatid => {
-                bot.send_message(msg.chat.id, format!("현재 채팅 ID: {}", msg.chat.id))
-                    .await?
-            }
-            GeneralCommand::Ping => {
-                let start = Instant::now();
-                let sent = bot.send_message(msg.chat.id, "Pong 측정 중...").await?;
-                let elapsed = start.elapsed();
-                let latency_secs = elapsed.as_secs_f64();
-                bot.edit_message_text(
-                    msg.chat.id,
-                    sent.id,
-                    format!("Pong! 응답 속도: {:.3}초", latency_secs),
-                )
-                .await?
+        let joined = !update.old_chat_member.is_present() && update.new_chat_member.is_present();
+        if !joined || !state.is_chat_allowed(update.chat.id.0).await {
+            return Ok(());
+        }
+        let user = &update.new_chat_member.user;
+        if let Some(cas) = &state.cas {
+            if cas.ban_on_match()
+                && !state.is_admin_user(user_to_i64(user))
+                && cas.is_banned(user_to_i64(user)).await
+            {
+                match bot.ban_chat_member(update.chat.id, user.id).await {
+                    Ok(_) => {
+                        state
+                            .processor
+                            .record_ban(update.chat.id.0, user_to_i64(user), "cas", false)
+                            .await;
+                        tracing::info!(
+                            target: "telegram",
+                            chat_id = update.chat.id.0,
+                            user_id = user_to_i64(user),
+                            "CAS-listed joiner banned"
+                        );
+                        notify_admin_group(
+                            &state.admin_bot,
+                            state.config.as_ref(),
+                            &tf(
+                                state.config.language,
+                                Msg::CasJoinerBanned,
+                                &[
+                                    ("user", &escape_html(&format_user_display(user))),
+                                    ("user_id", &user.id),
+                                    (
+                                        "chat",
+                                        &escape_html(update.chat.title().unwrap_or("Unknown")),
+                                    ),
+                                ],
+                            ),
+                        )
+                        .await;
+                        return Ok(());
+                    }
+                    Err(err) => {
+                        tracing::warn!(target: "telegram", error = %err, "failed to ban CAS-listed joiner")
+                    }
+                }
             }
-        };
+        }
+        Self::record_member_join(&state, update.chat.id.0, user_to_i64(user), update.date).await;
+        if let Some(captcha) = &state.captcha {
+            let locale = state.locale(update.chat.id.0).await;
+            captcha.challenge(&bot, update.chat.id, user, locale).await;
+        }
         Ok(())
     }
 
-    async fn maybe_handle_admin_command(
-        bot: &Bot,
-        msg: &Message,
-        text: &str,
+    /// Asks the admin group to whitelist a group the bot was just added to, or whitelists
+    /// it right away when the bot admin added the bot.
+    async fn on_my_chat_member(
+        bot: Bot,
+        update: ChatMemberUpdated,
         state: Arc<AppState>,
-    ) -> BotResult<bool> {
-        if !text.starts_with('/') {
-            return Ok(false);
+    ) -> BotResult<()> {
+        let joined = !update.old_chat_member.is_present() && update.new_chat_member.is_present();
+        let chat_id = update.chat.id.0;
+        if !joined
+            || !(update.chat.is_group() || update.chat.is_supergroup())
+            || state.extra_bot.is_some()
+            || state.is_honeypot(chat_id)
+            || state.is_chat_allowed(chat_id).await
+        {
+            return Ok(());
         }
-        if !state.is_admin_group(msg.chat.id.0) {
-            return Ok(false);
+        tracing::info!(target: "telegram", chat_id, "bot added to a group outside the whitelist");
+        if state.is_admin_user(user_to_i64(&update.from)) {
+            Self::auto_whitelist(&bot, &update, &state).await;
+            return Ok(());
         }
-        let from = match msg.from.as_ref() {
-            Some(user) => user,
-            None => return Ok(false),
+        let sent = whitelist_request::send(&state, &update.chat, Some(&update.from)).await;
+        if sent == Msg::WhitelistRequestSent {
+            let locale = state.locale(chat_id).await;
+            if let Err(err) = bot
+                .send_message(update.chat.id, t(locale, Msg::WhitelistJoinRequested))
+                .await
+            {
+                tracing::warn!(target: "telegram", error = %err, chat_id, "failed to post whitelist notice");
+            }
+        }
+        Ok(())
+    }
+
+    async fn auto_whitelist(bot: &Bot, update: &ChatMemberUpdated, state: &AppState) {
+        let chat_id = update.chat.id.0;
+        let entry = WhitelistEntry {
+            chat_id,
+            chat_title: update.chat.title().map(|t| t.to_string()),
+            chat_type: Some(format!("{:?}", update.chat.kind)),
+            added_by: Some(user_to_i64(&update.from)),
+            expires_at: None,
         };
-        if !state.is_admin_user(user_to_i64(from)) {
-            bot.send_message(msg.chat.id, "이 명령어는 관리자만 사용할 수 있습니다.")
-                .await?;
-            return Ok(true);
+        if let Err(err) = state.db.whitelist.add_or_replace(entry).await {
+            tracing::error!(target: "telegram", error = %err, chat_id, "failed to auto-whitelist group");
+            return;
         }
+        tracing::info!(target: "telegram", chat_id, "group whitelisted because the bot admin added the bot");
 
-        let mut parts = text.split_whitespace();
-        let command = parts.next().unwrap_or("");
-        match command {
-            "/whitelist_add" => {
-                if let Some(target) = parts.next() {
-                    match target.parse::<i64>() {
-                        Ok(chat_id) => {
-                            Self::whitelist_add(bot, msg, chat_id, state.clone()).await?;
-                        }
-                        Err(_) => {
-                            bot.send_message(
-                                msg.chat.id,
-                                "올바른 그룹 ID를 입력하세요. 예: /whitelist_add -1001234567890",
-                            )
-                            .await?;
-                        }
-                    }
-                } else {
-                    bot.send_message(
-                        msg.chat.id,
-                        "그룹 ID가 필요합니다. 예: /whitelist_add -1001234567890",
-                    )
-                    .await?;
+        let text = tf(
+            state.config.language,
+            Msg::WhitelistAutoAdded,
+            &[
+                (
+                    "chat",
+                    &escape_html(update.chat.title().unwrap_or("Unknown")),
+                ),
+                ("chat_id", &chat_id),
+            ],
+        );
+        notify_admin_group(&state.admin_bot, state.config.as_ref(), &text).await;
+        let locale = state.locale(chat_id).await;
+        if let Err(err) = bot
+            .send_message(update.chat.id, t(locale, Msg::WhitelistApproved))
+            .await
+        {
+            tracing::warn!(target: "telegram", error = %err, chat_id, "failed to post whitelist notice");
+        }
+    }
+
+    /// Deletes a CAS-listed sender's message without classifying it, and bans them
+    /// when `CAS_BAN` is on.
+    async fn remove_cas_listed(bot: &Bot, state: &AppState, cas: &CasClient, msg: &Message) {
+        let job = Self::bare_job(msg, state.config.live.get().web.max_urls_per_message);
+        if let Err(err) = state.processor.delete_spam(&job, CAS_REASON).await {
+            tracing::error!(target: "telegram", error = %err, "failed to delete CAS-listed message");
+            return;
+        }
+        if state.processor.dry_run(msg.chat.id.0).await {
+            return;
+        }
+        if let (true, Some(user)) = (cas.ban_on_match(), message_sender(msg)) {
+            match bot.ban_chat_member(msg.chat.id, user.id).await {
+                Ok(_) => {
+                    state
+                        .processor
+                        .record_ban(msg.chat.id.0, user_to_i64(user), "cas", false)
+                        .await;
+                }
+                Err(err) => {
+                    tracing::warn!(target: "telegram", error = %err, "failed to ban CAS-listed sender");
                 }
-                Ok(true)
             }
-            "/whitelist_remove" => {
-                if let Some(target) = parts.next() {
-                    match target.parse::<i64>() {
-                        Ok(chat_id) => {
-                            Self::whitelist_remove(bot, msg, chat_id, state.clone()).await?;
-                        }
-                        Err(_) => {
-                            bot.send_message(
-                                msg.chat.id,
-                                "올바른 그룹 ID를 입력하세요. 예: /whitelist_remove -1001234567890",
+        }
+    }
+
+    async fn is_blacklisted(state: &AppState, user_id: i64) -> bool {
+        state
+            .db
+            .user_blacklist
+            .contains(user_id)
+            .await
+            .unwrap_or_else(|err| {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to check user blacklist");
+                false
+            })
+    }
+
+    /// Deletes a blacklisted sender's message without classifying it, and bans them
+    /// when `BLACKLIST_PRE_BAN` is on.
+    async fn remove_blacklisted(bot: &Bot, state: &AppState, msg: &Message) {
+        let job = Self::bare_job(msg, state.config.live.get().web.max_urls_per_message);
+        if let Err(err) = state.processor.delete_spam(&job, BLACKLIST_REASON).await {
+            tracing::error!(target: "telegram", error = %err, "failed to delete blacklisted sender's message");
+            return;
+        }
+        if state.processor.dry_run(msg.chat.id.0).await {
+            return;
+        }
+        if let (true, Some(user)) = (state.config.blacklist.pre_ban, message_sender(msg)) {
+            match bot.ban_chat_member(msg.chat.id, user.id).await {
+                Ok(_) => {
+                    state
+                        .processor
+                        .record_ban(msg.chat.id.0, user_to_i64(user), "blacklist", false)
+                        .await;
+                }
+                Err(err) => {
+                    tracing::warn!(target: "telegram", error = %err, "failed to ban blacklisted sender");
+                }
+            }
+        }
+    }
+
+    /// Joins are tracked for probation and for the prefilter's membership tenure.
+    fn tracks_member_joins(state: &AppState) -> bool {
+        state.config.probation.is_some() || state.config.live.get().heuristics.prefilter.is_some()
+    }
+
+    async fn record_member_join(state: &AppState, chat_id: i64, user_id: i64, at: DateTime<Utc>) {
+        if !Self::tracks_member_joins(state) {
+            return;
+        }
+        if let Err(err) = state
+            .db
+            .member_joins
+            .record_join(chat_id, user_id, at)
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, user_id, "failed to record member join");
+        }
+    }
+
+    async fn record_recent_message(state: &AppState, msg: &Message, user_id: i64) {
+        if let Err(err) = state
+            .db
+            .recent_messages
+            .record(msg.chat.id.0, msg.id.0, user_id, msg.date)
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, user_id, "failed to record recent message");
+        }
+    }
+
+    /// Counts the message toward the sender's activity since joining; `None` when joins
+    /// aren't tracked or theirs was never seen.
+    async fn member_activity(
+        state: &AppState,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Option<MemberActivity> {
+        if !Self::tracks_member_joins(state) {
+            return None;
+        }
+        match state.db.member_joins.record_message(chat_id, user_id).await {
+            Ok(activity) => activity,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to count member message");
+                None
+            }
+        }
+    }
+
+    /// Whether the sender is still within the chat's probation window: fewer messages
+    /// than the limit or too little time since joining.
+    async fn on_probation(
+        state: &AppState,
+        chat_id: i64,
+        activity: MemberActivity,
+        at: DateTime<Utc>,
+    ) -> bool {
+        let Some(config) = &state.config.probation else {
+            return false;
+        };
+        let default_window = (
+            config.messages,
+            chrono::Duration::from_std(config.window).unwrap_or_default(),
+        );
+        let (messages, window) = match state.db.chat_settings.probation(chat_id).await {
+            Ok(Some(window)) => (window.messages, chrono::Duration::hours(window.hours)),
+            Ok(None) => default_window,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, chat_id, "failed to load probation window");
+                default_window
+            }
+        };
+        activity.messages <= messages || at - activity.joined_at < window
+    }
+
+    /// Whether any configured media analyzer applies to the message.
+    fn has_analyzable_media(msg: &Message, state: &AppState) -> bool {
+        (state.transcriber.is_some() && (msg.voice().is_some() || msg.video_note().is_some()))
+            || (state.video_ocr.is_some()
+                && (msg.video().is_some()
+                    || msg.video_note().is_some()
+                    || msg.animation().is_some()))
+            || (state.vision.is_some() && msg.photo().is_some())
+    }
+
+    /// Transcribes a voice message or video note when speech-to-text is configured,
+    /// returning the prompt label with the transcript; failures fall back to the plain
+    /// media placeholder.
+    async fn transcribe_speech(
+        bot: &Bot,
+        msg: &Message,
+        state: &AppState,
+    ) -> Option<(&'static str, String)> {
+        let transcriber = state.transcriber.as_ref()?;
+        let (file_id, duration, file_name, label) = if let Some(voice) = msg.voice() {
+            (&voice.file.id, voice.duration, "voice.ogg", "음성 메시지")
+        } else if let Some(note) = msg.video_note() {
+            (
+                &note.file.id,
+                note.duration,
+                "video_note.mp4",
+                "영상 메시지 음성",
+            )
+        } else {
+            return None;
+        };
+        if duration.seconds() > transcriber.max_duration_secs() {
+            return None;
+        }
+        let result = async {
+            let audio = media::download(bot, file_id).await?;
+            transcriber.transcribe(audio, file_name).await
+        }
+        .await;
+        match result {
+            Ok(transcript) if !transcript.is_empty() => Some((label, transcript)),
+            Ok(_) => None,
+            Err(err) => {
+                tracing::warn!(
+                    target: "telegram",
+                    error = %err,
+                    chat_id = msg.chat.id.0,
+                    "speech transcription failed"
+                );
+                None
+            }
+        }
+    }
+
+    /// OCRs a few frames of short videos, video notes and GIFs when video OCR is enabled.
+    async fn read_video_text(bot: &Bot, msg: &Message, state: &AppState) -> Option<String> {
+        let ocr = state.video_ocr.as_ref()?;
+        let (file_id, duration) = if let Some(video) = msg.video() {
+            (&video.file.id, video.duration.seconds())
+        } else if let Some(note) = msg.video_note() {
+            (&note.file.id, note.duration.seconds())
+        } else if let Some(animation) = msg.animation() {
+            (&animation.file.id, animation.duration.seconds())
+        } else {
+            return None;
+        };
+        if duration > ocr.max_duration_secs() {
+            return None;
+        }
+        let result = async {
+            let video = media::download(bot, file_id).await?;
+            ocr.extract_text(video, duration).await
+        }
+        .await;
+        match result {
+            Ok(text) if !text.is_empty() => Some(text),
+            Ok(_) => None,
+            Err(err) => {
+                tracing::warn!(
+                    target: "telegram",
+                    error = %err,
+                    chat_id = msg.chat.id.0,
+                    "video OCR failed"
+                );
+                None
+            }
+        }
+    }
+
+    /// Describes a photo with the vision model when one is configured, using the
+    /// largest size within the download limit.
+    async fn describe_photo(bot: &Bot, msg: &Message, state: &AppState) -> Option<String> {
+        let describer = state.vision.as_ref()?;
+        let photo = msg
+            .photo()?
+            .iter()
+            .filter(|size| size.file.size <= describer.max_bytes())
+            .max_by_key(|size| size.file.size)?;
+        let result = async {
+            let image = media::download(bot, &photo.file.id).await?;
+            describer.describe(image).await
+        }
+        .await;
+        match result {
+            Ok(description) if !description.is_empty() => Some(description),
+            Ok(_) => None,
+            Err(err) => {
+                tracing::warn!(
+                    target: "telegram",
+                    error = %err,
+                    chat_id = msg.chat.id.0,
+                    "photo description failed"
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns a deletion reason when the chat opted into strict mode and the risk
+    /// assessment says strict mode should act without asking the classifier.
+    async fn strict_mode_reason(
+        state: &AppState,
+        job: &MessageJob,
+        strict: bool,
+    ) -> Option<String> {
+        if !strict {
+            return None;
+        }
+        match state.db.whitelist.is_strict(job.chat_id.0).await {
+            Ok(true) => {
+                let strict_signals: Vec<&str> = job
+                    .signals
+                    .iter()
+                    .filter(|signal| signal.strict)
+                    .map(|signal| signal.detail.as_str())
+                    .collect();
+                Some(if strict_signals.is_empty() {
+                    format!("엄격 모드: 위험 점수 {}", job.priority_score)
+                } else {
+                    format!("엄격 모드: {}", strict_signals.join(", "))
+                })
+            }
+            Ok(false) => None,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to read strict mode flag");
+                None
+            }
+        }
+    }
+
+    async fn on_command(
+        bot: Bot,
+        msg: Message,
+        cmd: GeneralCommand,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.locale(msg.chat.id.0).await;
+        match cmd {
+            GeneralCommand::Start => {
+                let allowed = state.is_chat_allowed(msg.chat.id.0).await;
+                let status = if allowed {
+                    Msg::GroupActive
+                } else {
+                    Msg::GroupInactive
+                };
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::StartGreeting,
+                        &[("status", &t(locale, status))],
+                    ),
+                )
+                .await?
+            }
+            GeneralCommand::Help => bot.send_message(msg.chat.id, general_help(locale)).await?,
+            GeneralCommand::Status => {
+                let snapshot = (state.queue_snapshot)();
+                let mut text = tf(
+                    locale,
+                    Msg::BotStatus,
+                    &[
+                        ("high", &snapshot.high_priority),
+                        ("normal", &snapshot.normal_priority),
+                    ],
+                );
+                let tz = state
+                    .config
+                    .timezone
+                    .parse()
+                    .unwrap_or(chrono_tz::Asia::Seoul);
+                text.push_str(&process_status(locale, tz, &state.processor));
+                bot.send_message(msg.chat.id, text).await?
+            }
+            GeneralCommand::Chatid => {
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::CurrentChatId, &[("chat_id", &msg.chat.id)]),
+                )
+                .await?
+            }
+            GeneralCommand::Ping => {
+                let start = Instant::now();
+                let sent = bot
+                    .send_message(msg.chat.id, t(locale, Msg::PingMeasuring))
+                    .await?;
+                let elapsed = start.elapsed();
+                let latency_secs = format!("{:.3}", elapsed.as_secs_f64());
+                bot.edit_message_text(
+                    msg.chat.id,
+                    sent.id,
+                    tf(locale, Msg::PingResult, &[("secs", &latency_secs)]),
+                )
+                .await?
+            }
+            GeneralCommand::Appeal(note) => {
+                Self::appeal(&bot, &msg, &note, state).await?;
+                return Ok(());
+            }
+            GeneralCommand::Spam => {
+                Self::report_spam(&bot, &msg, state).await?;
+                return Ok(());
+            }
+            GeneralCommand::RequestWhitelist => {
+                Self::request_whitelist(&bot, &msg, state).await?;
+                return Ok(());
+            }
+            GeneralCommand::WhitelistHere => {
+                Self::whitelist_here(&bot, &msg, state).await?;
+                return Ok(());
+            }
+        };
+        Ok(())
+    }
+
+    /// `/spam` sent as a reply: a chat admin's report deletes the message, a trusted
+    /// user's report sends it to the classifier ahead of the queue, and both store it as
+    /// a spam example.
+    async fn report_spam(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        if msg.chat.is_private() || !state.is_chat_allowed(msg.chat.id.0).await {
+            return Ok(());
+        }
+        let Some(reporter) = msg.from.as_ref() else {
+            return Ok(());
+        };
+        let locale = state.locale(msg.chat.id.0).await;
+        let Some(reported) = msg.reply_to_message() else {
+            bot.send_message(msg.chat.id, t(locale, Msg::SpamReplyRequired))
+                .await?;
+            return Ok(());
+        };
+
+        let reporter_id = user_to_i64(reporter);
+        let is_chat_admin = state.is_admin_user(reporter_id)
+            || state
+                .chat_admins
+                .contains(bot, msg.chat.id, reporter.id)
+                .await;
+        if !is_chat_admin && !state.config.trusted_reporter_ids.contains(&reporter_id) {
+            bot.send_message(msg.chat.id, t(locale, Msg::SpamReportNotAllowed))
+                .await?;
+            return Ok(());
+        }
+
+        if let Err(err) = bot.delete_message(msg.chat.id, msg.id).await {
+            tracing::debug!(target: "telegram", error = %err, "failed to delete /spam command");
+        }
+        let job = Self::bare_job(reported, state.config.live.get().web.max_urls_per_message);
+        tracing::info!(
+            target: "telegram",
+            chat_id = job.chat_id.0,
+            message_id = job.message_id.0,
+            reporter_id,
+            is_chat_admin,
+            "spam reported"
+        );
+        if !is_chat_admin {
+            let reason = format!("신뢰 사용자 신고: {}", format_user_display(reporter));
+            Self::record_example(&state, job.chat_id.0, &job.text, true, Some(&reason)).await;
+            state.queue.push(Priority::High, job);
+            return Ok(());
+        }
+
+        let reason = format!("관리자 신고: {}", format_user_display(reporter));
+        if let Err(err) = state.processor.delete_spam(&job, &reason).await {
+            tracing::error!(target: "telegram", error = %err, "failed to delete reported message");
+            return Ok(());
+        }
+        Self::record_example(&state, job.chat_id.0, &job.text, true, Some(&reason)).await;
+        Ok(())
+    }
+
+    /// Stores a labeled few-shot example, skipping media with no readable text.
+    async fn record_example(
+        state: &AppState,
+        chat_id: i64,
+        text: &str,
+        spam: bool,
+        reason: Option<&str>,
+    ) {
+        if text == MEDIA_PLACEHOLDER {
+            return;
+        }
+        if let Err(err) = state
+            .db
+            .examples
+            .add(chat_id, text, spam, reason, text_fingerprint(text))
+            .await
+        {
+            tracing::warn!(target: "db", error = %err, chat_id, "failed to store labeled example");
+        }
+    }
+
+    /// `/request_whitelist`: a group admin asks the bot admin to whitelist the group.
+    async fn request_whitelist(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let reply = if msg.chat.is_private() {
+            Msg::WhitelistRequestGroupOnly
+        } else if state.extra_bot.is_some() {
+            Msg::WhitelistRequestUnavailable
+        } else if state.is_chat_allowed(msg.chat.id.0).await {
+            Msg::WhitelistAlreadyActive
+        } else {
+            match message_sender(msg) {
+                Some(user) if state.chat_admins.contains(bot, msg.chat.id, user.id).await => {
+                    whitelist_request::send(&state, &msg.chat, Some(user)).await
+                }
+                _ => Msg::WhitelistRequestAdminOnly,
+            }
+        };
+        let locale = state.locale(msg.chat.id.0).await;
+        bot.send_message(msg.chat.id, t(locale, reply)).await?;
+        Ok(())
+    }
+
+    /// `/whitelist_here`: the bot admin whitelists the group the command is sent in.
+    async fn whitelist_here(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let refusal = if msg.chat.is_private() {
+            Some(Msg::WhitelistRequestGroupOnly)
+        } else if state.extra_bot.is_some() {
+            Some(Msg::WhitelistRequestUnavailable)
+        } else if !msg
+            .from
+            .as_ref()
+            .is_some_and(|user| state.is_admin_user(user_to_i64(user)))
+        {
+            Some(Msg::AdminOnly)
+        } else {
+            None
+        };
+        if let Some(refusal) = refusal {
+            let locale = state.locale(msg.chat.id.0).await;
+            bot.send_message(msg.chat.id, t(locale, refusal)).await?;
+            return Ok(());
+        }
+        Self::whitelist_add(bot, msg, msg.chat.id.0, None, state).await
+    }
+
+    /// Job built from the message alone, without the media and heuristic analysis
+    /// regular messages go through; used for `/spam` reports and CAS-listed senders.
+    fn bare_job(msg: &Message, max_urls: usize) -> MessageJob {
+        let text = msg
+            .text()
+            .or_else(|| msg.caption())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| MEDIA_PLACEHOLDER.to_string());
+        let from = message_sender(msg);
+        let from_id = from.map(user_to_i64);
+        let sender_chat = msg.sender_chat.as_ref();
+        MessageJob {
+            chat_id: msg.chat.id,
+            chat_title: msg.chat.title().map(|t| t.to_string()),
+            message_id: msg.id,
+            topic_id: topic_id(msg),
+            from_id,
+            sender_chat_id: sender_chat.map(|chat| chat.id.0),
+            sender_is_chat_admin: false,
+            from_display: from
+                .map(format_user_display)
+                .or_else(|| sender_chat.map(format_chat_display))
+                .unwrap_or_else(|| "Unknown".to_string()),
+            username: from
+                .and_then(|u| u.username.clone())
+                .or_else(|| sender_chat.and_then(|chat| chat.username().map(str::to_string))),
+            urls: extract_message_urls(
+                &text,
+                msg.entities()
+                    .or_else(|| msg.caption_entities())
+                    .unwrap_or_default(),
+                max_urls,
+            ),
+            is_group_member: false,
+            on_probation: false,
+            member_since: None,
+            account_age_days: from_id.and_then(|id| account_age::estimate_age_days(id, msg.date)),
+            language: language::detect(&text),
+            priority_score: 0,
+            reputation: 0,
+            signals: Vec::new(),
+            timestamp: msg.date,
+            attempts: 0,
+            text,
+        }
+    }
+
+    async fn maybe_handle_admin_command(
+        bot: &Bot,
+        msg: &Message,
+        text: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<bool> {
+        if !text.starts_with('/') {
+            return Ok(false);
+        }
+        if !state.is_admin_group(msg.chat.id.0) {
+            return Ok(false);
+        }
+        let from = match msg.from.as_ref() {
+            Some(user) => user,
+            None => return Ok(false),
+        };
+        if !state.is_admin_user(user_to_i64(from)) {
+            let locale = state.locale(msg.chat.id.0).await;
+            bot.send_message(msg.chat.id, t(locale, Msg::AdminOnly))
+                .await?;
+            return Ok(true);
+        }
+
+        let locale = state.config.language;
+        let mut parts = text.split_whitespace();
+        let command = parts.next().unwrap_or("");
+        match command {
+            "/whitelist_add" => {
+                if let Some(target) = parts.next() {
+                    let ttl = parts.next().map(|value| parse_ttl(value).ok_or(value));
+                    match (target.parse::<i64>(), ttl.transpose()) {
+                        (Ok(chat_id), Ok(ttl)) => {
+                            Self::whitelist_add(bot, msg, chat_id, ttl, state.clone()).await?;
+                        }
+                        (Err(_), _) => {
+                            bot.send_message(
+                                msg.chat.id,
+                                tf(
+                                    locale,
+                                    Msg::InvalidGroupId,
+                                    &[("command", &"/whitelist_add")],
+                                ),
+                            )
+                            .await?;
+                        }
+                        (_, Err(value)) => {
+                            bot.send_message(
+                                msg.chat.id,
+                                tf(locale, Msg::InvalidTtl, &[("value", &value)]),
                             )
                             .await?;
                         }
                     }
                 } else {
-                    bot.send_message(
-                        msg.chat.id,
-                        "그룹 ID가 필요합니다. 예: /whitelist_remove -1001234567890",
-                    )
-                    .await?;
+                    bot.send_message(msg.chat.id, t(locale, Msg::WhitelistAddUsage))
+                        .await?;
                 }
                 Ok(true)
             }
-            "/whitelist_list" => {
-                Self::whitelist_list(bot, msg, state.clone()).await?;
-                Ok(true)
+            "/whitelist_remove" => {
+                if let Some(target) = parts.next() {
+                    match target.parse::<i64>() {
+                        Ok(chat_id) => {
+                            Self::whitelist_remove(bot, msg, chat_id, state.clone()).await?;
+                        }
+                        Err(_) => {
+                            bot.send_message(
+                                msg.chat.id,
+                                tf(
+                                    locale,
+                                    Msg::InvalidGroupId,
+                                    &[("command", &"/whitelist_remove")],
+                                ),
+                            )
+                            .await?;
+                        }
+                    }
+                } else {
+                    bot.send_message(msg.chat.id, t(locale, Msg::WhitelistRemoveUsage))
+                        .await?;
+                }
+                Ok(true)
+            }
+            "/whitelist_list" => {
+                Self::whitelist_list(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/restart_cron_list" => {
+                Self::restart_cron_list(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/restart_cron_add" => {
+                let spec = text[command.len()..].trim();
+                if spec.is_empty() {
+                    bot.send_message(msg.chat.id, t(locale, Msg::CronSpecRequired))
+                        .await?;
+                } else {
+                    Self::restart_cron_add(bot, msg, spec, state.clone()).await?;
+                }
+                Ok(true)
+            }
+            "/restart_cron_remove" => {
+                match parts.next().and_then(|v| v.parse::<usize>().ok()) {
+                    Some(index) => {
+                        Self::restart_cron_remove(bot, msg, index, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::CronIndexRequired))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/rule_add" => {
+                let (args, pattern) = split_args(text, 4);
+                let parsed = match args.as_slice() {
+                    [_, chat_id, kind, action] if !pattern.is_empty() => chat_id
+                        .parse::<i64>()
+                        .ok()
+                        .zip(RuleKind::parse(kind))
+                        .zip(RuleAction::parse(action)),
+                    _ => None,
+                };
+                match parsed {
+                    Some(((chat_id, kind), action)) => {
+                        Self::rule_add(bot, msg, chat_id, kind, action, pattern, state.clone())
+                            .await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::RuleAddUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/rule_list" => {
+                let chat_filter = parts.next().and_then(|v| v.parse::<i64>().ok());
+                Self::rule_list(bot, msg, chat_filter, state.clone()).await?;
+                Ok(true)
+            }
+            "/rule_remove" => {
+                match parts.next().and_then(|v| v.parse::<i64>().ok()) {
+                    Some(rule_id) => match state.rules.remove(rule_id).await {
+                        Ok(true) => {
+                            tracing::info!(target: "admin", rule_id, "chat rule removed");
+                            bot.send_message(
+                                msg.chat.id,
+                                tf(locale, Msg::RuleRemoved, &[("rule_id", &rule_id)]),
+                            )
+                            .await?;
+                        }
+                        Ok(false) => {
+                            bot.send_message(msg.chat.id, t(locale, Msg::RuleNotFound))
+                                .await?;
+                        }
+                        Err(err) => {
+                            tracing::error!(target: "admin", error = %err, "failed to remove chat rule");
+                            bot.send_message(msg.chat.id, t(locale, Msg::RuleRemoveFailed))
+                                .await?;
+                        }
+                    },
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::RuleIdRequired))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/whitelist_export" => {
+                match parts.next().map(FileFormat::parse) {
+                    Some(None) => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/whitelist_export [json|csv]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                    format => {
+                        let format = format.flatten().unwrap_or(FileFormat::Json);
+                        Self::whitelist_export(bot, msg, format, state.clone()).await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/whitelist_import" => {
+                Self::whitelist_import(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/whitelist_note" => {
+                let (args, note) = split_args(text, 2);
+                match args.get(1).and_then(|v| v.parse::<i64>().ok()) {
+                    Some(chat_id) if !note.is_empty() => {
+                        Self::whitelist_note(bot, msg, chat_id, note, state.clone()).await?;
+                    }
+                    _ => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::WhitelistNoteUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/criteria" => {
+                let (args, criteria) = split_args(text, 2);
+                match args.get(1).and_then(|v| v.parse::<i64>().ok()) {
+                    Some(chat_id) => {
+                        Self::chat_criteria(bot, msg, chat_id, criteria, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::CriteriaUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/action" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let action = parts.collect::<Vec<_>>().join(":");
+                match chat_id {
+                    Some(chat_id) => {
+                        Self::chat_action(bot, msg, chat_id, &action, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::ActionUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/probation" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let rest: Vec<&str> = parts.collect();
+                let window = match rest.as_slice() {
+                    [] => Some(None),
+                    [value] if value.eq_ignore_ascii_case("clear") => Some(Some(None)),
+                    [value] if value.eq_ignore_ascii_case("off") => {
+                        Some(Some(Some(ProbationWindow {
+                            messages: 0,
+                            hours: 0,
+                        })))
+                    }
+                    [messages, hours] => messages
+                        .parse::<i64>()
+                        .ok()
+                        .zip(hours.parse::<i64>().ok())
+                        .filter(|(messages, hours)| *messages >= 0 && *hours >= 0)
+                        .map(|(messages, hours)| Some(Some(ProbationWindow { messages, hours }))),
+                    _ => None,
+                };
+                match (chat_id, window) {
+                    (Some(chat_id), Some(update)) => {
+                        Self::chat_probation(bot, msg, chat_id, update, state.clone()).await?;
+                    }
+                    _ => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::ProbationUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/language" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let update = match parts.next() {
+                    None => Some(None),
+                    Some(value) if value.eq_ignore_ascii_case("clear") => Some(Some(None)),
+                    Some(value) => Locale::parse(value).map(|locale| Some(Some(locale))),
+                };
+                match chat_id.zip(update) {
+                    Some((chat_id, update)) => {
+                        Self::chat_language(bot, msg, chat_id, update, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/language <chat_id> [ko | en | clear]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/notice" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let update = match parts.next() {
+                    None => Some(None),
+                    Some(value) if value.eq_ignore_ascii_case("clear") => Some(Some(None)),
+                    Some(value) if value.eq_ignore_ascii_case("off") => Some(Some(Some(0))),
+                    Some(value) => value
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|secs| *secs >= 0)
+                        .map(|secs| Some(Some(secs))),
+                };
+                match chat_id.zip(update) {
+                    Some((chat_id, update)) => {
+                        Self::chat_notice(bot, msg, chat_id, update, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::NoticeUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/settings" => {
+                match parts.next().and_then(|v| v.parse::<i64>().ok()) {
+                    Some(chat_id) => {
+                        Self::chat_settings(bot, msg, chat_id, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(locale, Msg::UsageHint, &[("usage", &"/settings <chat_id>")]),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/strict_mode" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let enabled = match parts.next().map(|v| v.to_ascii_lowercase()) {
+                    Some(v) if v == "on" => Some(true),
+                    Some(v) if v == "off" => Some(false),
+                    _ => None,
+                };
+                match chat_id.zip(enabled) {
+                    Some((chat_id, enabled)) => {
+                        Self::strict_mode_set(bot, msg, chat_id, enabled, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/strict_mode <chat_id> <on|off>")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/invite_allow" | "/invite_allow_remove" => {
+                let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let target = parts.next().and_then(normalize_target);
+                match chat_id.zip(target) {
+                    Some((chat_id, target)) if command == "/invite_allow" => {
+                        Self::invite_allow(bot, msg, chat_id, &target, state.clone()).await?;
+                    }
+                    Some((chat_id, target)) => {
+                        Self::invite_allow_remove(bot, msg, chat_id, &target, state.clone())
+                            .await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(locale, Msg::InviteAllowUsage, &[("command", &command)]),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/blacklist_add" => {
+                let (args, reason) = split_args(text, 2);
+                match args.get(1).and_then(|v| v.parse::<i64>().ok()) {
+                    Some(user_id) => {
+                        Self::blacklist_add(bot, msg, user_id, reason, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::BlacklistAddUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/blacklist_remove" => {
+                match parts.next().and_then(|v| v.parse::<i64>().ok()) {
+                    Some(user_id) => {
+                        Self::blacklist_remove(bot, msg, user_id, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/blacklist_remove <user_id>")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/blacklist_list" => {
+                Self::blacklist_list(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/feedback_export" => {
+                match parts.next().map(|v| v.parse::<u32>()) {
+                    Some(Err(_)) => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/feedback_export [days]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                    days => {
+                        Self::feedback_export(bot, msg, days.and_then(Result::ok), state.clone())
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/unban" => {
+                match parts.next().and_then(|v| v.parse::<i64>().ok()) {
+                    Some(user_id) => {
+                        let chat_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                        Self::unban(bot, msg, user_id, chat_id, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/unban <user_id> [chat_id]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/logs" => {
+                let lines = parts
+                    .next()
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(LOGS_DEFAULT_LINES)
+                    .clamp(1, LOGS_MAX_LINES);
+                Self::logs(bot, msg, lines, state.clone()).await?;
+                Ok(true)
+            }
+            "/backup_now" => {
+                Self::backup_now(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/restart" => {
+                tracing::info!(target: "admin", admin_id = user_to_i64(from), "restart requested");
+                let reply = if state.config.scheduler.restart_wait_for_idle {
+                    t(locale, Msg::RestartRequestedWhenIdle)
+                } else {
+                    t(locale, Msg::RestartRequested)
+                };
+                bot.send_message(msg.chat.id, reply).await?;
+                state.restart_schedule.restart_now();
+                Ok(true)
+            }
+            "/update" => {
+                Self::update(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/version" => {
+                Self::version(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/queue" => {
+                Self::queue_list(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/test_spam" => {
+                let (_, sample) = split_args(text, 1);
+                let target = match msg.reply_to_message() {
+                    Some(reply) if sample.is_empty() => Some(Self::bare_job(
+                        reply,
+                        state.config.live.get().web.max_urls_per_message,
+                    )),
+                    _ if !sample.is_empty() => {
+                        let mut job =
+                            Self::bare_job(msg, state.config.live.get().web.max_urls_per_message);
+                        job.language = language::detect(sample);
+                        job.text = sample.to_string();
+                        Some(job)
+                    }
+                    _ => None,
+                };
+                match target {
+                    Some(job) => Self::test_spam(bot, msg, job, state.clone()).await?,
+                    None => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::TestSpamUsage))
+                            .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/purge_user" => {
+                let user_id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let mut chat_id = None;
+                let mut ban = false;
+                let mut valid = true;
+                for arg in parts.by_ref() {
+                    if arg.eq_ignore_ascii_case("ban") {
+                        ban = true;
+                    } else if let (None, Ok(id)) = (chat_id, arg.parse::<i64>()) {
+                        chat_id = Some(id);
+                    } else {
+                        valid = false;
+                    }
+                }
+                match user_id.filter(|_| valid) {
+                    Some(user_id) => {
+                        Self::purge_user(bot, msg, user_id, chat_id, ban, state.clone()).await?;
+                    }
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/purge_user <user_id> [chat_id] [ban]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/domain_block" | "/domain_allow" | "/domain_block_remove" | "/domain_allow_remove" => {
+                match parts.next().and_then(normalize_domain) {
+                    Some(domain) => match command {
+                        "/domain_block" => {
+                            Self::domain_block(bot, msg, &domain, state.clone()).await?
+                        }
+                        "/domain_allow" => {
+                            Self::domain_allow(bot, msg, &domain, state.clone()).await?
+                        }
+                        _ => {
+                            let blocked = command == "/domain_block_remove";
+                            Self::domain_remove(bot, msg, &domain, blocked, state.clone()).await?
+                        }
+                    },
+                    None => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(locale, Msg::DomainUsage, &[("command", &command)]),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/domain_list" => {
+                Self::domain_list(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/invite_allow_list" => {
+                let chat_filter = parts.next().and_then(|v| v.parse::<i64>().ok());
+                Self::invite_allow_list(bot, msg, chat_filter, state.clone()).await?;
+                Ok(true)
+            }
+            "/reload_config" => {
+                Self::reload_config(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/reload_prompt" => {
+                Self::reload_prompt(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/two_stage_stats" => {
+                Self::two_stage_stats(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/shadow_stats" => {
+                Self::shadow_stats(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/review" => {
+                let id = parts.next().and_then(|v| v.parse::<i64>().ok());
+                let decision = match parts.next().map(|v| v.to_ascii_lowercase()) {
+                    Some(v) if v == "accept" => Some(true),
+                    Some(v) if v == "reject" => Some(false),
+                    _ => None,
+                };
+                match (id, decision) {
+                    (None, _) => Self::review_list(bot, msg, state.clone()).await?,
+                    (Some(id), Some(accepted)) => {
+                        Self::review_decide(bot, msg, id, accepted, state.clone()).await?
+                    }
+                    (Some(_), None) => {
+                        bot.send_message(
+                            msg.chat.id,
+                            tf(
+                                locale,
+                                Msg::UsageHint,
+                                &[("usage", &"/review [<id> <accept|reject>]")],
+                            ),
+                        )
+                        .await?;
+                    }
+                }
+                Ok(true)
+            }
+            "/usage" => {
+                Self::usage(bot, msg, state.clone()).await?;
+                Ok(true)
+            }
+            "/sync_commands" => {
+                Self::sync_commands_for(bot, &state.config).await?;
+                bot.send_message(msg.chat.id, t(locale, Msg::CommandsSynced))
+                    .await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Re-reads `.env` and swaps in the settings that can change without a restart.
+    async fn reload_config(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let settings = state.config.live.reload();
+        let heuristics = &settings.heuristics;
+        let locale = state.config.language;
+        let reply = tf(
+            locale,
+            Msg::ReloadConfigReply,
+            &[
+                ("allowed", &settings.allowed_chat_ids.len()),
+                (
+                    "confidence",
+                    &format!("{:.2}", settings.ai_confidence_threshold),
+                ),
+                ("max_urls", &settings.web.max_urls_per_message),
+                ("high_priority", &heuristics.risk.high_priority_threshold),
+                ("strict", &heuristics.risk.strict_threshold),
+                (
+                    "prefilter",
+                    &t(
+                        locale,
+                        if heuristics.prefilter.is_some() {
+                            Msg::SettingOn
+                        } else {
+                            Msg::SettingOff
+                        },
+                    ),
+                ),
+            ],
+        );
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn reload_prompt(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let reply = match state.processor.system_prompt().reload() {
+            Ok(paths) if !paths.is_empty() => tf(
+                locale,
+                Msg::PromptReloaded,
+                &[(
+                    "paths",
+                    &paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )],
+            ),
+            Ok(_) => t(locale, Msg::PromptBuiltin).to_string(),
+            Err(err) => {
+                tracing::error!(target: "admin", error = %format!("{err:#}"), "failed to reload system prompt");
+                tf(
+                    locale,
+                    Msg::PromptReloadFailed,
+                    &[("error", &format!("{err:#}"))],
+                )
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn two_stage_stats(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let reply = match &state.two_stage {
+            Some(two_stage) => {
+                let report = two_stage.report();
+                tf(
+                    locale,
+                    Msg::TwoStageStats,
+                    &[
+                        ("flagged", &report.flagged),
+                        ("confirmed", &report.confirmed),
+                        ("overturned", &report.overturned()),
+                        (
+                            "rate",
+                            &format!("{:.1}", report.disagreement_rate() * 100.0),
+                        ),
+                    ],
+                )
+            }
+            None => t(locale, Msg::TwoStageDisabled).to_string(),
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn shadow_stats(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let Some(shadow) = &state.config.shadow else {
+            bot.send_message(msg.chat.id, t(locale, Msg::ShadowDisabled))
+                .await?;
+            return Ok(());
+        };
+        let results = &state.db.shadow_results;
+        let loaded = async {
+            let summary = results
+                .summary_since(Utc::now() - chrono::Duration::days(7))
+                .await?;
+            let disagreements = results.recent_disagreements(5).await?;
+            anyhow::Ok((summary, disagreements))
+        };
+        let reply = match loaded.await {
+            Ok((summary, disagreements)) => {
+                let rate = |count: i64| count as f64 * 100.0 / summary.total.max(1) as f64;
+                let mut reply = tf(
+                    locale,
+                    Msg::ShadowStats,
+                    &[
+                        ("model", &shadow.provider.model),
+                        (
+                            "prompt",
+                            &shadow
+                                .prompt_file
+                                .as_deref()
+                                .unwrap_or(t(locale, Msg::ShadowLivePrompt)),
+                        ),
+                        ("total", &summary.total),
+                        ("agreed", &summary.agreed),
+                        ("agreed_rate", &format!("{:.1}", rate(summary.agreed))),
+                        ("shadow_only", &summary.shadow_only),
+                        ("shadow_rate", &format!("{:.1}", rate(summary.shadow_only))),
+                        ("live_only", &summary.live_only),
+                        ("live_rate", &format!("{:.1}", rate(summary.live_only))),
+                    ],
+                );
+                if !disagreements.is_empty() {
+                    reply.push_str(t(locale, Msg::ShadowRecentDisagreements));
+                    for result in disagreements {
+                        let verdict = if result.shadow_spam {
+                            tf(
+                                locale,
+                                Msg::ShadowOnlySpam,
+                                &[(
+                                    "reason",
+                                    &result
+                                        .shadow_reason
+                                        .as_deref()
+                                        .unwrap_or(t(locale, Msg::NoReason)),
+                                )],
+                            )
+                        } else {
+                            t(locale, Msg::LiveOnlySpam).to_string()
+                        };
+                        let text: String = result.text.chars().take(60).collect();
+                        reply.push_str(&format!(
+                            "\n- [{}/{}] {verdict}: {text}",
+                            result.chat_id, result.message_id
+                        ));
+                    }
+                }
+                reply
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load shadow results");
+                t(locale, Msg::ShadowLoadFailed).to_string()
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn usage(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let now = Utc::now();
+        let mut reply = t(locale, Msg::UsageHeader).to_string();
+        for days in [1, 7, 30] {
+            let period = match days {
+                1 => t(locale, Msg::UsageLastDay).to_string(),
+                days => tf(locale, Msg::DigestPeriod, &[("days", &days)]),
+            };
+            match state
+                .db
+                .usage
+                .totals_since(now - chrono::Duration::days(days))
+                .await
+            {
+                Ok(totals) => reply.push_str(&format_usage(locale, &period, &totals)),
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load AI usage");
+                    bot.send_message(msg.chat.id, t(locale, Msg::UsageLoadFailed))
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Whitelists the chat, until `ttl` from now when given.
+    async fn whitelist_add(
+        bot: &Bot,
+        msg: &Message,
+        target_chat_id: i64,
+        ttl: Option<chrono::Duration>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match bot.get_chat(ChatId(target_chat_id)).await {
+            Ok(chat_info) => {
+                let expires_at = ttl.map(|ttl| Utc::now() + ttl);
+                let entry = WhitelistEntry {
+                    chat_id: target_chat_id,
+                    chat_title: chat_info.title().map(|t| t.to_string()),
+                    chat_type: Some(format!("{:?}", chat_info.kind)),
+                    added_by: msg.from.as_ref().map(user_to_i64),
+                    expires_at,
+                };
+                match state.db.whitelist.add_or_replace(entry).await {
+                    Ok(true) => {
+                        tracing::info!(
+                            target: "admin",
+                            chat_id = target_chat_id,
+                            added_by = msg.from.as_ref().map(user_to_i64),
+                            expires_at = expires_at.map(|at| at.to_rfc3339()),
+                            "whitelist entry added"
+                        );
+                        let mut reply =
+                            tf(locale, Msg::WhitelistAdded, &[("chat_id", &target_chat_id)]);
+                        if let Some(expires_at) = expires_at {
+                            reply.push_str(&tf(
+                                locale,
+                                Msg::WhitelistExpires,
+                                &[("at", &expires_at.format("%Y-%m-%d %H:%M UTC"))],
+                            ));
+                        }
+                        bot.send_message(msg.chat.id, reply).await?;
+                    }
+                    Ok(false) => {
+                        bot.send_message(msg.chat.id, t(locale, Msg::WhitelistExists))
+                            .await?;
+                    }
+                    Err(err) => {
+                        tracing::error!(target: "admin", error = %err, "failed to add whitelist");
+                        bot.send_message(msg.chat.id, t(locale, Msg::WhitelistAddFailed))
+                            .await?;
+                    }
+                }
+            }
+            Err(_) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::GroupNotFound))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn on_callback_query(bot: Bot, q: CallbackQuery, state: Arc<AppState>) -> BotResult<()> {
+        let Some(data) = q.data.as_deref() else {
+            return Ok(());
+        };
+
+        if let Some(captcha) = data.strip_prefix("captcha:") {
+            if let Some(gate) = &state.captcha {
+                let answer = gate.answer(&bot, user_to_i64(&q.from), captcha).await;
+                let locale = match &q.message {
+                    Some(message) => state.locale(message.chat().id.0).await,
+                    None => state.config.language,
+                };
+                bot.answer_callback_query(q.id)
+                    .text(t(locale, answer))
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        // Only handle ban actions from the admin group
+        let Some(message) = q.message else {
+            return Ok(());
+        };
+        let chat = message.chat();
+        if !state.is_admin_group(chat.id.0) {
+            return Ok(());
+        }
+
+        let locale = state.config.language;
+        if !state.is_admin_user(user_to_i64(&q.from)) {
+            bot.answer_callback_query(q.id)
+                .text(t(locale, Msg::AdminActionOnly))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        }
+
+        if let Some(review) = data.strip_prefix("review:") {
+            Self::resolve_review(&bot, q.id, chat.id, message.id(), review, state).await?;
+            return Ok(());
+        }
+
+        if let Some(action) = data.strip_prefix("spamlog:") {
+            let admin_id = user_to_i64(&q.from);
+            Self::resolve_spam_log(&bot, q.id, &message, action, admin_id, state).await?;
+            return Ok(());
+        }
+
+        if let Some(request) = data.strip_prefix("wlreq:") {
+            Self::resolve_whitelist_request(
+                &bot,
+                q.id,
+                chat.id,
+                message.id(),
+                request,
+                user_to_i64(&q.from),
+                state,
+            )
+            .await?;
+            return Ok(());
+        }
+
+        if let Some(setting) = data.strip_prefix("settings:") {
+            Self::update_setting(&bot, q.id, chat.id, message.id(), setting, state).await?;
+            return Ok(());
+        }
+
+        if !data.starts_with("ban:") {
+            return Ok(());
+        }
+
+        let parts: Vec<&str> = data.split(':').collect();
+        if parts.len() != 3 {
+            bot.answer_callback_query(q.id)
+                .text(t(locale, Msg::InvalidRequest))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        }
+
+        let chat_id: i64 = match parts[1].parse() {
+            Ok(id) => id,
+            Err(_) => {
+                bot.answer_callback_query(q.id)
+                    .text(t(locale, Msg::InvalidChatId))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let user_id_raw: i64 = match parts[2].parse() {
+            Ok(id) => id,
+            Err(_) => {
+                bot.answer_callback_query(q.id)
+                    .text(t(locale, Msg::InvalidUserId))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        if user_id_raw < 0 {
+            bot.answer_callback_query(q.id)
+                .text(t(locale, Msg::InvalidUserId))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        }
+
+        let target_user = UserId(user_id_raw as u64);
+
+        match state
+            .processor
+            .bot_for(ChatId(chat_id))
+            .ban_chat_member(ChatId(chat_id), target_user)
+            .await
+        {
+            Ok(_) => {
+                state
+                    .processor
+                    .record_ban(chat_id, user_id_raw, "admin", false)
+                    .await;
+                bot.answer_callback_query(q.id)
+                    .text(t(locale, Msg::BanDone))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(
+                    target: "telegram",
+                    error = %err,
+                    chat_id,
+                    user_id = user_id_raw,
+                    "failed to ban user via callback"
+                );
+                bot.answer_callback_query(q.id)
+                    .text(t(locale, Msg::BanFailed))
+                    .show_alert(true)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the oldest pending false-positive cases.
+    async fn review_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let reply = match state.db.appeals.pending(10).await {
+            Ok(appeals) if appeals.is_empty() => t(locale, Msg::ReviewEmpty).to_string(),
+            Ok(appeals) => {
+                let mut reply = t(locale, Msg::ReviewListHeader).to_string();
+                for appeal in appeals {
+                    let source = if appeal.source == AppealSource::Dm.as_str() {
+                        t(locale, Msg::ReviewSourceAppeal)
+                    } else {
+                        t(locale, Msg::ReviewSourceRestore)
+                    };
+                    let text: String = appeal.text.chars().take(80).collect();
+                    reply.push_str(&tf(
+                        locale,
+                        Msg::ReviewListEntry,
+                        &[
+                            ("id", &appeal.id),
+                            ("source", &source),
+                            ("chat_id", &appeal.chat_id),
+                            ("user", &appeal.user_display),
+                            ("text", &text),
+                        ],
+                    ));
+                    if let Some(note) = &appeal.note {
+                        reply.push_str(&tf(locale, Msg::ReviewListNote, &[("note", note)]));
+                    }
+                }
+                reply
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load appeals");
+                t(locale, Msg::ReviewLoadFailed).to_string()
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Closes a case: accepting records a false positive, rejecting stores the message
+    /// as a confirmed spam example. DM appellants are told the outcome.
+    async fn review_decide(
+        bot: &Bot,
+        msg: &Message,
+        id: i64,
+        accepted: bool,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let admin_locale = state.config.language;
+        let appeal = match state.db.appeals.decide(id, accepted).await {
+            Ok(Some(appeal)) => appeal,
+            Ok(None) => {
+                bot.send_message(
+                    msg.chat.id,
+                    tf(admin_locale, Msg::ReviewNotFound, &[("id", &id)]),
+                )
+                .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, id, "failed to decide appeal");
+                bot.send_message(msg.chat.id, t(admin_locale, Msg::ReviewDecideFailed))
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        if accepted {
+            Self::record_false_positive(&state, appeal.chat_id, appeal.user_id, &appeal.text).await;
+        } else {
+            Self::record_example(
+                &state,
+                appeal.chat_id,
+                &appeal.text,
+                true,
+                Some("오탐 신고 기각"),
+            )
+            .await;
+        }
+        Self::record_feedback(
+            &state,
+            NewFeedback {
+                chat_id: appeal.chat_id,
+                user_id: appeal.user_id,
+                text: &appeal.text,
+                spam: !accepted,
+                model_reason: Some(&appeal.reason),
+                confidence: appeal.confidence,
+                source: &appeal.source,
+                decided_by: msg.from.as_ref().map(user_to_i64),
+                appeal_id: Some(appeal.id),
+            },
+        )
+        .await;
+        tracing::info!(target: "admin", id, accepted, source = %appeal.source, "appeal decided");
+
+        if let (true, Some(user_id)) = (appeal.source == AppealSource::Dm.as_str(), appeal.user_id)
+        {
+            let notice = if accepted {
+                Msg::AppealAccepted
+            } else {
+                Msg::AppealRejected
+            };
+            let locale = state.locale(appeal.chat_id).await;
+            if let Err(err) = bot.send_message(ChatId(user_id), t(locale, notice)).await {
+                tracing::warn!(target: "telegram", error = %err, user_id, "failed to notify appellant");
+            }
+        }
+        let reply = if accepted {
+            Msg::ReviewAccepted
+        } else {
+            Msg::ReviewRejected
+        };
+        bot.send_message(msg.chat.id, tf(admin_locale, reply, &[("id", &id)]))
+            .await?;
+        Ok(())
+    }
+
+    /// `/appeal` in a private chat: disputes the sender's latest deleted message.
+    async fn appeal(bot: &Bot, msg: &Message, note: &str, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.locale(msg.chat.id.0).await;
+        if !msg.chat.is_private() {
+            bot.send_message(msg.chat.id, t(locale, Msg::AppealPrivateOnly))
+                .await?;
+            return Ok(());
+        }
+        let Some(user_id) = msg.from.as_ref().map(user_to_i64) else {
+            return Ok(());
+        };
+        let entry = match state.db.spam_log.latest_for_user(user_id).await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::AppealNoRecord))
+                    .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                tracing::error!(target: "db", error = %err, user_id, "failed to load spam log");
+                bot.send_message(msg.chat.id, t(locale, Msg::AppealFailed))
+                    .await?;
+                return Ok(());
+            }
+        };
+        // Answer in the language of the chat the message was deleted from.
+        let locale = state.locale(entry.chat_id).await;
+
+        let note = note.trim();
+        let opened = state
+            .db
+            .appeals
+            .open(
+                entry.id,
+                AppealSource::Dm,
+                (!note.is_empty()).then_some(note),
+            )
+            .await;
+        let reply = match opened {
+            Ok(Some(id)) => {
+                tracing::info!(target: "telegram", id, user_id, "appeal opened");
+                let admin_locale = state.config.language;
+                let text: String = entry.text.chars().take(200).collect();
+                notify_admin_group(
+                    bot,
+                    state.config.as_ref(),
+                    &tf(
+                        admin_locale,
+                        Msg::AppealNotice,
+                        &[
+                            ("id", &id),
+                            (
+                                "chat",
+                                &escape_html(entry.chat_title.as_deref().unwrap_or("Unknown")),
+                            ),
+                            ("user", &escape_html(&entry.user_display)),
+                            ("text", &escape_html(&text)),
+                            (
+                                "note",
+                                &escape_html(if note.is_empty() {
+                                    t(admin_locale, Msg::NoneLabel)
+                                } else {
+                                    note
+                                }),
+                            ),
+                        ],
+                    ),
+                )
+                .await;
+                Msg::AppealReceived
+            }
+            Ok(None) => Msg::AppealDuplicate,
+            Err(err) => {
+                tracing::error!(target: "db", error = %err, user_id, "failed to open appeal");
+                Msg::AppealFailed
+            }
+        };
+        bot.send_message(msg.chat.id, t(locale, reply)).await?;
+        Ok(())
+    }
+
+    /// Handles the buttons on an admin spam log (`spamlog:<restore|ban|ignore>:<log id>`):
+    /// restoring re-posts the text and queues an appeal for `/review`, whose decision
+    /// overwrites the not-spam feedback stored now, while ignoring records the false
+    /// positive right away. Only the handled entry's buttons are removed, so a
+    /// combined log keeps the others.
+    async fn resolve_spam_log(
+        bot: &Bot,
+        query_id: CallbackQueryId,
+        log: &MaybeInaccessibleMessage,
+        data: &str,
+        admin_id: i64,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let parsed = data
+            .split_once(':')
+            .and_then(|(action, id)| id.parse::<i64>().ok().map(|id| (action, id)));
+        let entry = match parsed {
+            Some((_, id)) => state.db.spam_log.get(id).await.unwrap_or_else(|err| {
+                tracing::error!(target: "db", error = %err, id, "failed to load spam log entry");
+                None
+            }),
+            None => None,
+        };
+        let (Some((action, _)), Some(entry)) = (parsed, entry) else {
+            bot.answer_callback_query(query_id)
+                .text(t(locale, Msg::SpamLogNotFound))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        };
+
+        let answer = match action {
+            "restore" => {
+                let text = tf(
+                    state.locale(entry.chat_id).await,
+                    Msg::RestoredMessage,
+                    &[("sender", &entry.user_display), ("text", &entry.text)],
+                );
+                if let Err(err) = state
+                    .processor
+                    .bot_for(ChatId(entry.chat_id))
+                    .send_message(ChatId(entry.chat_id), text)
+                    .await
+                {
+                    tracing::error!(
+                        target: "telegram",
+                        error = %err,
+                        chat_id = entry.chat_id,
+                        "failed to re-post restored message"
+                    );
+                    bot.answer_callback_query(query_id)
+                        .text(t(locale, Msg::RestoreFailed))
+                        .show_alert(true)
+                        .await?;
+                    return Ok(());
+                }
+                match state
+                    .db
+                    .appeals
+                    .open(entry.id, AppealSource::Restore, None)
+                    .await
+                {
+                    Ok(appeal_id) => {
+                        if let Some(appeal_id) = appeal_id {
+                            Self::record_feedback(
+                                &state,
+                                NewFeedback {
+                                    chat_id: entry.chat_id,
+                                    user_id: entry.user_id,
+                                    text: &entry.text,
+                                    spam: false,
+                                    model_reason: Some(&entry.reason),
+                                    confidence: entry.confidence,
+                                    source: AppealSource::Restore.as_str(),
+                                    decided_by: Some(admin_id),
+                                    appeal_id: Some(appeal_id),
+                                },
+                            )
+                            .await;
+                        }
+                        Msg::Restored
+                    }
+                    Err(err) => {
+                        tracing::error!(target: "db", error = %err, "failed to open appeal");
+                        Msg::RestoredNotQueued
+                    }
+                }
+            }
+            "ban" => {
+                let Some(user_id) = entry.user_id.filter(|id| *id >= 0) else {
+                    bot.answer_callback_query(query_id)
+                        .text(t(locale, Msg::SenderUnknown))
+                        .show_alert(true)
+                        .await?;
+                    return Ok(());
+                };
+                if let Err(err) = state
+                    .processor
+                    .bot_for(ChatId(entry.chat_id))
+                    .ban_chat_member(ChatId(entry.chat_id), UserId(user_id as u64))
+                    .await
+                {
+                    tracing::error!(
+                        target: "telegram",
+                        error = %err,
+                        chat_id = entry.chat_id,
+                        user_id,
+                        "failed to ban user via spam log"
+                    );
+                    bot.answer_callback_query(query_id)
+                        .text(t(locale, Msg::BanFailed))
+                        .show_alert(true)
+                        .await?;
+                    return Ok(());
+                }
+                state
+                    .processor
+                    .record_ban(entry.chat_id, user_id, "admin", false)
+                    .await;
+                if state.config.blacklist.auto_add {
+                    state
+                        .processor
+                        .blacklist_user(user_id, &entry.reason, &format!("admin:{admin_id}"))
+                        .await;
+                }
+                Msg::BanDone
+            }
+            "ignore" => {
+                Self::record_false_positive(&state, entry.chat_id, entry.user_id, &entry.text)
+                    .await;
+                Self::record_feedback(
+                    &state,
+                    NewFeedback {
+                        chat_id: entry.chat_id,
+                        user_id: entry.user_id,
+                        text: &entry.text,
+                        spam: false,
+                        model_reason: Some(&entry.reason),
+                        confidence: entry.confidence,
+                        source: "ignore",
+                        decided_by: Some(admin_id),
+                        appeal_id: None,
+                    },
+                )
+                .await;
+                Msg::RecordedFalsePositive
+            }
+            _ => {
+                bot.answer_callback_query(query_id)
+                    .text(t(locale, Msg::InvalidRequest))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        tracing::info!(
+            target: "telegram",
+            chat_id = entry.chat_id,
+            message_id = entry.message_id,
+            action,
+            "spam log action handled"
+        );
+        bot.answer_callback_query(query_id)
+            .text(t(locale, answer))
+            .await?;
+        let suffix = format!(":{}", entry.id);
+        let remaining: Vec<Vec<InlineKeyboardButton>> = log
+            .regular_message()
+            .and_then(|message| message.reply_markup())
+            .map(|markup| {
+                markup
+                    .inline_keyboard
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .filter(|button| match &button.kind {
+                                InlineKeyboardButtonKind::CallbackData(data) => {
+                                    !(data.starts_with("spamlog:") && data.ends_with(&suffix))
+                                }
+                                _ => true,
+                            })
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|row| !row.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut edit = bot.edit_message_reply_markup(log.chat().id, log.id());
+        if !remaining.is_empty() {
+            edit = edit.reply_markup(InlineKeyboardMarkup::new(remaining));
+        }
+        if let Err(err) = edit.await {
+            tracing::debug!(target: "telegram", error = %err, "failed to clear spam log buttons");
+        }
+        Ok(())
+    }
+
+    /// Handles the buttons on a whitelist request (`wlreq:<approve|reject>:<chat_id>`)
+    /// and tells the requesting group the outcome.
+    async fn resolve_whitelist_request(
+        bot: &Bot,
+        query_id: CallbackQueryId,
+        request_chat: ChatId,
+        request_message: MessageId,
+        data: &str,
+        admin_id: i64,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let admin_locale = state.config.language;
+        let Some((action, chat_id)) = data
+            .split_once(':')
+            .and_then(|(action, id)| id.parse::<i64>().ok().map(|id| (action, id)))
+        else {
+            bot.answer_callback_query(query_id)
+                .text(t(admin_locale, Msg::InvalidRequest))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        };
+
+        let (answer, notice) = match action {
+            "approve" => {
+                let chat_info = match bot.get_chat(ChatId(chat_id)).await {
+                    Ok(chat) => chat,
+                    Err(_) => {
+                        bot.answer_callback_query(query_id)
+                            .text(t(admin_locale, Msg::RequestGroupNotFound))
+                            .show_alert(true)
+                            .await?;
+                        return Ok(());
+                    }
+                };
+                let entry = WhitelistEntry {
+                    chat_id,
+                    chat_title: chat_info.title().map(|t| t.to_string()),
+                    chat_type: Some(format!("{:?}", chat_info.kind)),
+                    added_by: Some(admin_id),
+                    expires_at: None,
+                };
+                match state.db.whitelist.add_or_replace(entry).await {
+                    Ok(true) => (Msg::RequestApproved, Some(Msg::WhitelistApproved)),
+                    Ok(false) => (Msg::WhitelistExists, None),
+                    Err(err) => {
+                        tracing::error!(target: "admin", error = %err, "failed to add whitelist");
+                        bot.answer_callback_query(query_id)
+                            .text(t(admin_locale, Msg::WhitelistAddFailed))
+                            .show_alert(true)
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            "reject" => (Msg::RequestRejected, Some(Msg::WhitelistRejected)),
+            _ => {
+                bot.answer_callback_query(query_id)
+                    .text(t(admin_locale, Msg::InvalidRequest))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        if action == "reject" {
+            state.whitelist_requests.reject(chat_id);
+        } else {
+            state.whitelist_requests.resolve(chat_id);
+        }
+        tracing::info!(
+            target: "admin",
+            chat_id,
+            action,
+            admin_id = admin_id,
+            "whitelist request handled"
+        );
+        bot.answer_callback_query(query_id)
+            .text(t(admin_locale, answer))
+            .await?;
+        if let Err(err) = bot
+            .edit_message_reply_markup(request_chat, request_message)
+            .await
+        {
+            tracing::debug!(target: "telegram", error = %err, "failed to clear whitelist request buttons");
+        }
+        if let Some(notice) = notice {
+            let locale = state.locale(chat_id).await;
+            if let Err(err) = bot.send_message(ChatId(chat_id), t(locale, notice)).await {
+                tracing::warn!(target: "telegram", error = %err, chat_id, "failed to post whitelist decision");
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores a wrongly deleted message as a not-spam example and takes back the
+    /// sender's strike and reputation penalty.
+    async fn record_false_positive(
+        state: &AppState,
+        chat_id: i64,
+        user_id: Option<i64>,
+        text: &str,
+    ) {
+        Self::record_example(state, chat_id, text, false, None).await;
+        if let Some(user_id) = user_id {
+            match state.db.user_blacklist.remove(user_id).await {
+                Ok(true) => {
+                    tracing::info!(target: "admin", user_id, "false positive sender unblacklisted")
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::warn!(target: "db", error = %err, user_id, "failed to unblacklist user");
+                }
+            }
+            if let Err(err) = state.db.strikes.forgive(chat_id, user_id).await {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to forgive strike");
+            }
+            if let Err(err) = state
+                .db
+                .reputation
+                .forgive_offense(user_id, state.config.reputation.offense_penalty)
+                .await
+            {
+                tracing::warn!(target: "db", error = %err, user_id, "failed to restore reputation");
+            }
+        }
+    }
+
+    async fn record_feedback(state: &AppState, feedback: NewFeedback<'_>) {
+        if let Err(err) = state.db.feedback.record(&feedback).await {
+            tracing::warn!(target: "db", error = %err, "failed to record feedback");
+        }
+    }
+
+    /// Applies an admin's decision on a low-confidence spam verdict
+    /// (`review:<delete|keep>:<chat>:<message>`).
+    async fn resolve_review(
+        bot: &Bot,
+        query_id: CallbackQueryId,
+        review_chat: ChatId,
+        review_message: MessageId,
+        data: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let parts: Vec<&str> = data.split(':').collect();
+        let parsed = match parts.as_slice() {
+            [action, chat_id, message_id] => chat_id
+                .parse::<i64>()
+                .ok()
+                .zip(message_id.parse::<i32>().ok())
+                .map(|ids| (*action, ids)),
+            _ => None,
+        };
+        let Some((action, (chat_id, message_id))) = parsed else {
+            bot.answer_callback_query(query_id)
+                .text(t(locale, Msg::InvalidRequest))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        };
+
+        let pending = state.processor.take_review(chat_id, message_id);
+        if let (Some(review), "delete" | "keep") = (&pending, action) {
+            let spam = action == "delete";
+            Self::record_example(
+                &state,
+                chat_id,
+                &review.job.text,
+                spam,
+                spam.then_some(review.reason.as_str()),
+            )
+            .await;
+        }
+        let answer = match action {
+            "delete" => {
+                let result = match &pending {
+                    Some(review) => state
+                        .processor
+                        .remove_confirmed(&review.job, &review.reason)
+                        .await
+                        .map(|_| ()),
+                    None => state
+                        .processor
+                        .bot_for(ChatId(chat_id))
+                        .delete_message(ChatId(chat_id), MessageId(message_id))
+                        .await
+                        .map(|_| ())
+                        .map_err(Into::into),
+                };
+                match result {
+                    Ok(()) => Msg::ReviewDeleted,
+                    Err(err) => {
+                        tracing::error!(
+                            target: "telegram",
+                            error = %err,
+                            chat_id,
+                            message_id,
+                            "failed to delete reviewed message"
+                        );
+                        bot.answer_callback_query(query_id)
+                            .text(t(locale, Msg::ReviewDeleteFailed))
+                            .show_alert(true)
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+            "keep" => Msg::ReviewKept,
+            _ => {
+                bot.answer_callback_query(query_id)
+                    .text(t(locale, Msg::InvalidRequest))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        tracing::info!(
+            target: "telegram",
+            chat_id,
+            message_id,
+            action,
+            "spam review resolved"
+        );
+        bot.answer_callback_query(query_id)
+            .text(t(locale, answer))
+            .await?;
+        if let Err(err) = bot
+            .edit_message_reply_markup(review_chat, review_message)
+            .await
+        {
+            tracing::debug!(target: "telegram", error = %err, "failed to clear review buttons");
+        }
+        Ok(())
+    }
+
+    async fn whitelist_remove(
+        bot: &Bot,
+        msg: &Message,
+        target_chat_id: i64,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.whitelist.remove(target_chat_id).await {
+            Ok(true) => {
+                tracing::info!(
+                    target: "admin",
+                    chat_id = target_chat_id,
+                    removed_by = msg.from.as_ref().map(user_to_i64),
+                    "whitelist entry removed"
+                );
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::WhitelistEntryRemoved,
+                        &[("chat_id", &target_chat_id)],
+                    ),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::WhitelistMissing))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to remove whitelist");
+                bot.send_message(msg.chat.id, t(locale, Msg::WhitelistRemoveFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn whitelist_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.whitelist.list().await {
+            Ok(rows) => {
+                if rows.is_empty() {
+                    bot.send_message(msg.chat.id, t(locale, Msg::WhitelistEmpty))
+                        .await?;
+                    return Ok(());
+                }
+                let mut message = t(locale, Msg::WhitelistListHeader).to_string();
+                for (idx, row) in rows.iter().enumerate() {
+                    message.push_str(&tf(
+                        locale,
+                        Msg::WhitelistListEntry,
+                        &[
+                            ("index", &(idx + 1)),
+                            ("chat_id", &row.chat_id),
+                            (
+                                "title",
+                                &row.chat_title
+                                    .as_deref()
+                                    .unwrap_or(t(locale, Msg::UntitledChat)),
+                            ),
+                            ("added", &row.added_at.format("%Y-%m-%d")),
+                        ],
+                    ));
+                    if row.status != WhitelistStatus::Active {
+                        message.push_str(&tf(
+                            locale,
+                            Msg::WhitelistListStatus,
+                            &[("status", &row.status.label(locale))],
+                        ));
+                    }
+                    if row.strict_mode {
+                        message.push_str(t(locale, Msg::WhitelistListStrict));
+                    }
+                    if let Some(note) = &row.note {
+                        message.push_str(&tf(locale, Msg::WhitelistListNote, &[("note", note)]));
+                    }
+                    if let Some(expires_at) = row.expires_at {
+                        message.push_str(&tf(
+                            locale,
+                            Msg::WhitelistListExpires,
+                            &[("at", &expires_at.format("%Y-%m-%d %H:%M UTC"))],
+                        ));
+                    }
+                }
+                bot.send_message(msg.chat.id, message).await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to list whitelist");
+                bot.send_message(msg.chat.id, t(locale, Msg::WhitelistListFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn whitelist_export(
+        bot: &Bot,
+        msg: &Message,
+        format: FileFormat,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let exported = state
+            .db
+            .whitelist
+            .list()
+            .await
+            .and_then(|rows| Ok((rows.len(), whitelist_file::export(&rows, format)?)));
+        match exported {
+            Ok((count, text)) => {
+                let file_name = format!(
+                    "whitelist-{}.{}",
+                    Utc::now().format("%Y%m%d"),
+                    format.extension()
+                );
+                bot.send_document(msg.chat.id, InputFile::memory(text).file_name(file_name))
+                    .caption(tf(locale, Msg::WhitelistExported, &[("count", &count)]))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to export whitelist");
+                bot.send_message(msg.chat.id, t(locale, Msg::WhitelistExportFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the recorded false-positive verdicts as JSON Lines, one message with its
+    /// correct label and the classifier's reason per line, for prompt tuning.
+    async fn feedback_export(
+        bot: &Bot,
+        msg: &Message,
+        days: Option<u32>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let since = days.map(|days| Utc::now() - chrono::Duration::days(i64::from(days)));
+        let exported = state
+            .db
+            .feedback
+            .recent(since, FEEDBACK_EXPORT_LIMIT)
+            .await
+            .and_then(|rows| {
+                let mut text = String::new();
+                for row in &rows {
+                    text.push_str(&serde_json::to_string(row)?);
+                    text.push('\n');
+                }
+                Ok((rows.len(), text))
+            });
+        match exported {
+            Ok((0, _)) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::FeedbackEmpty))
+                    .await?;
+            }
+            Ok((count, text)) => {
+                let file_name = format!("feedback-{}.jsonl", Utc::now().format("%Y%m%d"));
+                bot.send_document(msg.chat.id, InputFile::memory(text).file_name(file_name))
+                    .caption(tf(locale, Msg::FeedbackExported, &[("count", &count)]))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to export feedback");
+                bot.send_message(msg.chat.id, t(locale, Msg::FeedbackExportFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores entries from a JSON or CSV file attached to the command or to the
+    /// message it replies to. Entries that have already expired are skipped.
+    async fn whitelist_import(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let document = msg
+            .document()
+            .or_else(|| msg.reply_to_message().and_then(|reply| reply.document()));
+        let Some(document) = document else {
+            bot.send_message(msg.chat.id, t(locale, Msg::WhitelistImportUsage))
+                .await?;
+            return Ok(());
+        };
+        let entries = match media::download(bot, &document.file.id).await {
+            Ok(bytes) => whitelist_file::import(&bytes),
+            Err(err) => Err(err),
+        };
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::warn!(target: "admin", error = %err, "failed to read whitelist import");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::FileReadFailed,
+                        &[("error", &format!("{err:#}"))],
+                    ),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        let now = Utc::now();
+        let (mut restored, mut expired, mut failed) = (0, 0, 0);
+        for entry in &entries {
+            if entry.expires_at.is_some_and(|at| at <= now) {
+                expired += 1;
+                continue;
+            }
+            match state.db.whitelist.restore(entry).await {
+                Ok(()) => restored += 1,
+                Err(err) => {
+                    tracing::error!(
+                        target: "admin",
+                        error = %err,
+                        chat_id = entry.chat_id,
+                        "failed to restore whitelist entry"
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        tracing::info!(target: "admin", restored, expired, failed, "whitelist imported");
+        let mut reply = tf(locale, Msg::WhitelistImported, &[("count", &restored)]);
+        if expired > 0 {
+            reply.push_str(&tf(
+                locale,
+                Msg::WhitelistImportExpired,
+                &[("count", &expired)],
+            ));
+        }
+        if failed > 0 {
+            reply.push_str(&tf(
+                locale,
+                Msg::WhitelistImportFailed,
+                &[("count", &failed)],
+            ));
+        }
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Sets the admin note on a whitelist entry (`clear` removes it).
+    async fn whitelist_note(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        note: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let cleared = note.eq_ignore_ascii_case("clear");
+        let reply = match state
+            .db
+            .whitelist
+            .set_note(chat_id, (!cleared).then_some(note))
+            .await
+        {
+            Ok(true) => {
+                tracing::info!(target: "admin", chat_id, cleared, "whitelist note updated");
+                if cleared {
+                    tf(locale, Msg::NoteCleared, &[("chat_id", &chat_id)])
+                } else {
+                    tf(locale, Msg::NoteSaved, &[("chat_id", &chat_id)])
+                }
+            }
+            Ok(false) => t(locale, Msg::ChatNotWhitelisted).to_string(),
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to update whitelist note");
+                t(locale, Msg::NoteFailed).to_string()
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Shows the chat's extra spam criteria, or replaces them with `criteria` (`clear`
+    /// removes them).
+    async fn chat_criteria(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        criteria: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let settings = &state.db.chat_settings;
+        let reply = if criteria.is_empty() {
+            match settings.criteria(chat_id).await {
+                Ok(Some(criteria)) => tf(
+                    locale,
+                    Msg::CriteriaShow,
+                    &[("chat_id", &chat_id), ("criteria", &criteria)],
+                ),
+                Ok(None) => tf(locale, Msg::CriteriaNone, &[("chat_id", &chat_id)]),
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load chat criteria");
+                    t(locale, Msg::CriteriaLoadFailed).to_string()
+                }
+            }
+        } else {
+            let cleared = criteria.eq_ignore_ascii_case("clear");
+            match settings
+                .set_criteria(chat_id, (!cleared).then_some(criteria))
+                .await
+            {
+                Ok(()) => {
+                    tracing::info!(target: "admin", chat_id, cleared, "chat criteria updated");
+                    if cleared {
+                        tf(locale, Msg::CriteriaCleared, &[("chat_id", &chat_id)])
+                    } else {
+                        tf(locale, Msg::CriteriaSaved, &[("chat_id", &chat_id)])
+                    }
+                }
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to update chat criteria");
+                    t(locale, Msg::CriteriaFailed).to_string()
+                }
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Shows the chat's enforcement policy, or replaces it with `action` (`clear` falls
+    /// back to `ENFORCEMENT_ACTION`).
+    async fn chat_action(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        action: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let settings = &state.db.chat_settings;
+        let default_action = state.config.enforcement.default_action;
+        let reply = if action.is_empty() {
+            match settings.action(chat_id).await {
+                Ok(stored) => match stored.as_deref().and_then(EnforcementAction::parse) {
+                    Some(action) => tf(
+                        locale,
+                        Msg::ChatActionShow,
+                        &[("chat_id", &chat_id), ("action", &action.describe(locale))],
+                    ),
+                    None => tf(
+                        locale,
+                        Msg::ChatActionDefault,
+                        &[
+                            ("chat_id", &chat_id),
+                            ("action", &default_action.describe(locale)),
+                        ],
+                    ),
+                },
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load chat action");
+                    t(locale, Msg::ChatActionLoadFailed).to_string()
+                }
+            }
+        } else if action.eq_ignore_ascii_case("clear") {
+            match settings.set_action(chat_id, None).await {
+                Ok(()) => {
+                    tracing::info!(target: "admin", chat_id, "chat action cleared");
+                    tf(
+                        locale,
+                        Msg::ChatActionCleared,
+                        &[
+                            ("chat_id", &chat_id),
+                            ("action", &default_action.describe(locale)),
+                        ],
+                    )
+                }
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to clear chat action");
+                    t(locale, Msg::ChatActionFailed).to_string()
+                }
+            }
+        } else {
+            match EnforcementAction::parse(action) {
+                Some(parsed) => match settings.set_action(chat_id, Some(&parsed.name())).await {
+                    Ok(()) => {
+                        tracing::info!(
+                            target: "admin",
+                            chat_id,
+                            action = %parsed.name(),
+                            "chat action updated"
+                        );
+                        tf(
+                            locale,
+                            Msg::ChatActionSet,
+                            &[("chat_id", &chat_id), ("action", &parsed.describe(locale))],
+                        )
+                    }
+                    Err(err) => {
+                        tracing::error!(target: "admin", error = %err, "failed to update chat action");
+                        t(locale, Msg::ChatActionFailed).to_string()
+                    }
+                },
+                None => t(locale, Msg::ChatActionInvalid).to_string(),
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Shows the chat's probation window, or replaces it (`Some(None)` falls back to the
+    /// `PROBATION_*` defaults).
+    async fn chat_probation(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        update: Option<Option<ProbationWindow>>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let Some(config) = &state.config.probation else {
+            bot.send_message(msg.chat.id, t(locale, Msg::ProbationDisabled))
+                .await?;
+            return Ok(());
+        };
+        let describe = |window: ProbationWindow| {
+            if window.messages == 0 && window.hours == 0 {
+                t(locale, Msg::Disabled).to_string()
+            } else {
+                tf(
+                    locale,
+                    Msg::ProbationWindowDesc,
+                    &[("messages", &window.messages), ("hours", &window.hours)],
+                )
+            }
+        };
+        let default_window = ProbationWindow {
+            messages: config.messages,
+            hours: (config.window.as_secs() / 3600) as i64,
+        };
+        let settings = &state.db.chat_settings;
+        let reply = match update {
+            None => match settings.probation(chat_id).await {
+                Ok(Some(window)) => tf(
+                    locale,
+                    Msg::ProbationShow,
+                    &[("chat_id", &chat_id), ("window", &describe(window))],
+                ),
+                Ok(None) => tf(
+                    locale,
+                    Msg::ProbationDefault,
+                    &[("chat_id", &chat_id), ("window", &describe(default_window))],
+                ),
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load probation window");
+                    t(locale, Msg::ProbationLoadFailed).to_string()
+                }
+            },
+            Some(window) => match settings.set_probation(chat_id, window).await {
+                Ok(()) => {
+                    tracing::info!(target: "admin", chat_id, ?window, "probation window updated");
+                    tf(
+                        locale,
+                        Msg::ProbationSet,
+                        &[
+                            ("chat_id", &chat_id),
+                            ("window", &describe(window.unwrap_or(default_window))),
+                        ],
+                    )
+                }
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to update probation window");
+                    t(locale, Msg::ProbationFailed).to_string()
+                }
+            },
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// `/language`: shows the chat's reply language, sets it, or with `clear` falls
+    /// back to `BOT_LANGUAGE`.
+    async fn chat_language(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        update: Option<Option<Locale>>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let admin_locale = state.config.language;
+        let settings = &state.db.chat_settings;
+        let default_code = state.config.language.code();
+        let reply = match update {
+            None => match settings.language(chat_id).await {
+                Ok(Some(code)) => tf(
+                    admin_locale,
+                    Msg::LanguageShow,
+                    &[("chat_id", &chat_id), ("code", &code)],
+                ),
+                Ok(None) => tf(
+                    admin_locale,
+                    Msg::LanguageDefault,
+                    &[("chat_id", &chat_id), ("code", &default_code)],
+                ),
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load chat language");
+                    t(admin_locale, Msg::LanguageLoadFailed).to_string()
+                }
+            },
+            Some(locale) => {
+                match settings
+                    .set_language(chat_id, locale.map(Locale::code))
+                    .await
+                {
+                    Ok(()) => {
+                        tracing::info!(target: "admin", chat_id, ?locale, "chat language updated");
+                        match locale {
+                            Some(locale) => tf(
+                                admin_locale,
+                                Msg::LanguageSet,
+                                &[("chat_id", &chat_id), ("code", &locale.code())],
+                            ),
+                            None => tf(
+                                admin_locale,
+                                Msg::LanguageCleared,
+                                &[("chat_id", &chat_id), ("code", &default_code)],
+                            ),
+                        }
+                    }
+                    Err(err) => {
+                        tracing::error!(target: "admin", error = %err, "failed to update chat language");
+                        t(admin_locale, Msg::LanguageFailed).to_string()
+                    }
+                }
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// `/notice`: shows how long the chat's deletion notice stays up, sets it, or with
+    /// `clear` falls back to `DELETION_NOTICE_SECS`.
+    async fn chat_notice(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        update: Option<Option<i64>>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let describe = |secs: Option<i64>| match secs {
+            Some(secs) if secs > 0 => tf(locale, Msg::NoticeAfter, &[("secs", &secs)]),
+            _ => t(locale, Msg::Disabled).to_string(),
+        };
+        let default_secs = state
+            .config
+            .enforcement
+            .notice_ttl
+            .map(|ttl| ttl.as_secs() as i64);
+        let settings = &state.db.chat_settings;
+        let reply = match update {
+            None => match settings.notice_secs(chat_id).await {
+                Ok(Some(secs)) => tf(
+                    locale,
+                    Msg::NoticeShow,
+                    &[("chat_id", &chat_id), ("notice", &describe(Some(secs)))],
+                ),
+                Ok(None) => tf(
+                    locale,
+                    Msg::NoticeDefault,
+                    &[("chat_id", &chat_id), ("notice", &describe(default_secs))],
+                ),
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to load notice setting");
+                    t(locale, Msg::NoticeLoadFailed).to_string()
+                }
+            },
+            Some(secs) => match settings.set_notice_secs(chat_id, secs).await {
+                Ok(()) => {
+                    tracing::info!(target: "admin", chat_id, ?secs, "deletion notice updated");
+                    tf(
+                        locale,
+                        Msg::NoticeSet,
+                        &[
+                            ("chat_id", &chat_id),
+                            ("notice", &describe(secs.or(default_secs))),
+                        ],
+                    )
+                }
+                Err(err) => {
+                    tracing::error!(target: "admin", error = %err, "failed to update notice setting");
+                    t(locale, Msg::NoticeFailed).to_string()
+                }
+            },
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// `/settings`: the chat's spam action, dry-run, strict mode and reply language, with
+    /// buttons that change them.
+    async fn chat_settings(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        match Self::settings_panel(chat_id, &state).await {
+            Ok((text, keyboard)) => {
+                bot.send_message(msg.chat.id, text)
+                    .reply_markup(keyboard)
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load chat settings");
+                bot.send_message(
+                    msg.chat.id,
+                    t(state.config.language, Msg::SettingsLoadFailed),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn settings_panel(
+        chat_id: i64,
+        state: &AppState,
+    ) -> Result<(String, InlineKeyboardMarkup)> {
+        let settings = &state.db.chat_settings;
+        let action = settings
+            .action(chat_id)
+            .await?
+            .as_deref()
+            .and_then(EnforcementAction::parse);
+        let dry_run = settings.dry_run(chat_id).await?;
+        let language = settings.language(chat_id).await?;
+        let whitelisted = state.db.whitelist.is_allowed(chat_id).await?;
+        let strict = state.db.whitelist.is_strict(chat_id).await?;
+
+        let locale = state.config.language;
+        let on_off = |enabled: bool| {
+            t(
+                locale,
+                if enabled {
+                    Msg::SettingOn
+                } else {
+                    Msg::SettingOff
+                },
+            )
+        };
+        let default_value =
+            |value: &str| tf(locale, Msg::SettingDefaultValue, &[("value", &value)]);
+        let action_label = match action {
+            Some(action) => action.describe(locale),
+            None => default_value(&state.config.enforcement.default_action.describe(locale)),
+        };
+        let language_label = match language {
+            Some(code) => code,
+            None => default_value(state.config.language.code()),
+        };
+        let strict_label = if whitelisted {
+            on_off(strict)
+        } else {
+            t(locale, Msg::SettingNotWhitelisted)
+        };
+        let text = tf(
+            locale,
+            Msg::SettingsPanel,
+            &[
+                ("chat_id", &chat_id),
+                ("action", &action_label),
+                ("dry_run", &on_off(dry_run)),
+                ("strict", &strict_label),
+                ("language", &language_label),
+            ],
+        );
+
+        let button = |label: &str, key: &str, value: &str| {
+            InlineKeyboardButton::callback(label, format!("settings:{chat_id}:{key}:{value}"))
+        };
+        let toggle = |enabled: bool| if enabled { "off" } else { "on" };
+        let mut rows = vec![
+            vec![
+                button(t(locale, Msg::SettingActionDelete), "action", "delete"),
+                button(t(locale, Msg::SettingActionRestrict), "action", "restrict"),
+                button(t(locale, Msg::SettingActionBan), "action", "ban"),
+                button(t(locale, Msg::SettingDefault), "action", "clear"),
+            ],
+            vec![button(
+                t(
+                    locale,
+                    if dry_run {
+                        Msg::SettingDryRunOff
+                    } else {
+                        Msg::SettingDryRunOn
+                    },
+                ),
+                "dry_run",
+                toggle(dry_run),
+            )],
+        ];
+        if whitelisted {
+            rows.push(vec![button(
+                t(
+                    locale,
+                    if strict {
+                        Msg::SettingStrictOff
+                    } else {
+                        Msg::SettingStrictOn
+                    },
+                ),
+                "strict",
+                toggle(strict),
+            )]);
+        }
+        let mut languages: Vec<InlineKeyboardButton> = Locale::ALL
+            .iter()
+            .map(|locale| button(locale.code(), "language", locale.code()))
+            .collect();
+        languages.push(button(t(locale, Msg::SettingDefault), "language", "clear"));
+        rows.push(languages);
+        Ok((text, InlineKeyboardMarkup::new(rows)))
+    }
+
+    /// Handles a `/settings` button (`settings:<chat_id>:<setting>:<value>`) and redraws
+    /// the panel.
+    async fn update_setting(
+        bot: &Bot,
+        query_id: CallbackQueryId,
+        panel_chat: ChatId,
+        panel_message: MessageId,
+        data: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let mut fields = data.splitn(3, ':');
+        let chat_id = fields.next().and_then(|v| v.parse::<i64>().ok());
+        let (Some(chat_id), Some(key), Some(value)) = (chat_id, fields.next(), fields.next())
+        else {
+            bot.answer_callback_query(query_id)
+                .text(t(state.config.language, Msg::SettingInvalid))
+                .show_alert(true)
+                .await?;
+            return Ok(());
+        };
+
+        let settings = &state.db.chat_settings;
+        let result = match (key, value) {
+            ("action", "clear") => settings.set_action(chat_id, None).await.map(|()| true),
+            ("action", value) => match EnforcementAction::parse(value) {
+                Some(action) => settings
+                    .set_action(chat_id, Some(&action.name()))
+                    .await
+                    .map(|()| true),
+                None => Ok(false),
+            },
+            ("dry_run", "on" | "off") => settings
+                .set_dry_run(chat_id, value == "on")
+                .await
+                .map(|()| true),
+            ("strict", "on" | "off") => state.db.whitelist.set_strict(chat_id, value == "on").await,
+            ("language", "clear") => settings.set_language(chat_id, None).await.map(|()| true),
+            ("language", value) => match Locale::parse(value) {
+                Some(locale) => settings
+                    .set_language(chat_id, Some(locale.code()))
+                    .await
+                    .map(|()| true),
+                None => Ok(false),
+            },
+            _ => Ok(false),
+        };
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                bot.answer_callback_query(query_id)
+                    .text(t(state.config.language, Msg::SettingInvalid))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, chat_id, key, "failed to update chat setting");
+                bot.answer_callback_query(query_id)
+                    .text(t(state.config.language, Msg::SettingFailed))
+                    .show_alert(true)
+                    .await?;
+                return Ok(());
+            }
+        }
+
+        tracing::info!(target: "admin", chat_id, key, value, "chat setting updated");
+        bot.answer_callback_query(query_id)
+            .text(t(state.config.language, Msg::SettingSaved))
+            .await?;
+        match Self::settings_panel(chat_id, &state).await {
+            Ok((text, keyboard)) => {
+                if let Err(err) = bot
+                    .edit_message_text(panel_chat, panel_message, text)
+                    .reply_markup(keyboard)
+                    .await
+                {
+                    tracing::debug!(target: "telegram", error = %err, "failed to redraw settings panel");
+                }
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load chat settings");
+            }
+        }
+        Ok(())
+    }
+
+    async fn strict_mode_set(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        enabled: bool,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.whitelist.set_strict(chat_id, enabled).await {
+            Ok(true) => {
+                tracing::info!(target: "admin", chat_id, enabled, "strict mode updated");
+                let reply = if enabled {
+                    Msg::StrictModeOn
+                } else {
+                    Msg::StrictModeOff
+                };
+                bot.send_message(msg.chat.id, tf(locale, reply, &[("chat_id", &chat_id)]))
+                    .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::ChatNotWhitelisted))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to update strict mode");
+                bot.send_message(msg.chat.id, t(locale, Msg::StrictModeFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn invite_allow(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        target: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let added_by = msg.from.as_ref().map(user_to_i64);
+        match state
+            .db
+            .invite_allowlist
+            .add(chat_id, target, added_by)
+            .await
+        {
+            Ok(true) => {
+                tracing::info!(target: "admin", chat_id, invite = target, "invite target allowlisted");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::InviteAllowed,
+                        &[("chat_id", &chat_id), ("target", &target)],
+                    ),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteAlreadyAllowed))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to allowlist invite target");
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteAllowFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn invite_allow_remove(
+        bot: &Bot,
+        msg: &Message,
+        chat_id: i64,
+        target: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.invite_allowlist.remove(chat_id, target).await {
+            Ok(true) => {
+                tracing::info!(target: "admin", chat_id, invite = target, "invite target removed from allowlist");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::InviteDisallowed,
+                        &[("chat_id", &chat_id), ("target", &target)],
+                    ),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteNotAllowed))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to remove invite allowlist entry");
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteDisallowFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn invite_allow_list(
+        bot: &Bot,
+        msg: &Message,
+        chat_filter: Option<i64>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.invite_allowlist.list(chat_filter).await {
+            Ok(rows) if rows.is_empty() => {
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteListEmpty))
+                    .await?;
+            }
+            Ok(rows) => {
+                let mut message = t(locale, Msg::InviteListHeader).to_string();
+                for (chat_id, target) in rows {
+                    message.push_str(&format!("\n- {chat_id}: {target}"));
+                }
+                bot.send_message(msg.chat.id, message).await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to list invite allowlist");
+                bot.send_message(msg.chat.id, t(locale, Msg::InviteListFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Blocks the domain and its subdomains: messages linking them are deleted without
+    /// an AI call. The domain leaves the allowlist.
+    async fn domain_block(
+        bot: &Bot,
+        msg: &Message,
+        domain: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let result = match state.db.allowed_domains.remove(domain).await {
+            Ok(_) => state.db.blocked_domains.add(domain, ADMIN_SOURCE).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(true) => {
+                tracing::info!(target: "admin", domain, "domain blocked");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::DomainBlocked, &[("domain", &domain)]),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::DomainAlreadyBlocked))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to block domain");
+                bot.send_message(msg.chat.id, t(locale, Msg::DomainBlockFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Trusts the domain and its subdomains: their links are never fetched. The domain
+    /// leaves the blocklist.
+    async fn domain_allow(
+        bot: &Bot,
+        msg: &Message,
+        domain: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let added_by = msg.from.as_ref().map(user_to_i64);
+        let result = match state.db.blocked_domains.remove(domain).await {
+            Ok(_) => state.db.allowed_domains.add(domain, added_by).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(true) => {
+                tracing::info!(target: "admin", domain, "domain allowlisted");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::DomainAllowed, &[("domain", &domain)]),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::DomainAlreadyAllowed))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to allowlist domain");
+                bot.send_message(msg.chat.id, t(locale, Msg::DomainAllowFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn domain_remove(
+        bot: &Bot,
+        msg: &Message,
+        domain: &str,
+        blocked: bool,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let (result, removed, missing) = if blocked {
+            (
+                state.db.blocked_domains.remove(domain).await,
+                Msg::DomainUnblocked,
+                Msg::DomainNotBlocked,
+            )
+        } else {
+            (
+                state.db.allowed_domains.remove(domain).await,
+                Msg::DomainUnallowed,
+                Msg::DomainNotAllowed,
+            )
+        };
+        match result {
+            Ok(true) => {
+                tracing::info!(target: "admin", domain, blocked, "domain removed from list");
+                bot.send_message(msg.chat.id, tf(locale, removed, &[("domain", &domain)]))
+                    .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, missing)).await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to remove domain from list");
+                bot.send_message(msg.chat.id, t(locale, Msg::DomainRemoveFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn domain_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let lists = match state.db.blocked_domains.list(DOMAIN_LIST_LIMIT).await {
+            Ok(blocked) => state
+                .db
+                .allowed_domains
+                .list()
+                .await
+                .map(|allowed| (blocked, allowed)),
+            Err(err) => Err(err),
+        };
+        let reply = match lists {
+            Ok((blocked, allowed)) => {
+                let mut reply = tf(
+                    locale,
+                    Msg::DomainListBlocked,
+                    &[("limit", &DOMAIN_LIST_LIMIT)],
+                );
+                if blocked.is_empty() {
+                    reply.push_str(t(locale, Msg::DomainListNone));
+                }
+                for (domain, source) in &blocked {
+                    reply.push_str(&format!(
+                        "\n- {domain} ({})",
+                        source.as_deref().unwrap_or("-")
+                    ));
+                }
+                reply.push_str(t(locale, Msg::DomainListAllowed));
+                if allowed.is_empty() {
+                    reply.push_str(t(locale, Msg::DomainListNone));
+                }
+                for domain in &allowed {
+                    reply.push_str(&format!("\n- {domain}"));
+                }
+                reply
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to list domains");
+                t(locale, Msg::DomainListFailed).to_string()
+            }
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn blacklist_add(
+        bot: &Bot,
+        msg: &Message,
+        user_id: i64,
+        reason: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let reason = if reason.is_empty() {
+            t(locale, Msg::BlacklistAdminReason)
+        } else {
+            reason
+        };
+        match state.db.user_blacklist.add(user_id, reason, "admin").await {
+            Ok(true) => {
+                tracing::info!(target: "admin", user_id, "user blacklisted");
+                let mut reply = tf(locale, Msg::BlacklistAdded, &[("user_id", &user_id)]);
+                if state.config.blacklist.pre_ban {
+                    let banned = state.processor.ban_everywhere(user_id).await;
+                    reply.push_str(&tf(locale, Msg::BlacklistPreBanned, &[("count", &banned)]));
+                }
+                bot.send_message(msg.chat.id, reply).await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistExists))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to blacklist user");
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistAddFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn blacklist_remove(
+        bot: &Bot,
+        msg: &Message,
+        user_id: i64,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.user_blacklist.remove(user_id).await {
+            Ok(true) => {
+                tracing::info!(target: "admin", user_id, "user removed from blacklist");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::BlacklistRemoved, &[("user_id", &user_id)]),
+                )
+                .await?;
+            }
+            Ok(false) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistMissing))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to remove blacklisted user");
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistRemoveFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lifts the user's recorded bans, plus one in `chat_id` the bot may not have
+    /// recorded.
+    async fn unban(
+        bot: &Bot,
+        msg: &Message,
+        user_id: i64,
+        chat_id: Option<i64>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let bans = match state.db.bans.for_user(user_id).await {
+            Ok(rows) => rows,
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load bans");
+                bot.send_message(msg.chat.id, t(locale, Msg::BansLoadFailed))
+                    .await?;
+                return Ok(());
+            }
+        };
+        let mut targets: Vec<(i64, Option<String>)> = bans
+            .into_iter()
+            .map(|ban| {
+                let mut detail = tf(
+                    locale,
+                    Msg::UnbanDetail,
+                    &[
+                        ("reason", &ban.reason),
+                        ("at", &ban.banned_at.format("%Y-%m-%d %H:%M UTC")),
+                    ],
+                );
+                if let Some(expires_at) = ban.expires_at {
+                    detail.push_str(&tf(
+                        locale,
+                        Msg::UnbanDetailExpires,
+                        &[("at", &expires_at.format("%Y-%m-%d %H:%M UTC"))],
+                    ));
+                }
+                (ban.chat_id, Some(detail))
+            })
+            .collect();
+        if let Some(chat_id) = chat_id.filter(|id| targets.iter().all(|(chat, _)| chat != id)) {
+            targets.push((chat_id, None));
+        }
+        if targets.is_empty() {
+            bot.send_message(msg.chat.id, t(locale, Msg::UnbanNoRecords))
+                .await?;
+            return Ok(());
+        }
+
+        let mut reply = tf(locale, Msg::UnbanHeader, &[("user_id", &user_id)]);
+        let mut lifted = 0;
+        for (chat_id, detail) in &targets {
+            let result = if state.processor.lift_ban(*chat_id, user_id).await {
+                lifted += 1;
+                t(locale, Msg::UnbanLifted)
+            } else {
+                t(locale, Msg::UnbanFailed)
+            };
+            match detail {
+                Some(detail) => reply.push_str(&format!("\n- {chat_id} ({detail}): {result}")),
+                None => reply.push_str(&format!("\n- {chat_id}: {result}")),
+            }
+        }
+        tracing::info!(
+            target: "admin",
+            user_id,
+            lifted,
+            total = targets.len(),
+            "user unbanned"
+        );
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Running version and build, and whether a newer GitHub release exists.
+    async fn version(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let mut reply = tf(
+            locale,
+            Msg::VersionInfo,
+            &[
+                ("version", &env!("CARGO_PKG_VERSION")),
+                ("target", &updater::BUILD_TARGET),
+                ("commit", &updater::GIT_COMMIT),
+            ],
+        );
+        match updater::check_latest_version(&state.config).await {
+            Ok((current, latest)) if latest > current => {
+                reply.push_str(&tf(
+                    locale,
+                    Msg::VersionUpdateAvailable,
+                    &[("latest", &latest)],
+                ));
+            }
+            Ok((_, latest)) => {
+                reply.push_str(&tf(locale, Msg::VersionUpToDate, &[("latest", &latest)]))
+            }
+            Err(err) => {
+                tracing::warn!(target: "update", error = %err, "failed to check latest release");
+                reply.push_str(t(locale, Msg::VersionCheckFailed));
+            }
+        }
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    async fn backup_now(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let backup = DatabaseBackup::new(state.db.backups.clone(), &state.config);
+        let reply = match backup.run().await {
+            Ok((path, size)) => {
+                tracing::info!(target: "admin", path = %path.display(), "manual backup written");
+                tf(
+                    locale,
+                    Msg::BackupSaved,
+                    &[
+                        ("path", &path.display()),
+                        ("size", &format!("{:.1}", size as f64 / (1024.0 * 1024.0))),
+                    ],
+                )
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %format!("{err:#}"), "manual backup failed");
+                tf(locale, Msg::BackupFailed, &[("error", &format!("{err:#}"))])
             }
-            "/sync_commands" => {
-                Self::sync_commands_for(bot, &state.config).await?;
-                bot.send_message(msg.chat.id, "봇 명령어 동기화를 완료했습니다.")
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
+
+    /// Sends the last `lines` lines of the current log file, as a document when they do
+    /// not fit in a message.
+    async fn logs(bot: &Bot, msg: &Message, lines: usize, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let logs_dir = std::path::Path::new(&state.config.directories.logs_dir);
+        let Some(path) = logging::current_log_file(logs_dir) else {
+            bot.send_message(msg.chat.id, t(locale, Msg::LogFileMissing))
+                .await?;
+            return Ok(());
+        };
+        let tail = match logging::tail(&path, lines) {
+            Ok(tail) if tail.is_empty() => {
+                bot.send_message(msg.chat.id, t(locale, Msg::LogFileEmpty))
                     .await?;
-                Ok(true)
+                return Ok(());
             }
-            _ => Ok(false),
+            Ok(tail) => tail,
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to read log file");
+                bot.send_message(msg.chat.id, t(locale, Msg::LogFileReadFailed))
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        if tail.chars().count() <= LOGS_INLINE_CHARS {
+            bot.send_message(msg.chat.id, tail).await?;
+        } else {
+            let file_name = path
+                .file_name()
+                .map(|name| format!("{}-tail.txt", name.to_string_lossy()))
+                .unwrap_or_else(|| "bot-log-tail.txt".to_string());
+            bot.send_document(msg.chat.id, InputFile::memory(tail).file_name(file_name))
+                .caption(tf(locale, Msg::LogTail, &[("lines", &lines)]))
+                .await?;
         }
+        Ok(())
     }
 
-    async fn whitelist_add(
-        bot: &Bot,
-        msg: &Message,
-        target_chat_id: i64,
-        state: Arc<AppState>,
-    ) -> BotResult<()> {
-        match bot.get_chat(ChatId(target_chat_id)).await {
-            Ok(chat_info) => {
-                let entry = WhitelistEntry {
-                    chat_id: target_chat_id,
-                    chat_title: chat_info.title().map(|t| t.to_string()),
-                    chat_type: Some(format!("{:?}", chat_info.kind)),
-                    added_by: msg.from.as_ref().map(user_to_i64),
-                };
-                match state.whitelist.add_or_replace(entry).await {
-                    Ok(true) => {
-                        tracing::info!(
-                            target: "admin",
-                            chat_id = target_chat_id,
-                            added_by = msg.from.as_ref().map(user_to_i64),
-                            "whitelist entry added"
-                        );
-                        bot.send_message(
-                            msg.chat.id,
-                            format!("그룹 (ID: {target_chat_id})이 화이트리스트에 추가되었습니다."),
-                        )
-                        .await?;
-                    }
-                    Ok(false) => {
-                        bot.send_message(msg.chat.id, "이미 등록된 그룹입니다.")
-                            .await?;
-                    }
-                    Err(err) => {
-                        tracing::error!(target: "admin", error = %err, "failed to add whitelist");
-                        bot.send_message(msg.chat.id, "화이트리스트 추가 중 오류가 발생했습니다.")
-                            .await?;
-                    }
+    /// Installs the latest release now and restarts into it, reporting each step.
+    async fn update(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let latest = match updater::check_latest_version(&state.config).await {
+            Ok((current, latest)) if latest <= current => {
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::AlreadyLatest, &[("current", &current)]),
+                )
+                .await?;
+                return Ok(());
+            }
+            Ok((_, latest)) => latest,
+            Err(err) => {
+                tracing::warn!(target: "update", error = %err, "failed to check latest release");
+                bot.send_message(msg.chat.id, t(locale, Msg::ReleaseCheckFailed))
+                    .await?;
+                return Ok(());
+            }
+        };
+        bot.send_message(
+            msg.chat.id,
+            tf(locale, Msg::UpdateDownloading, &[("latest", &latest)]),
+        )
+        .await?;
+
+        match updater::apply_update_now(&state.config).await {
+            Ok(UpdateStatus::UpToDate) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::UpdateNothing))
+                    .await?;
+            }
+            Ok(UpdateStatus::Installed {
+                new_version,
+                old_version,
+            }) => {
+                tracing::info!(target: "update", %old_version, %new_version, "update installed on demand");
+                if state.config.update.auto_restart {
+                    bot.send_message(
+                        msg.chat.id,
+                        tf(
+                            locale,
+                            Msg::UpdateDoneRestarting,
+                            &[("old", &old_version), ("new", &new_version)],
+                        ),
+                    )
+                    .await?;
+                    state.restart_schedule.restart_now();
+                } else {
+                    bot.send_message(
+                        msg.chat.id,
+                        tf(
+                            locale,
+                            Msg::UpdateDoneRestartManually,
+                            &[("old", &old_version), ("new", &new_version)],
+                        ),
+                    )
+                    .await?;
                 }
             }
-            Err(_) => {
+            Err(err) => {
+                tracing::error!(target: "update", error = %format!("{err:#}"), "on-demand update failed");
                 bot.send_message(
                     msg.chat.id,
-                    "해당 그룹을 찾을 수 없습니다. 봇이 그룹에 추가되어 있는지 확인하세요.",
+                    tf(locale, Msg::UpdateFailed, &[("error", &format!("{err:#}"))]),
                 )
                 .await?;
             }
@@ -556,151 +4576,380 @@ impl TelegramService {
         Ok(())
     }
 
-    async fn on_callback_query(bot: Bot, q: CallbackQuery, state: Arc<AppState>) -> BotResult<()> {
-        let Some(data) = q.data.as_deref() else {
-            return Ok(());
-        };
-
-        // Only handle ban actions from the admin group
-        let Some(message) = q.message else {
-            return Ok(());
-        };
-        let chat = message.chat();
-        if !state.is_admin_group(chat.id.0) {
-            return Ok(());
-        }
-
-        if !state.is_admin_user(user_to_i64(&q.from)) {
-            bot.answer_callback_query(q.id)
-                .text("관리자만 실행할 수 있습니다.")
-                .show_alert(true)
+    /// Lists the jobs waiting for classification in the order they will be taken.
+    async fn queue_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let snapshot = state.queue.snapshot();
+        let jobs = state.queue.peek(QUEUE_LIST_LIMIT);
+        if jobs.is_empty() {
+            bot.send_message(msg.chat.id, t(locale, Msg::QueueEmpty))
                 .await?;
             return Ok(());
         }
 
-        if !data.starts_with("ban:") {
-            return Ok(());
+        let now = Utc::now();
+        let mut reply = tf(
+            locale,
+            Msg::QueueHeader,
+            &[
+                ("high", &snapshot.high_priority),
+                ("normal", &snapshot.normal_priority),
+            ],
+        );
+        for (priority, job) in &jobs {
+            let label = match priority {
+                Priority::High => t(locale, Msg::QueuePriority),
+                Priority::Normal => t(locale, Msg::QueueNormal),
+            };
+            let mut excerpt: String = job.text.chars().take(QUEUE_EXCERPT_CHARS).collect();
+            if job.text.chars().count() > QUEUE_EXCERPT_CHARS {
+                excerpt.push('…');
+            }
+            reply.push_str(&tf(
+                locale,
+                Msg::QueueEntry,
+                &[
+                    ("label", &label),
+                    (
+                        "chat",
+                        &job.chat_title
+                            .as_deref()
+                            .unwrap_or(t(locale, Msg::UntitledChat)),
+                    ),
+                    ("chat_id", &job.chat_id.0),
+                    ("user", &job.from_display),
+                    (
+                        "user_id",
+                        &job.from_id
+                            .map(|id| id.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                    ),
+                    ("score", &job.priority_score),
+                    ("secs", &(now - job.timestamp).num_seconds().max(0)),
+                    ("text", &excerpt),
+                ],
+            ));
         }
-
-        let parts: Vec<&str> = data.split(':').collect();
-        if parts.len() != 3 {
-            bot.answer_callback_query(q.id)
-                .text("잘못된 요청입니다.")
-                .show_alert(true)
-                .await?;
-            return Ok(());
+        let remaining =
+            (snapshot.high_priority + snapshot.normal_priority).saturating_sub(jobs.len());
+        if remaining > 0 {
+            reply.push_str(&tf(locale, Msg::QueueMore, &[("count", &remaining)]));
         }
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
 
-        let chat_id: i64 = match parts[1].parse() {
-            Ok(id) => id,
-            Err(_) => {
-                bot.answer_callback_query(q.id)
-                    .text("chat_id 파싱 실패")
-                    .show_alert(true)
-                    .await?;
-                return Ok(());
+    /// Replies with the classifier's verdict on `job` without acting on it.
+    async fn test_spam(
+        bot: &Bot,
+        msg: &Message,
+        job: MessageJob,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let reply = match state.processor.classify_only(job).await {
+            Ok(Some(decision)) => tf(
+                locale,
+                Msg::TestSpamResult,
+                &[
+                    (
+                        "verdict",
+                        &t(
+                            locale,
+                            if decision.spam {
+                                Msg::VerdictSpam
+                            } else {
+                                Msg::VerdictHam
+                            },
+                        ),
+                    ),
+                    ("confidence", &format!("{:.2}", decision.confidence)),
+                    ("reason", &decision.reason.as_deref().unwrap_or("-")),
+                ],
+            ),
+            Ok(None) => t(locale, Msg::TestSpamNoVerdict).to_string(),
+            Err(err) => {
+                tracing::warn!(target: "admin", error = %format!("{err:#}"), "test classification failed");
+                tf(locale, Msg::TestSpamFailed, &[("error", &err)])
             }
         };
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
 
-        let user_id_raw: i64 = match parts[2].parse() {
-            Ok(id) => id,
-            Err(_) => {
-                bot.answer_callback_query(q.id)
-                    .text("user_id 파싱 실패")
-                    .show_alert(true)
+    /// Deletes the user's messages from the last 48 hours, in `chat_id` only when given,
+    /// and with `ban` also bans them from every moderated chat.
+    async fn purge_user(
+        bot: &Bot,
+        msg: &Message,
+        user_id: i64,
+        chat_id: Option<i64>,
+        ban: bool,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let messages = match state.db.recent_messages.for_user(user_id, chat_id).await {
+            Ok(messages) => messages,
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to load recent messages");
+                bot.send_message(msg.chat.id, t(locale, Msg::PurgeLoadFailed))
                     .await?;
                 return Ok(());
             }
         };
 
-        if user_id_raw < 0 {
-            bot.answer_callback_query(q.id)
-                .text("user_id 형식이 올바르지 않습니다.")
-                .show_alert(true)
-                .await?;
-            return Ok(());
+        let mut by_chat: Vec<(i64, Vec<MessageId>)> = Vec::new();
+        for (chat_id, message_id) in messages {
+            match by_chat.last_mut() {
+                Some((last, ids)) if *last == chat_id => ids.push(MessageId(message_id)),
+                _ => by_chat.push((chat_id, vec![MessageId(message_id)])),
+            }
+        }
+        let mut deleted = 0;
+        let mut failed = 0;
+        for (chat_id, ids) in &by_chat {
+            let chat_id = ChatId(*chat_id);
+            for chunk in ids.chunks(PURGE_CHUNK) {
+                match state
+                    .processor
+                    .bot_for(chat_id)
+                    .delete_messages(chat_id, chunk.to_vec())
+                    .await
+                {
+                    Ok(_) => deleted += chunk.len(),
+                    Err(err) => {
+                        failed += chunk.len();
+                        tracing::warn!(
+                            target: "admin",
+                            error = %err,
+                            chat_id = chat_id.0,
+                            user_id,
+                            "failed to purge messages"
+                        );
+                    }
+                }
+            }
         }
+        if let Err(err) = state.db.recent_messages.forget(user_id, chat_id).await {
+            tracing::warn!(target: "db", error = %err, user_id, "failed to forget purged messages");
+        }
+        tracing::info!(target: "admin", user_id, chats = by_chat.len(), deleted, failed, "user purged");
 
-        let target_user = UserId(user_id_raw as u64);
+        let mut reply = tf(
+            locale,
+            Msg::PurgeDone,
+            &[
+                ("user_id", &user_id),
+                ("deleted", &deleted),
+                ("chats", &by_chat.len()),
+            ],
+        );
+        if failed > 0 {
+            reply.push_str(&tf(locale, Msg::PurgeFailedCount, &[("count", &failed)]));
+        }
+        if ban {
+            let banned = state.processor.ban_everywhere(user_id).await;
+            reply.push_str(&tf(locale, Msg::PurgeBanned, &[("count", &banned)]));
+        }
+        bot.send_message(msg.chat.id, reply).await?;
+        Ok(())
+    }
 
-        match bot.ban_chat_member(ChatId(chat_id), target_user).await {
-            Ok(_) => {
-                bot.answer_callback_query(q.id).text("밴 완료").await?;
+    async fn blacklist_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.user_blacklist.list(BLACKLIST_LIST_LIMIT).await {
+            Ok(entries) if entries.is_empty() => {
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistEmpty))
+                    .await?;
             }
-            Err(err) => {
-                tracing::error!(
-                    target: "telegram",
-                    error = %err,
-                    chat_id,
-                    user_id = user_id_raw,
-                    "failed to ban user via callback"
+            Ok(entries) => {
+                let mut message = tf(
+                    locale,
+                    Msg::BlacklistListHeader,
+                    &[("limit", &BLACKLIST_LIST_LIMIT)],
                 );
-                bot.answer_callback_query(q.id)
-                    .text("밴 실패: 권한 또는 네트워크 오류")
-                    .show_alert(true)
+                for entry in entries {
+                    message.push_str(&format!(
+                        "\n- {} [{}] {} ({})",
+                        entry.user_id,
+                        entry.source.as_deref().unwrap_or("-"),
+                        entry.reason.as_deref().unwrap_or("-"),
+                        entry.added_at.format("%Y-%m-%d"),
+                    ));
+                }
+                bot.send_message(msg.chat.id, message).await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to list blacklist");
+                bot.send_message(msg.chat.id, t(locale, Msg::BlacklistListFailed))
                     .await?;
             }
         }
-
         Ok(())
     }
 
-    async fn whitelist_remove(
+    async fn rule_add(
         bot: &Bot,
         msg: &Message,
-        target_chat_id: i64,
+        chat_id: i64,
+        kind: RuleKind,
+        action: RuleAction,
+        pattern: &str,
         state: Arc<AppState>,
     ) -> BotResult<()> {
-        match state.whitelist.remove(target_chat_id).await {
-            Ok(true) => {
+        let locale = state.config.language;
+        let created_by = msg.from.as_ref().map(user_to_i64);
+        match state
+            .rules
+            .add(chat_id, kind, action, pattern, created_by)
+            .await
+        {
+            Ok(rule_id) => {
                 tracing::info!(
                     target: "admin",
-                    chat_id = target_chat_id,
-                    removed_by = msg.from.as_ref().map(user_to_i64),
-                    "whitelist entry removed"
+                    rule_id,
+                    chat_id,
+                    kind = kind.as_str(),
+                    action = action.as_str(),
+                    "chat rule added"
                 );
                 bot.send_message(
                     msg.chat.id,
-                    format!("그룹 (ID: {target_chat_id})이 화이트리스트에서 제거되었습니다."),
+                    tf(
+                        locale,
+                        Msg::RuleAdded,
+                        &[
+                            ("rule_id", &rule_id),
+                            ("chat_id", &chat_id),
+                            ("kind", &kind.as_str()),
+                            ("action", &action.as_str()),
+                            ("pattern", &pattern),
+                        ],
+                    ),
                 )
                 .await?;
             }
-            Ok(false) => {
-                bot.send_message(msg.chat.id, "화이트리스트에 등록되지 않은 그룹입니다.")
-                    .await?;
-            }
             Err(err) => {
-                tracing::error!(target: "admin", error = %err, "failed to remove whitelist");
-                bot.send_message(msg.chat.id, "화이트리스트 제거 중 오류가 발생했습니다.")
-                    .await?;
+                tracing::warn!(target: "admin", error = %err, "failed to add chat rule");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(locale, Msg::RuleAddFailed, &[("error", &err)]),
+                )
+                .await?;
             }
         }
         Ok(())
     }
 
-    async fn whitelist_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
-        match state.whitelist.list().await {
+    async fn rule_list(
+        bot: &Bot,
+        msg: &Message,
+        chat_filter: Option<i64>,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.db.rules.list().await {
             Ok(rows) => {
+                let rows: Vec<_> = rows
+                    .into_iter()
+                    .filter(|row| chat_filter.is_none_or(|chat_id| row.chat_id == chat_id))
+                    .collect();
                 if rows.is_empty() {
-                    bot.send_message(msg.chat.id, "화이트리스트가 비어있습니다.")
+                    bot.send_message(msg.chat.id, t(locale, Msg::RuleListEmpty))
                         .await?;
                     return Ok(());
                 }
-                let mut message = String::from("화이트리스트 목록:\n\n");
-                for (idx, row) in rows.iter().enumerate() {
+                let mut message = t(locale, Msg::RuleListHeader).to_string();
+                for row in rows {
                     message.push_str(&format!(
-                        "{}. ID: {}\n   저장된 이름: {}\n   등록일: {}\n",
-                        idx + 1,
-                        row.chat_id,
-                        row.chat_title.as_deref().unwrap_or("(제목 없음)"),
-                        row.added_at.format("%Y-%m-%d"),
+                        "\n#{} [{}] {} / {}: {}",
+                        row.id, row.chat_id, row.kind, row.action, row.pattern
                     ));
                 }
                 bot.send_message(msg.chat.id, message).await?;
             }
             Err(err) => {
-                tracing::error!(target: "admin", error = %err, "failed to list whitelist");
-                bot.send_message(msg.chat.id, "화이트리스트 조회 중 오류가 발생했습니다.")
+                tracing::error!(target: "admin", error = %err, "failed to list chat rules");
+                bot.send_message(msg.chat.id, t(locale, Msg::RuleListFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn restart_cron_list(bot: &Bot, msg: &Message, state: Arc<AppState>) -> BotResult<()> {
+        let locale = state.config.language;
+        let specs = state.restart_schedule.list().await;
+        if specs.is_empty() {
+            bot.send_message(msg.chat.id, t(locale, Msg::CronListEmpty))
+                .await?;
+            return Ok(());
+        }
+        let mut message = t(locale, Msg::CronListHeader).to_string();
+        for (idx, spec) in specs.iter().enumerate() {
+            message.push_str(&format!("\n{}. {}", idx + 1, spec));
+        }
+        bot.send_message(msg.chat.id, message).await?;
+        Ok(())
+    }
+
+    async fn restart_cron_add(
+        bot: &Bot,
+        msg: &Message,
+        spec: &str,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        let added_by = msg.from.as_ref().map(user_to_i64);
+        match state.restart_schedule.add(spec, added_by).await {
+            Ok(index) => {
+                tracing::info!(target: "admin", cron = spec, ?added_by, "restart cron added");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::CronAdded,
+                        &[("index", &index), ("spec", &spec)],
+                    ),
+                )
+                .await?;
+            }
+            Err(err) => {
+                tracing::warn!(target: "admin", error = %err, cron = spec, "failed to add restart cron");
+                bot.send_message(msg.chat.id, t(locale, Msg::CronAddFailed))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn restart_cron_remove(
+        bot: &Bot,
+        msg: &Message,
+        index: usize,
+        state: Arc<AppState>,
+    ) -> BotResult<()> {
+        let locale = state.config.language;
+        match state.restart_schedule.remove(index).await {
+            Ok(Some(spec)) => {
+                tracing::info!(target: "admin", cron = %spec, "restart cron removed");
+                bot.send_message(
+                    msg.chat.id,
+                    tf(
+                        locale,
+                        Msg::CronRemoved,
+                        &[("index", &index), ("spec", &spec)],
+                    ),
+                )
+                .await?;
+            }
+            Ok(None) => {
+                bot.send_message(msg.chat.id, t(locale, Msg::CronNotFound))
+                    .await?;
+            }
+            Err(err) => {
+                tracing::error!(target: "admin", error = %err, "failed to remove restart cron");
+                bot.send_message(msg.chat.id, t(locale, Msg::CronRemoveFailed))
                     .await?;
             }
         }
@@ -708,14 +4957,29 @@ impl TelegramService {
     }
 
     async fn sync_commands(&self) -> BotResult<()> {
+        if self.state.extra_bot.is_some() {
+            return Self::sync_general_commands(&self.bot, &self.state.config).await;
+        }
         Self::sync_commands_for(&self.bot, &self.state.config).await
     }
 
+    /// Registers the general command menu in `BOT_LANGUAGE`, plus a translation per
+    /// locale that Telegram picks by the user's app language.
+    async fn sync_general_commands(bot: &Bot, config: &AppConfig) -> BotResult<()> {
+        bot.set_my_commands(general_command_list(config.language))
+            .await?;
+        for locale in Locale::ALL {
+            bot.set_my_commands(general_command_list(locale))
+                .language_code(locale.code())
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn sync_commands_for(bot: &Bot, config: &AppConfig) -> BotResult<()> {
-        let general = GeneralCommand::bot_commands();
-        bot.set_my_commands(general.clone()).await?;
+        Self::sync_general_commands(bot, config).await?;
 
-        let admin_commands = admin_command_list();
+        let admin_commands = admin_command_list(config.language);
         if let Some(admin_user_id) = config.admin_user_id {
             bot.set_my_commands(admin_commands.clone())
                 .scope(BotCommandScope::Chat {
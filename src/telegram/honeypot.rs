@@ -0,0 +1,83 @@
+use teloxide::prelude::*;
+
+use crate::heuristics::known_spam::{is_blockable, link_domains, text_fingerprint};
+
+use super::{types::AppState, utils::user_to_i64};
+
+const HONEYPOT_REASON: &str = "허니팟 채팅방 메시지";
+
+/// Treats a honeypot message as confirmed spam: the sender, text fingerprint and linked
+/// domains are recorded for use in real chats, and the message is removed.
+pub async fn harvest(bot: &Bot, state: &AppState, msg: &Message) {
+    let chat_id = msg.chat.id.0;
+    let source = format!("honeypot:{chat_id}");
+    let text = msg.text().or_else(|| msg.caption()).unwrap_or_default();
+    let entities = msg
+        .entities()
+        .or_else(|| msg.caption_entities())
+        .unwrap_or_default();
+
+    let mut harvested = Vec::new();
+    if let Some(user) = msg.from.as_ref().filter(|user| !user.is_bot) {
+        let user_id = user_to_i64(user);
+        if !state.is_admin_user(user_id) {
+            match state
+                .db
+                .user_blacklist
+                .add(user_id, HONEYPOT_REASON, &source)
+                .await
+            {
+                Ok(true) => harvested.push(format!("user:{user_id}")),
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::warn!(target: "db", error = %err, "failed to blacklist honeypot sender");
+                }
+            }
+        }
+    }
+    if let Some(fingerprint) = text_fingerprint(text) {
+        match state
+            .db
+            .spam_fingerprints
+            .add(fingerprint, text, &source)
+            .await
+        {
+            Ok(true) => harvested.push(format!("fingerprint:{fingerprint:x}")),
+            Ok(false) => {}
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to store honeypot fingerprint");
+            }
+        }
+    }
+    for domain in link_domains(text, entities)
+        .into_iter()
+        .filter(|domain| is_blockable(domain))
+    {
+        if state
+            .db
+            .allowed_domains
+            .contains(&domain)
+            .await
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        match state.db.blocked_domains.add(&domain, &source).await {
+            Ok(true) => harvested.push(format!("domain:{domain}")),
+            Ok(false) => {}
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to block honeypot domain");
+            }
+        }
+    }
+
+    if let Err(err) = bot.delete_message(msg.chat.id, msg.id).await {
+        tracing::warn!(target: "telegram", error = %err, chat_id, "failed to delete honeypot message");
+    }
+    tracing::info!(
+        target: "telegram",
+        chat_id,
+        harvested = ?harvested,
+        "honeypot message harvested"
+    );
+}
@@ -1,5 +1,13 @@
+pub mod captcha;
+pub mod chat_admins;
+mod flood;
 mod handler;
+mod honeypot;
+pub mod profile;
+pub mod raid;
 pub mod types;
 pub mod utils;
+pub mod whitelist_file;
+pub mod whitelist_request;
 
 pub use handler::TelegramService;
@@ -0,0 +1,55 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use teloxide::prelude::*;
+
+/// Entries beyond this trigger a sweep of expired lookups.
+const MAX_ENTRIES: usize = 10_000;
+
+/// Caches whether users have a profile photo, so repeat posters cost one API call per TTL.
+pub struct ProfileCache {
+    ttl: Duration,
+    photos: Mutex<HashMap<UserId, (bool, Instant)>>,
+}
+
+impl ProfileCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            photos: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns `None` when the lookup failed; failures are not cached.
+    pub async fn has_photo(&self, bot: &Bot, user_id: UserId) -> Option<bool> {
+        if let Some((has_photo, checked_at)) = self.photos.lock().get(&user_id).copied() {
+            if checked_at.elapsed() < self.ttl {
+                return Some(has_photo);
+            }
+        }
+
+        let has_photo = match bot.get_user_profile_photos(user_id).limit(1).await {
+            Ok(photos) => photos.total_count > 0,
+            Err(err) => {
+                tracing::warn!(
+                    target: "telegram",
+                    error = %err,
+                    user_id = user_id.0,
+                    "get_user_profile_photos failed"
+                );
+                return None;
+            }
+        };
+
+        let mut photos = self.photos.lock();
+        if photos.len() >= MAX_ENTRIES {
+            let ttl = self.ttl;
+            photos.retain(|_, (_, checked_at)| checked_at.elapsed() < ttl);
+        }
+        photos.insert(user_id, (has_photo, Instant::now()));
+        Some(has_photo)
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use teloxide::{prelude::*, types::ChatPermissions};
+
+use crate::{
+    config::RaidConfig,
+    db::lockdowns::{Lockdown, LockdownRepository},
+    domain::MessageJob,
+    i18n::{t, tf, Msg},
+    infrastructure::notifier::notify_admin_group,
+    telegram::utils::escape_html,
+};
+
+use super::types::AppState;
+
+const RAID_REASON: &str = "조직적 스팸 공격(다계정 동일 메시지)";
+
+/// Applies the configured batch actions to every message of a detected raid and sends
+/// one consolidated admin alert. A dry-run chat gets review requests instead of
+/// deletions and no bans or lockdown.
+pub async fn respond(bot: &Bot, state: &AppState, config: &RaidConfig, jobs: Vec<MessageJob>) {
+    let Some(first) = jobs.first() else {
+        return;
+    };
+    let chat_id = first.chat_id;
+    let mut accounts: BTreeMap<i64, &str> = BTreeMap::new();
+    for job in &jobs {
+        if let Some(user_id) = job.from_id {
+            accounts.insert(user_id, job.from_display.as_str());
+        }
+    }
+    tracing::warn!(
+        target: "telegram",
+        chat_id = chat_id.0,
+        accounts = accounts.len(),
+        messages = jobs.len(),
+        "coordinated raid detected"
+    );
+
+    let locale = state.config.language;
+    let dry_run = state.processor.dry_run(chat_id.0).await;
+    let mut actions = Vec::new();
+    if config.delete {
+        let mut deleted = 0;
+        for job in &jobs {
+            match state.processor.remove_spam(job, RAID_REASON).await {
+                Ok(Some(_)) => deleted += 1,
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(target: "telegram", error = %err, "failed to delete raid message");
+                }
+            }
+        }
+        actions.push(tf(locale, Msg::RaidDeleted, &[("count", &deleted)]));
+    }
+    if config.ban && !dry_run {
+        let mut banned = 0;
+        for user_id in accounts.keys() {
+            if ban(bot, state, chat_id, *user_id).await {
+                banned += 1;
+            }
+        }
+        actions.push(tf(locale, Msg::RaidBanned, &[("count", &banned)]));
+    }
+    if let (Some(duration), false) = (config.lockdown, dry_run) {
+        if lockdown(bot, &state.db.lockdowns, chat_id, duration).await {
+            actions.push(tf(
+                locale,
+                Msg::RaidLockdown,
+                &[("minutes", &duration.as_secs().div_ceil(60))],
+            ));
+        }
+    }
+
+    let mut message = tf(
+        locale,
+        Msg::RaidAlert,
+        &[
+            (
+                "chat",
+                &escape_html(first.chat_title.as_deref().unwrap_or("Unknown")),
+            ),
+            ("chat_id", &chat_id.0),
+            ("accounts", &accounts.len()),
+            ("messages", &jobs.len()),
+            (
+                "actions",
+                &if actions.is_empty() {
+                    t(locale, Msg::NoneLabel).to_string()
+                } else {
+                    actions.join(", ")
+                },
+            ),
+        ],
+    );
+    for (user_id, display) in &accounts {
+        message.push_str(&format!("\n- {} ({user_id})", escape_html(display)));
+    }
+    message.push_str(&tf(
+        locale,
+        Msg::RaidExample,
+        &[("text", &escape_html(&first.text))],
+    ));
+    notify_admin_group(&state.admin_bot, state.config.as_ref(), &message).await;
+}
+
+/// Handles another copy of an ongoing raid quietly, without a new alert.
+pub async fn respond_follow_up(bot: &Bot, state: &AppState, config: &RaidConfig, job: &MessageJob) {
+    if config.delete {
+        if let Err(err) = state.processor.remove_spam(job, RAID_REASON).await {
+            tracing::warn!(target: "telegram", error = %err, "failed to delete raid follow-up");
+        }
+    }
+    if config.ban && !state.processor.dry_run(job.chat_id.0).await {
+        if let Some(user_id) = job.from_id {
+            ban(bot, state, job.chat_id, user_id).await;
+        }
+    }
+}
+
+async fn ban(bot: &Bot, state: &AppState, chat_id: ChatId, user_id: i64) -> bool {
+    match bot.ban_chat_member(chat_id, UserId(user_id as u64)).await {
+        Ok(_) => {
+            state
+                .processor
+                .record_ban(chat_id.0, user_id, "raid", true)
+                .await;
+            true
+        }
+        Err(err) => {
+            tracing::warn!(target: "telegram", error = %err, user_id, "failed to ban raid account");
+            false
+        }
+    }
+}
+
+/// Mutes the chat and restores its previous default permissions after `duration`. The
+/// lockdown is persisted so a restart still lifts it, and a chat that is already locked
+/// down is left alone rather than having its empty permissions captured as "previous".
+async fn lockdown(
+    bot: &Bot,
+    lockdowns: &Arc<LockdownRepository>,
+    chat_id: ChatId,
+    duration: Duration,
+) -> bool {
+    match lockdowns.is_active(chat_id.0).await {
+        Ok(false) => {}
+        Ok(true) => {
+            tracing::info!(target: "telegram", chat_id = chat_id.0, "chat already locked down");
+            return false;
+        }
+        Err(err) => {
+            tracing::warn!(target: "db", error = %err, "failed to check raid lockdown");
+            return false;
+        }
+    }
+    let previous = match bot.get_chat(chat_id).await {
+        Ok(chat) => chat.permissions().unwrap_or_else(ChatPermissions::all),
+        Err(err) => {
+            tracing::warn!(target: "telegram", error = %err, "failed to read chat permissions");
+            return false;
+        }
+    };
+    if let Err(err) = bot
+        .set_chat_permissions(chat_id, ChatPermissions::empty())
+        .await
+    {
+        tracing::warn!(target: "telegram", error = %err, "failed to lock down chat");
+        return false;
+    }
+
+    let lockdown = Lockdown {
+        chat_id: chat_id.0,
+        previous,
+        expires_at: Utc::now() + chrono::Duration::from_std(duration).unwrap_or_default(),
+    };
+    if let Err(err) = lockdowns
+        .start(
+            lockdown.chat_id,
+            lockdown.previous.clone(),
+            lockdown.expires_at,
+        )
+        .await
+    {
+        tracing::warn!(target: "db", error = %err, "failed to persist raid lockdown");
+    }
+    schedule_lift(bot.clone(), lockdowns.clone(), lockdown);
+    true
+}
+
+/// Schedules the lift of every lockdown persisted before a restart; overdue ones are
+/// lifted right away.
+pub async fn resume_lockdowns(bot_for: impl Fn(ChatId) -> Bot, lockdowns: Arc<LockdownRepository>) {
+    match lockdowns.all().await {
+        Ok(rows) => {
+            for lockdown in rows {
+                let bot = bot_for(ChatId(lockdown.chat_id));
+                schedule_lift(bot, lockdowns.clone(), lockdown);
+            }
+        }
+        Err(err) => tracing::warn!(target: "db", error = %err, "failed to load raid lockdowns"),
+    }
+}
+
+fn schedule_lift(bot: Bot, lockdowns: Arc<LockdownRepository>, lockdown: Lockdown) {
+    tokio::spawn(async move {
+        let remaining = (lockdown.expires_at - Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        tokio::time::sleep(remaining).await;
+        let chat_id = ChatId(lockdown.chat_id);
+        match bot.set_chat_permissions(chat_id, lockdown.previous).await {
+            Ok(_) => {
+                tracing::info!(target: "telegram", chat_id = chat_id.0, "raid lockdown lifted");
+                if let Err(err) = lockdowns.remove(chat_id.0).await {
+                    tracing::warn!(target: "db", error = %err, "failed to clear raid lockdown");
+                }
+            }
+            // The row stays so the next start retries the lift.
+            Err(err) => tracing::error!(
+                target: "telegram",
+                error = %err,
+                chat_id = chat_id.0,
+                "failed to lift raid lockdown"
+            ),
+        }
+    });
+}
@@ -2,25 +2,64 @@ use std::sync::Arc;
 
 use teloxide::{
     prelude::*,
-    types::{ChatId, ChatMemberStatus},
+    types::{ChatId, ChatMemberStatus, MessageEntity},
     utils::command::BotCommands,
 };
+use tokio::sync::Semaphore;
 
 use crate::{
-    config::AppConfig,
-    db::whitelist::WhitelistRepository,
+    ai::two_stage::TwoStageClassifier,
+    cas::CasClient,
+    config::{AppConfig, ExtraBotConfig},
+    db::Repositories,
     domain::{types::QueueSnapshot, MessageJob},
-    tasks::queue::MessageQueue,
+    heuristics::{
+        flood::FloodDetector,
+        known_spam::{link_domains, text_fingerprint, KnownSpam},
+        raid::RaidDetector,
+    },
+    i18n::Locale,
+    media::{ocr::VideoOcr, transcription::Transcriber, vision::ImageDescriber},
+    rules::RuleEngine,
+    tasks::{processor::MessageProcessor, queue::MessageQueue, scheduler::RestartScheduleManager},
+    url_reputation::UrlReputationClient,
+};
+
+use super::{
+    captcha::CaptchaGate, chat_admins::ChatAdminCache, profile::ProfileCache,
+    whitelist_request::WhitelistRequests,
 };
 
 pub type QueueSnapshotProvider = Arc<dyn Fn() -> QueueSnapshot + Send + Sync>;
 pub type BotResult<T> = Result<T, teloxide::RequestError>;
 
+#[derive(Clone)]
 pub struct AppState {
     pub config: Arc<AppConfig>,
-    pub whitelist: Arc<WhitelistRepository>,
+    pub db: Repositories,
     pub queue: Arc<MessageQueue<MessageJob>>,
     pub queue_snapshot: QueueSnapshotProvider,
+    pub restart_schedule: Arc<RestartScheduleManager>,
+    pub processor: Arc<MessageProcessor>,
+    pub rules: Arc<RuleEngine>,
+    pub profiles: Arc<ProfileCache>,
+    pub chat_admins: Arc<ChatAdminCache>,
+    pub whitelist_requests: Arc<WhitelistRequests>,
+    pub transcriber: Option<Arc<Transcriber>>,
+    pub video_ocr: Option<Arc<VideoOcr>>,
+    pub vision: Option<Arc<ImageDescriber>>,
+    /// Bounds the background media analysis shared by every dispatcher.
+    pub media_slots: Arc<Semaphore>,
+    pub two_stage: Option<Arc<TwoStageClassifier>>,
+    pub raids: Option<Arc<RaidDetector>>,
+    pub floods: Option<Arc<FloodDetector>>,
+    pub captcha: Option<Arc<CaptchaGate>>,
+    pub cas: Option<Arc<CasClient>>,
+    pub url_reputation: Option<Arc<UrlReputationClient>>,
+    /// Primary bot, which posts to the admin group for every dispatcher.
+    pub admin_bot: Bot,
+    /// Set when this state belongs to one of the `EXTRA_BOTS` dispatchers.
+    pub extra_bot: Option<Arc<ExtraBotConfig>>,
 }
 
 impl AppState {
@@ -28,17 +67,87 @@ impl AppState {
         if chat_id >= 0 {
             return true;
         }
+        if let Some(extra) = &self.extra_bot {
+            return extra.chat_ids.contains(&chat_id);
+        }
         if self.config.admin_group_id == Some(chat_id) {
             return true;
         }
-        if self.config.allowed_chat_ids.contains(&chat_id) {
+        if self.config.live.get().allowed_chat_ids.contains(&chat_id) {
             return true;
         }
-        self.whitelist.is_allowed(chat_id).await.unwrap_or(false)
+        self.db.whitelist.is_allowed(chat_id).await.unwrap_or(false)
     }
 
+    pub fn is_honeypot(&self, chat_id: i64) -> bool {
+        self.config.honeypot_chat_ids.contains(&chat_id)
+    }
+
+    /// Looks the sender, text and linked domains up in the stores seeded by honeypots.
+    pub async fn known_spam(
+        &self,
+        user_id: Option<i64>,
+        text: &str,
+        entities: &[MessageEntity],
+    ) -> KnownSpam {
+        let blacklisted_user = match user_id {
+            Some(user_id) => self.db.user_blacklist.contains(user_id).await,
+            None => Ok(false),
+        };
+        let known_text = match text_fingerprint(text) {
+            Some(fingerprint) => self.db.spam_fingerprints.contains(fingerprint).await,
+            None => Ok(false),
+        };
+        let blocked_domains = self
+            .db
+            .blocked_domains
+            .matching(&link_domains(text, entities))
+            .await;
+        KnownSpam {
+            blacklisted_user: blacklisted_user.unwrap_or_else(|err| {
+                tracing::warn!(target: "db", error = %err, "failed to check user blacklist");
+                false
+            }),
+            known_text: known_text.unwrap_or_else(|err| {
+                tracing::warn!(target: "db", error = %err, "failed to check spam fingerprints");
+                false
+            }),
+            blocked_domains: blocked_domains.unwrap_or_else(|err| {
+                tracing::warn!(target: "db", error = %err, "failed to check blocked domains");
+                Vec::new()
+            }),
+        }
+    }
+
+    /// Drops invite targets the chat has allowlisted as partners.
+    pub async fn unlisted_invites(&self, chat_id: i64, targets: Vec<String>) -> Vec<String> {
+        if targets.is_empty() {
+            return targets;
+        }
+        let allowed = match self.db.invite_allowlist.list(Some(chat_id)).await {
+            Ok(rows) => rows,
+            Err(err) => {
+                tracing::warn!(target: "db", error = %err, "failed to load invite allowlist");
+                return targets;
+            }
+        };
+        targets
+            .into_iter()
+            .filter(|target| !allowed.iter().any(|(_, allowed)| allowed == target))
+            .collect()
+    }
+
+    /// Admin commands are only taken by the primary bot.
     pub fn is_admin_group(&self, chat_id: i64) -> bool {
-        self.config.admin_group_id == Some(chat_id)
+        self.extra_bot.is_none() && self.config.admin_group_id == Some(chat_id)
+    }
+
+    /// Reply language for `chat_id`: its `/language` setting or `BOT_LANGUAGE`.
+    pub async fn locale(&self, chat_id: i64) -> Locale {
+        self.db
+            .chat_settings
+            .locale(chat_id, self.config.language)
+            .await
     }
 
     pub fn is_admin_user(&self, user_id: i64) -> bool {
@@ -59,6 +168,14 @@ pub enum GeneralCommand {
     Chatid,
     #[command(description = "응답 속도 측정")]
     Ping,
+    #[command(description = "답장한 메시지를 스팸으로 신고 (채팅방 관리자·신뢰 사용자)")]
+    Spam,
+    #[command(description = "삭제된 내 메시지에 이의 신청 (개인 대화)")]
+    Appeal(String),
+    #[command(description = "이 그룹의 화이트리스트 등록 요청 (그룹 관리자)")]
+    RequestWhitelist,
+    #[command(description = "현재 그룹을 화이트리스트에 추가 (봇 관리자)")]
+    WhitelistHere,
 }
 
 pub async fn is_group_member(bot: &Bot, chat_id: ChatId, user_id: UserId) -> bool {
@@ -2,19 +2,12 @@ use std::convert::TryFrom;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
-use teloxide::{
-    types::{BotCommand, User},
-    utils::command::BotCommands,
-};
+use teloxide::types::{BotCommand, Chat, Message, MessageEntity, MessageEntityKind, User};
 
-use crate::{tasks::queue::Priority, telegram::types::GeneralCommand};
+use crate::i18n::{t, Locale, Msg};
 
 static URL_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"https?://[^\s]+").expect("valid url regex"));
-static TELEGRAM_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)(https?://)?(t\.me|telegram\.me|telegram\.dog)/[A-Za-z0-9_/\-]+")
-        .expect("valid telegram regex")
-});
 
 pub fn extract_urls(text: &str, limit: usize) -> Vec<String> {
     URL_REGEX
@@ -25,26 +18,42 @@ pub fn extract_urls(text: &str, limit: usize) -> Vec<String> {
         .collect()
 }
 
-pub fn calc_priority(text: &str, is_member: bool) -> (Priority, i32) {
-    let mut score = 1;
-    if has_telegram_group_link(text) {
-        score += 20;
-    }
-    if URL_REGEX.is_match(text) {
-        score += 5;
-    }
-    if !is_member {
-        score += 10;
+/// URLs in the text plus those hidden behind `text_link` entities, where spam disguises
+/// t.me links as innocuous words. Hidden links come first.
+pub fn extract_message_urls(text: &str, entities: &[MessageEntity], limit: usize) -> Vec<String> {
+    let hidden = entities.iter().filter_map(|entity| match &entity.kind {
+        MessageEntityKind::TextLink { url } => Some(normalize_url(url.as_str())),
+        _ => None,
+    });
+    let mut urls: Vec<String> = Vec::new();
+    for url in hidden.chain(extract_urls(text, usize::MAX)) {
+        if !url.is_empty() && !urls.contains(&url) {
+            urls.push(url);
+        }
     }
-    if score >= 15 {
-        (Priority::High, score)
-    } else {
-        (Priority::Normal, score)
+    urls.truncate(limit);
+    urls
+}
+
+pub fn contains_url(text: &str) -> bool {
+    URL_REGEX.is_match(text)
+}
+
+/// The user behind a message, or `None` when it was sent on behalf of a channel or by
+/// an anonymous group admin, whose `from` is only a placeholder bot account.
+pub fn message_sender(msg: &Message) -> Option<&User> {
+    if msg.sender_chat.is_some() {
+        return None;
     }
+    msg.from.as_ref()
 }
 
-fn has_telegram_group_link(text: &str) -> bool {
-    TELEGRAM_REGEX.is_match(text)
+pub fn format_chat_display(chat: &Chat) -> String {
+    match (chat.username(), chat.title()) {
+        (Some(username), _) => format!("@{username}"),
+        (None, Some(title)) if !title.trim().is_empty() => title.trim().to_string(),
+        _ => "Unknown".to_string(),
+    }
 }
 
 pub fn format_user_display(user: &User) -> String {
@@ -69,18 +78,157 @@ pub fn user_to_i64(user: &User) -> i64 {
     i64::try_from(user.id.0).unwrap_or(i64::MAX)
 }
 
-pub fn admin_command_list() -> Vec<BotCommand> {
-    let mut commands = GeneralCommand::bot_commands();
-    commands.extend(vec![
-        BotCommand::new("whitelist_add", "그룹을 화이트리스트에 추가"),
-        BotCommand::new("whitelist_remove", "화이트리스트에서 제거"),
-        BotCommand::new("whitelist_list", "화이트리스트 목록"),
-        BotCommand::new("sync_commands", "봇 명령어 동기화"),
-    ]);
+/// Commands anyone can use, described in `locale`. Names match `GeneralCommand`.
+pub fn general_command_list(locale: Locale) -> Vec<BotCommand> {
+    [
+        ("start", Msg::CommandStart),
+        ("help", Msg::CommandHelp),
+        ("status", Msg::CommandStatus),
+        ("chatid", Msg::CommandChatid),
+        ("ping", Msg::CommandPing),
+        ("spam", Msg::CommandSpam),
+        ("appeal", Msg::CommandAppeal),
+        ("request_whitelist", Msg::CommandRequestWhitelist),
+    ]
+    .into_iter()
+    .map(|(command, description)| BotCommand::new(command, t(locale, description)))
+    .collect()
+}
+
+/// `/help` reply listing the general commands.
+pub fn general_help(locale: Locale) -> String {
+    let mut text = format!("{}\n", t(locale, Msg::CommandsHeader));
+    for command in general_command_list(locale) {
+        text.push_str(&format!("\n/{} — {}", command.command, command.description));
+    }
+    text
+}
+
+pub fn admin_command_list(locale: Locale) -> Vec<BotCommand> {
+    let mut commands = general_command_list(locale);
+    commands.extend(
+        [
+            ("whitelist_add", Msg::CommandWhitelistAdd),
+            ("whitelist_remove", Msg::CommandWhitelistRemove),
+            ("whitelist_list", Msg::CommandWhitelistList),
+            ("whitelist_note", Msg::CommandWhitelistNote),
+            ("whitelist_export", Msg::CommandWhitelistExport),
+            ("whitelist_import", Msg::CommandWhitelistImport),
+            ("restart_cron_list", Msg::CommandRestartCronList),
+            ("restart_cron_add", Msg::CommandRestartCronAdd),
+            ("restart_cron_remove", Msg::CommandRestartCronRemove),
+            ("rule_add", Msg::CommandRuleAdd),
+            ("rule_list", Msg::CommandRuleList),
+            ("rule_remove", Msg::CommandRuleRemove),
+            ("settings", Msg::CommandSettings),
+            ("strict_mode", Msg::CommandStrictMode),
+            ("criteria", Msg::CommandCriteria),
+            ("action", Msg::CommandAction),
+            ("probation", Msg::CommandProbation),
+            ("notice", Msg::CommandNotice),
+            ("language", Msg::CommandLanguage),
+            ("review", Msg::CommandReview),
+            ("blacklist_add", Msg::CommandBlacklistAdd),
+            ("blacklist_remove", Msg::CommandBlacklistRemove),
+            ("blacklist_list", Msg::CommandBlacklistList),
+            ("unban", Msg::CommandUnban),
+            ("domain_block", Msg::CommandDomainBlock),
+            ("domain_block_remove", Msg::CommandDomainBlockRemove),
+            ("domain_allow", Msg::CommandDomainAllow),
+            ("domain_allow_remove", Msg::CommandDomainAllowRemove),
+            ("domain_list", Msg::CommandDomainList),
+            ("feedback_export", Msg::CommandFeedbackExport),
+            ("purge_user", Msg::CommandPurgeUser),
+            ("logs", Msg::CommandLogs),
+            ("backup_now", Msg::CommandBackupNow),
+            ("restart", Msg::CommandRestart),
+            ("update", Msg::CommandUpdate),
+            ("version", Msg::CommandVersion),
+            ("queue", Msg::CommandQueue),
+            ("test_spam", Msg::CommandTestSpam),
+            ("invite_allow", Msg::CommandInviteAllow),
+            ("invite_allow_list", Msg::CommandInviteAllowList),
+            ("invite_allow_remove", Msg::CommandInviteAllowRemove),
+            ("reload_prompt", Msg::CommandReloadPrompt),
+            ("reload_config", Msg::CommandReloadConfig),
+            ("two_stage_stats", Msg::CommandTwoStageStats),
+            ("shadow_stats", Msg::CommandShadowStats),
+            ("usage", Msg::CommandUsage),
+            ("sync_commands", Msg::CommandSyncCommands),
+        ]
+        .into_iter()
+        .map(|(command, description)| BotCommand::new(command, t(locale, description))),
+    );
     commands
 }
 
-fn normalize_url(raw: &str) -> String {
+/// Parses a lifetime such as `90m`, `12h`, `30d` or `2w`.
+pub fn parse_ttl(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+    match unit.to_ascii_lowercase() {
+        'm' => chrono::Duration::try_minutes(amount),
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Splits the first `count` whitespace-separated tokens off `text`, returning them together
+/// with the untouched remainder (used for commands whose last argument may contain spaces).
+pub fn split_args(text: &str, count: usize) -> (Vec<&str>, &str) {
+    let mut args = Vec::with_capacity(count);
+    let mut rest = text.trim_start();
+    while args.len() < count && !rest.is_empty() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        args.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    (args, rest.trim_end())
+}
+
+/// Forum topic of a message; `None` outside forum topics.
+pub fn topic_id(msg: &Message) -> Option<i32> {
+    msg.thread_id
+        .filter(|_| msg.is_topic_message)
+        .map(|thread| thread.0 .0)
+}
+
+/// `t.me/c` link to a message for the supergroup's members; `None` for basic groups and
+/// private chats, which have no such links.
+pub fn message_link(chat_id: i64, topic_id: Option<i32>, message_id: i32) -> Option<String> {
+    const SUPERGROUP_OFFSET: i64 = -1_000_000_000_000;
+    let internal_id = SUPERGROUP_OFFSET - chat_id;
+    if internal_id <= 0 {
+        return None;
+    }
+    Some(match topic_id {
+        Some(topic) => format!("https://t.me/c/{internal_id}/{topic}/{message_id}"),
+        None => format!("https://t.me/c/{internal_id}/{message_id}"),
+    })
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+pub fn normalize_url(raw: &str) -> String {
     let mut cleaned = raw.trim_end_matches(char::is_whitespace).to_string();
     while let Some(last) = cleaned.chars().last() {
         let should_trim = match last {
@@ -119,8 +267,41 @@ mod tests {
     }
 
     #[test]
-    fn telegram_regex_matches_deeplinks() {
-        assert!(has_telegram_group_link("https://t.me/c/2485256729/1/205"));
-        assert!(has_telegram_group_link("t.me/MyChannel"));
+    fn extract_message_urls_includes_hidden_links() {
+        let text = "자세한 내용은 여기 참고하세요 https://example.com";
+        let entities = vec![MessageEntity::text_link(
+            "https://t.me/joinchat/abc".parse().unwrap(),
+            8,
+            2,
+        )];
+        let urls = extract_message_urls(text, &entities, 5);
+        assert_eq!(
+            urls,
+            vec![
+                "https://t.me/joinchat/abc".to_string(),
+                "https://example.com".to_string()
+            ]
+        );
+        assert_eq!(extract_message_urls(text, &entities, 1).len(), 1);
+    }
+
+    #[test]
+    fn message_link_uses_internal_supergroup_id() {
+        assert_eq!(
+            message_link(-1002485256729, None, 205).as_deref(),
+            Some("https://t.me/c/2485256729/205")
+        );
+        assert_eq!(
+            message_link(-1002485256729, Some(1), 205).as_deref(),
+            Some("https://t.me/c/2485256729/1/205")
+        );
+        assert_eq!(message_link(-4123456789, None, 5), None);
+    }
+
+    #[test]
+    fn split_args_keeps_remainder_intact() {
+        let (args, rest) = split_args("/rule_add -100123 regex delete  종목  추천 ", 4);
+        assert_eq!(args, vec!["/rule_add", "-100123", "regex", "delete"]);
+        assert_eq!(rest, "종목  추천");
     }
 }
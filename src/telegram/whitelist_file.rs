@@ -0,0 +1,205 @@
+//! File formats for `/whitelist_export` and `/whitelist_import`: a JSON array of
+//! entries, or CSV with a header row.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::db::whitelist::{WhitelistImport, WhitelistRow};
+
+const CSV_COLUMNS: [&str; 8] = [
+    "chat_id",
+    "chat_title",
+    "chat_type",
+    "added_at",
+    "added_by",
+    "strict_mode",
+    "expires_at",
+    "note",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Csv,
+}
+
+impl FileFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+pub fn export(rows: &[WhitelistRow], format: FileFormat) -> Result<String> {
+    match format {
+        FileFormat::Json => Ok(serde_json::to_string_pretty(rows)?),
+        FileFormat::Csv => {
+            let mut text = CSV_COLUMNS.join(",");
+            text.push('\n');
+            for row in rows {
+                let fields = [
+                    row.chat_id.to_string(),
+                    row.chat_title.clone().unwrap_or_default(),
+                    row.chat_type.clone().unwrap_or_default(),
+                    row.added_at.to_rfc3339(),
+                    row.added_by.map(|id| id.to_string()).unwrap_or_default(),
+                    row.strict_mode.to_string(),
+                    row.expires_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                    row.note.clone().unwrap_or_default(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                text.push_str(&fields.join(","));
+                text.push('\n');
+            }
+            Ok(text)
+        }
+    }
+}
+
+/// Reads either format, told apart by the first character: JSON files are an array.
+pub fn import(bytes: &[u8]) -> Result<Vec<WhitelistImport>> {
+    let text = std::str::from_utf8(bytes).context("the file is not UTF-8 text")?;
+    let text = text.trim_start_matches('\u{feff}').trim();
+    if text.starts_with('[') {
+        return serde_json::from_str(text).context("invalid JSON");
+    }
+
+    let mut records = parse_csv(text).into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let column = |name: &str| header.iter().position(|column| column.trim() == name);
+    let Some(chat_id_column) = column("chat_id") else {
+        bail!("the CSV header has no chat_id column");
+    };
+    let columns: Vec<Option<usize>> = CSV_COLUMNS.iter().map(|name| column(name)).collect();
+
+    let mut entries = Vec::new();
+    for (index, record) in records.enumerate() {
+        let line = index + 2;
+        let field = |position: usize| {
+            columns[position]
+                .and_then(|column| record.get(column))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let time = |position: usize| -> Result<Option<DateTime<Utc>>> {
+            field(position)
+                .map(|value| {
+                    DateTime::parse_from_rfc3339(value)
+                        .map(|at| at.with_timezone(&Utc))
+                        .with_context(|| format!("line {line}: invalid time {value}"))
+                })
+                .transpose()
+        };
+        let Some(chat_id) = record.get(chat_id_column).map(|value| value.trim()) else {
+            continue;
+        };
+        if chat_id.is_empty() {
+            continue;
+        }
+        entries.push(WhitelistImport {
+            chat_id: chat_id
+                .parse()
+                .with_context(|| format!("line {line}: invalid chat_id {chat_id}"))?,
+            chat_title: field(1).map(str::to_string),
+            chat_type: field(2).map(str::to_string),
+            added_at: time(3)?,
+            added_by: field(4).and_then(|value| value.parse().ok()),
+            strict_mode: field(5).is_some_and(|value| matches!(value, "true" | "1")),
+            expires_at: time(6)?,
+            note: field(7).map(str::to_string),
+        });
+    }
+    Ok(entries)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits CSV text into records, honouring quoted fields with commas, doubled quotes
+/// and line breaks.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::whitelist::WhitelistStatus;
+
+    #[test]
+    fn csv_round_trips_quoted_fields() {
+        let row = WhitelistRow {
+            chat_id: -1001234567890,
+            chat_title: Some("Acme, \"Support\"".to_string()),
+            chat_type: Some("Supergroup".to_string()),
+            added_at: Utc::now(),
+            added_by: Some(42),
+            status: WhitelistStatus::Active,
+            last_checked_at: None,
+            strict_mode: true,
+            expires_at: None,
+            note: Some("owner: @alice\ncustomer".to_string()),
+        };
+        let text = export(std::slice::from_ref(&row), FileFormat::Csv).unwrap();
+        let entries = import(text.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.chat_id, row.chat_id);
+        assert_eq!(entry.chat_title, row.chat_title);
+        assert_eq!(entry.note, row.note);
+        assert_eq!(entry.added_by, Some(42));
+        assert!(entry.strict_mode);
+        assert_eq!(entry.expires_at, None);
+    }
+
+    #[test]
+    fn json_export_is_importable() {
+        let text = r#"[{"chat_id": -100, "chat_title": "A", "status": "Active"}]"#;
+        let entries = import(text.as_bytes()).unwrap();
+        assert_eq!(entries[0].chat_id, -100);
+        assert!(!entries[0].strict_mode);
+    }
+}
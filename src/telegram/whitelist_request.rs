@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use teloxide::{
+    prelude::*,
+    types::{Chat, InlineKeyboardButton, InlineKeyboardMarkup, ParseMode, User},
+};
+
+use crate::i18n::{t, tf, Msg};
+
+use super::{
+    types::AppState,
+    utils::{escape_html, format_user_display, user_to_i64},
+};
+
+/// Another request for the same chat is refused for this long unless an admin has
+/// approved the earlier one; a rejection restarts it.
+const REQUEST_COOLDOWN: Duration = Duration::from_secs(3600);
+
+/// Chats with a whitelist request waiting in the admin group, so repeated requests do
+/// not flood it.
+#[derive(Default)]
+pub struct WhitelistRequests {
+    pending: Mutex<HashMap<i64, Instant>>,
+}
+
+impl WhitelistRequests {
+    /// `false` while an earlier request for the chat is still pending.
+    fn claim(&self, chat_id: i64) -> bool {
+        let mut pending = self.pending.lock();
+        pending.retain(|_, sent_at| sent_at.elapsed() < REQUEST_COOLDOWN);
+        if pending.contains_key(&chat_id) {
+            return false;
+        }
+        pending.insert(chat_id, Instant::now());
+        true
+    }
+
+    /// Forgets the chat's request once it has been approved or could not be sent.
+    pub fn resolve(&self, chat_id: i64) {
+        self.pending.lock().remove(&chat_id);
+    }
+
+    /// Restarts the cooldown from the rejection, so the chat cannot ask again at once.
+    pub fn reject(&self, chat_id: i64) {
+        self.pending.lock().insert(chat_id, Instant::now());
+    }
+}
+
+/// Posts a request with approve and reject buttons to the admin group and returns the
+/// reply for the requesting chat.
+pub async fn send(state: &AppState, chat: &Chat, requester: Option<&User>) -> Msg {
+    let Some(admin_group_id) = state.config.admin_group_id.filter(|id| *id != 0) else {
+        return Msg::WhitelistRequestUnavailable;
+    };
+    if !state.whitelist_requests.claim(chat.id.0) {
+        return Msg::WhitelistRequestPending;
+    }
+
+    let locale = state.config.language;
+    let text = tf(
+        locale,
+        Msg::WhitelistRequest,
+        &[
+            ("chat", &escape_html(chat.title().unwrap_or("Unknown"))),
+            ("chat_id", &chat.id.0),
+            (
+                "user",
+                &requester
+                    .map(|user| escape_html(&format_user_display(user)))
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ),
+            (
+                "user_id",
+                &requester
+                    .map(|user| user_to_i64(user).to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+        ],
+    );
+    let button = |label: Msg, action: &str| {
+        InlineKeyboardButton::callback(t(locale, label), format!("wlreq:{action}:{}", chat.id.0))
+    };
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        button(Msg::WhitelistRequestApprove, "approve"),
+        button(Msg::WhitelistRequestReject, "reject"),
+    ]]);
+    let result = state
+        .admin_bot
+        .send_message(ChatId(admin_group_id), text)
+        .parse_mode(ParseMode::Html)
+        .reply_markup(keyboard)
+        .await;
+    if let Err(err) = result {
+        tracing::error!(
+            target: "telegram",
+            error = %err,
+            chat_id = chat.id.0,
+            "failed to send whitelist request"
+        );
+        state.whitelist_requests.resolve(chat.id.0);
+        return Msg::WhitelistRequestFailed;
+    }
+    tracing::info!(
+        target: "telegram",
+        chat_id = chat.id.0,
+        requester = requester.map(user_to_i64),
+        "whitelist request sent"
+    );
+    Msg::WhitelistRequestSent
+}
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use teloxide::Bot;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// One Bot API method invocation received by [`MockBotApi`].
+#[derive(Debug, Clone)]
+pub struct ApiCall {
+    /// Lowercased, as the Bot API matches method names case-insensitively.
+    pub method: String,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct Recorder {
+    calls: Vec<ApiCall>,
+    results: HashMap<String, Value>,
+}
+
+/// Minimal HTTP server speaking the Bot API's JSON envelope on a loopback port.
+/// Every call is recorded; methods answer `true` unless a result is configured.
+pub struct MockBotApi {
+    url: reqwest::Url,
+    recorder: Arc<Mutex<Recorder>>,
+    server: JoinHandle<()>,
+}
+
+impl MockBotApi {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = reqwest::Url::parse(&format!("http://{}", listener.local_addr()?))?;
+        let recorder = Arc::new(Mutex::new(Recorder::default()));
+        recorder
+            .lock()
+            .results
+            .insert("getchatmember".to_string(), member_result());
+        let server = {
+            let recorder = recorder.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let recorder = recorder.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = serve(stream, recorder).await {
+                            tracing::warn!(target: "telegram", error = %err, "mock bot api request failed");
+                        }
+                    });
+                }
+            })
+        };
+        Ok(Self {
+            url,
+            recorder,
+            server,
+        })
+    }
+
+    /// A bot whose requests all go to this server.
+    pub fn bot(&self) -> Bot {
+        Bot::new("123456:TEST").set_api_url(self.url.clone())
+    }
+
+    /// Overrides the `result` returned for `method`.
+    pub fn respond(&self, method: &str, result: Value) {
+        self.recorder
+            .lock()
+            .results
+            .insert(method.to_ascii_lowercase(), result);
+    }
+
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.recorder.lock().calls.clone()
+    }
+
+    pub fn calls_to(&self, method: &str) -> Vec<ApiCall> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method.eq_ignore_ascii_case(method))
+            .collect()
+    }
+}
+
+impl Drop for MockBotApi {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn serve(mut stream: TcpStream, recorder: Arc<Mutex<Recorder>>) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let (header_end, content_length) = loop {
+        let read = stream.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before headers");
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            let headers = String::from_utf8_lossy(&buf[..pos]).to_ascii_lowercase();
+            let length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            break (pos + 4, length);
+        }
+    };
+    while buf.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        anyhow::ensure!(read > 0, "connection closed before body");
+        buf.extend_from_slice(&chunk[..read]);
+    }
+
+    let request_line = String::from_utf8_lossy(&buf[..header_end]);
+    let method = request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let body = serde_json::from_slice(&buf[header_end..header_end + content_length])
+        .unwrap_or(Value::Null);
+
+    let result = {
+        let mut recorder = recorder.lock();
+        let result = recorder
+            .results
+            .get(&method)
+            .cloned()
+            .unwrap_or(Value::Bool(true));
+        recorder.calls.push(ApiCall { method, body });
+        result
+    };
+    let payload = json!({ "ok": true, "result": result }).to_string();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        payload.len(),
+        payload
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn member_result() -> Value {
+    json!({
+        "status": "member",
+        "user": { "id": 1, "is_bot": false, "first_name": "Member" }
+    })
+}
@@ -0,0 +1,87 @@
+use chrono::Utc;
+use serde_json::json;
+use teloxide::types::{ChatId, Message, MessageId};
+
+use crate::domain::MessageJob;
+
+/// A plain group text message from a regular user.
+pub fn message(chat_id: i64, message_id: i32, user_id: u64, text: &str) -> Message {
+    serde_json::from_value(json!({
+        "message_id": message_id,
+        "date": Utc::now().timestamp(),
+        "chat": { "id": chat_id, "type": "supergroup", "title": "Test" },
+        "from": {
+            "id": user_id,
+            "is_bot": false,
+            "first_name": "Tester",
+            "username": format!("tester{user_id}")
+        },
+        "text": text
+    }))
+    .expect("valid message fixture")
+}
+
+/// Builds a [`MessageJob`] with neutral defaults for the fields a test doesn't set.
+pub struct MessageJobBuilder {
+    job: MessageJob,
+}
+
+impl MessageJobBuilder {
+    pub fn new(chat_id: i64, message_id: i32) -> Self {
+        Self {
+            job: MessageJob {
+                chat_id: ChatId(chat_id),
+                chat_title: Some("Test".to_string()),
+                message_id: MessageId(message_id),
+                topic_id: None,
+                from_id: None,
+                sender_chat_id: None,
+                sender_is_chat_admin: false,
+                from_display: "Unknown".to_string(),
+                username: None,
+                text: String::new(),
+                urls: Vec::new(),
+                is_group_member: true,
+                on_probation: false,
+                member_since: None,
+                account_age_days: None,
+                language: None,
+                priority_score: 0,
+                reputation: 0,
+                signals: Vec::new(),
+                timestamp: Utc::now(),
+                attempts: 0,
+            },
+        }
+    }
+
+    pub fn from(mut self, user_id: i64, display: &str) -> Self {
+        self.job.from_id = Some(user_id);
+        self.job.from_display = display.to_string();
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.job.text = text.to_string();
+        self
+    }
+
+    pub fn non_member(mut self) -> Self {
+        self.job.is_group_member = false;
+        self
+    }
+
+    pub fn joined_days_ago(mut self, days: i64) -> Self {
+        self.job.member_since = Some(self.job.timestamp - chrono::Duration::days(days));
+        self
+    }
+
+    pub fn score(mut self, score: i32) -> Self {
+        self.job.priority_score = score;
+        self
+    }
+
+    pub fn build(self) -> MessageJob {
+        self.job
+    }
+}
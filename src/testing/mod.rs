@@ -0,0 +1,616 @@
+//! In-process stand-ins for the Telegram Bot API and the classifier, plus fixtures,
+//! so the handler → queue → processor flow can be exercised without live tokens.
+//! Compiled into test builds with the `testing` feature.
+
+mod bot_api;
+mod fixtures;
+
+use std::{env, sync::Arc};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use teloxide::prelude::*;
+use tempfile::TempDir;
+
+use crate::{
+    ai::{prompt::SystemPrompt, render_batch, ClassificationProvider, PromptEntry},
+    config::{self, AppConfig, LiveConfig},
+    db::{self, whitelist::WhitelistEntry, Repositories},
+    domain::{ClassificationDecision, ClassificationMap},
+    infrastructure::shutdown::Shutdown,
+    rules::RuleEngine,
+    tasks::{processor::MessageProcessor, queue::MessageQueue, scheduler::RestartScheduleManager},
+    telegram::{
+        chat_admins::ChatAdminCache, profile::ProfileCache, types::AppState, TelegramService,
+    },
+    web_content::WebContentFetcher,
+};
+
+pub use bot_api::MockBotApi;
+pub use fixtures::{message, MessageJobBuilder};
+
+type Responder = Box<dyn Fn(&str) -> ClassificationMap + Send + Sync>;
+
+/// Classifier answering every prompt from a closure and remembering what it was asked.
+pub struct MockClassifier {
+    responder: Responder,
+    prompts: Mutex<Vec<String>>,
+}
+
+impl MockClassifier {
+    pub fn new(responder: impl Fn(&str) -> ClassificationMap + Send + Sync + 'static) -> Self {
+        Self {
+            responder: Box::new(responder),
+            prompts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Flags every message whose prompt entry contains `needle` as spam.
+    pub fn flagging(needle: &'static str) -> Self {
+        Self::new(move |prompt| {
+            prompt
+                .split("\n\n")
+                .filter(|entry| entry.contains(needle))
+                .filter_map(|entry| entry.split_once(':'))
+                .map(|(message_id, _)| {
+                    (
+                        message_id.to_string(),
+                        ClassificationDecision {
+                            spam: true,
+                            reason: Some(format!("'{needle}' 포함")),
+                            confidence: 1.0,
+                        },
+                    )
+                })
+                .collect()
+        })
+    }
+
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().clone()
+    }
+}
+
+impl ClassificationProvider for MockClassifier {
+    fn classify<'a>(
+        &'a self,
+        _system: &'a str,
+        entries: &'a [PromptEntry],
+    ) -> BoxFuture<'a, Result<ClassificationMap>> {
+        let prompt = render_batch(entries);
+        let result = (self.responder)(&prompt);
+        self.prompts.lock().push(prompt);
+        Box::pin(async move { Ok(result) })
+    }
+}
+
+/// Fully wired application state backed by a temporary database, a [`MockBotApi`]
+/// and a [`MockClassifier`].
+pub struct TestHarness {
+    pub api: MockBotApi,
+    pub classifier: Arc<MockClassifier>,
+    pub bot: Bot,
+    pub state: Arc<AppState>,
+    processor: Arc<MessageProcessor>,
+    shutdown: Shutdown,
+    _dir: TempDir,
+}
+
+impl TestHarness {
+    pub async fn new(classifier: MockClassifier) -> Result<Self> {
+        let config = Arc::new(test_config()?);
+        let dir = TempDir::new()?;
+        let repos = Repositories::new(db::init_pool(&dir.path().join("test.db")).await?);
+        let api = MockBotApi::start().await?;
+        let bot = api.bot();
+        let classifier = Arc::new(classifier);
+        let queue = Arc::new(MessageQueue::new());
+        let processor = Arc::new(MessageProcessor::new(
+            queue.clone(),
+            bot.clone(),
+            classifier.clone(),
+            Arc::new(SystemPrompt::load(config.prompt.clone())?),
+            Arc::new(WebContentFetcher::new(config.live.clone(), None)?),
+            config.clone(),
+            repos.clone(),
+        ));
+        let (shutdown, _) = Shutdown::new();
+        let restart_schedule = RestartScheduleManager::load(
+            tokio_cron_scheduler::JobScheduler::new().await?,
+            repos.restart_crons.clone(),
+            &[],
+            Arc::new(|| {}),
+        )
+        .await?;
+        let queue_snapshot = {
+            let queue = queue.clone();
+            Arc::new(move || queue.snapshot())
+        };
+        let state = Arc::new(AppState {
+            config: config.clone(),
+            db: repos.clone(),
+            queue,
+            queue_snapshot,
+            restart_schedule,
+            processor: processor.clone(),
+            rules: RuleEngine::load(repos.rules.clone(), chrono_tz::Asia::Seoul).await?,
+            profiles: Arc::new(ProfileCache::new(
+                config.live.get().heuristics.profile_cache_ttl,
+            )),
+            chat_admins: Arc::new(ChatAdminCache::new(config.chat_admins.cache_ttl)),
+            whitelist_requests: Arc::default(),
+            transcriber: None,
+            video_ocr: None,
+            vision: None,
+            media_slots: Arc::new(tokio::sync::Semaphore::new(1)),
+            two_stage: None,
+            raids: None,
+            floods: None,
+            captcha: None,
+            cas: None,
+            url_reputation: None,
+            admin_bot: bot.clone(),
+            extra_bot: None,
+        });
+        Ok(Self {
+            api,
+            classifier,
+            bot,
+            state,
+            processor,
+            shutdown,
+            _dir: dir,
+        })
+    }
+
+    pub async fn allow_chat(&self, chat_id: i64) -> Result<()> {
+        self.state
+            .db
+            .whitelist
+            .add_or_replace(WhitelistEntry {
+                chat_id,
+                chat_title: Some("Test".to_string()),
+                chat_type: Some("supergroup".to_string()),
+                added_by: None,
+                expires_at: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Runs an incoming message through the same handler the dispatcher uses.
+    pub async fn receive(&self, msg: Message) -> Result<()> {
+        TelegramService::on_plain_message(self.bot.clone(), msg, self.state.clone()).await?;
+        Ok(())
+    }
+
+    /// Classifies and acts on everything currently queued.
+    pub async fn process(&self) -> Result<()> {
+        self.processor
+            .process_pending(&mut self.shutdown.subscribe())
+            .await
+    }
+}
+
+/// Configuration built from defaults only; the process environment is ignored apart
+/// from the bot token this sets.
+fn test_config() -> Result<AppConfig> {
+    env::set_var("TELEGRAM_BOT_TOKEN", "123456:TEST");
+    let mut config = config::load_config()?;
+    config.admin_group_id = None;
+    config.admin_user_id = None;
+    config.honeypot_chat_ids.clear();
+    config.raid = None;
+    let mut live = (*config.live.get()).clone();
+    live.allowed_chat_ids.clear();
+    live.heuristics.prefilter = None;
+    config.live = Arc::new(LiveConfig::new(live));
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{env::StrikeConfig, EnforcementAction},
+        db::{
+            appeals::AppealSource, feedback::NewFeedback, shadow_results::ShadowResult,
+            spam_log::SpamLogEntry,
+        },
+        tasks::queue::Priority,
+    };
+
+    const CHAT_ID: i64 = -1001234567890;
+
+    #[tokio::test]
+    async fn flagged_message_is_deleted_and_logged() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        harness.allow_chat(CHAT_ID).await?;
+
+        harness
+            .receive(message(CHAT_ID, 10, 555, "무료 리딩방 입장하세요"))
+            .await?;
+        harness
+            .receive(message(CHAT_ID, 11, 556, "오늘 회의 몇 시인가요?"))
+            .await?;
+        harness.process().await?;
+
+        let deleted = harness.api.calls_to("deleteMessage");
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].body["message_id"], 10);
+        let prompts = harness.classifier.prompts();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("오늘 회의"));
+        let logged = harness.state.db.spam_log.list_after(0, 10).await?;
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].user_id, Some(555));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn queued_job_reaches_the_prompt() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        harness.api.respond(
+            "getChatMember",
+            serde_json::json!({
+                "status": "left",
+                "user": { "id": 557, "is_bot": false, "first_name": "Left" }
+            }),
+        );
+        harness.allow_chat(CHAT_ID).await?;
+
+        harness
+            .receive(message(CHAT_ID, 12, 557, "안녕하세요 처음 왔어요"))
+            .await?;
+        harness.state.queue.push(
+            Priority::High,
+            MessageJobBuilder::new(CHAT_ID, 13)
+                .from(558, "Queued")
+                .text("수익 보장")
+                .score(42)
+                .non_member()
+                .build(),
+        );
+        harness.process().await?;
+
+        let prompts = harness.classifier.prompts();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("[Queued | - | 비멤버] [위험 점수: 42] 수익 보장"));
+        assert!(prompts[0].contains("tester557 | 비멤버]"));
+        assert!(harness.api.calls_to("deleteMessage").is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn low_confidence_verdict_is_not_deleted() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::new(|prompt| {
+            let (message_id, _) = prompt.split_once(':').unwrap_or_default();
+            ClassificationMap::from([(
+                message_id.to_string(),
+                ClassificationDecision {
+                    spam: true,
+                    reason: Some("애매함".to_string()),
+                    confidence: 0.4,
+                },
+            )])
+        }))
+        .await?;
+        harness.allow_chat(CHAT_ID).await?;
+
+        harness
+            .receive(message(CHAT_ID, 14, 559, "좋은 정보 공유합니다"))
+            .await?;
+        harness.process().await?;
+
+        assert_eq!(harness.classifier.prompts().len(), 1);
+        assert!(harness.api.calls_to("deleteMessage").is_empty());
+        assert!(harness
+            .state
+            .db
+            .spam_log
+            .list_after(0, 10)
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn new_member_on_probation_is_deleted_at_lower_confidence() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::new(|prompt| {
+            prompt
+                .split("\n\n")
+                .filter_map(|entry| entry.split_once(':'))
+                .map(|(message_id, _)| {
+                    (
+                        message_id.to_string(),
+                        ClassificationDecision {
+                            spam: true,
+                            reason: Some("홍보 의심".to_string()),
+                            confidence: 0.6,
+                        },
+                    )
+                })
+                .collect()
+        }))
+        .await?;
+        harness.allow_chat(CHAT_ID).await?;
+        harness
+            .state
+            .db
+            .member_joins
+            .record_join(CHAT_ID, 560, chrono::Utc::now())
+            .await?;
+
+        harness
+            .receive(message(CHAT_ID, 15, 560, "채널 구경 오세요"))
+            .await?;
+        harness
+            .receive(message(CHAT_ID, 16, 561, "채널 구경 오세요"))
+            .await?;
+        harness.process().await?;
+
+        assert!(harness.classifier.prompts()[0].contains("최근 참여한 신규 멤버"));
+        let logged = harness.state.db.spam_log.list_after(0, 10).await?;
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].message_id, 15);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn examples_prefer_the_batch_chats() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let examples = &harness.state.db.examples;
+        examples
+            .add(CHAT_ID, "무료 리딩방", true, Some("리딩방 홍보"), Some(1))
+            .await?;
+        examples
+            .add(-100777, "코인 에어드랍", true, Some("코인 홍보"), Some(2))
+            .await?;
+        examples
+            .add(-100777, "무료 리딩방", false, None, Some(1))
+            .await?;
+
+        let spam = examples.recent(&[-100777], true, 5).await?;
+        assert_eq!(spam.len(), 1);
+        assert_eq!(spam[0].text, "코인 에어드랍");
+        let ham = examples.recent(&[CHAT_ID], false, 5).await?;
+        assert_eq!(ham.len(), 1);
+        assert_eq!(ham[0].text, "무료 리딩방");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn chat_with_criteria_is_classified_separately() -> Result<()> {
+        const OTHER_CHAT_ID: i64 = -1009876543210;
+        let harness = TestHarness::new(MockClassifier::flagging("구인")).await?;
+        harness.allow_chat(CHAT_ID).await?;
+        harness.allow_chat(OTHER_CHAT_ID).await?;
+        harness
+            .state
+            .db
+            .chat_settings
+            .set_criteria(CHAT_ID, Some("구인·알바 모집 글은 스팸"))
+            .await?;
+
+        harness
+            .receive(message(CHAT_ID, 20, 560, "주말 구인합니다"))
+            .await?;
+        harness
+            .receive(message(OTHER_CHAT_ID, 21, 561, "오늘 회의 몇 시인가요?"))
+            .await?;
+        harness.process().await?;
+
+        let prompts = harness.classifier.prompts();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts
+            .iter()
+            .all(|prompt| prompt.split("\n\n").count() == 1));
+        assert_eq!(harness.api.calls_to("deleteMessage").len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shadow_results_summarize_disagreements() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let result = |message_id, live_spam, shadow_spam| ShadowResult {
+            chat_id: CHAT_ID,
+            message_id,
+            text: format!("메시지 {message_id}"),
+            live_spam,
+            shadow_spam,
+            shadow_reason: None,
+        };
+        let results = &harness.state.db.shadow_results;
+        results
+            .record(&[
+                result(1, true, true),
+                result(2, false, false),
+                result(3, false, true),
+                result(4, true, false),
+            ])
+            .await?;
+
+        let summary = results
+            .summary_since(chrono::Utc::now() - chrono::Duration::hours(1))
+            .await?;
+        assert_eq!(
+            (
+                summary.total,
+                summary.agreed,
+                summary.shadow_only,
+                summary.live_only
+            ),
+            (4, 2, 1, 1)
+        );
+        let disagreements = results.recent_disagreements(5).await?;
+        assert_eq!(disagreements.len(), 2);
+        assert_eq!(disagreements[0].message_id, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn strikes_escalate_and_expire() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let strikes = &harness.state.db.strikes;
+        let now = chrono::Utc::now();
+        let config = StrikeConfig {
+            restrict_at: 2,
+            restrict_hours: 24,
+            ban_at: 3,
+            ttl: None,
+        };
+
+        let mut actions = Vec::new();
+        for _ in 0..3 {
+            let count = strikes.add(CHAT_ID, 42, now, None).await?;
+            actions.push(config.action_for(count));
+        }
+        assert_eq!(
+            actions,
+            vec![
+                EnforcementAction::Delete,
+                EnforcementAction::Restrict { hours: 24 },
+                EnforcementAction::Ban,
+            ]
+        );
+        assert_eq!(strikes.add(CHAT_ID + 1, 42, now, None).await?, 1);
+
+        let later = now + chrono::Duration::days(31);
+        let expired_before = later - chrono::Duration::days(30);
+        assert_eq!(
+            strikes
+                .add(CHAT_ID, 42, later, Some(expired_before))
+                .await?,
+            1
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn appeal_is_opened_once_and_decided() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let now = chrono::Utc::now();
+        let log_id = harness
+            .state
+            .db
+            .spam_log
+            .record(SpamLogEntry {
+                chat_id: CHAT_ID,
+                chat_title: None,
+                message_id: 7,
+                user_id: Some(42),
+                user_display: "tester".to_string(),
+                text: "오늘 모임 장소 공유합니다".to_string(),
+                reason: "광고".to_string(),
+                priority_score: 0,
+                sent_at: now,
+                deleted_at: now,
+            })
+            .await?;
+        let appeals = &harness.state.db.appeals;
+
+        let id = appeals
+            .open(log_id, AppealSource::Dm, Some("스팸 아닙니다"))
+            .await?
+            .expect("first appeal is opened");
+        assert!(appeals
+            .open(log_id, AppealSource::Restore, None)
+            .await?
+            .is_none());
+        let pending = appeals.pending(10).await?;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].user_id, Some(42));
+
+        let decided = appeals.decide(id, true).await?.expect("pending appeal");
+        assert_eq!(decided.text, "오늘 모임 장소 공유합니다");
+        assert!(appeals.decide(id, false).await?.is_none());
+        assert!(appeals.pending(10).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn review_decision_replaces_restore_feedback() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let feedback = &harness.state.db.feedback;
+        let verdict = |spam, decided_by, appeal_id| NewFeedback {
+            chat_id: CHAT_ID,
+            user_id: Some(42),
+            text: "오늘 모임 장소 공유합니다",
+            spam,
+            model_reason: Some("광고"),
+            confidence: Some(0.7),
+            source: AppealSource::Restore.as_str(),
+            decided_by: Some(decided_by),
+            appeal_id,
+        };
+
+        feedback.record(&verdict(false, 1, Some(5))).await?;
+        feedback.record(&verdict(false, 1, None)).await?;
+        feedback.record(&verdict(false, 1, None)).await?;
+        feedback.record(&verdict(true, 2, Some(5))).await?;
+
+        let rows = feedback.recent(None, 10).await?;
+        assert_eq!(rows.len(), 3);
+        let decided: Vec<_> = rows.iter().filter(|row| row.spam).collect();
+        assert_eq!(decided.len(), 1);
+        assert_eq!(decided[0].decided_by, Some(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stale_cas_results_are_not_served() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+        let cache = &harness.state.db.cas_cache;
+        let now = chrono::Utc::now();
+        let fresh_after = now - chrono::Duration::hours(24);
+
+        cache
+            .put(42, true, now - chrono::Duration::hours(30))
+            .await?;
+        assert_eq!(cache.get(42, fresh_after).await?, None);
+        cache.put(42, false, now).await?;
+        assert_eq!(cache.get(42, fresh_after).await?, Some(false));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unlisted_chat_is_ignored() -> Result<()> {
+        let harness = TestHarness::new(MockClassifier::flagging("리딩방")).await?;
+
+        harness
+            .receive(message(CHAT_ID, 10, 555, "무료 리딩방 입장하세요"))
+            .await?;
+        harness.process().await?;
+
+        assert!(harness.classifier.prompts().is_empty());
+        assert!(harness.api.calls().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn removed_default_restart_crons_stay_removed() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let repos = Repositories::new(db::init_pool(&dir.path().join("crons.db")).await?);
+        let defaults = vec!["0 0 0 * * *".to_string(), "0 0 12 * * *".to_string()];
+        let load = || async {
+            RestartScheduleManager::load(
+                tokio_cron_scheduler::JobScheduler::new().await?,
+                repos.restart_crons.clone(),
+                &defaults,
+                Arc::new(|| {}),
+            )
+            .await
+        };
+
+        let schedule = load().await?;
+        assert_eq!(schedule.list().await, defaults);
+        assert_eq!(schedule.remove(1).await?.as_deref(), Some("0 0 0 * * *"));
+        assert_eq!(schedule.remove(1).await?.as_deref(), Some("0 0 12 * * *"));
+
+        let reloaded = load().await?;
+        assert!(reloaded.list().await.is_empty());
+        assert!(repos.restart_crons.list().await?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{config::UrlReputationConfig, db::url_reputation_cache::UrlReputationCacheRepository};
+
+const SAFE_BROWSING_URL: &str = "https://safebrowsing.googleapis.com/v4/threatMatches:find";
+const URLHAUS_URL: &str = "https://urlhaus-api.abuse.ch/v1/url/";
+/// How long a feed is skipped after a failed lookup, so an outage doesn't add a timeout
+/// to every message with a link.
+const FAILURE_BACKOFF: Duration = Duration::from_secs(60);
+const SAFE_BROWSING_THREATS: [&str; 4] = [
+    "MALWARE",
+    "SOCIAL_ENGINEERING",
+    "UNWANTED_SOFTWARE",
+    "POTENTIALLY_HARMFUL_APPLICATION",
+];
+
+/// A linked URL listed by a threat feed.
+#[derive(Debug, Clone)]
+pub struct UrlThreat {
+    pub url: String,
+    /// Feed and threat type, e.g. "Safe Browsing: SOCIAL_ENGINEERING".
+    pub threat: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SafeBrowsingResponse {
+    #[serde(default)]
+    matches: Vec<SafeBrowsingMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeBrowsingMatch {
+    threat_type: String,
+    threat: SafeBrowsingEntry,
+}
+
+#[derive(Debug, Deserialize)]
+struct SafeBrowsingEntry {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UrlhausResponse {
+    query_status: String,
+    threat: Option<String>,
+}
+
+/// Checks linked URLs against Google Safe Browsing and abuse.ch URLhaus, backed by a
+/// SQLite cache.
+pub struct UrlReputationClient {
+    http: Client,
+    config: UrlReputationConfig,
+    cache: Arc<UrlReputationCacheRepository>,
+    safe_browsing_down_until: Mutex<Option<Instant>>,
+    urlhaus_down_until: Mutex<Option<Instant>>,
+}
+
+impl UrlReputationClient {
+    pub fn new(
+        http: Client,
+        config: UrlReputationConfig,
+        cache: Arc<UrlReputationCacheRepository>,
+    ) -> Self {
+        Self {
+            http,
+            config,
+            cache,
+            safe_browsing_down_until: Mutex::new(None),
+            urlhaus_down_until: Mutex::new(None),
+        }
+    }
+
+    /// The first of `urls` a feed lists; lookup failures count as clean and are not
+    /// cached, and a failing feed is skipped for [`FAILURE_BACKOFF`] while the other
+    /// still answers.
+    pub async fn first_threat(&self, urls: &[String]) -> Option<UrlThreat> {
+        let now = Utc::now();
+        let fresh_after =
+            now - chrono::Duration::from_std(self.config.cache_ttl).unwrap_or_default();
+        let mut unchecked = Vec::new();
+        for url in urls {
+            match self.cache.get(url, fresh_after).await {
+                Ok(Some(Some(threat))) => {
+                    return Some(UrlThreat {
+                        url: url.clone(),
+                        threat,
+                    })
+                }
+                Ok(Some(None)) => {}
+                Ok(None) => unchecked.push(url.clone()),
+                Err(err) => {
+                    tracing::warn!(target: "db", error = %err, "failed to read URL reputation cache");
+                    unchecked.push(url.clone());
+                }
+            }
+        }
+        if unchecked.is_empty() {
+            return None;
+        }
+
+        let mut threats = Vec::new();
+        // Clean results are cached only when every configured feed answered.
+        let mut complete = true;
+        if let Some(key) = &self.config.safe_browsing_key {
+            if backing_off(&self.safe_browsing_down_until) {
+                complete = false;
+            } else {
+                match self.safe_browsing(key, &unchecked).await {
+                    Ok(found) => threats.extend(found),
+                    Err(err) => {
+                        tracing::warn!(target: "web", error = %err, "Safe Browsing lookup failed");
+                        back_off(&self.safe_browsing_down_until);
+                        complete = false;
+                    }
+                }
+            }
+        }
+        if let Some(key) = &self.config.urlhaus_key {
+            let available = !backing_off(&self.urlhaus_down_until);
+            complete &= available;
+            for url in unchecked.iter().filter(|_| available) {
+                if threats.iter().any(|threat: &UrlThreat| &threat.url == url) {
+                    continue;
+                }
+                match self.urlhaus(key, url).await {
+                    Ok(Some(threat)) => threats.push(UrlThreat {
+                        url: url.clone(),
+                        threat,
+                    }),
+                    Ok(None) => {}
+                    Err(err) => {
+                        tracing::warn!(target: "web", error = %err, url = %url, "URLhaus lookup failed");
+                        back_off(&self.urlhaus_down_until);
+                        complete = false;
+                        break;
+                    }
+                }
+            }
+        }
+
+        for url in &unchecked {
+            let threat = threats.iter().find(|threat| &threat.url == url);
+            if threat.is_none() && !complete {
+                continue;
+            }
+            if let Err(err) = self
+                .cache
+                .put(url, threat.map(|threat| threat.threat.as_str()), now)
+                .await
+            {
+                tracing::warn!(target: "db", error = %err, "failed to cache URL reputation");
+            }
+        }
+        let threat = threats.into_iter().next()?;
+        tracing::info!(target: "web", url = %threat.url, threat = %threat.threat, "linked URL is on a threat feed");
+        Some(threat)
+    }
+
+    async fn safe_browsing(&self, key: &str, urls: &[String]) -> Result<Vec<UrlThreat>> {
+        let entries: Vec<_> = urls.iter().map(|url| json!({ "url": url })).collect();
+        let body = json!({
+            "client": {
+                "clientId": env!("CARGO_PKG_NAME"),
+                "clientVersion": env!("CARGO_PKG_VERSION"),
+            },
+            "threatInfo": {
+                "threatTypes": SAFE_BROWSING_THREATS,
+                "platformTypes": ["ANY_PLATFORM"],
+                "threatEntryTypes": ["URL"],
+                "threatEntries": entries,
+            },
+        });
+        let response: SafeBrowsingResponse = self
+            .http
+            .post(SAFE_BROWSING_URL)
+            .query(&[("key", key)])
+            .json(&body)
+            .timeout(self.config.timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            // The request URL carries the API key.
+            .map_err(reqwest::Error::without_url)?
+            .json()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("failed to parse Safe Browsing response")?;
+        Ok(response
+            .matches
+            .into_iter()
+            .map(|found| UrlThreat {
+                url: found.threat.url,
+                threat: format!("Safe Browsing: {}", found.threat_type),
+            })
+            .collect())
+    }
+
+    async fn urlhaus(&self, key: &str, url: &str) -> Result<Option<String>> {
+        let response: UrlhausResponse = self
+            .http
+            .post(URLHAUS_URL)
+            .header("Auth-Key", key)
+            .form(&[("url", url)])
+            .timeout(self.config.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse URLhaus response")?;
+        Ok((response.query_status == "ok").then(|| {
+            format!(
+                "URLhaus: {}",
+                response.threat.as_deref().unwrap_or("malware_download")
+            )
+        }))
+    }
+}
+
+fn backing_off(down_until: &Mutex<Option<Instant>>) -> bool {
+    down_until
+        .lock()
+        .is_some_and(|until| Instant::now() < until)
+}
+
+fn back_off(down_until: &Mutex<Option<Instant>>) {
+    *down_until.lock() = Some(Instant::now() + FAILURE_BACKOFF);
+}
@@ -1,31 +1,154 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
 use anyhow::{Context, Result};
+use dom_query::Document;
 use dom_smoothie::{Config as ReadabilityConfig, Readability, TextMode};
-use reqwest::Client;
-use tracing::warn;
-use url::Url;
+use futures::future::join_all;
+use parking_lot::Mutex;
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect, Client, Response,
+};
+use serde_json::json;
+use tokio::{
+    sync::Semaphore,
+    time::{sleep_until, Instant},
+};
+use tracing::{info, warn};
+use url::{Host, Url};
+
+use crate::{
+    config::{LiveConfig, PageRenderConfig, WebContentConfig},
+    domain::WebContent,
+    heuristics::known_spam::url_domain,
+};
+
+/// Redirects followed before a fetch is given up.
+const MAX_REDIRECTS: usize = 5;
 
-use crate::{config::WebContentConfig, domain::WebContent};
+/// Hosts serving Telegram's public previews of channels, groups and invite links.
+const TELEGRAM_HOSTS: [&str; 3] = ["t.me", "telegram.me", "telegram.dog"];
 
+/// Request URLs the renderer must refuse while loading a page: localhost, private and
+/// link-local IPv4 literals and every IPv6 literal. Chrome normalizes numeric hosts to
+/// dotted form before matching.
+const RENDER_REJECT_PATTERNS: [&str; 4] = [
+    r"^[a-z]+://([^/:]+\.)?localhost\.?(:\d+)?(/|$)",
+    r"^[a-z]+://(0|10|127)\.\d+\.\d+\.\d+(:\d+)?(/|$)",
+    r"^[a-z]+://(169\.254|192\.168|172\.(1[6-9]|2\d|3[01])|100\.(6[4-9]|[7-9]\d|1[01]\d|12[0-7]))\.\d+\.\d+(:\d+)?(/|$)",
+    r"^[a-z]+://\[",
+];
+
+/// Fetches linked pages for the classifier. Links are posted by strangers, so only
+/// public addresses are ever contacted, redirects included, and only HTML bodies are read,
+/// up to `WEBPAGE_MAX_BODY_BYTES`.
 pub struct WebContentFetcher {
     client: Client,
-    config: WebContentConfig,
+    /// Read on every fetch so reloaded limits apply right away.
+    live: Arc<LiveConfig>,
+    /// Caps fetches in flight at `WEBPAGE_FETCH_CONCURRENCY`, sized at startup.
+    permits: Semaphore,
+    /// Earliest time the next request to each domain may start.
+    domain_slots: Mutex<HashMap<String, Instant>>,
+    render: Option<PageRenderConfig>,
+    /// Plain client for the render endpoint, which runs on an egress-isolated network.
+    render_client: Client,
 }
 
 impl WebContentFetcher {
-    pub fn new(client: Client, config: WebContentConfig) -> Result<Self> {
-        Ok(Self { client, config })
+    pub fn new(live: Arc<LiveConfig>, render: Option<PageRenderConfig>) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(format!("fuckyou-spam-rust/{}", env!("CARGO_PKG_VERSION")))
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(redirect::Policy::custom(|attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if !is_public_url(attempt.url()) {
+                    attempt.error("redirect to a private address")
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .context("failed to build web content client")?;
+        let permits = Semaphore::new(live.get().web.fetch_concurrency.max(1));
+        Ok(Self {
+            client,
+            live,
+            permits,
+            domain_slots: Mutex::new(HashMap::new()),
+            render,
+            render_client: Client::new(),
+        })
+    }
+
+    /// Fetches `urls` concurrently, each distinct URL once, and returns the pages that
+    /// yielded content. Requests to one domain are spaced `WEBPAGE_DOMAIN_INTERVAL_MS`
+    /// apart; failed fetches are logged and left out.
+    pub async fn fetch_all<'a>(
+        &self,
+        urls: impl IntoIterator<Item = &'a str>,
+    ) -> HashMap<String, WebContent> {
+        let mut unique: Vec<&str> = Vec::new();
+        for url in urls {
+            if !unique.contains(&url) {
+                unique.push(url);
+            }
+        }
+        join_all(unique.into_iter().map(|url| async move {
+            let content = self.fetch_limited(url).await?;
+            Some((url.to_string(), content))
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
     }
 
-    pub async fn fetch(&self, raw_url: &str) -> Result<Option<WebContent>> {
+    async fn fetch_limited(&self, url: &str) -> Option<WebContent> {
+        if let Some(domain) = url_domain(url) {
+            sleep_until(self.reserve_slot(domain)).await;
+        }
+        let _permit = self.permits.acquire().await.ok()?;
+        match self.fetch(url).await {
+            Ok(content) => content,
+            Err(err) => {
+                warn!(target: "web", url = %url, error = %err, "failed to fetch linked page");
+                None
+            }
+        }
+    }
+
+    /// Books the domain's next request slot and returns when it starts.
+    fn reserve_slot(&self, domain: String) -> Instant {
+        let interval = self.live.get().web.domain_interval;
+        let now = Instant::now();
+        let mut slots = self.domain_slots.lock();
+        slots.retain(|_, next| *next > now);
+        let slot = slots.get(&domain).copied().unwrap_or(now).max(now);
+        slots.insert(domain, slot + interval);
+        slot
+    }
+
+    async fn fetch(&self, raw_url: &str) -> Result<Option<WebContent>> {
+        let config = self.live.get().web.clone();
         let url = match Url::parse(raw_url) {
             Ok(url) if matches!(url.scheme(), "http" | "https") => url,
             _ => return Ok(None),
         };
+        if !is_public_url(&url) {
+            warn!(target: "web", url = %url, "refusing to fetch a private address");
+            return Ok(None);
+        }
 
-        let response = self
+        let mut response = self
             .client
             .get(url.clone())
-            .timeout(self.config.fetch_timeout)
+            .timeout(config.fetch_timeout)
             .send()
             .await
             .with_context(|| format!("failed to fetch {}", url))?;
@@ -33,47 +156,272 @@ impl WebContentFetcher {
         if !response.status().is_success() {
             return Ok(None);
         }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        if !config.content_types.contains(&content_type) {
+            warn!(target: "web", url = %url, content_type = %content_type, "skipping page with unsupported content type");
+            return Ok(None);
+        }
+        if let Some(length) = response
+            .content_length()
+            .filter(|length| *length > config.max_body_bytes as u64)
+        {
+            warn!(target: "web", url = %url, length, limit = config.max_body_bytes, "skipping page larger than the body limit");
+            return Ok(None);
+        }
 
-        let body = response.text().await?;
-        let smoothie_cfg = ReadabilityConfig {
-            text_mode: TextMode::Formatted,
-            ..Default::default()
-        };
+        let body = read_body(&mut response, config.max_body_bytes, &url).await?;
+        if url
+            .host_str()
+            .is_some_and(|host| TELEGRAM_HOSTS.contains(&host.trim_start_matches("www.")))
+        {
+            return Ok(telegram_preview(&body, config.content_max_length));
+        }
+        if let Some(content) = extract_page(&body, &url, config.content_max_length) {
+            return Ok(Some(content));
+        }
+        match &self.render {
+            Some(render) => Ok(self.render(render, &url, &config).await),
+            None => Ok(None),
+        }
+    }
 
-        let mut readability =
-            match Readability::new(body.as_str(), Some(url.as_str()), Some(smoothie_cfg)) {
-                Ok(reader) => reader,
-                Err(err) => {
-                    warn!(target: "web", error = %err, url = %url, "Readability init failed");
-                    return Ok(None);
+    /// Loads `url` in the headless browser behind `PAGE_RENDER_ENDPOINT`, for script-only
+    /// landing pages whose static HTML holds nothing to extract. Subrequests to private
+    /// address literals are rejected through [`RENDER_REJECT_PATTERNS`]; hostnames that
+    /// resolve inward are left to the renderer's network isolation. Failures are logged
+    /// and count as no content.
+    async fn render(
+        &self,
+        render: &PageRenderConfig,
+        url: &Url,
+        config: &WebContentConfig,
+    ) -> Option<WebContent> {
+        let mut request = self
+            .render_client
+            .post(&render.endpoint)
+            .json(&json!({
+                "url": url.as_str(),
+                "rejectRequestPattern": RENDER_REJECT_PATTERNS,
+            }))
+            .timeout(render.timeout);
+        if let Some(token) = &render.token {
+            request = request.bearer_auth(token);
+        }
+        let rendered = async {
+            let mut response = request.send().await?.error_for_status()?;
+            read_body(&mut response, config.max_body_bytes, url).await
+        }
+        .await;
+        match rendered {
+            Ok(html) => {
+                let content = extract_page(&html, url, config.content_max_length);
+                if content.is_some() {
+                    info!(target: "web", url = %url, "extracted content from rendered page");
                 }
-            };
-
-        let article = match readability.parse() {
-            Ok(article) => article,
+                content
+            }
             Err(err) => {
-                warn!(target: "web", error = %err, url = %url, "Readability parse failed");
-                return Ok(None);
+                warn!(target: "web", url = %url, error = %err, "page render failed");
+                None
             }
-        };
+        }
+    }
+}
 
-        let title = clean_str(Some(article.title));
-        let site_name = clean_str(article.site_name);
+/// Reads at most `limit` bytes of the body, dropping the rest.
+async fn read_body(response: &mut Response, limit: usize, url: &Url) -> Result<String> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let room = limit - bytes.len();
+        bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        if chunk.len() >= room {
+            warn!(target: "web", url = %url, limit, "page body truncated");
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
 
-        let mut text = article.text_content.to_string();
-        text = text.trim().to_string();
-        if text.len() > self.config.content_max_length {
-            text.truncate(self.config.content_max_length);
+/// The page's article via Readability, or its meta tags when there is none.
+fn extract_page(html: &str, url: &Url, max_length: usize) -> Option<WebContent> {
+    let smoothie_cfg = ReadabilityConfig {
+        text_mode: TextMode::Formatted,
+        ..Default::default()
+    };
+    let article = Readability::new(html, Some(url.as_str()), Some(smoothie_cfg))
+        .and_then(|mut readability| readability.parse());
+    let content = match article {
+        Ok(article) => {
+            let mut text = article.text_content.trim().to_string();
+            truncate_text(&mut text, max_length);
+            Some(WebContent {
+                title: clean_str(Some(article.title)),
+                site_name: clean_str(article.site_name),
+                content: if text.is_empty() { None } else { Some(text) },
+                members: None,
+            })
+            .filter(|content| content.title.is_some() || content.content.is_some())
         }
+        Err(err) => {
+            warn!(target: "web", error = %err, url = %url, "Readability failed; falling back to meta tags");
+            None
+        }
+    };
+    content.or_else(|| meta_preview(html, max_length))
+}
 
-        Ok(Some(WebContent {
-            title,
-            site_name,
-            content: if text.is_empty() { None } else { Some(text) },
-        }))
+/// `<title>` and OpenGraph tags, for landing pages Readability finds no article in.
+fn meta_preview(html: &str, max_length: usize) -> Option<WebContent> {
+    let doc = Document::from(html);
+    let title = clean_str(meta_content(&doc, "og:title"))
+        .or_else(|| clean_str(Some(doc.select("head title").text().to_string())));
+    let mut description = clean_str(meta_content(&doc, "og:description"))
+        .or_else(|| clean_str(meta_content(&doc, "description")));
+    if let Some(text) = description.as_mut() {
+        truncate_text(text, max_length);
+    }
+    if title.is_none() && description.is_none() {
+        return None;
+    }
+    Some(WebContent {
+        title,
+        site_name: clean_str(meta_content(&doc, "og:site_name")),
+        content: description,
+        members: None,
+    })
+}
+
+/// A `<meta>` tag's content, declared through either `property` or `name`.
+fn meta_content(doc: &Document, key: &str) -> Option<String> {
+    doc.select(&format!(r#"meta[property="{key}"], meta[name="{key}"]"#))
+        .attr("content")
+        .map(|value| value.to_string())
+}
+
+fn truncate_text(text: &mut String, max_length: usize) {
+    if text.len() > max_length {
+        text.truncate(text.floor_char_boundary(max_length));
     }
 }
 
+/// Title, description and member count from a t.me preview page. Readability finds no
+/// article there, yet the channel name and blurb are what give stock-pump and
+/// investment-group spam away. `None` for usernames that do not exist.
+fn telegram_preview(html: &str, max_length: usize) -> Option<WebContent> {
+    let doc = Document::from(html);
+    let title =
+        clean_str(Some(doc.select(".tgme_page_title").text().to_string())).or_else(|| {
+            clean_str(meta_content(&doc, "og:title"))
+                .filter(|title| !title.starts_with("Telegram: Contact @"))
+        })?;
+    let mut description = clean_str(Some(
+        doc.select(".tgme_page_description").text().to_string(),
+    ))
+    .or_else(|| clean_str(meta_content(&doc, "og:description")));
+    if let Some(text) = description.as_mut() {
+        truncate_text(text, max_length);
+    }
+    Some(WebContent {
+        title: Some(title),
+        site_name: Some("Telegram".to_string()),
+        content: description,
+        members: clean_str(Some(doc.select(".tgme_page_extra").text().to_string())),
+    })
+}
+
+/// Resolves hostnames to their public addresses only, so a link whose name points into
+/// the bot's own network is never connected to.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} has no public address").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// `false` for URLs whose host is a literal non-public IP, which skip DNS resolution.
+fn is_public_url(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        Some(Host::Domain(_)) => true,
+        None => false,
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match embedded_v4(ip) {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+/// The IPv4 address an IPv6 one routes to: IPv4-mapped `::ffff:a.b.c.d`,
+/// IPv4-compatible `::a.b.c.d`, NAT64 `64:ff9b::/96` and 6to4 `2002::/16`.
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let octets = ip.octets();
+    let segments = ip.segments();
+    if let Some(v4) = ip.to_ipv4() {
+        return Some(v4);
+    }
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        return Some(Ipv4Addr::new(
+            octets[12], octets[13], octets[14], octets[15],
+        ));
+    }
+    if segments[0] == 0x2002 {
+        return Some(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5]));
+    }
+    None
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8, carrier-grade NAT 100.64.0.0/10, IETF 192.0.0.0/24, benchmarking
+        // 198.18.0.0/15 and reserved 240.0.0.0/4.
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && ip.octets()[2] == 0)
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        // Documentation 2001:db8::/32.
+        || (ip.segments()[0] == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
 fn clean_str(value: Option<String>) -> Option<String> {
     value.and_then(|v| {
         let trimmed = v.trim().to_string();
@@ -84,3 +432,105 @@ fn clean_str(value: Option<String>) -> Option<String> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renderer_rejects_private_subrequests() {
+        let patterns: Vec<_> = RENDER_REJECT_PATTERNS
+            .iter()
+            .map(|pattern| regex::Regex::new(pattern).unwrap())
+            .collect();
+        let rejected = |url: &str| patterns.iter().any(|pattern| pattern.is_match(url));
+        for url in [
+            "http://localhost:8080/admin",
+            "http://api.localhost/",
+            "http://127.0.0.1/",
+            "http://10.0.0.5:9000/metrics",
+            "http://172.20.1.1/",
+            "http://192.168.0.1",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.64.0.1/",
+            "http://[::1]/",
+        ] {
+            assert!(rejected(url), "{url}");
+        }
+        for url in [
+            "https://example.com/",
+            "https://10.example.com/",
+            "https://172.32.0.1/",
+            "https://8.8.8.8/",
+            "https://notlocalhost.com/",
+        ] {
+            assert!(!rejected(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn only_public_addresses_are_fetched() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::169.254.169.254",
+            "64:ff9b::a9fe:a9fe",
+            "2002:a9fe:a9fe::1",
+            "2002:7f00:1::",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "1.1.1.1",
+            "93.184.216.34",
+            "2606:4700::1111",
+            "64:ff9b::101:101",
+            "2002:101:101::1",
+        ] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        assert!(!is_public_url(&Url::parse("http://[::1]:8080/").unwrap()));
+        assert!(is_public_url(&Url::parse("https://example.com/").unwrap()));
+    }
+
+    #[test]
+    fn telegram_preview_reads_channel_card() {
+        let html = r#"<html><head>
+            <meta property="og:title" content="주식 리딩방">
+            <meta property="og:description" content="og blurb">
+            </head><body>
+            <div class="tgme_page_title"><span dir="auto">주식 리딩방 VIP</span></div>
+            <div class="tgme_page_extra">12 345 subscribers</div>
+            <div class="tgme_page_description">매일 급등주 무료 공유</div>
+            </body></html>"#;
+        let preview = telegram_preview(html, 1_000).unwrap();
+        assert_eq!(preview.title.as_deref(), Some("주식 리딩방 VIP"));
+        assert_eq!(preview.content.as_deref(), Some("매일 급등주 무료 공유"));
+        assert_eq!(preview.members.as_deref(), Some("12 345 subscribers"));
+
+        let missing = r#"<meta property="og:title" content="Telegram: Contact @nobody">"#;
+        assert!(telegram_preview(missing, 1_000).is_none());
+    }
+
+    #[test]
+    fn meta_preview_falls_back_to_title_and_og_tags() {
+        let html = r#"<html><head><title>무료 코인 에어드랍</title>
+            <meta name="description" content="지금 지갑을 연결하고 보상을 받으세요">
+            <meta property="og:site_name" content="AirdropHub">
+            </head><body><script>app()</script></body></html>"#;
+        let preview = meta_preview(html, 12).unwrap();
+        assert_eq!(preview.title.as_deref(), Some("무료 코인 에어드랍"));
+        assert_eq!(preview.site_name.as_deref(), Some("AirdropHub"));
+        assert_eq!(preview.content.as_deref(), Some("지금 지"));
+        assert!(meta_preview("<html><body></body></html>", 100).is_none());
+    }
+}